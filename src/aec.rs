@@ -3,6 +3,7 @@
 //! When enabled, processes mic input to remove TTS audio echo.
 //! AEC runs on the VAD thread since VoipAec3 is not Send.
 
+use crate::audiofile::{write_pcm16, write_wav_header};
 use aec3::voip::VoipAec3;
 use std::fs::File;
 use std::io::{BufWriter, Seek, SeekFrom, Write};
@@ -42,7 +43,7 @@ impl DebugWavWriter {
         let mut writer = BufWriter::new(file);
         Self::write_header(&mut writer, 0)?;
         writer.flush()?;
-        eprintln!("Debug WAV: writing to {}", path);
+        crate::logger::info!("Debug WAV: writing to {}", path);
         Ok(Self {
             writer,
             num_samples: 0,
@@ -50,33 +51,12 @@ impl DebugWavWriter {
     }
 
     fn write_header(w: &mut BufWriter<File>, num_samples: u32) -> std::io::Result<()> {
-        let sample_rate = AEC_SAMPLE_RATE as u32;
-        let byte_rate = sample_rate * 2;
-        let data_size = num_samples * 2;
-        let file_size = 36 + data_size;
-
         w.seek(SeekFrom::Start(0))?;
-        w.write_all(b"RIFF")?;
-        w.write_all(&file_size.to_le_bytes())?;
-        w.write_all(b"WAVE")?;
-        w.write_all(b"fmt ")?;
-        w.write_all(&16u32.to_le_bytes())?;
-        w.write_all(&1u16.to_le_bytes())?;
-        w.write_all(&1u16.to_le_bytes())?;
-        w.write_all(&sample_rate.to_le_bytes())?;
-        w.write_all(&byte_rate.to_le_bytes())?;
-        w.write_all(&2u16.to_le_bytes())?;
-        w.write_all(&16u16.to_le_bytes())?;
-        w.write_all(b"data")?;
-        w.write_all(&data_size.to_le_bytes())?;
-        Ok(())
+        write_wav_header(w, AEC_SAMPLE_RATE as u32, num_samples)
     }
 
     pub fn write_samples(&mut self, samples: &[f32]) {
-        for &s in samples {
-            let i = (s.clamp(-1.0, 1.0) * 32767.0) as i16;
-            let _ = self.writer.write_all(&i.to_le_bytes());
-        }
+        let _ = write_pcm16(&mut self.writer, samples);
         self.num_samples += samples.len() as u32;
     }
 
@@ -91,7 +71,7 @@ impl DebugWavWriter {
 impl Drop for DebugWavWriter {
     fn drop(&mut self) {
         self.flush();
-        eprintln!("Debug WAV: {} samples written", self.num_samples);
+        crate::logger::info!("Debug WAV: {} samples written", self.num_samples);
     }
 }
 
@@ -8,7 +8,7 @@ use std::time::{Duration, Instant};
 
 #[cfg(feature = "aec")]
 use crate::aec::AecProcessor;
-use crate::state::SharedState;
+use crate::state::{RuntimeState, SharedState};
 use crate::vad::VadEngine;
 
 const TARGET_RATE: usize = 16000; // 16khz
@@ -21,16 +21,98 @@ const MIN_PREVIEW_SAMPLES: usize = TARGET_RATE / 2;
 const VAD_FRAME_SAMPLES: usize = 480;
 const VAD_MIN_SPEECH_SAMPLES: usize = TARGET_RATE / 2;
 const VAD_MAX_SPEECH_SECONDS: f32 = 10.0;
-const VAD_SILENCE_FRAMES_TO_END: usize = 15;
+/// Default trailing silence before an utterance is considered finished (~15 frames)
+pub const DEFAULT_SILENCE_MS: u32 = 450;
 const VAD_PREFILL_FRAMES: usize = 10;
 const VAD_ONSET_FRAMES: usize = 3;
 const MAX_SPEECH_BUFFER_SIZE: usize = (TARGET_RATE as f32 * VAD_MAX_SPEECH_SECONDS) as usize; // 10s
 
+const VAD_FRAME_MS: usize = VAD_FRAME_SAMPLES * 1000 / TARGET_RATE;
+
+/// Convert a silence duration in ms to a number of VAD frames (at least one)
+pub fn silence_ms_to_frames(silence_ms: u32) -> usize {
+    (silence_ms as usize / VAD_FRAME_MS).max(1)
+}
+
+/// Convert a trailing pad in ms to a number of VAD frames (zero disables it)
+fn trailing_pad_frames(trailing_pad_ms: u32) -> usize {
+    trailing_pad_ms as usize / VAD_FRAME_MS
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 enum VadState {
     Idle,
     Onset(usize),
     Speaking(usize),
+    /// Speech has ended; still recording this many frames of tail
+    Trailing(usize),
+}
+
+/// After enough silence, either start the trailing pad or leave the state for
+/// `should_emit` to pick up
+fn end_speech(state: &mut VadState, silence_frames: usize, trailing_frames: usize) {
+    if trailing_frames > 0 && matches!(*state, VadState::Speaking(s) if s >= silence_frames) {
+        *state = VadState::Trailing(trailing_frames);
+    }
+}
+
+/// Whether the utterance in `speech_buf` is finished
+fn should_emit(state: VadState, speech_buf: &[f32], silence_frames: usize) -> bool {
+    let ended = match state {
+        VadState::Speaking(silence) => silence >= silence_frames,
+        VadState::Trailing(remaining) => remaining == 0,
+        _ => false,
+    };
+    ended || speech_buf.len() >= MAX_SPEECH_BUFFER_SIZE
+}
+
+/// Advance the VAD state machine by one frame. Returns true when speech
+/// starts, at which point the prefill (ending with the onset frames) becomes
+/// the start of `speech_buf`.
+///
+/// Every frame before speech goes through the prefill ring, so pre-speech
+/// context is kept whatever `onset_frames` is; 0 and 1 both start on the
+/// first speech frame.
+fn advance_vad_state(
+    state: &mut VadState,
+    frame: &[f32],
+    is_speech: bool,
+    speech_buf: &mut Vec<f32>,
+    prefill: &mut PrefillRing,
+    onset_frames: usize,
+) -> bool {
+    match *state {
+        VadState::Idle | VadState::Onset(_) => {
+            prefill.push(frame);
+            if !is_speech {
+                *state = VadState::Idle;
+                return false;
+            }
+            let count = match *state {
+                VadState::Onset(count) => count + 1,
+                _ => 1,
+            };
+            if count >= onset_frames {
+                speech_buf.clear();
+                prefill.drain_to(speech_buf);
+                *state = VadState::Speaking(0);
+                true
+            } else {
+                *state = VadState::Onset(count);
+                false
+            }
+        }
+        VadState::Speaking(silence) => {
+            speech_buf.extend_from_slice(frame);
+            *state = VadState::Speaking(if is_speech { 0 } else { silence + 1 });
+            false
+        }
+        VadState::Trailing(remaining) => {
+            speech_buf.extend_from_slice(frame);
+            *state = VadState::Trailing(remaining.saturating_sub(1));
+            false
+        }
+    }
 }
 
 /// Ring buffer for prefill frames - avoids per-frame allocations
@@ -76,11 +158,68 @@ impl PrefillRing {
             let start = idx * self.frame_size;
             out.extend_from_slice(&self.buf[start..start + self.frame_size]);
         }
+        self.clear();
+    }
+
+    /// Forget buffered frames, e.g. after audio was skipped
+    fn clear(&mut self) {
         self.count = 0;
         self.write_pos = 0;
     }
 }
 
+/// Samples at or above this magnitude count as clipped
+const CLIP_LEVEL: f32 = 0.98;
+/// A frame with this many clipped samples is a clipping frame
+const CLIP_SAMPLES_PER_FRAME: u32 = 3;
+/// Clipping is reported while this many of the last 32 frames (~1s) clipped,
+/// so a single pop doesn't trigger it and the warning stays up long enough
+/// to read
+const CLIP_SUSTAIN_FRAMES: u32 = 3;
+/// Per-frame decay of the smoothed level when it's falling (rises are instant)
+const LEVEL_RELEASE: f32 = 0.8;
+
+/// Smoothed input level plus sustained-clipping detection for the UI
+struct LevelMeter {
+    rms: f32,
+    /// One bit per recent frame, set when that frame clipped
+    clip_history: u32,
+}
+
+impl LevelMeter {
+    fn new() -> Self {
+        Self {
+            rms: 0.0,
+            clip_history: 0,
+        }
+    }
+
+    /// Feed one frame, returning the smoothed RMS
+    fn update(&mut self, frame: &[f32]) -> f32 {
+        let rms = (frame.iter().map(|s| s * s).sum::<f32>() / frame.len().max(1) as f32).sqrt();
+        self.rms = if rms > self.rms {
+            rms
+        } else {
+            self.rms * LEVEL_RELEASE + rms * (1.0 - LEVEL_RELEASE)
+        };
+
+        let clipped = frame.iter().filter(|s| s.abs() >= CLIP_LEVEL).count() as u32;
+        self.clip_history = (self.clip_history << 1) | (clipped >= CLIP_SAMPLES_PER_FRAME) as u32;
+        self.rms
+    }
+
+    fn clipping(&self) -> bool {
+        self.clip_history.count_ones() >= CLIP_SUSTAIN_FRAMES
+    }
+
+    fn event(&self) -> crate::DisplayEvent {
+        crate::DisplayEvent::AudioLevel {
+            rms: self.rms,
+            clipping: self.clipping(),
+        }
+    }
+}
+
 struct FrameResampler {
     resampler: Option<FftFixedIn<f32>>,
     in_buf: Vec<f32>,
@@ -126,19 +265,35 @@ impl FrameResampler {
     }
 }
 
+/// Index of the device `query` picks: an exact (case-insensitive) name match,
+/// else the first name containing it
+pub(crate) fn find_device(names: &[String], query: &str) -> Option<usize> {
+    let query = query.trim().to_lowercase();
+    let names: Vec<String> = names.iter().map(|n| n.to_lowercase()).collect();
+    names
+        .iter()
+        .position(|n| *n == query)
+        .or_else(|| names.iter().position(|n| n.contains(&query)))
+}
+
 /// Start audio capture - sends raw mono samples to channel
+///
+/// `device_name` picks the input by name (`audio.input_device`); `None` uses
+/// the system default.
 pub fn start_capture(
     tx: Sender<Vec<f32>>,
+    device_name: Option<&str>,
 ) -> Result<Stream, Box<dyn std::error::Error + Send + Sync>> {
-    let host = cpal::default_host();
-    let device = host.default_input_device().ok_or("No input device")?;
+    let device = input_device(device_name)?;
     let supported = device.default_input_config()?;
     let input_rate = supported.sample_rate() as usize;
     let channels = supported.channels() as usize;
 
-    println!(
+    crate::logger::info!(
         "Audio: {}Hz {}ch -> {}Hz mono",
-        input_rate, channels, TARGET_RATE
+        input_rate,
+        channels,
+        TARGET_RATE
     );
 
     let mut resampler = FrameResampler::new(input_rate, TARGET_RATE, VAD_FRAME_SAMPLES);
@@ -160,7 +315,7 @@ pub fn start_capture(
                 let _ = tx.send(frame.to_vec());
             });
         },
-        |err| eprintln!("Stream error: {}", err),
+        |err| crate::logger::error!("Stream error: {}", err),
         None,
     )?;
 
@@ -168,8 +323,151 @@ pub fn start_capture(
     Ok(stream)
 }
 
+fn device_name(device: &cpal::Device) -> String {
+    device
+        .description()
+        .map(|d| d.name().to_string())
+        .unwrap_or_else(|_| "<unknown>".to_string())
+}
+
+/// The input device matching `name` (all or part of its name), or the
+/// default when `name` is unset or nothing matches
+pub fn input_device(
+    name: Option<&str>,
+) -> Result<cpal::Device, Box<dyn std::error::Error + Send + Sync>> {
+    let host = cpal::default_host();
+    if let Some(query) = name.filter(|n| !n.trim().is_empty()) {
+        let devices: Vec<cpal::Device> = host.input_devices()?.collect();
+        let names: Vec<String> = devices.iter().map(device_name).collect();
+        match find_device(&names, query) {
+            Some(idx) => {
+                crate::logger::info!("Audio input: {}", names[idx]);
+                return Ok(devices
+                    .into_iter()
+                    .nth(idx)
+                    .ok_or("Input device vanished")?);
+            }
+            None => crate::logger::warn!(
+                "No input device matches '{}', using the default (see `silly devices`)",
+                query
+            ),
+        }
+    }
+    Ok(host.default_input_device().ok_or("No input device")?)
+}
+
+/// List input and output devices for `silly devices`, marking the defaults
+/// and the ones `audio.input_device` / `audio.output_device` select
+pub fn run_devices(
+    input: Option<&str>,
+    output: Option<&str>,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let host = cpal::default_host();
+    let default = host.default_input_device().map(|d| device_name(&d));
+    let names: Vec<String> = host.input_devices()?.map(|d| device_name(&d)).collect();
+    print_devices(
+        "Input",
+        &names,
+        default.as_deref(),
+        input,
+        "audio.input_device",
+    );
+
+    // Output goes through rodio's cpal, list it the way playback matches it
+    let (names, default) = crate::tts::output_devices()?;
+    println!();
+    print_devices(
+        "Output",
+        &names,
+        default.as_deref(),
+        output,
+        "audio.output_device",
+    );
+    Ok(())
+}
+
+fn print_devices(
+    kind: &str,
+    names: &[String],
+    default: Option<&str>,
+    configured: Option<&str>,
+    key: &str,
+) {
+    if names.is_empty() {
+        println!("No {} devices found", kind.to_lowercase());
+        return;
+    }
+
+    let selected = configured.and_then(|query| find_device(names, query));
+    println!("{} devices:", kind);
+    for (idx, name) in names.iter().enumerate() {
+        let mut tags = Vec::new();
+        if default == Some(name.as_str()) {
+            tags.push("default".to_string());
+        }
+        if selected == Some(idx) {
+            tags.push(format!("selected by {}", key));
+        }
+        if tags.is_empty() {
+            println!("  {}", name);
+        } else {
+            println!("  {} ({})", name, tags.join(", "));
+        }
+    }
+    if let (Some(query), None) = (configured, selected) {
+        println!(
+            "{} = \"{}\" matches nothing, the default is used",
+            key, query
+        );
+    }
+}
+
+/// Feed recorded 16kHz audio into `tx` as if it came from the mic (`--replay`)
+///
+/// Frames are paced in real time so VAD timeouts and previews behave as they
+/// would live. Once the file ends, silence keeps flowing so the last utterance
+/// is finalized and the assistant stays up to respond, until `running` is
+/// cleared.
+pub fn start_replay(samples: Vec<f32>, tx: Sender<Vec<f32>>, running: Arc<AtomicBool>) {
+    std::thread::spawn(move || {
+        let frame_duration = Duration::from_secs_f64(VAD_FRAME_SAMPLES as f64 / TARGET_RATE as f64);
+        let silence = vec![0.0; VAD_FRAME_SAMPLES];
+        let mut frames = samples.chunks(VAD_FRAME_SAMPLES);
+        let mut next = Instant::now();
+        while running.load(Ordering::SeqCst) {
+            let frame = match frames.next() {
+                Some(chunk) if chunk.len() == VAD_FRAME_SAMPLES => chunk.to_vec(),
+                Some(chunk) => {
+                    let mut padded = chunk.to_vec();
+                    padded.resize(VAD_FRAME_SAMPLES, 0.0);
+                    padded
+                }
+                None => silence.clone(),
+            };
+            if tx.send(frame).is_err() {
+                break;
+            }
+            next += frame_duration;
+            std::thread::sleep(next.saturating_duration_since(Instant::now()));
+        }
+    });
+}
+
+/// Split 16kHz audio from another capture path (system or app audio) into
+/// VAD frames, as `start_capture` does for the mic
+#[cfg(feature = "listen")]
+pub fn forward_frames(rx: flume::Receiver<Vec<f32>>, tx: Sender<Vec<f32>>) {
+    let mut framer = FrameResampler::new(TARGET_RATE, TARGET_RATE, VAD_FRAME_SAMPLES);
+    while let Ok(samples) = rx.recv() {
+        framer.push(&samples, |frame| {
+            let _ = tx.send(frame.to_vec());
+        });
+    }
+}
+
 /// VAD processor - runs on separate thread
 /// final_tx: preserves all events, preview_tx: lossy (capacity 1)
+#[allow(clippy::too_many_arguments)]
 pub fn run_vad_processor(
     rx: Receiver<Vec<f32>>,
     final_tx: Sender<Arc<[f32]>>,
@@ -178,13 +476,21 @@ pub fn run_vad_processor(
     tts_playing: Arc<AtomicBool>,
     mic_muted: Arc<AtomicBool>,
     level_tx: Sender<crate::DisplayEvent>,
+    silence_ms: u32,
+    trailing_pad_ms: u32,
+    ptt_held: Option<Arc<AtomicBool>>,
 ) {
+    let silence_frames = silence_ms_to_frames(silence_ms);
+    let trailing_frames = trailing_pad_frames(trailing_pad_ms);
     let mut state = VadState::Idle;
     let mut speech_buf: Vec<f32> = Vec::with_capacity(MAX_SPEECH_BUFFER_SIZE);
     let mut prefill = PrefillRing::new(VAD_FRAME_SAMPLES, VAD_PREFILL_FRAMES);
     let mut last_preview = Instant::now();
     let mut last_level = Instant::now();
+    let mut meter = LevelMeter::new();
+    let mut spectrum = crate::spectrum::SpectrumAnalyzer::new(TARGET_RATE as u32);
     let chunk_size = (TARGET_RATE as f32 * CHUNK_SECONDS) as usize;
+    let mut ptt_was_held = false;
 
     loop {
         let frame = match rx.recv() {
@@ -192,19 +498,35 @@ pub fn run_vad_processor(
             Err(_) => break,
         };
 
-        // Send audio level every 50ms
+        // Send audio level and spectrum every 50ms
+        meter.update(&frame);
         let now = Instant::now();
         if now.duration_since(last_level) >= Duration::from_millis(50) {
-            let rms = (frame.iter().map(|s| s * s).sum::<f32>() / frame.len() as f32).sqrt();
-            let _ = level_tx.send(crate::DisplayEvent::AudioLevel(rms));
+            let _ = level_tx.send(meter.event());
+            let _ = level_tx.send(crate::DisplayEvent::AudioBands(spectrum.bands(&frame)));
             last_level = now;
         }
 
         // Skip VAD processing while TTS is playing or mic is muted
         if tts_playing.load(Ordering::SeqCst) || mic_muted.load(Ordering::SeqCst) {
-            // Reset state to avoid partial utterances
+            // Reset state to avoid partial utterances (and stale prefill)
             state = VadState::Idle;
             speech_buf.clear();
+            prefill.clear();
+            continue;
+        }
+
+        // Push-to-talk bypasses VAD entirely
+        if let Some(ref held) = ptt_held {
+            process_ptt_frame(
+                &frame,
+                held.load(Ordering::SeqCst),
+                &mut ptt_was_held,
+                &mut speech_buf,
+                &mut last_preview,
+                &final_tx,
+                &preview_tx,
+            );
             continue;
         }
 
@@ -218,6 +540,8 @@ pub fn run_vad_processor(
                 &mut last_preview,
                 &final_tx,
                 &preview_tx,
+                silence_frames,
+                trailing_frames,
             );
         } else {
             // No VAD - fixed chunks
@@ -238,6 +562,7 @@ pub fn run_vad_processor(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn process_vad_frame(
     frame: &[f32],
     vad: &mut VadEngine,
@@ -247,49 +572,24 @@ fn process_vad_frame(
     last_preview: &mut Instant,
     final_tx: &Sender<Arc<[f32]>>,
     preview_tx: &SyncSender<Arc<[f32]>>,
+    silence_frames: usize,
+    trailing_frames: usize,
 ) {
     let is_speaking = matches!(state, VadState::Speaking(_));
     let is_speech = vad.is_speech(frame, is_speaking);
 
-    match state {
-        VadState::Idle => {
-            prefill.push(frame);
-            if is_speech {
-                *state = VadState::Onset(1);
-            }
-        }
-        VadState::Onset(count) => {
-            prefill.push(frame);
-            if is_speech {
-                *count += 1;
-                if *count >= VAD_ONSET_FRAMES {
-                    prefill.drain_to(speech_buf);
-                    *state = VadState::Speaking(0);
-                }
-            } else {
-                *state = VadState::Idle;
-                speech_buf.clear(); // Clear buffer on onset failure
-            }
-        }
-        VadState::Speaking(silence_count) => {
-            speech_buf.extend_from_slice(frame);
-            if is_speech {
-                *silence_count = 0;
-            } else {
-                *silence_count += 1;
-            }
-        }
-    }
+    advance_vad_state(
+        state,
+        frame,
+        is_speech,
+        speech_buf,
+        prefill,
+        VAD_ONSET_FRAMES,
+    );
+    end_speech(state, silence_frames, trailing_frames);
 
     // Check emit - add memory limit check
-    let should_emit = match state {
-        VadState::Speaking(silence) => {
-            *silence >= VAD_SILENCE_FRAMES_TO_END || speech_buf.len() >= MAX_SPEECH_BUFFER_SIZE
-        }
-        _ => false,
-    };
-
-    if should_emit {
+    if should_emit(*state, speech_buf, silence_frames) {
         if speech_buf.len() >= VAD_MIN_SPEECH_SAMPLES {
             let samples: Arc<[f32]> = std::mem::take(speech_buf).into();
             // Emit samples
@@ -314,6 +614,105 @@ fn process_vad_frame(
     }
 }
 
+/// Push-to-talk: buffer raw frames while the key is held and emit on release.
+/// Nothing is emitted if the key was released without capturing any audio.
+fn process_ptt_frame(
+    frame: &[f32],
+    held: bool,
+    was_held: &mut bool,
+    speech_buf: &mut Vec<f32>,
+    last_preview: &mut Instant,
+    final_tx: &Sender<Arc<[f32]>>,
+    preview_tx: &SyncSender<Arc<[f32]>>,
+) {
+    if held {
+        if !*was_held {
+            speech_buf.clear();
+            *last_preview = Instant::now();
+        }
+        speech_buf.extend_from_slice(frame);
+
+        if speech_buf.len() >= MAX_SPEECH_BUFFER_SIZE {
+            let samples: Arc<[f32]> = std::mem::take(speech_buf).into();
+            let _ = final_tx.send(samples);
+        } else {
+            let now = Instant::now();
+            if speech_buf.len() > MIN_PREVIEW_SAMPLES
+                && now.duration_since(*last_preview) >= PREVIEW_INTERVAL
+            {
+                let _ = preview_tx.try_send(Arc::from(speech_buf.as_slice()));
+                *last_preview = now;
+            }
+        }
+    } else if *was_held && !speech_buf.is_empty() {
+        let samples: Arc<[f32]> = std::mem::take(speech_buf).into();
+        let _ = final_tx.send(samples);
+    }
+    *was_held = held;
+}
+
+/// TTS output level above which the echo gate engages
+const ECHO_GATE_TTS_LEVEL: f32 = 0.02;
+/// While TTS plays, mic frames quieter than `tts_level * ratio` are treated as echo
+const ECHO_GATE_RATIO: f32 = 1.0;
+
+/// Zero a mic frame whose level is explained by concurrent TTS output.
+///
+/// A crude stand-in for AEC: loud speech (barge-in) still gets through, but
+/// the speaker bleeding into the mic doesn't trigger VAD. Returns true if gated.
+fn echo_gate(frame: &mut [f32], tts_level: f32) -> bool {
+    if tts_level < ECHO_GATE_TTS_LEVEL || frame.is_empty() {
+        return false;
+    }
+    let rms = (frame.iter().map(|s| s * s).sum::<f32>() / frame.len() as f32).sqrt();
+    if rms < tts_level * ECHO_GATE_RATIO {
+        frame.fill(0.0);
+        true
+    } else {
+        false
+    }
+}
+
+/// Ducks TTS to `interaction.duck_volume` while the user talks over it
+/// (crosstalk), restoring it once they stop or playback ends
+struct Ducker {
+    ducked: bool,
+}
+
+impl Ducker {
+    fn new() -> Self {
+        Self { ducked: false }
+    }
+
+    /// Track one frame. `talking_over` is TTS playing with crosstalk on;
+    /// `is_speaking` holds the duck through pauses within an utterance.
+    fn update(
+        &mut self,
+        state: &RuntimeState,
+        talking_over: bool,
+        is_speech: bool,
+        is_speaking: bool,
+    ) {
+        if talking_over && is_speech {
+            if !self.ducked {
+                state.duck_tts();
+                self.ducked = true;
+            }
+        } else if self.ducked && !(talking_over && is_speaking) {
+            self.release(state);
+        }
+    }
+
+    /// Restore the volume if ducked (utterance done, mic muted, or frames
+    /// no longer processed)
+    fn release(&mut self, state: &RuntimeState) {
+        if self.ducked {
+            state.restore_tts_volume();
+            self.ducked = false;
+        }
+    }
+}
+
 // ============================================================================
 // Crosstalk-enabled VAD processor
 // ============================================================================
@@ -330,6 +729,7 @@ fn process_vad_frame(
 ///
 /// final_tx: preserves all events, preview_tx: lossy (capacity 1)
 #[cfg(feature = "aec")]
+#[allow(clippy::too_many_arguments)]
 pub fn run_vad_processor_with_state(
     rx: Receiver<Vec<f32>>,
     final_tx: Sender<Arc<[f32]>>,
@@ -338,11 +738,24 @@ pub fn run_vad_processor_with_state(
     state: SharedState,
     level_tx: Sender<crate::DisplayEvent>,
     aec: Option<AecProcessor>,
+    silence_ms: u32,
+    trailing_pad_ms: u32,
 ) {
-    run_vad_processor_inner(rx, final_tx, preview_tx, vad, state, level_tx, aec)
+    run_vad_processor_inner(
+        rx,
+        final_tx,
+        preview_tx,
+        vad,
+        state,
+        level_tx,
+        aec,
+        silence_ms,
+        trailing_pad_ms,
+    )
 }
 
 #[cfg(not(feature = "aec"))]
+#[allow(clippy::too_many_arguments)]
 pub fn run_vad_processor_with_state(
     rx: Receiver<Vec<f32>>,
     final_tx: Sender<Arc<[f32]>>,
@@ -350,11 +763,23 @@ pub fn run_vad_processor_with_state(
     vad: Option<VadEngine>,
     state: SharedState,
     level_tx: Sender<crate::DisplayEvent>,
+    silence_ms: u32,
+    trailing_pad_ms: u32,
 ) {
-    run_vad_processor_inner(rx, final_tx, preview_tx, vad, state, level_tx)
+    run_vad_processor_inner(
+        rx,
+        final_tx,
+        preview_tx,
+        vad,
+        state,
+        level_tx,
+        silence_ms,
+        trailing_pad_ms,
+    )
 }
 
 #[cfg(feature = "aec")]
+#[allow(clippy::too_many_arguments)]
 fn run_vad_processor_inner(
     rx: Receiver<Vec<f32>>,
     final_tx: Sender<Arc<[f32]>>,
@@ -363,16 +788,23 @@ fn run_vad_processor_inner(
     state: SharedState,
     level_tx: Sender<crate::DisplayEvent>,
     mut aec: Option<AecProcessor>,
+    silence_ms: u32,
+    trailing_pad_ms: u32,
 ) {
+    let silence_frames = silence_ms_to_frames(silence_ms);
+    let trailing_frames = trailing_pad_frames(trailing_pad_ms);
     let mut vad_state = VadState::Idle;
     let mut speech_buf: Vec<f32> = Vec::with_capacity(MAX_SPEECH_BUFFER_SIZE);
     let mut prefill = PrefillRing::new(VAD_FRAME_SAMPLES, VAD_PREFILL_FRAMES);
     let mut last_preview = Instant::now();
     let mut last_level = Instant::now();
+    let mut meter = LevelMeter::new();
+    let mut spectrum = crate::spectrum::SpectrumAnalyzer::new(TARGET_RATE as u32);
     let chunk_size = (TARGET_RATE as f32 * CHUNK_SECONDS) as usize;
 
     let mut barge_in_active = false;
-    let mut speech_during_tts = false;
+    let mut ducker = Ducker::new();
+    let mut ptt_was_held = false;
 
     loop {
         let raw_frame = match rx.recv() {
@@ -381,7 +813,7 @@ fn run_vad_processor_inner(
         };
 
         // Apply AEC if enabled
-        let frame = if let Some(ref mut aec_proc) = aec {
+        let mut frame = if let Some(ref mut aec_proc) = aec {
             if state.aec_enabled.load(Ordering::SeqCst) {
                 aec_proc.process_capture(&raw_frame)
             } else {
@@ -391,21 +823,23 @@ fn run_vad_processor_inner(
             raw_frame
         };
 
-        // Send audio level every 50ms
+        // Send audio level and spectrum every 50ms
+        let rms = meter.update(&frame);
         let now = Instant::now();
         if now.duration_since(last_level) >= Duration::from_millis(50) {
-            let rms = (frame.iter().map(|s| s * s).sum::<f32>() / frame.len() as f32).sqrt();
-            let _ = level_tx.send(crate::DisplayEvent::AudioLevel(rms));
+            let _ = level_tx.send(meter.event());
+            let _ = level_tx.send(crate::DisplayEvent::AudioBands(spectrum.bands(&frame)));
             state.set_mic_level(rms);
             last_level = now;
         }
 
-        // Check if mic is muted
-        if state.mic_muted.load(Ordering::SeqCst) {
+        // Check if mic is muted (and not listening for the wake word)
+        if state.is_deaf() {
             vad_state = VadState::Idle;
             speech_buf.clear();
+            prefill.clear();
             barge_in_active = false;
-            speech_during_tts = false;
+            ducker.release(&state);
             continue;
         }
 
@@ -416,8 +850,30 @@ fn run_vad_processor_inner(
         if tts_playing && !crosstalk_enabled {
             vad_state = VadState::Idle;
             speech_buf.clear();
+            prefill.clear();
             barge_in_active = false;
-            speech_during_tts = false;
+            ducker.release(&state);
+            continue;
+        }
+
+        // Fall back to the echo gate when the AEC processor isn't available
+        if aec.is_none() && state.aec_enabled.load(Ordering::SeqCst) {
+            echo_gate(&mut frame, state.get_tts_level());
+        }
+
+        // Push-to-talk bypasses VAD entirely
+        if state.ptt_mode.load(Ordering::SeqCst) {
+            let held = state.ptt_held.load(Ordering::SeqCst);
+            ducker.update(&state, tts_playing && crosstalk_enabled, held, held);
+            process_ptt_frame(
+                &frame,
+                held,
+                &mut ptt_was_held,
+                &mut speech_buf,
+                &mut last_preview,
+                &final_tx,
+                &preview_tx,
+            );
             continue;
         }
 
@@ -426,75 +882,31 @@ fn run_vad_processor_inner(
             let is_speaking = matches!(vad_state, VadState::Speaking(_));
             let is_speech = vad_engine.is_speech(&frame, is_speaking);
 
-            // Handle crosstalk: duck volume when speech detected during TTS
-            if tts_playing && crosstalk_enabled {
-                if is_speech {
-                    if !speech_during_tts {
-                        // First speech frame during TTS - duck volume
-                        state.duck_tts();
-                        speech_during_tts = true;
-                    }
-                } else if speech_during_tts && !is_speaking {
-                    // Speech ended during TTS - restore volume
-                    state.restore_tts_volume();
-                    speech_during_tts = false;
-                }
-            } else {
-                // TTS not playing - ensure volume is restored
-                if speech_during_tts {
-                    state.restore_tts_volume();
-                    speech_during_tts = false;
-                }
-            }
+            // Handle crosstalk: duck volume while the user talks over TTS
+            ducker.update(
+                &state,
+                tts_playing && crosstalk_enabled,
+                is_speech,
+                is_speaking,
+            );
 
             // Process VAD state machine
-            match vad_state {
-                VadState::Idle => {
-                    prefill.push(&frame);
-                    if is_speech {
-                        vad_state = VadState::Onset(1);
-                    }
-                }
-                VadState::Onset(count) => {
-                    prefill.push(&frame);
-                    if is_speech {
-                        let new_count = count + 1;
-                        if new_count >= VAD_ONSET_FRAMES {
-                            prefill.drain_to(&mut speech_buf);
-                            vad_state = VadState::Speaking(0);
-
-                            // If TTS is playing, mark barge-in
-                            if tts_playing && crosstalk_enabled {
-                                barge_in_active = true;
-                            }
-                        } else {
-                            vad_state = VadState::Onset(new_count);
-                        }
-                    } else {
-                        vad_state = VadState::Idle;
-                        speech_buf.clear();
-                    }
-                }
-                VadState::Speaking(silence_count) => {
-                    speech_buf.extend_from_slice(&frame);
-                    if is_speech {
-                        vad_state = VadState::Speaking(0);
-                    } else {
-                        vad_state = VadState::Speaking(silence_count + 1);
-                    }
-                }
+            let started = advance_vad_state(
+                &mut vad_state,
+                &frame,
+                is_speech,
+                &mut speech_buf,
+                &mut prefill,
+                VAD_ONSET_FRAMES,
+            );
+            // If TTS is playing, mark barge-in
+            if started && tts_playing && crosstalk_enabled {
+                barge_in_active = true;
             }
+            end_speech(&mut vad_state, silence_frames, trailing_frames);
 
             // Check if we should emit
-            let should_emit = match vad_state {
-                VadState::Speaking(silence) => {
-                    silence >= VAD_SILENCE_FRAMES_TO_END
-                        || speech_buf.len() >= MAX_SPEECH_BUFFER_SIZE
-                }
-                _ => false,
-            };
-
-            if should_emit {
+            if should_emit(vad_state, &speech_buf, silence_frames) {
                 if speech_buf.len() >= VAD_MIN_SPEECH_SAMPLES {
                     // If this was a barge-in, request cancel before emitting
                     if barge_in_active {
@@ -511,10 +923,7 @@ fn run_vad_processor_inner(
                 last_preview = Instant::now();
 
                 // Restore volume after speech ends
-                if speech_during_tts {
-                    state.restore_tts_volume();
-                    speech_during_tts = false;
-                }
+                ducker.release(&state);
                 continue;
             }
 
@@ -548,6 +957,7 @@ fn run_vad_processor_inner(
 }
 
 #[cfg(not(feature = "aec"))]
+#[allow(clippy::too_many_arguments)]
 fn run_vad_processor_inner(
     rx: Receiver<Vec<f32>>,
     final_tx: Sender<Arc<[f32]>>,
@@ -555,36 +965,45 @@ fn run_vad_processor_inner(
     mut vad: Option<VadEngine>,
     state: SharedState,
     level_tx: Sender<crate::DisplayEvent>,
+    silence_ms: u32,
+    trailing_pad_ms: u32,
 ) {
+    let silence_frames = silence_ms_to_frames(silence_ms);
+    let trailing_frames = trailing_pad_frames(trailing_pad_ms);
     let mut vad_state = VadState::Idle;
     let mut speech_buf: Vec<f32> = Vec::with_capacity(MAX_SPEECH_BUFFER_SIZE);
     let mut prefill = PrefillRing::new(VAD_FRAME_SAMPLES, VAD_PREFILL_FRAMES);
     let mut last_preview = Instant::now();
     let mut last_level = Instant::now();
+    let mut meter = LevelMeter::new();
+    let mut spectrum = crate::spectrum::SpectrumAnalyzer::new(TARGET_RATE as u32);
     let chunk_size = (TARGET_RATE as f32 * CHUNK_SECONDS) as usize;
 
     let mut barge_in_active = false;
-    let mut speech_during_tts = false;
+    let mut ducker = Ducker::new();
+    let mut ptt_was_held = false;
 
     loop {
-        let frame = match rx.recv() {
+        let mut frame = match rx.recv() {
             Ok(f) => f,
             Err(_) => break,
         };
 
+        let rms = meter.update(&frame);
         let now = Instant::now();
         if now.duration_since(last_level) >= Duration::from_millis(50) {
-            let rms = (frame.iter().map(|s| s * s).sum::<f32>() / frame.len() as f32).sqrt();
-            let _ = level_tx.send(crate::DisplayEvent::AudioLevel(rms));
+            let _ = level_tx.send(meter.event());
+            let _ = level_tx.send(crate::DisplayEvent::AudioBands(spectrum.bands(&frame)));
             state.set_mic_level(rms);
             last_level = now;
         }
 
-        if state.mic_muted.load(Ordering::SeqCst) {
+        if state.is_deaf() {
             vad_state = VadState::Idle;
             speech_buf.clear();
+            prefill.clear();
             barge_in_active = false;
-            speech_during_tts = false;
+            ducker.release(&state);
             continue;
         }
 
@@ -594,8 +1013,30 @@ fn run_vad_processor_inner(
         if tts_playing && !crosstalk_enabled {
             vad_state = VadState::Idle;
             speech_buf.clear();
+            prefill.clear();
             barge_in_active = false;
-            speech_during_tts = false;
+            ducker.release(&state);
+            continue;
+        }
+
+        // Without the aec feature, gate out frames that are just our own TTS
+        if state.aec_enabled.load(Ordering::SeqCst) {
+            echo_gate(&mut frame, state.get_tts_level());
+        }
+
+        // Push-to-talk bypasses VAD entirely
+        if state.ptt_mode.load(Ordering::SeqCst) {
+            let held = state.ptt_held.load(Ordering::SeqCst);
+            ducker.update(&state, tts_playing && crosstalk_enabled, held, held);
+            process_ptt_frame(
+                &frame,
+                held,
+                &mut ptt_was_held,
+                &mut speech_buf,
+                &mut last_preview,
+                &final_tx,
+                &preview_tx,
+            );
             continue;
         }
 
@@ -603,58 +1044,27 @@ fn run_vad_processor_inner(
             let is_speaking = matches!(vad_state, VadState::Speaking(_));
             let is_speech = vad_engine.is_speech(&frame, is_speaking);
 
-            if tts_playing && crosstalk_enabled {
-                if is_speech && !speech_during_tts {
-                    state.duck_tts();
-                    speech_during_tts = true;
-                } else if !is_speech && speech_during_tts && !is_speaking {
-                    state.restore_tts_volume();
-                    speech_during_tts = false;
-                }
-            } else if speech_during_tts {
-                state.restore_tts_volume();
-                speech_during_tts = false;
-            }
+            ducker.update(
+                &state,
+                tts_playing && crosstalk_enabled,
+                is_speech,
+                is_speaking,
+            );
 
-            match vad_state {
-                VadState::Idle => {
-                    prefill.push(&frame);
-                    if is_speech {
-                        vad_state = VadState::Onset(1);
-                    }
-                }
-                VadState::Onset(count) => {
-                    prefill.push(&frame);
-                    if is_speech {
-                        let new_count = count + 1;
-                        if new_count >= VAD_ONSET_FRAMES {
-                            prefill.drain_to(&mut speech_buf);
-                            vad_state = VadState::Speaking(0);
-                            if tts_playing && crosstalk_enabled {
-                                barge_in_active = true;
-                            }
-                        } else {
-                            vad_state = VadState::Onset(new_count);
-                        }
-                    } else {
-                        vad_state = VadState::Idle;
-                        speech_buf.clear();
-                    }
-                }
-                VadState::Speaking(silence_count) => {
-                    speech_buf.extend_from_slice(&frame);
-                    vad_state = if is_speech {
-                        VadState::Speaking(0)
-                    } else {
-                        VadState::Speaking(silence_count + 1)
-                    };
-                }
+            let started = advance_vad_state(
+                &mut vad_state,
+                &frame,
+                is_speech,
+                &mut speech_buf,
+                &mut prefill,
+                VAD_ONSET_FRAMES,
+            );
+            if started && tts_playing && crosstalk_enabled {
+                barge_in_active = true;
             }
+            end_speech(&mut vad_state, silence_frames, trailing_frames);
 
-            let should_emit = matches!(vad_state, VadState::Speaking(s) if s >= VAD_SILENCE_FRAMES_TO_END)
-                || speech_buf.len() >= MAX_SPEECH_BUFFER_SIZE;
-
-            if should_emit {
+            if should_emit(vad_state, &speech_buf, silence_frames) {
                 if speech_buf.len() >= VAD_MIN_SPEECH_SAMPLES {
                     if barge_in_active {
                         state.request_cancel();
@@ -667,10 +1077,7 @@ fn run_vad_processor_inner(
                 }
                 vad_state = VadState::Idle;
                 last_preview = Instant::now();
-                if speech_during_tts {
-                    state.restore_tts_volume();
-                    speech_during_tts = false;
-                }
+                ducker.release(&state);
                 continue;
             }
 
@@ -699,3 +1106,329 @@ fn run_vad_processor_inner(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_silence_ms_to_frames() {
+        assert_eq!(silence_ms_to_frames(DEFAULT_SILENCE_MS), 15);
+        assert_eq!(silence_ms_to_frames(100), 3);
+        // Zero (or anything shorter than a frame) still needs one silent frame
+        assert_eq!(silence_ms_to_frames(0), 1);
+        assert_eq!(silence_ms_to_frames(10), 1);
+    }
+
+    /// Run energy-VAD frames through `process_vad_frame`, returning the first
+    /// emitted utterance
+    fn run_vad_frames(
+        frames: &[Vec<f32>],
+        silence_frames: usize,
+        trailing_frames: usize,
+    ) -> Vec<f32> {
+        let (final_tx, final_rx) = std::sync::mpsc::channel();
+        let (preview_tx, _preview_rx) = std::sync::mpsc::sync_channel(1);
+        let mut vad = VadEngine::energy();
+        let mut state = VadState::Idle;
+        let mut speech_buf = Vec::new();
+        let mut prefill = PrefillRing::new(VAD_FRAME_SAMPLES, VAD_PREFILL_FRAMES);
+        let mut last_preview = Instant::now();
+        for frame in frames {
+            process_vad_frame(
+                frame,
+                &mut vad,
+                &mut state,
+                &mut speech_buf,
+                &mut prefill,
+                &mut last_preview,
+                &final_tx,
+                &preview_tx,
+                silence_frames,
+                trailing_frames,
+            );
+            if let Ok(samples) = final_rx.try_recv() {
+                return samples.to_vec();
+            }
+        }
+        panic!("no utterance emitted");
+    }
+
+    #[test]
+    fn test_trailing_pad() {
+        let silence = vec![0.0; VAD_FRAME_SAMPLES];
+        let speech = vec![0.1; VAD_FRAME_SAMPLES];
+        let mut frames = vec![silence.clone(); 2];
+        frames.extend(std::iter::repeat_n(speech, 20));
+        // Speech resuming inside the pad doesn't extend the utterance
+        frames.extend(std::iter::repeat_n(silence.clone(), 5));
+        frames.extend(std::iter::repeat_n(vec![0.1; VAD_FRAME_SAMPLES], 10));
+
+        // 2 prefill + 20 speech + 3 silence frames
+        let plain = run_vad_frames(&frames, 3, 0);
+        assert_eq!(plain.len(), 25 * VAD_FRAME_SAMPLES);
+
+        // ...plus 4 frames of tail, the last two of them speech
+        assert_eq!(trailing_pad_frames(120), 4);
+        let padded = run_vad_frames(&frames, 3, trailing_pad_frames(120));
+        assert_eq!(padded.len(), 29 * VAD_FRAME_SAMPLES);
+        assert_eq!(padded[..plain.len()], plain[..]);
+        assert_eq!(padded[padded.len() - 1], 0.1);
+    }
+
+    /// Feed `quiet` non-speech frames (frame `i` filled with `i`) and then
+    /// speech frames (filled with -1) until speech starts; returns the buffer
+    /// at that point
+    fn onset_buffer(quiet: usize, onset_frames: usize) -> Vec<f32> {
+        let mut state = VadState::Idle;
+        let mut speech_buf = Vec::new();
+        let mut prefill = PrefillRing::new(VAD_FRAME_SAMPLES, VAD_PREFILL_FRAMES);
+        for i in 0..quiet {
+            let frame = vec![i as f32; VAD_FRAME_SAMPLES];
+            assert!(!advance_vad_state(
+                &mut state,
+                &frame,
+                false,
+                &mut speech_buf,
+                &mut prefill,
+                onset_frames
+            ));
+        }
+        let speech = vec![-1.0; VAD_FRAME_SAMPLES];
+        for _ in 0..onset_frames.max(1) {
+            if advance_vad_state(
+                &mut state,
+                &speech,
+                true,
+                &mut speech_buf,
+                &mut prefill,
+                onset_frames,
+            ) {
+                assert_eq!(state, VadState::Speaking(0));
+                return speech_buf;
+            }
+        }
+        panic!("speech never started");
+    }
+
+    /// First sample of each frame in `buf`
+    fn frame_levels(buf: &[f32]) -> Vec<f32> {
+        buf.chunks(VAD_FRAME_SAMPLES).map(|f| f[0]).collect()
+    }
+
+    #[test]
+    fn test_prefill_with_immediate_onset() {
+        // Speech starts on the first speech frame, preceded by all the context
+        let buf = onset_buffer(4, 0);
+        assert_eq!(frame_levels(&buf), [0.0, 1.0, 2.0, 3.0, -1.0]);
+        assert_eq!(onset_buffer(4, 1), buf);
+    }
+
+    #[test]
+    fn test_prefill_with_onset_frames() {
+        // Ring holds 10 frames: the last 7 quiet ones plus the 3 onset frames
+        let buf = onset_buffer(12, 3);
+        let mut expected: Vec<f32> = (5..12).map(|i| i as f32).collect();
+        expected.extend([-1.0; 3]);
+        assert_eq!(frame_levels(&buf), expected);
+    }
+
+    #[test]
+    fn test_failed_onset_keeps_prefill() {
+        let mut state = VadState::Idle;
+        let mut speech_buf = Vec::new();
+        let mut prefill = PrefillRing::new(VAD_FRAME_SAMPLES, VAD_PREFILL_FRAMES);
+        let quiet = vec![0.0; VAD_FRAME_SAMPLES];
+        let speech = vec![0.1; VAD_FRAME_SAMPLES];
+        let mut step = |frame: &[f32], is_speech: bool| {
+            advance_vad_state(
+                &mut state,
+                frame,
+                is_speech,
+                &mut speech_buf,
+                &mut prefill,
+                3,
+            )
+        };
+
+        // A one-frame blip doesn't start speech but stays in the context
+        step(&quiet, false);
+        step(&speech, true);
+        step(&quiet, false);
+        for _ in 0..2 {
+            assert!(!step(&speech, true));
+        }
+        assert!(step(&speech, true));
+        assert_eq!(frame_levels(&speech_buf), [0.0, 0.1, 0.0, 0.1, 0.1, 0.1]);
+    }
+
+    #[test]
+    fn test_replay_stops_when_not_running() {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let running = Arc::new(AtomicBool::new(true));
+        start_replay(vec![0.1; VAD_FRAME_SAMPLES * 2], tx, Arc::clone(&running));
+
+        // Keeps sending silence after the samples run out
+        for _ in 0..4 {
+            assert!(rx.recv_timeout(Duration::from_secs(1)).is_ok());
+        }
+
+        // Once cleared, the thread exits and drops its sender
+        running.store(false, Ordering::SeqCst);
+        let deadline = Instant::now() + Duration::from_secs(1);
+        loop {
+            match rx.recv_timeout(Duration::from_secs(1)) {
+                Ok(_) => assert!(Instant::now() < deadline, "replay kept running"),
+                Err(e) => {
+                    assert_eq!(e, std::sync::mpsc::RecvTimeoutError::Disconnected);
+                    break;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_push_to_talk_emits_on_release() {
+        let (final_tx, final_rx) = std::sync::mpsc::channel();
+        let (preview_tx, _preview_rx) = std::sync::mpsc::sync_channel(1);
+        let mut was_held = false;
+        let mut speech_buf = Vec::new();
+        let mut last_preview = Instant::now();
+        let frame = vec![0.1; VAD_FRAME_SAMPLES];
+
+        let mut step = |held: bool, buf: &mut Vec<f32>, was: &mut bool| {
+            process_ptt_frame(
+                &frame,
+                held,
+                was,
+                buf,
+                &mut last_preview,
+                &final_tx,
+                &preview_tx,
+            )
+        };
+
+        // Not held: audio is ignored
+        step(false, &mut speech_buf, &mut was_held);
+        assert!(speech_buf.is_empty());
+
+        // Held for 3 frames, then released
+        for _ in 0..3 {
+            step(true, &mut speech_buf, &mut was_held);
+        }
+        assert!(final_rx.try_recv().is_err());
+        step(false, &mut speech_buf, &mut was_held);
+        assert_eq!(final_rx.try_recv().unwrap().len(), 3 * VAD_FRAME_SAMPLES);
+
+        // Further releases emit nothing
+        step(false, &mut speech_buf, &mut was_held);
+        assert!(final_rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_echo_gate() {
+        // Quiet mic frame while TTS is loud: treated as echo
+        let mut frame = vec![0.05; VAD_FRAME_SAMPLES];
+        assert!(echo_gate(&mut frame, 0.2));
+        assert!(frame.iter().all(|&s| s == 0.0));
+
+        // User speaking over TTS still gets through
+        let mut frame = vec![0.5; VAD_FRAME_SAMPLES];
+        assert!(!echo_gate(&mut frame, 0.2));
+        assert_eq!(frame[0], 0.5);
+
+        // No TTS output: nothing to gate
+        let mut frame = vec![0.05; VAD_FRAME_SAMPLES];
+        assert!(!echo_gate(&mut frame, 0.0));
+        assert_eq!(frame[0], 0.05);
+    }
+
+    #[test]
+    fn test_level_meter_clipping() {
+        // A loud sine just under full scale, flattened at the top like a clipped ADC
+        let clipped: Vec<f32> = (0..VAD_FRAME_SAMPLES)
+            .map(|i| (1.2 * (i as f32 * 0.3).sin()).clamp(-0.999, 0.999))
+            .collect();
+        let quiet = vec![0.05; VAD_FRAME_SAMPLES];
+        let mut meter = LevelMeter::new();
+
+        // One pop isn't sustained clipping
+        meter.update(&clipped);
+        meter.update(&quiet);
+        assert!(!meter.clipping());
+
+        for _ in 0..CLIP_SUSTAIN_FRAMES {
+            meter.update(&clipped);
+        }
+        assert!(meter.clipping());
+        assert!(matches!(
+            meter.event(),
+            crate::DisplayEvent::AudioLevel { clipping: true, .. }
+        ));
+
+        // The warning holds briefly, then clears once the input is clean
+        meter.update(&quiet);
+        assert!(meter.clipping());
+        for _ in 0..32 {
+            meter.update(&quiet);
+        }
+        assert!(!meter.clipping());
+    }
+
+    #[test]
+    fn test_find_device() {
+        let names: Vec<String> = ["MacBook Pro Microphone", "USB Audio", "USB Audio Pro"]
+            .iter()
+            .map(|n| n.to_string())
+            .collect();
+        assert_eq!(find_device(&names, "usb audio pro"), Some(2));
+        // An exact name wins over an earlier partial match
+        assert_eq!(find_device(&names, "USB Audio"), Some(1));
+        assert_eq!(find_device(&names, " macbook "), Some(0));
+        assert_eq!(find_device(&names, "Yeti"), None);
+    }
+
+    #[test]
+    fn test_ducker() {
+        let config = crate::config::Config::default();
+        let state = RuntimeState::new(&config);
+        let duck = config.interaction.duck_volume;
+        let mut ducker = Ducker::new();
+
+        // Speech without TTS playing (or crosstalk off) leaves the volume alone
+        ducker.update(&state, false, true, true);
+        assert_eq!(state.playback_volume(), 1.0);
+
+        // Talking over TTS ducks it, and pauses within the utterance keep it
+        ducker.update(&state, true, true, false);
+        assert_eq!(state.playback_volume(), duck);
+        ducker.update(&state, true, false, true);
+        assert_eq!(state.playback_volume(), duck);
+        // A blip that never became an utterance restores right away
+        ducker.update(&state, true, false, false);
+        assert_eq!(state.playback_volume(), 1.0);
+
+        // Playback ending mid-utterance restores
+        ducker.update(&state, true, true, true);
+        ducker.update(&state, false, true, true);
+        assert_eq!(state.playback_volume(), 1.0);
+
+        // So does muting, which stops frames reaching `update`
+        ducker.update(&state, true, true, true);
+        ducker.release(&state);
+        assert_eq!(state.playback_volume(), 1.0);
+    }
+
+    #[test]
+    fn test_level_meter_smoothing() {
+        let mut meter = LevelMeter::new();
+        let loud = meter.update(&vec![0.5; VAD_FRAME_SAMPLES]);
+        assert!((loud - 0.5).abs() < 1e-6, "rises immediately");
+        let falling = meter.update(&vec![0.0; VAD_FRAME_SAMPLES]);
+        assert!(
+            falling > 0.3 && falling < 0.5,
+            "falls gradually: {}",
+            falling
+        );
+    }
+}
@@ -0,0 +1,648 @@
+//! Reading and writing audio files
+//!
+//! WAV is always available; OGG Vorbis, MP3 and FLAC need `--features listen`.
+//! Audio is mono f32 throughout: readers return the samples with their sample
+//! rate, and `resample` converts to the 16kHz the transcriber expects.
+
+use std::fs::{self, File};
+use std::io::{BufWriter, Read, Write};
+use std::path::Path;
+
+#[cfg(feature = "listen")]
+pub use crate::resample::resample;
+
+#[cfg(feature = "listen")]
+use crate::capture::TARGET_RATE;
+
+// ============================================================================
+// WAV
+// ============================================================================
+
+/// Read a WAV file (16-bit PCM or 32-bit float), averaging channels down to mono
+pub fn read_wav(path: &Path) -> Result<(Vec<f32>, u32), Box<dyn std::error::Error + Send + Sync>> {
+    parse_wav(&fs::read(path)?)
+}
+
+const WAVE_FORMAT_PCM: u16 = 1;
+const WAVE_FORMAT_IEEE_FLOAT: u16 = 3;
+const WAVE_FORMAT_EXTENSIBLE: u16 = 0xFFFE;
+
+/// The `fmt ` chunk fields needed to decode `data`
+#[derive(Debug, Clone, Copy)]
+struct WavFormat {
+    format_tag: u16,
+    channels: u16,
+    sample_rate: u32,
+    bits_per_sample: u16,
+}
+
+impl WavFormat {
+    fn parse(body: &[u8]) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        if body.len() < 16 {
+            return Err("WAV fmt chunk is too short".into());
+        }
+        let u16_at = |i: usize| u16::from_le_bytes([body[i], body[i + 1]]);
+        let mut format_tag = u16_at(0);
+        // Extensible files keep the real format in the first two bytes of the
+        // subformat GUID
+        if format_tag == WAVE_FORMAT_EXTENSIBLE && body.len() >= 26 {
+            format_tag = u16_at(24);
+        }
+        Ok(Self {
+            format_tag,
+            channels: u16_at(2),
+            sample_rate: u32::from_le_bytes([body[4], body[5], body[6], body[7]]),
+            bits_per_sample: u16_at(14),
+        })
+    }
+}
+
+/// Walk the RIFF chunks for `fmt ` and `data`, skipping anything else
+/// (`LIST`, `fact`, `cue `, ...) wherever it appears
+fn parse_wav(bytes: &[u8]) -> Result<(Vec<f32>, u32), Box<dyn std::error::Error + Send + Sync>> {
+    if bytes.len() < 12 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+        return Err("Not a WAV file".into());
+    }
+
+    let mut format = None;
+    let mut pos = 12;
+    while pos + 8 <= bytes.len() {
+        let id = &bytes[pos..pos + 4];
+        let size = u32::from_le_bytes([
+            bytes[pos + 4],
+            bytes[pos + 5],
+            bytes[pos + 6],
+            bytes[pos + 7],
+        ]) as usize;
+        // Streamed files may leave the size unset, so clamp to what's there
+        let body = &bytes[pos + 8..(pos + 8).saturating_add(size).min(bytes.len())];
+
+        match id {
+            b"fmt " => format = Some(WavFormat::parse(body)?),
+            b"data" => {
+                let format = format.ok_or("WAV data chunk before fmt chunk")?;
+                return Ok((decode_wav_samples(body, format)?, format.sample_rate));
+            }
+            _ => {}
+        }
+
+        // Chunks are padded to an even length
+        pos = pos.saturating_add(8 + size + (size & 1));
+    }
+
+    Err("WAV file has no data chunk".into())
+}
+
+fn decode_wav_samples(
+    data: &[u8],
+    format: WavFormat,
+) -> Result<Vec<f32>, Box<dyn std::error::Error + Send + Sync>> {
+    let interleaved: Vec<f32> = match (format.format_tag, format.bits_per_sample) {
+        (WAVE_FORMAT_PCM, 16) => data
+            .chunks_exact(2)
+            .map(|c| i16::from_le_bytes([c[0], c[1]]) as f32 / 32768.0)
+            .collect(),
+        (WAVE_FORMAT_IEEE_FLOAT, 32) => data
+            .chunks_exact(4)
+            .map(|c| f32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+            .collect(),
+        (tag, bits) => {
+            return Err(format!(
+                "Unsupported WAV encoding (format {}, {} bits), expected 16-bit PCM or 32-bit float",
+                tag, bits
+            )
+            .into());
+        }
+    };
+
+    Ok(downmix(&interleaved, format.channels as usize))
+}
+
+/// Average interleaved frames of `channels` samples down to mono
+pub fn downmix(interleaved: &[f32], channels: usize) -> Vec<f32> {
+    if channels <= 1 {
+        return interleaved.to_vec();
+    }
+    interleaved
+        .chunks_exact(channels)
+        .map(|frame| frame.iter().sum::<f32>() / channels as f32)
+        .collect()
+}
+
+/// Write mono samples as a 16-bit PCM WAV file
+pub fn write_wav(path: &Path, samples: &[f32], sample_rate: u32) -> std::io::Result<()> {
+    let mut w = BufWriter::new(File::create(path)?);
+    write_wav_header(&mut w, sample_rate, samples.len() as u32)?;
+    write_pcm16(&mut w, samples)?;
+    w.flush()
+}
+
+/// Header of a 16-bit PCM mono WAV holding `num_samples`; files written as
+/// the audio arrives rewrite it once the length is known
+pub fn write_wav_header(
+    w: &mut impl Write,
+    sample_rate: u32,
+    num_samples: u32,
+) -> std::io::Result<()> {
+    let data_size = num_samples * 2;
+    w.write_all(b"RIFF")?;
+    w.write_all(&(36 + data_size).to_le_bytes())?;
+    w.write_all(b"WAVE")?;
+    w.write_all(b"fmt ")?;
+    w.write_all(&16u32.to_le_bytes())?;
+    w.write_all(&WAVE_FORMAT_PCM.to_le_bytes())?;
+    w.write_all(&1u16.to_le_bytes())?; // mono
+    w.write_all(&sample_rate.to_le_bytes())?;
+    w.write_all(&(sample_rate * 2).to_le_bytes())?;
+    w.write_all(&2u16.to_le_bytes())?;
+    w.write_all(&16u16.to_le_bytes())?;
+    w.write_all(b"data")?;
+    w.write_all(&data_size.to_le_bytes())
+}
+
+/// Append samples as the 16-bit PCM data of a WAV
+pub fn write_pcm16(w: &mut impl Write, samples: &[f32]) -> std::io::Result<()> {
+    for &s in samples {
+        let i = (s.clamp(-1.0, 1.0) * 32767.0) as i16;
+        w.write_all(&i.to_le_bytes())?;
+    }
+    Ok(())
+}
+
+// ============================================================================
+// OGG Vorbis
+// ============================================================================
+
+/// Layout of an audio file, as read before decoding
+#[cfg(feature = "listen")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AudioInfo {
+    pub sample_rate: u32,
+    pub channels: u16,
+    /// Length in seconds, if the container records it
+    pub duration_secs: Option<f32>,
+}
+
+/// Read an OGG Vorbis file, averaging channels down to mono
+#[cfg(feature = "listen")]
+pub fn read_ogg(path: &Path) -> Result<(Vec<f32>, u32), Box<dyn std::error::Error + Send + Sync>> {
+    let (info, stream) = read_ogg_streaming(path)?;
+    let mut samples = Vec::new();
+    for chunk in stream {
+        samples.extend(downmix(&chunk?, info.channels as usize));
+    }
+    Ok((samples, info.sample_rate))
+}
+
+/// Open an OGG Vorbis file for decoding a packet at a time
+///
+/// Chunks keep the source's interleaved channels (see `AudioInfo::channels`);
+/// pass them through `downmix` for mono.
+#[cfg(feature = "listen")]
+pub fn read_ogg_streaming(
+    path: &Path,
+) -> Result<(AudioInfo, OggStream), Box<dyn std::error::Error + Send + Sync>> {
+    use lewton::inside_ogg::OggStreamReader;
+
+    let mut file = File::open(path)?;
+    let last_granule = last_ogg_granule(&mut file)?;
+    let reader = OggStreamReader::new(file)?;
+    let sample_rate = reader.ident_hdr.audio_sample_rate;
+    let info = AudioInfo {
+        sample_rate,
+        channels: reader.ident_hdr.audio_channels as u16,
+        duration_secs: last_granule.map(|g| g as f32 / sample_rate as f32),
+    };
+    Ok((info, OggStream { reader }))
+}
+
+/// Decoded packets of interleaved samples from `read_ogg_streaming`
+#[cfg(feature = "listen")]
+pub struct OggStream {
+    reader: lewton::inside_ogg::OggStreamReader<File>,
+}
+
+#[cfg(feature = "listen")]
+impl Iterator for OggStream {
+    type Item = Result<Vec<f32>, Box<dyn std::error::Error + Send + Sync>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.reader.read_dec_packet_itl() {
+            Ok(Some(packet)) => Some(Ok(packet.iter().map(|&s| s as f32 / 32768.0).collect())),
+            Ok(None) => None,
+            Err(e) => Some(Err(e.into())),
+        }
+    }
+}
+
+/// Granule position (total samples per channel) of the last OGG page,
+/// leaving `file` rewound to the start
+#[cfg(feature = "listen")]
+fn last_ogg_granule(file: &mut File) -> std::io::Result<Option<u64>> {
+    use std::io::{Read, Seek, SeekFrom};
+
+    // Pages are at most ~64KB, so the last one starts within this tail
+    const TAIL: u64 = 65536;
+    let len = file.seek(SeekFrom::End(0))?;
+    file.seek(SeekFrom::Start(len.saturating_sub(TAIL)))?;
+    let mut tail = Vec::new();
+    file.read_to_end(&mut tail)?;
+    file.seek(SeekFrom::Start(0))?;
+
+    Ok(tail
+        .windows(14)
+        .rposition(|w| &w[..4] == b"OggS")
+        .map(|i| {
+            let mut granule = [0u8; 8];
+            granule.copy_from_slice(&tail[i + 6..i + 14]);
+            u64::from_le_bytes(granule)
+        })
+        .filter(|&g| g != u64::MAX))
+}
+
+/// Write mono samples as an OGG Vorbis file
+#[cfg(feature = "listen")]
+#[allow(dead_code)]
+pub fn write_ogg(
+    path: &Path,
+    samples: &[f32],
+    sample_rate: u32,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let mut writer = OggWriter::create(path, sample_rate)?;
+    writer.write(samples)?;
+    writer.finish()?;
+    Ok(())
+}
+
+/// Incremental mono OGG Vorbis writer for recordings of unknown length
+#[cfg(feature = "listen")]
+pub struct OggWriter {
+    encoder: vorbis_rs::VorbisEncoder<File>,
+    sample_rate: u32,
+    samples: usize,
+}
+
+#[cfg(feature = "listen")]
+impl OggWriter {
+    pub fn create(
+        path: &Path,
+        sample_rate: u32,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        use std::num::NonZero;
+
+        let encoder = vorbis_rs::VorbisEncoderBuilder::new(
+            NonZero::new(sample_rate).ok_or("Sample rate must be non-zero")?,
+            NonZero::new(1).unwrap(),
+            File::create(path)?,
+        )?
+        .build()?;
+        Ok(Self {
+            encoder,
+            sample_rate,
+            samples: 0,
+        })
+    }
+
+    pub fn write(
+        &mut self,
+        samples: &[f32],
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.encoder.encode_audio_block([samples])?;
+        self.samples += samples.len();
+        Ok(())
+    }
+
+    /// Finish the stream, returning the duration written in seconds
+    pub fn finish(self) -> Result<f32, Box<dyn std::error::Error + Send + Sync>> {
+        self.encoder.finish()?;
+        Ok(self.samples as f32 / self.sample_rate as f32)
+    }
+}
+
+// ============================================================================
+// MP3 / FLAC
+// ============================================================================
+
+/// Decode any format symphonia recognizes (MP3 and FLAC are enabled),
+/// averaging channels down to mono
+#[cfg(feature = "listen")]
+pub fn read_symphonia(
+    path: &Path,
+) -> Result<(Vec<f32>, u32), Box<dyn std::error::Error + Send + Sync>> {
+    use symphonia::core::audio::SampleBuffer;
+    use symphonia::core::codecs::{CODEC_TYPE_NULL, DecoderOptions};
+    use symphonia::core::errors::Error as SymphoniaError;
+    use symphonia::core::formats::FormatOptions;
+    use symphonia::core::io::MediaSourceStream;
+    use symphonia::core::meta::MetadataOptions;
+    use symphonia::core::probe::Hint;
+
+    let unsupported = |e: SymphoniaError| format!("Can't decode {}: {}", path.display(), e);
+
+    let stream = MediaSourceStream::new(Box::new(File::open(path)?), Default::default());
+    let mut hint = Hint::new();
+    if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
+        hint.with_extension(ext);
+    }
+    let mut format = symphonia::default::get_probe()
+        .format(
+            &hint,
+            stream,
+            &FormatOptions::default(),
+            &MetadataOptions::default(),
+        )
+        .map_err(unsupported)?
+        .format;
+
+    let track = format
+        .tracks()
+        .iter()
+        .find(|t| t.codec_params.codec != CODEC_TYPE_NULL)
+        .ok_or_else(|| format!("No audio track in {}", path.display()))?;
+    let track_id = track.id;
+    let mut sample_rate = track.codec_params.sample_rate;
+    let mut decoder = symphonia::default::get_codecs()
+        .make(&track.codec_params, &DecoderOptions::default())
+        .map_err(unsupported)?;
+
+    let mut samples = Vec::new();
+    let mut buffer: Option<SampleBuffer<f32>> = None;
+    loop {
+        let packet = match format.next_packet() {
+            Ok(packet) => packet,
+            Err(SymphoniaError::IoError(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
+                break;
+            }
+            Err(e) => return Err(unsupported(e).into()),
+        };
+        if packet.track_id() != track_id {
+            continue;
+        }
+
+        let decoded = match decoder.decode(&packet) {
+            Ok(decoded) => decoded,
+            // A corrupt frame; skip it rather than losing the whole file
+            Err(SymphoniaError::DecodeError(_)) => continue,
+            Err(e) => return Err(unsupported(e).into()),
+        };
+        let spec = *decoded.spec();
+        sample_rate.get_or_insert(spec.rate);
+        let needed = decoded.capacity() * spec.channels.count();
+        if buffer.as_ref().is_none_or(|b| b.capacity() < needed) {
+            buffer = Some(SampleBuffer::new(decoded.capacity() as u64, spec));
+        }
+        if let Some(buffer) = buffer.as_mut() {
+            buffer.copy_interleaved_ref(decoded);
+            samples.extend(downmix(buffer.samples(), spec.channels.count()));
+        }
+    }
+
+    let sample_rate =
+        sample_rate.ok_or_else(|| format!("No audio decoded from {}", path.display()))?;
+    Ok((samples, sample_rate))
+}
+
+// ============================================================================
+// By format
+// ============================================================================
+
+#[derive(Debug, PartialEq)]
+enum FileKind {
+    Wav,
+    Ogg,
+    /// MP3, FLAC or anything else symphonia can probe
+    Other,
+}
+
+/// Pick a decoder from the extension, or from the first bytes when the
+/// extension is missing or unfamiliar
+fn file_kind(path: &Path) -> std::io::Result<FileKind> {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_ascii_lowercase);
+    match ext.as_deref() {
+        Some("wav" | "wave") => return Ok(FileKind::Wav),
+        Some("ogg" | "oga") => return Ok(FileKind::Ogg),
+        Some("mp3" | "flac") => return Ok(FileKind::Other),
+        _ => {}
+    }
+
+    let mut head = [0u8; 12];
+    let n = File::open(path)?.read(&mut head)?;
+    Ok(sniff(&head[..n]))
+}
+
+fn sniff(head: &[u8]) -> FileKind {
+    if head.starts_with(b"RIFF") && head.get(8..12) == Some(b"WAVE") {
+        FileKind::Wav
+    } else if head.starts_with(b"OggS") {
+        FileKind::Ogg
+    } else {
+        FileKind::Other
+    }
+}
+
+/// Read a WAV, OGG, MP3 or FLAC file as mono
+pub fn read_audio(
+    path: &Path,
+) -> Result<(Vec<f32>, u32), Box<dyn std::error::Error + Send + Sync>> {
+    match file_kind(path)? {
+        FileKind::Wav => read_wav(path),
+        #[cfg(feature = "listen")]
+        FileKind::Ogg => read_ogg(path),
+        #[cfg(feature = "listen")]
+        FileKind::Other => read_symphonia(path),
+        #[cfg(not(feature = "listen"))]
+        _ => Err(format!(
+            "{} isn't a WAV file (OGG, MP3 and FLAC require --features listen)",
+            path.display()
+        )
+        .into()),
+    }
+}
+
+/// Read an audio file as 16kHz mono, e.g. for `--replay`. Without
+/// `--features listen` there's no resampler, so only 16kHz WAV is accepted
+pub fn read_audio_16k(path: &Path) -> Result<Vec<f32>, Box<dyn std::error::Error + Send + Sync>> {
+    let (samples, sample_rate) = read_audio(path)?;
+    #[cfg(feature = "listen")]
+    {
+        resample(&samples, sample_rate as usize, TARGET_RATE)
+    }
+    #[cfg(not(feature = "listen"))]
+    {
+        if sample_rate != 16000 {
+            return Err(format!(
+                "{} is {}Hz, only 16kHz WAV is supported without --features listen",
+                path.display(),
+                sample_rate
+            )
+            .into());
+        }
+        Ok(samples)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("silly-{}-{}", std::process::id(), name))
+    }
+
+    fn tone(len: usize) -> Vec<f32> {
+        (0..len).map(|i| (i as f32 * 0.05).sin() * 0.5).collect()
+    }
+
+    #[test]
+    fn test_wav_round_trip() {
+        let path = temp_path("round-trip.wav");
+        let samples = tone(1600);
+        write_wav(&path, &samples, 16000).unwrap();
+        let bytes = fs::read(&path).unwrap();
+        let (read, sample_rate) = read_wav(&path).unwrap();
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(bytes.len(), 44 + samples.len() * 2);
+        assert_eq!(sample_rate, 16000);
+        assert_eq!(read.len(), samples.len());
+        assert!(read.iter().zip(&samples).all(|(a, b)| (a - b).abs() < 1e-4));
+    }
+
+    /// Build a WAV file with `chunks` between `fmt ` and `data`
+    fn wav_bytes(format_tag: u16, channels: u16, bits: u16, chunks: &[u8], data: &[u8]) -> Vec<u8> {
+        let mut bytes = b"RIFF\0\0\0\0WAVE".to_vec();
+        bytes.extend(b"fmt ");
+        bytes.extend(16u32.to_le_bytes());
+        bytes.extend(format_tag.to_le_bytes());
+        bytes.extend(channels.to_le_bytes());
+        bytes.extend(22050u32.to_le_bytes());
+        bytes.extend((22050 * (channels * bits / 8) as u32).to_le_bytes());
+        bytes.extend((channels * bits / 8).to_le_bytes());
+        bytes.extend(bits.to_le_bytes());
+        bytes.extend(chunks);
+        bytes.extend(b"data");
+        bytes.extend((data.len() as u32).to_le_bytes());
+        bytes.extend(data);
+        bytes
+    }
+
+    fn pcm16(samples: &[i16]) -> Vec<u8> {
+        samples.iter().flat_map(|s| s.to_le_bytes()).collect()
+    }
+
+    #[test]
+    fn test_wav_skips_extra_chunks() {
+        // An odd-sized LIST chunk (padded), then fact and cue chunks
+        let mut chunks = b"LIST".to_vec();
+        chunks.extend(5u32.to_le_bytes());
+        chunks.extend(b"INFO!\0");
+        chunks.extend(b"fact");
+        chunks.extend(4u32.to_le_bytes());
+        chunks.extend(2u32.to_le_bytes());
+        chunks.extend(b"cue ");
+        chunks.extend(4u32.to_le_bytes());
+        chunks.extend(0u32.to_le_bytes());
+        let bytes = wav_bytes(WAVE_FORMAT_PCM, 1, 16, &chunks, &pcm16(&[16384, -16384]));
+
+        let (samples, sample_rate) = parse_wav(&bytes).unwrap();
+        assert_eq!(sample_rate, 22050);
+        assert_eq!(samples, [0.5, -0.5]);
+    }
+
+    #[test]
+    fn test_wav_stereo_downmix() {
+        let data = pcm16(&[16384, 0, -16384, -16384]);
+        let (samples, _) = parse_wav(&wav_bytes(WAVE_FORMAT_PCM, 2, 16, &[], &data)).unwrap();
+        assert_eq!(samples, [0.25, -0.5]);
+
+        let data: Vec<u8> = [1.0f32, 0.5, 0.0, -1.0]
+            .iter()
+            .flat_map(|s| s.to_le_bytes())
+            .collect();
+        let (samples, _) =
+            parse_wav(&wav_bytes(WAVE_FORMAT_IEEE_FLOAT, 2, 32, &[], &data)).unwrap();
+        assert_eq!(samples, [0.75, -0.5]);
+    }
+
+    #[test]
+    fn test_wav_rejects_bad_input() {
+        assert!(parse_wav(b"not a wav file at all").is_err());
+        assert!(parse_wav(b"RIFF\0\0\0\0WAVE").is_err());
+        // 32-bit integer PCM isn't float, whatever the bit depth suggests
+        let bytes = wav_bytes(WAVE_FORMAT_PCM, 1, 32, &[], &[0; 8]);
+        assert!(parse_wav(&bytes).is_err());
+    }
+
+    #[cfg(feature = "listen")]
+    #[test]
+    fn test_ogg_round_trip() {
+        let path = temp_path("round-trip.ogg");
+        let samples = tone(16000);
+        write_ogg(&path, &samples, 16000).unwrap();
+        let (read, sample_rate) = read_audio(&path).unwrap();
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(sample_rate, 16000);
+        // Vorbis is lossy and pads the final block
+        assert!(read.len().abs_diff(samples.len()) < 2048);
+        let rms = |s: &[f32]| (s.iter().map(|x| x * x).sum::<f32>() / s.len() as f32).sqrt();
+        assert!((rms(&read) - rms(&samples)).abs() < 0.05);
+    }
+
+    #[cfg(feature = "listen")]
+    #[test]
+    fn test_ogg_streaming_info() {
+        let path = temp_path("streaming.ogg");
+        write_ogg(&path, &tone(32000), 16000).unwrap();
+        let (info, stream) = read_ogg_streaming(&path).unwrap();
+        let chunks: Vec<Vec<f32>> = stream.map(Result::unwrap).collect();
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(info.sample_rate, 16000);
+        assert_eq!(info.channels, 1);
+        assert!((info.duration_secs.unwrap() - 2.0).abs() < 0.01);
+        assert!(chunks.len() > 1);
+        let decoded: usize = chunks.iter().map(Vec::len).sum();
+        assert!(decoded.abs_diff(32000) < 2048);
+    }
+
+    #[test]
+    fn test_downmix() {
+        assert_eq!(downmix(&[0.5, -0.5], 1), [0.5, -0.5]);
+        assert_eq!(downmix(&[1.0, 0.0, 0.5, 0.5], 2), [0.5, 0.5]);
+        assert_eq!(downmix(&[0.3, 0.3, 0.3, 0.9], 3), [0.3]);
+    }
+
+    #[test]
+    fn test_sniff() {
+        assert_eq!(sniff(b"RIFF\x24\0\0\0WAVEfmt "), FileKind::Wav);
+        assert_eq!(sniff(b"OggS\0\x02"), FileKind::Ogg);
+        assert_eq!(sniff(b"fLaC\0\0\0\x22"), FileKind::Other);
+        assert_eq!(sniff(b"ID3\x04"), FileKind::Other);
+        assert_eq!(sniff(b""), FileKind::Other);
+    }
+
+    #[test]
+    fn test_read_audio_sniffs_unknown_extension() {
+        let path = temp_path("recording.bin");
+        write_wav(&path, &[0.5; 160], 8000).unwrap();
+        let (samples, sample_rate) = read_audio(&path).unwrap();
+        let _ = fs::remove_file(&path);
+        assert_eq!(sample_rate, 8000);
+        assert_eq!(samples.len(), 160);
+    }
+
+    #[cfg(feature = "listen")]
+    #[test]
+    fn test_symphonia_rejects_corrupt_file() {
+        let path = temp_path("corrupt.mp3");
+        fs::write(&path, b"definitely not an mp3 file").unwrap();
+        let result = read_audio(&path);
+        let _ = fs::remove_file(&path);
+        assert!(result.unwrap_err().to_string().contains("Can't decode"));
+    }
+}
@@ -0,0 +1,134 @@
+//! Transcription benchmark (`silly bench`)
+//!
+//! Runs the transcriber over one buffer several times and reports latency
+//! percentiles and the realtime factor (seconds of audio per second of
+//! inference), handy for comparing models and reporting performance issues.
+//! Without `--file` a few seconds of synthetic voiced audio is used.
+
+use crate::audiofile;
+use crate::config::{self, Config};
+use crate::model_manager;
+use crate::transcriber::Transcriber;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+const SAMPLE_RATE: u32 = 16000;
+/// Length of the synthetic buffer used without `--file`
+const SYNTHETIC_SECS: f32 = 5.0;
+
+/// Latency percentiles over all iterations
+#[derive(Debug, PartialEq)]
+struct Summary {
+    min: Duration,
+    median: Duration,
+    p95: Duration,
+}
+
+/// Nearest-rank percentiles of `latencies` (sorted in place)
+fn summarize(latencies: &mut [Duration]) -> Summary {
+    latencies.sort();
+    let rank = |p: f32| {
+        let idx = (p * latencies.len() as f32).ceil() as usize;
+        latencies[idx.saturating_sub(1).min(latencies.len() - 1)]
+    };
+    Summary {
+        min: latencies[0],
+        median: rank(0.5),
+        p95: rank(0.95),
+    }
+}
+
+/// A vowel-like tone: harmonics of a wavering pitch under a syllable-rate
+/// envelope, loud enough to pass the transcriber's silence check
+fn synthetic_speech(secs: f32) -> Vec<f32> {
+    let rate = SAMPLE_RATE as f32;
+    (0..(secs * rate) as usize)
+        .map(|i| {
+            let t = i as f32 / rate;
+            let pitch = 140.0 + 20.0 * (t * 2.0 * std::f32::consts::PI * 0.7).sin();
+            let envelope = 0.5 + 0.5 * (t * 2.0 * std::f32::consts::PI * 4.0).sin().abs();
+            let voice: f32 = (1..=5)
+                .map(|h| (t * 2.0 * std::f32::consts::PI * pitch * h as f32).sin() / h as f32)
+                .sum();
+            0.1 * envelope * voice
+        })
+        .collect()
+}
+
+pub fn run_bench(
+    file: Option<PathBuf>,
+    iters: usize,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let iters = iters.max(1);
+    let (samples, source) = match &file {
+        Some(path) => (audiofile::read_audio_16k(path)?, path.display().to_string()),
+        None => (synthetic_speech(SYNTHETIC_SECS), "synthetic".to_string()),
+    };
+    let audio_secs = samples.len() as f32 / SAMPLE_RATE as f32;
+
+    let model_path = model_manager::transcriber_model_path(
+        &Config::load().map_err(|p| config::problem_report(&p))?,
+    );
+    println!("Model: {}", model_path.display());
+    // No silence floor: a quiet file would otherwise skip the model and
+    // report an impossible realtime factor
+    let mut transcriber = Transcriber::new(&model_path)?.with_silence_floor(0.0);
+
+    // Keep the cold start out of the numbers
+    let warmup = transcriber.warmup();
+    println!("Warmup: {}ms", warmup.as_millis());
+
+    println!(
+        "Audio: {:.1}s ({}) x {} iterations",
+        audio_secs, source, iters
+    );
+    let mut latencies = Vec::with_capacity(iters);
+    for _ in 0..iters {
+        let start = Instant::now();
+        transcriber.transcribe_slice(&samples)?;
+        latencies.push(start.elapsed());
+    }
+
+    let summary = summarize(&mut latencies);
+    println!(
+        "Latency: min {}ms | median {}ms | p95 {}ms",
+        summary.min.as_millis(),
+        summary.median.as_millis(),
+        summary.p95.as_millis()
+    );
+    println!(
+        "Realtime factor: {:.1}x (median), {:.1}x (best)",
+        audio_secs / summary.median.as_secs_f32(),
+        audio_secs / summary.min.as_secs_f32()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summarize() {
+        let ms = Duration::from_millis;
+        let mut latencies: Vec<Duration> = (1..=20).rev().map(ms).collect();
+        assert_eq!(
+            summarize(&mut latencies),
+            Summary {
+                min: ms(1),
+                median: ms(10),
+                p95: ms(19),
+            }
+        );
+
+        let mut single = [ms(7)];
+        assert_eq!(
+            summarize(&mut single),
+            Summary {
+                min: ms(7),
+                median: ms(7),
+                p95: ms(7),
+            }
+        );
+    }
+}
@@ -1,44 +1,26 @@
+use crate::resample::StreamResampler;
 use flume::Sender;
-use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 
 pub const TARGET_RATE: usize = 16000;
 const CAPTURE_SAMPLE_RATE: usize = 48000;
 
-pub fn resample(samples: &[f32], from_rate: usize, to_rate: usize) -> Vec<f32> {
-    if from_rate == to_rate {
-        return samples.to_vec();
-    }
-    let ratio = to_rate as f64 / from_rate as f64;
-    let new_len = (samples.len() as f64 * ratio) as usize;
-    (0..new_len)
-        .map(|i| {
-            let src_idx = i as f64 / ratio;
-            let idx = src_idx as usize;
-            let frac = src_idx - idx as f64;
-            if idx + 1 < samples.len() {
-                samples[idx] * (1.0 - frac as f32) + samples[idx + 1] * frac as f32
-            } else {
-                samples.get(idx).copied().unwrap_or(0.0)
-            }
-        })
-        .collect()
-}
-
 pub fn capture_mic(
     tx: Sender<Vec<f32>>,
     running: Arc<AtomicBool>,
+    device_name: Option<&str>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+    use cpal::traits::{DeviceTrait, StreamTrait};
 
-    let host = cpal::default_host();
-    let device = host.default_input_device().ok_or("No input device")?;
+    let device = crate::audio::input_device(device_name)?;
     let supported = device.default_input_config()?;
     let sample_rate = u32::from(supported.sample_rate()) as usize;
     let channels = supported.channels() as usize;
 
     println!("Mic: {}Hz {}ch", sample_rate, channels);
 
+    let mut resampler = StreamResampler::new(sample_rate, TARGET_RATE)?;
     let stream = device.build_input_stream(
         &supported.config(),
         move |data: &[f32], _| {
@@ -49,8 +31,10 @@ pub fn capture_mic(
                     .map(|c| c.iter().sum::<f32>() / channels as f32)
                     .collect()
             };
-            let resampled = resample(&mono, sample_rate, TARGET_RATE);
-            let _ = tx.send(resampled);
+            let resampled = resampler.process(&mono);
+            if !resampled.is_empty() {
+                let _ = tx.send(resampled);
+            }
         },
         |e| eprintln!("Mic error: {}", e),
         None,
@@ -102,6 +86,7 @@ pub fn capture_system(
         .with_channel_count(1);
 
     let mut stream = SCStream::new(&filter, &config);
+    let resampler = Mutex::new(StreamResampler::new(CAPTURE_SAMPLE_RATE, TARGET_RATE)?);
 
     stream.add_output_handler(
         move |sample: CMSampleBuffer, of_type: SCStreamOutputType| {
@@ -118,8 +103,13 @@ pub fn capture_system(
                         .chunks_exact(4)
                         .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
                         .collect();
-                    let resampled = resample(&samples, CAPTURE_SAMPLE_RATE, TARGET_RATE);
-                    let _ = tx.send(resampled);
+                    let Ok(mut resampler) = resampler.lock() else {
+                        return;
+                    };
+                    let resampled = resampler.process(&samples);
+                    if !resampled.is_empty() {
+                        let _ = tx.send(resampled);
+                    }
                 }
             }
         },
@@ -1,4 +1,67 @@
-use crate::llm::{LlmBackend, Message, Role};
+use crate::config::TrimStrategy;
+use crate::llm::{LlmBackend, Message, Role, TransientError};
+use chrono::{DateTime, Local};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+/// Directory for saved conversations (`/save`, `/load`)
+const SESSIONS_DIR: &str = "sessions";
+
+/// Session name auto-saved and restored when `chat.persist` is set
+pub const LAST_SESSION: &str = "last";
+
+/// Default context budget (`chat.max_context_words`)
+pub const DEFAULT_MAX_CONTEXT_WORDS: usize = 3000;
+
+/// Room left in the budget for the summary note when summarizing
+const SUMMARY_WORDS: usize = 100;
+
+/// Default retries for requests that fail to start (`chat.max_retries`)
+pub const DEFAULT_MAX_RETRIES: u32 = 3;
+
+/// Wait before the first retry, doubled for each one after
+const RETRY_DELAY: Duration = Duration::from_millis(500);
+
+/// Path of a saved conversation, `None` if the name isn't a plain file name
+pub fn session_path(name: &str) -> Option<PathBuf> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '_');
+    valid.then(|| Path::new(SESSIONS_DIR).join(format!("{}.json", name)))
+}
+
+/// Resolve `chat.system_prompt` into the prompt text
+///
+/// The setting is read as a file when it names one, and used as the prompt
+/// itself otherwise. Unset means the default persona. `Err` if it looks like
+/// a file path but there's no such file.
+pub fn resolve_persona(setting: Option<&str>, name: &str) -> Result<String, String> {
+    let Some(setting) = setting.map(str::trim).filter(|s| !s.is_empty()) else {
+        return Ok(system_prompt(name));
+    };
+
+    let path = Path::new(setting);
+    let prompt = if path.is_file() {
+        fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", setting, e))?
+    } else if is_path_like(setting) {
+        return Err(format!("Persona file not found: {}", setting));
+    } else {
+        setting.to_string()
+    };
+    Ok(prompt.replace("{name}", name))
+}
+
+/// A single word with a directory or prompt-file extension, e.g. `personas/pirate.md`
+fn is_path_like(setting: &str) -> bool {
+    !setting.contains(char::is_whitespace)
+        && (setting.contains('/')
+            || [".txt", ".md", ".prompt"]
+                .iter()
+                .any(|ext| setting.ends_with(ext)))
+}
 
 pub fn system_prompt(name: &str) -> String {
     format!(
@@ -24,6 +87,11 @@ pub fn system_prompt(name: &str) -> String {
 pub struct Chat {
     backend: Box<dyn LlmBackend>,
     history: Vec<Message>,
+    /// History is trimmed once it grows past this many words
+    max_context_words: usize,
+    trim_strategy: TrimStrategy,
+    max_retries: u32,
+    retry_delay: Duration,
 }
 
 impl Chat {
@@ -31,9 +99,24 @@ impl Chat {
         Self {
             backend,
             history: Vec::new(),
+            max_context_words: DEFAULT_MAX_CONTEXT_WORDS,
+            trim_strategy: TrimStrategy::default(),
+            max_retries: DEFAULT_MAX_RETRIES,
+            retry_delay: RETRY_DELAY,
         }
     }
 
+    pub fn with_context_budget(mut self, max_words: usize, strategy: TrimStrategy) -> Self {
+        self.max_context_words = max_words;
+        self.trim_strategy = strategy;
+        self
+    }
+
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
     /// Estimate total words in conversation history
     pub fn context_words(&self) -> usize {
         self.history
@@ -47,6 +130,7 @@ impl Chat {
         self.history.push(Message {
             role: Role::User,
             content: message.to_string(),
+            time: Some(Local::now()),
         });
     }
 
@@ -55,6 +139,7 @@ impl Chat {
         self.history.push(Message {
             role: Role::Assistant,
             content: message.to_string(),
+            time: Some(Local::now()),
         });
     }
 
@@ -63,12 +148,209 @@ impl Chat {
         self.history.pop();
     }
 
+    /// Write the conversation to `path` as a JSON list of role/content messages
+    pub fn save_history(
+        &self,
+        path: &Path,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(&self.history)?)?;
+        Ok(())
+    }
+
+    /// Render the user/assistant turns as Markdown, `None` if there are none
+    ///
+    /// Turns restored by `/load` have no timestamp and get a bare header.
+    pub fn to_markdown(&self, exported: DateTime<Local>) -> Option<String> {
+        let turns: Vec<&Message> = self
+            .history
+            .iter()
+            .filter(|m| m.role != Role::System)
+            .collect();
+        if turns.is_empty() {
+            return None;
+        }
+
+        let mut md = format!(
+            "# Conversation\n\n_Exported {}_\n",
+            exported.format("%Y-%m-%d %H:%M")
+        );
+        for m in turns {
+            let role = if m.role == Role::User {
+                "User"
+            } else {
+                "Assistant"
+            };
+            match m.time {
+                Some(time) => {
+                    md.push_str(&format!("\n## {} ({})\n\n", role, time.format("%H:%M:%S")))
+                }
+                None => md.push_str(&format!("\n## {}\n\n", role)),
+            }
+            md.push_str(m.content.trim());
+            md.push('\n');
+        }
+        Some(md)
+    }
+
+    /// Replace the conversation with one saved by `save_history`
+    ///
+    /// Histories over the context budget lose their oldest messages. Returns
+    /// the number of words restored.
+    pub fn load_history(
+        &mut self,
+        path: &Path,
+    ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+        let history: Vec<Message> = serde_json::from_str(&fs::read_to_string(path)?)?;
+        self.history = history;
+        let drop = self.messages_over(self.max_context_words);
+        self.history.drain(..drop);
+        Ok(self.context_words())
+    }
+
+    /// Bring the history back under the context budget
+    ///
+    /// `DropOldest` discards the oldest turns. `Summarize` asks the LLM to
+    /// condense them into one system note; if that fails they're dropped and
+    /// the error returned. Returns the number of messages removed.
+    pub fn trim_context(&mut self) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+        if self.context_words() <= self.max_context_words {
+            return Ok(0);
+        }
+
+        match self.trim_strategy {
+            TrimStrategy::DropOldest => {
+                let drop = self.messages_over(self.max_context_words);
+                self.history.drain(..drop);
+                Ok(drop)
+            }
+            TrimStrategy::Summarize => {
+                let budget = self.max_context_words.saturating_sub(SUMMARY_WORDS);
+                let drop = self.messages_over(budget);
+                let dropped: Vec<Message> = self.history.drain(..drop).collect();
+                let summary = self.summarize(&dropped)?;
+                self.history.insert(
+                    0,
+                    Message {
+                        role: Role::System,
+                        content: format!("Summary of the earlier conversation: {}", summary),
+                        time: None,
+                    },
+                );
+                Ok(drop)
+            }
+        }
+    }
+
+    /// Ask the LLM for a short summary of `messages`
+    fn summarize(
+        &mut self,
+        messages: &[Message],
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let transcript: Vec<String> = messages
+            .iter()
+            .map(|m| {
+                let role = match m.role {
+                    Role::System => "Note",
+                    Role::User => "User",
+                    Role::Assistant => "Assistant",
+                };
+                format!("{}: {}", role, m.content)
+            })
+            .collect();
+        let request = Message {
+            role: Role::User,
+            content: format!(
+                "Summarize this conversation in under {} words, keeping names, facts and decisions. Reply with the summary only.\n\n{}",
+                SUMMARY_WORDS,
+                transcript.join("\n")
+            ),
+            time: None,
+        };
+        let summary = self.backend.generate(&[request], &mut |_| {})?;
+        let summary = summary.trim();
+        if summary.is_empty() {
+            return Err("LLM returned an empty summary".into());
+        }
+        Ok(summary.to_string())
+    }
+
+    /// Number of oldest messages to drop for the history to fit in `max_words`
+    ///
+    /// The newest message is always kept, and what's left never starts with
+    /// an assistant reply.
+    fn messages_over(&self, max_words: usize) -> usize {
+        let words: Vec<usize> = self
+            .history
+            .iter()
+            .map(|m| m.content.split_whitespace().count())
+            .collect();
+        let mut total: usize = words.iter().sum();
+        let mut drop = 0;
+        while drop + 1 < self.history.len()
+            && (total > max_words || self.history[drop].role == Role::Assistant)
+        {
+            total -= words[drop];
+            drop += 1;
+        }
+        drop
+    }
+
+    /// Replace the system prompt and start the conversation over
+    pub fn set_system_prompt(&mut self, prompt: &str) {
+        self.backend.set_system_prompt(prompt);
+        self.history.clear();
+    }
+
+    /// Switch the backend to another model, keeping the history
+    pub fn set_model(
+        &mut self,
+        name: &str,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("Model name can't be empty".into());
+        }
+        self.backend.set_model(name)
+    }
+
     /// Generate response with streaming callback
+    ///
+    /// Stops early once `cancel` is set, returning the partial response.
+    /// Requests that fail to start with a `TransientError` are retried with
+    /// exponential backoff, calling `on_retry(attempt, max_retries)` first;
+    /// once tokens have streamed, errors are returned as they are.
     pub fn generate(
         &mut self,
+        cancel: &AtomicBool,
+        mut on_retry: impl FnMut(u32, u32),
         mut on_token: impl FnMut(&str),
     ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
-        self.backend.generate(&self.history, &mut on_token)
+        let mut delay = self.retry_delay;
+        let mut attempt = 0;
+        loop {
+            let mut streaming = false;
+            let result = self
+                .backend
+                .generate_cancellable(&self.history, cancel, &mut |token| {
+                    streaming = true;
+                    on_token(token);
+                });
+
+            match result {
+                Err(e) if !streaming && attempt < self.max_retries && e.is::<TransientError>() => {
+                    attempt += 1;
+                    on_retry(attempt, self.max_retries);
+                    if !sleep_unless_cancelled(delay, cancel) {
+                        return Ok(String::new());
+                    }
+                    delay *= 2;
+                }
+                result => return result,
+            }
+        }
     }
 
     /// Get number of messages in history
@@ -77,3 +359,273 @@ impl Chat {
         self.history.len()
     }
 }
+
+/// Sleep for `duration`, waking early if `cancel` is set. Returns false if cancelled.
+fn sleep_unless_cancelled(duration: Duration, cancel: &AtomicBool) -> bool {
+    let step = Duration::from_millis(50);
+    let mut remaining = duration;
+    while !remaining.is_zero() {
+        if cancel.load(Ordering::SeqCst) {
+            return false;
+        }
+        let nap = remaining.min(step);
+        std::thread::sleep(nap);
+        remaining -= nap;
+    }
+    !cancel.load(Ordering::SeqCst)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct NoBackend;
+
+    impl LlmBackend for NoBackend {
+        fn generate_cancellable(
+            &mut self,
+            _messages: &[Message],
+            _cancel: &AtomicBool,
+            _on_token: &mut dyn FnMut(&str),
+        ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+            Ok(String::new())
+        }
+
+        fn set_system_prompt(&mut self, _prompt: &str) {}
+    }
+
+    /// History of `turns` user/assistant pairs, 100 words per message
+    fn long_chat(turns: usize) -> Chat {
+        let mut chat = Chat::new(Box::new(NoBackend));
+        let words = "word ".repeat(100);
+        for i in 0..turns {
+            chat.history_push_user(&format!("question {} {}", i, words));
+            chat.history_push_assistant(&words);
+        }
+        chat
+    }
+
+    #[test]
+    fn test_session_path() {
+        assert_eq!(
+            session_path("monday-standup"),
+            Some(PathBuf::from("sessions/monday-standup.json"))
+        );
+        assert_eq!(session_path(""), None);
+        assert_eq!(session_path("../config"), None);
+        assert_eq!(session_path("a/b"), None);
+    }
+
+    #[test]
+    fn test_save_and_load_history() {
+        let path = std::env::temp_dir().join(format!("silly-history-{}.json", std::process::id()));
+        let mut chat = Chat::new(Box::new(NoBackend));
+        chat.history_push_user("what is the capital of France");
+        chat.history_push_assistant("Paris.");
+        chat.save_history(&path).unwrap();
+
+        let json = fs::read_to_string(&path).unwrap();
+        assert!(json.contains(r#""role": "user""#));
+
+        let mut restored = Chat::new(Box::new(NoBackend));
+        assert_eq!(restored.load_history(&path).unwrap(), 7);
+        assert_eq!(restored.history_len(), 2);
+        assert_eq!(restored.history[1].role, Role::Assistant);
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_load_trims_oldest() {
+        let mut chat = Chat::new(Box::new(NoBackend));
+        let turn = "word ".repeat(DEFAULT_MAX_CONTEXT_WORDS / 4);
+        for _ in 0..4 {
+            chat.history_push_user(&turn);
+            chat.history_push_assistant(&turn);
+        }
+        let path = std::env::temp_dir().join(format!("silly-trim-{}.json", std::process::id()));
+        chat.save_history(&path).unwrap();
+
+        let words = chat.load_history(&path).unwrap();
+        assert!(words <= DEFAULT_MAX_CONTEXT_WORDS);
+        assert_eq!(chat.history_len(), 4);
+        assert_eq!(chat.history[0].role, Role::User);
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_to_markdown() {
+        use chrono::TimeZone;
+
+        let mut chat = Chat::new(Box::new(NoBackend));
+        let exported = Local.with_ymd_and_hms(2026, 3, 14, 9, 30, 0).unwrap();
+        assert_eq!(chat.to_markdown(exported), None);
+
+        chat.history_push_user("what is the capital of France");
+        chat.history_push_assistant("Paris.\n");
+        chat.history[0].time = Some(Local.with_ymd_and_hms(2026, 3, 14, 9, 29, 5).unwrap());
+        chat.history[1].time = None;
+        assert_eq!(
+            chat.to_markdown(exported).unwrap(),
+            "# Conversation\n\n_Exported 2026-03-14 09:30_\n\n\
+             ## User (09:29:05)\n\nwhat is the capital of France\n\n\
+             ## Assistant\n\nParis.\n"
+        );
+    }
+
+    #[test]
+    fn test_trim_drop_oldest() {
+        let mut chat = long_chat(20).with_context_budget(1000, TrimStrategy::DropOldest);
+        assert!(chat.context_words() > 4000);
+
+        let dropped = chat.trim_context().unwrap();
+        assert!(chat.context_words() <= 1000);
+        assert_eq!(dropped + chat.history_len(), 40);
+        // Starts on a user turn and keeps the newest exchange
+        assert_eq!(chat.history[0].role, Role::User);
+        assert!(
+            chat.history[chat.history_len() - 2]
+                .content
+                .starts_with("question 19")
+        );
+
+        // Already within budget
+        assert_eq!(chat.trim_context().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_trim_summarize() {
+        struct Summarizer;
+
+        impl LlmBackend for Summarizer {
+            fn generate_cancellable(
+                &mut self,
+                messages: &[Message],
+                _cancel: &AtomicBool,
+                _on_token: &mut dyn FnMut(&str),
+            ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+                assert!(messages[0].content.contains("User: question 0"));
+                Ok(" They asked twenty questions. ".to_string())
+            }
+
+            fn set_system_prompt(&mut self, _prompt: &str) {}
+        }
+
+        let mut chat = long_chat(20).with_context_budget(1000, TrimStrategy::Summarize);
+        chat.backend = Box::new(Summarizer);
+        chat.trim_context().unwrap();
+        assert!(chat.context_words() <= 1000);
+        assert_eq!(chat.history[0].role, Role::System);
+        assert!(
+            chat.history[0]
+                .content
+                .ends_with("They asked twenty questions.")
+        );
+        assert_eq!(chat.history[1].role, Role::User);
+    }
+
+    #[test]
+    fn test_set_model() {
+        let mut chat = long_chat(1);
+        assert!(chat.set_model("  ").is_err());
+        // Backends that can't switch say so instead of panicking
+        assert!(chat.set_model("llama3").is_err());
+        assert_eq!(chat.history_len(), 2);
+    }
+
+    #[test]
+    fn test_resolve_persona() {
+        assert_eq!(resolve_persona(None, "Silly"), Ok(system_prompt("Silly")));
+        assert_eq!(
+            resolve_persona(Some("  "), "Silly"),
+            Ok(system_prompt("Silly"))
+        );
+        assert_eq!(
+            resolve_persona(Some("You are {name}, a pirate."), "Polly"),
+            Ok("You are Polly, a pirate.".to_string())
+        );
+
+        let path = std::env::temp_dir().join(format!("silly-persona-{}.md", std::process::id()));
+        fs::write(&path, "You are {name}. Answer in haiku.").unwrap();
+        assert_eq!(
+            resolve_persona(path.to_str(), "Basho"),
+            Ok("You are Basho. Answer in haiku.".to_string())
+        );
+        let _ = fs::remove_file(&path);
+
+        assert!(resolve_persona(Some("personas/missing.md"), "Silly").is_err());
+    }
+
+    #[test]
+    fn test_set_system_prompt_resets_history() {
+        let mut chat = long_chat(3);
+        chat.set_system_prompt("You are terse.");
+        assert_eq!(chat.history_len(), 0);
+    }
+
+    /// Fails with `error` for the first `failures` calls, then answers
+    struct Flaky {
+        failures: u32,
+        calls: u32,
+        error: fn() -> Box<dyn std::error::Error + Send + Sync>,
+    }
+
+    impl LlmBackend for Flaky {
+        fn generate_cancellable(
+            &mut self,
+            _messages: &[Message],
+            _cancel: &AtomicBool,
+            on_token: &mut dyn FnMut(&str),
+        ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+            self.calls += 1;
+            if self.calls <= self.failures {
+                return Err((self.error)());
+            }
+            on_token("Hello.");
+            Ok("Hello.".to_string())
+        }
+
+        fn set_system_prompt(&mut self, _prompt: &str) {}
+    }
+
+    fn flaky_chat(failures: u32, error: fn() -> Box<dyn std::error::Error + Send + Sync>) -> Chat {
+        let mut chat = Chat::new(Box::new(Flaky {
+            failures,
+            calls: 0,
+            error,
+        }));
+        chat.retry_delay = Duration::from_millis(1);
+        chat.history_push_user("hi");
+        chat
+    }
+
+    #[test]
+    fn test_retry_transient_errors() {
+        let mut chat = flaky_chat(2, || Box::new(TransientError("connection refused".into())));
+        let mut retries = Vec::new();
+        let result = chat.generate(
+            &AtomicBool::new(false),
+            |n, max| retries.push((n, max)),
+            |_| {},
+        );
+        assert_eq!(result.unwrap(), "Hello.");
+        assert_eq!(
+            retries,
+            [(1, DEFAULT_MAX_RETRIES), (2, DEFAULT_MAX_RETRIES)]
+        );
+
+        // Gives up after max_retries
+        let mut chat =
+            flaky_chat(10, || Box::new(TransientError("timed out".into()))).with_max_retries(1);
+        let result = chat.generate(&AtomicBool::new(false), |_, _| {}, |_| {});
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_no_retry_on_client_errors() {
+        let mut chat = flaky_chat(1, || "API error 404 Not Found: model not found".into());
+        let mut retries = 0;
+        let result = chat.generate(&AtomicBool::new(false), |_, _| retries += 1, |_| {});
+        assert!(result.is_err());
+        assert_eq!(retries, 0);
+    }
+}
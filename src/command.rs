@@ -4,28 +4,53 @@
 //! 1. Stop commands - halt TTS immediately, don't pass to LLM
 //! 2. Mode commands - change application mode
 //! 3. Toggle commands - mute/unmute, enable/disable features
-//! 4. Pass-through - send to LLM for processing
+//! 4. Pass-through - send to LLM for processing (rejected in Command mode)
 
 use crate::config::Config;
-use crate::fuzzy::{clean_for_matching, fuzzy_match};
-use std::fs::OpenOptions;
-use std::io::Write;
-
-fn debug_log(msg: &str) {
-    if let Ok(mut file) = OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open("debug.log")
-    {
-        let _ = writeln!(
-            file,
-            "{}: {}",
-            chrono::Utc::now().format("%H:%M:%S%.3f"),
-            msg
-        );
-    }
-}
-use crate::state::{AppMode, SharedState};
+use crate::fuzzy::{clean_for_matching, fuzzy_match_within, fuzzy_match_words, levenshtein};
+use crate::logger;
+use crate::render::OrbStyle;
+use std::path::PathBuf;
+
+use crate::state::{AppMode, MAX_TTS_STEPS, MIN_TTS_STEPS, SharedState, TTS_SPEED_STEP};
+
+/// Edits allowed per character of each word when fuzzy matching built-in
+/// commands (capped by `commands.fuzzy_budget`)
+const BUILTIN_FUZZY_BUDGET: f32 = 0.15;
+
+/// How long a shutdown waits for confirmation (`commands.confirm_shutdown`)
+const SHUTDOWN_CONFIRM_SECS: u64 = 10;
+
+/// Replies that go ahead with a pending shutdown
+const SHUTDOWN_CONFIRM_PHRASES: &[&str] = &["confirm", "yes", "yes confirm", "confirmed"];
+
+/// Replies that cancel a pending shutdown without reaching the LLM
+const SHUTDOWN_CANCEL_PHRASES: &[&str] = &["no", "cancel", "never mind", "nevermind"];
+
+/// Voice command summary for "what can you do", short enough to speak
+const SPOKEN_HELP: &str = "You can say stop, pause, resume, mute, unmute, speak faster or slower, \
+                           repeat that, take a note, typing mode, export conversation, or stand down.";
+
+/// Language of the default transcriber model, so "switch to English" gets
+/// back to it when English has no `transcriber.models` entry of its own
+const DEFAULT_MODEL_LANGUAGE: &str = "en";
+
+/// Language codes (`transcriber.models` keys) and their spoken names
+const LANGUAGE_NAMES: &[(&str, &str)] = &[
+    ("en", "english"),
+    ("es", "spanish"),
+    ("fr", "french"),
+    ("de", "german"),
+    ("it", "italian"),
+    ("pt", "portuguese"),
+    ("nl", "dutch"),
+    ("pl", "polish"),
+    ("ru", "russian"),
+    ("uk", "ukrainian"),
+    ("ja", "japanese"),
+    ("zh", "chinese"),
+    ("ko", "korean"),
+];
 
 /// Result of command processing
 #[derive(Debug, Clone)]
@@ -34,6 +59,9 @@ pub enum CommandResult {
     /// Optional string is a response to speak
     Handled(Option<String>),
 
+    /// Command was handled and its reply is spoken as well as shown
+    Announce(String),
+
     /// Mode change requested
     ModeChange {
         mode: AppMode,
@@ -48,6 +76,33 @@ pub enum CommandResult {
 
     /// Request application shutdown
     Shutdown,
+
+    /// Switch the orb UI to a different visual style
+    SetVisualStyle(OrbStyle),
+
+    /// Re-speak the last assistant response without querying the LLM
+    Replay,
+
+    /// Save the conversation history to a file
+    SaveHistory(PathBuf),
+
+    /// Replace the conversation history with a saved one
+    LoadHistory(PathBuf),
+
+    /// Write the conversation to a Markdown file
+    Export { path: PathBuf },
+
+    /// Write the last spoken response to a WAV file
+    SaveClip { path: PathBuf },
+
+    /// Switch the LLM to another model, keeping the conversation
+    SetModel(String),
+
+    /// Switch the TTS voice, or list the voices if none is given
+    Voice(Option<String>),
+
+    /// Re-read the persona (optionally from a new file) and reset the conversation
+    LoadPersona(Option<String>),
 }
 
 /// Command processor - checks input against registered commands
@@ -60,6 +115,12 @@ pub struct CommandProcessor {
 
     /// Custom commands from config
     custom_commands: Vec<CustomCommandDef>,
+
+    /// Edits allowed per character when fuzzy matching spoken commands
+    fuzzy_budget: f32,
+
+    /// Shutdown commands wait for a "confirm" first
+    confirm_shutdown: bool,
 }
 
 struct CustomCommandDef {
@@ -110,6 +171,8 @@ impl CommandProcessor {
             stop_phrases,
             builtin_enabled: config.commands.enable_builtin,
             custom_commands,
+            fuzzy_budget: config.commands.fuzzy_budget,
+            confirm_shutdown: config.commands.confirm_shutdown,
         }
     }
 
@@ -120,6 +183,17 @@ impl CommandProcessor {
         // Trim punctuation for better matching
         let text_trimmed = text_lower.trim_end_matches(|c: char| c.is_ascii_punctuation());
 
+        // A pending shutdown goes ahead on "confirm", anything else cancels it
+        if state.take_shutdown_request(SHUTDOWN_CONFIRM_SECS) {
+            let reply = clean_for_matching(text_trimmed);
+            if SHUTDOWN_CONFIRM_PHRASES.contains(&reply.trim()) {
+                return CommandResult::Shutdown;
+            }
+            if SHUTDOWN_CANCEL_PHRASES.contains(&reply.trim()) {
+                return CommandResult::Announce("Okay, staying on.".to_string());
+            }
+        }
+
         // 1. Check stop phrases first (highest priority)
         if self.is_stop_command(text_trimmed) {
             return CommandResult::Stop;
@@ -127,8 +201,15 @@ impl CommandProcessor {
 
         // 2. Check built-in commands
         if self.builtin_enabled {
-            if let Some(result) = self.check_builtin(text_trimmed, state) {
-                return result;
+            match self.check_builtin(text_trimmed, state) {
+                Some(CommandResult::Shutdown) if self.confirm_shutdown => {
+                    state.request_shutdown_confirmation();
+                    return CommandResult::Announce(
+                        "Are you sure? Say confirm to exit.".to_string(),
+                    );
+                }
+                Some(result) => return result,
+                None => {}
             }
         }
 
@@ -137,7 +218,10 @@ impl CommandProcessor {
             return result;
         }
 
-        // 4. Pass through to LLM
+        // 4. Pass through to LLM, unless only commands are accepted
+        if state.mode() == AppMode::Command {
+            return CommandResult::Handled(Some(format!("[Not a command] {}", text)));
+        }
         CommandResult::PassThrough(text.to_string())
     }
 
@@ -147,146 +231,116 @@ impl CommandProcessor {
         self.stop_phrases.iter().any(|phrase| {
             let phrase_clean = clean_for_matching(phrase);
             // Exact match or fuzzy match
-            text_clean == phrase_clean || fuzzy_match(&phrase_clean, &text_clean)
+            text_clean == phrase_clean
+                || fuzzy_match_within(&phrase_clean, &text_clean, self.fuzzy_budget)
         })
     }
 
     /// Check built-in commands
+    ///
+    /// Phrases are tried exactly first, in table order. Failing that, the
+    /// closest multi-word phrase whose words are each within
+    /// `BUILTIN_FUZZY_BUDGET` wins, so "transcribed mode" still switches.
+    /// Single words aren't matched fuzzily, too many ordinary words are one
+    /// edit from "quit" or "pause".
     fn check_builtin(&self, text: &str, state: &SharedState) -> Option<CommandResult> {
-        // Shutdown commands
-        if text.contains("stand down")
-            || text.contains("standdown")
-            || text == "quit"
-            || text == "exit"
-        {
-            return Some(CommandResult::Shutdown);
-        }
-
-        // Mode commands
-        if text.contains("start chat")
-            || text.contains("let's chat")
-            || text.contains("lets chat")
-            || text.contains("resume")
+        // Model switching (checked early, model names can contain anything)
+        if let Some(name) = text
+            .strip_prefix("switch to model ")
+            .or_else(|| text.strip_prefix("use model "))
         {
-            return Some(CommandResult::ModeChange {
-                mode: AppMode::Chat,
-                announcement: Some("Resuming conversation.".to_string()),
-            });
+            return Some(CommandResult::SetModel(name.trim().to_string()));
         }
-
-        if text.contains("pause") || text.contains("pause conversation") {
-            return Some(CommandResult::ModeChange {
-                mode: AppMode::Paused,
-                announcement: Some("Conversation paused. Say wake word to resume.".to_string()),
-            });
-        }
-
-        if text.contains("start transcription") || text.contains("transcribe mode") {
-            return Some(CommandResult::ModeChange {
-                mode: AppMode::Transcribe,
-                announcement: Some("Entering transcription mode.".to_string()),
-            });
+        if text == "list voices" {
+            return Some(CommandResult::Voice(None));
         }
-
-        if text.contains("take a note") || text.contains("note mode") {
-            return Some(CommandResult::ModeChange {
-                mode: AppMode::NoteTaking,
-                announcement: Some("Entering note-taking mode.".to_string()),
-            });
-        }
-
-        if text.contains("command mode") || text.contains("commands only") {
-            return Some(CommandResult::ModeChange {
-                mode: AppMode::Command,
-                announcement: Some(
-                    "Entering command mode. Only commands will be processed.".to_string(),
-                ),
-            });
-        }
-
-        // Typing mode (voice-to-keyboard)
-        if text.contains("typing mode")
-            || text.contains("start typing")
-            || text.contains("dictation mode")
+        // Voice names are single words ("af heart" is af_heart)
+        if let Some(name) = text
+            .strip_prefix("switch to voice ")
+            .or_else(|| text.strip_prefix("use voice "))
         {
-            return Some(CommandResult::ModeChange {
-                mode: AppMode::Typing,
-                announcement: Some(
-                    "Entering typing mode. Speech will be typed into active app.".to_string(),
-                ),
-            });
-        }
-
-        // Toggle commands
-        if text == "mute" || text == "mute mic" || text == "mute microphone" {
-            state
-                .mic_muted
-                .store(true, std::sync::atomic::Ordering::SeqCst);
-            return Some(CommandResult::Handled(Some(
-                "Microphone muted.".to_string(),
-            )));
-        }
-
-        if text == "unmute" || text == "unmute mic" || text == "unmute microphone" {
-            state
-                .mic_muted
-                .store(false, std::sync::atomic::Ordering::SeqCst);
-            return Some(CommandResult::Handled(Some(
-                "Microphone unmuted.".to_string(),
-            )));
+            let name = name.split_whitespace().collect::<Vec<_>>().join("_");
+            return Some(CommandResult::Voice(Some(name)));
         }
-
-        if text == "be quiet" || text == "silence" || text == "disable speech" {
-            state
-                .tts_enabled
-                .store(false, std::sync::atomic::Ordering::SeqCst);
-            return Some(CommandResult::Handled(None)); // No spoken response since TTS is disabled
-        }
-
-        if text == "speak" || text == "enable speech" || text == "talk to me" {
-            state
-                .tts_enabled
-                .store(true, std::sync::atomic::Ordering::SeqCst);
-            return Some(CommandResult::Handled(Some("Speech enabled.".to_string())));
+        if let Some(percent) = ["set volume to ", "set the volume to ", "volume "]
+            .iter()
+            .find_map(|prefix| text.strip_prefix(prefix))
+            .and_then(parse_percent)
+        {
+            return Some(set_volume(percent, state));
         }
-
-        if text == "enable crosstalk" || text == "crosstalk on" {
-            state
-                .crosstalk_enabled
-                .store(true, std::sync::atomic::Ordering::SeqCst);
-            return Some(CommandResult::Handled(Some(
-                "Crosstalk enabled. I'll keep listening while speaking.".to_string(),
-            )));
+        // "switch to Spanish", only once there are models to switch between;
+        // "switch back" returns to the default model
+        if let Some(name) = text
+            .strip_prefix("switch to ")
+            .or_else(|| text.strip_prefix("transcribe in "))
+            .filter(|name| {
+                *name == "default" || LANGUAGE_NAMES.iter().any(|(_, known)| known == name)
+            })
+            .or_else(|| (text == "switch back").then_some("default"))
+            .filter(|_| !state.languages().is_empty())
+        {
+            return Some(switch_language(name, state));
         }
 
-        if text == "disable crosstalk" || text == "crosstalk off" {
-            state
-                .crosstalk_enabled
-                .store(false, std::sync::atomic::Ordering::SeqCst);
-            return Some(CommandResult::Handled(Some(
-                "Crosstalk disabled.".to_string(),
-            )));
-        }
+        self.match_builtin(text)
+            .map(|action| run_builtin(action, state))
+    }
 
-        if text == "disable wake word" || text == "no wake word" {
-            state
-                .wake_enabled
-                .store(false, std::sync::atomic::Ordering::SeqCst);
-            return Some(CommandResult::Handled(Some(
-                "Wake word disabled. I'm always listening.".to_string(),
-            )));
+    /// Check only the mode commands, for modes that otherwise take speech
+    /// verbatim
+    ///
+    /// The whole utterance has to be the phrase, with no fuzzy matching, so a
+    /// note that mentions "resume" or "chat mode" stays a note.
+    pub fn check_mode_change(&self, text: &str) -> Option<CommandResult> {
+        if !self.builtin_enabled {
+            return None;
         }
+        let text_clean = clean_for_matching(text);
+        let text_clean = text_clean.trim();
+        BUILTIN_COMMANDS.iter().find_map(|cmd| match cmd.action {
+            Builtin::Mode(mode, announcement)
+                if cmd
+                    .phrases
+                    .iter()
+                    .any(|phrase| clean_for_matching(phrase) == text_clean) =>
+            {
+                Some(CommandResult::ModeChange {
+                    mode,
+                    announcement: Some(announcement.to_string()),
+                })
+            }
+            _ => None,
+        })
+    }
 
-        if text == "enable wake word" || text == "require wake word" {
-            state
-                .wake_enabled
-                .store(true, std::sync::atomic::Ordering::SeqCst);
-            return Some(CommandResult::Handled(Some(
-                "Wake word enabled.".to_string(),
-            )));
+    /// Find the built-in command `text` asks for
+    fn match_builtin(&self, text: &str) -> Option<Builtin> {
+        let exact = BUILTIN_COMMANDS.iter().find(|cmd| {
+            cmd.phrases.iter().any(|phrase| {
+                if cmd.whole {
+                    text == *phrase
+                } else {
+                    text.contains(phrase)
+                }
+            })
+        });
+        if let Some(cmd) = exact {
+            return Some(cmd.action);
         }
 
-        None
+        // Ordinary speech is often a letter or two from a command ("start
+        // timing"), so builtins get a much tighter budget than stop phrases
+        let budget = self.fuzzy_budget.min(BUILTIN_FUZZY_BUDGET);
+        let text_clean = clean_for_matching(text);
+        BUILTIN_COMMANDS
+            .iter()
+            .flat_map(|cmd| cmd.phrases.iter().map(move |phrase| (cmd, phrase)))
+            .filter(|(_, phrase)| phrase.contains(' '))
+            .map(|(cmd, phrase)| (cmd, clean_for_matching(phrase)))
+            .filter(|(_, phrase)| fuzzy_match_words(phrase, &text_clean, budget))
+            .min_by_key(|(_, phrase)| levenshtein(phrase, &text_clean))
+            .map(|(cmd, _)| cmd.action)
     }
 
     /// Check custom commands from config
@@ -300,6 +354,236 @@ impl CommandProcessor {
     }
 }
 
+/// What a built-in voice command does
+#[derive(Debug, Clone, Copy)]
+enum Builtin {
+    Shutdown,
+    Mode(AppMode, &'static str),
+    AdjustSpeed(f32),
+    NormalSpeed,
+    /// Double (true) or halve the TTS denoising steps
+    Quality(bool),
+    ReloadPersona,
+    Export,
+    SaveClip,
+    Replay,
+    Help,
+    NextStyle,
+    PrevStyle,
+    LowPower,
+    /// Set a flag, with an optional spoken confirmation
+    Set(ToggleTarget, bool, Option<&'static str>),
+}
+
+/// A built-in voice command: any of `phrases` anywhere in the input, or only
+/// as the whole input when `whole` is set
+struct BuiltinCommand {
+    phrases: &'static [&'static str],
+    whole: bool,
+    action: Builtin,
+}
+
+const fn anywhere(phrases: &'static [&'static str], action: Builtin) -> BuiltinCommand {
+    BuiltinCommand {
+        phrases,
+        whole: false,
+        action,
+    }
+}
+
+const fn whole(phrases: &'static [&'static str], action: Builtin) -> BuiltinCommand {
+    BuiltinCommand {
+        phrases,
+        whole: true,
+        action,
+    }
+}
+
+/// Built-in voice commands in priority order: shutdown, then modes, then
+/// everything else, then toggles
+const BUILTIN_COMMANDS: &[BuiltinCommand] = &[
+    anywhere(&["stand down", "standdown"], Builtin::Shutdown),
+    whole(&["quit", "exit"], Builtin::Shutdown),
+    anywhere(
+        &["start chat", "let's chat", "lets chat", "resume"],
+        Builtin::Mode(AppMode::Chat, "Resuming conversation."),
+    ),
+    anywhere(
+        &["pause", "pause conversation"],
+        Builtin::Mode(
+            AppMode::Paused,
+            "Conversation paused. Say wake word to resume.",
+        ),
+    ),
+    anywhere(
+        &["start transcription", "transcribe mode"],
+        Builtin::Mode(AppMode::Transcribe, "Entering transcription mode."),
+    ),
+    anywhere(
+        &["take a note", "note mode"],
+        Builtin::Mode(AppMode::NoteTaking, "Entering note-taking mode."),
+    ),
+    anywhere(
+        &["command mode", "commands only"],
+        Builtin::Mode(
+            AppMode::Command,
+            "Entering command mode. Only commands will be processed.",
+        ),
+    ),
+    // Typing mode (voice-to-keyboard)
+    anywhere(
+        &["typing mode", "start typing", "dictation mode"],
+        Builtin::Mode(
+            AppMode::Typing,
+            "Entering typing mode. Speech will be typed into active app.",
+        ),
+    ),
+    anywhere(
+        &["speak faster", "talk faster"],
+        Builtin::AdjustSpeed(TTS_SPEED_STEP),
+    ),
+    anywhere(
+        &["speak slower", "talk slower"],
+        Builtin::AdjustSpeed(-TTS_SPEED_STEP),
+    ),
+    anywhere(&["normal speed"], Builtin::NormalSpeed),
+    anywhere(
+        &["higher quality", "better quality"],
+        Builtin::Quality(true),
+    ),
+    anywhere(&["faster speech", "lower quality"], Builtin::Quality(false)),
+    whole(&["reload persona"], Builtin::ReloadPersona),
+    anywhere(&["reload your persona"], Builtin::ReloadPersona),
+    anywhere(
+        &[
+            "export conversation",
+            "export the conversation",
+            "export chat",
+        ],
+        Builtin::Export,
+    ),
+    anywhere(
+        &["save last clip", "save the last clip", "save that clip"],
+        Builtin::SaveClip,
+    ),
+    whole(
+        &["what can you do", "list commands", "list the commands"],
+        Builtin::Help,
+    ),
+    whole(&["repeat"], Builtin::Replay),
+    anywhere(&["repeat that", "say that again"], Builtin::Replay),
+    anywhere(
+        &["change style", "next style", "next orb"],
+        Builtin::NextStyle,
+    ),
+    anywhere(&["previous style", "previous orb"], Builtin::PrevStyle),
+    // Whole phrases only, "my laptop's battery saver" is just talk
+    whole(
+        &[
+            "low power",
+            "low power mode",
+            "battery saver",
+            "battery saver mode",
+        ],
+        Builtin::LowPower,
+    ),
+    whole(
+        &["mute", "mute mic", "mute mike", "mute microphone"],
+        Builtin::Set(ToggleTarget::Mute, true, Some("Microphone muted.")),
+    ),
+    whole(
+        &["unmute", "unmute mic", "unmute mike", "unmute microphone"],
+        Builtin::Set(ToggleTarget::Mute, false, Some("Microphone unmuted.")),
+    ),
+    // No spoken response since TTS is disabled
+    whole(
+        &["be quiet", "silence", "disable speech"],
+        Builtin::Set(ToggleTarget::Tts, false, None),
+    ),
+    whole(
+        &["speak", "enable speech", "talk to me"],
+        Builtin::Set(ToggleTarget::Tts, true, Some("Speech enabled.")),
+    ),
+    whole(
+        &["enable crosstalk", "crosstalk on"],
+        Builtin::Set(
+            ToggleTarget::Crosstalk,
+            true,
+            Some("Crosstalk enabled. I'll keep listening while speaking."),
+        ),
+    ),
+    whole(
+        &["disable crosstalk", "crosstalk off"],
+        Builtin::Set(ToggleTarget::Crosstalk, false, Some("Crosstalk disabled.")),
+    ),
+    whole(
+        &["disable wake word", "no wake word"],
+        Builtin::Set(
+            ToggleTarget::Wake,
+            false,
+            Some("Wake word disabled. I'm always listening."),
+        ),
+    ),
+    whole(
+        &["enable wake word", "require wake word"],
+        Builtin::Set(ToggleTarget::Wake, true, Some("Wake word enabled.")),
+    ),
+];
+
+/// Apply a built-in command's side effects and describe the result
+fn run_builtin(action: Builtin, state: &SharedState) -> CommandResult {
+    match action {
+        Builtin::Shutdown => CommandResult::Shutdown,
+        Builtin::Mode(mode, announcement) => CommandResult::ModeChange {
+            mode,
+            announcement: Some(announcement.to_string()),
+        },
+        Builtin::AdjustSpeed(step) => {
+            let speed = state.adjust_tts_speed(step);
+            CommandResult::Handled(Some(format!("Speech speed {:.1}.", speed)))
+        }
+        Builtin::NormalSpeed => {
+            let speed = state.reset_tts_speed();
+            CommandResult::Handled(Some(format!("Speech speed {:.1}.", speed)))
+        }
+        Builtin::Quality(higher) => {
+            let steps = state.scale_tts_steps(higher);
+            CommandResult::Handled(Some(format!("Speech quality {} steps.", steps)))
+        }
+        Builtin::ReloadPersona => CommandResult::LoadPersona(None),
+        Builtin::Export => CommandResult::Export {
+            path: default_export_path(),
+        },
+        Builtin::SaveClip => CommandResult::SaveClip {
+            path: default_clip_path(),
+        },
+        Builtin::Replay => CommandResult::Replay,
+        Builtin::Help => CommandResult::Announce(SPOKEN_HELP.to_string()),
+        Builtin::NextStyle => {
+            let style = state.orb_style().next();
+            state.set_orb_style(style);
+            CommandResult::SetVisualStyle(style)
+        }
+        Builtin::PrevStyle => {
+            let style = state.orb_style().prev();
+            state.set_orb_style(style);
+            CommandResult::SetVisualStyle(style)
+        }
+        Builtin::LowPower => CommandResult::SetVisualStyle(state.toggle_low_power()),
+        Builtin::Set(target, on, message) => {
+            let flag = match target {
+                ToggleTarget::Mute => &state.mic_muted,
+                ToggleTarget::Tts => &state.tts_enabled,
+                ToggleTarget::Crosstalk => &state.crosstalk_enabled,
+                ToggleTarget::Aec => &state.aec_enabled,
+                ToggleTarget::Wake => &state.wake_enabled,
+            };
+            flag.store(on, std::sync::atomic::Ordering::SeqCst);
+            CommandResult::Handled(message.map(str::to_string))
+        }
+    }
+}
+
 /// Parse action string from config into CommandAction
 fn parse_action(action: &str) -> Option<CommandAction> {
     if action.starts_with("mode:") {
@@ -482,36 +766,137 @@ pub fn process_slash_command(input: &str, state: &SharedState) -> Option<Command
             mode: AppMode::Typing,
             announcement: Some("Typing mode - speech will be typed into active app".to_string()),
         }),
+        "style" | "style next" => {
+            let style = state.orb_style().next();
+            state.set_orb_style(style);
+            Some(CommandResult::SetVisualStyle(style))
+        }
+        "style prev" | "style previous" => {
+            let style = state.orb_style().prev();
+            state.set_orb_style(style);
+            Some(CommandResult::SetVisualStyle(style))
+        }
+        "lowpower" | "low power" => Some(CommandResult::SetVisualStyle(state.toggle_low_power())),
+        "quality" => Some(CommandResult::Handled(Some(format!(
+            "Speech quality {} steps (/quality <{}-{}>)",
+            state.tts_steps(),
+            MIN_TTS_STEPS,
+            MAX_TTS_STEPS
+        )))),
+        cmd if cmd.starts_with("quality ") => {
+            Some(CommandResult::Handled(Some(match cmd[8..].trim() {
+                "up" | "higher" => format!("Speech quality {} steps", state.scale_tts_steps(true)),
+                "down" | "faster" => {
+                    format!("Speech quality {} steps", state.scale_tts_steps(false))
+                }
+                arg => match arg.parse() {
+                    Ok(steps) => format!("Speech quality {} steps", state.set_tts_steps(steps)),
+                    Err(_) => format!(
+                        "Usage: /quality [up|down|{}-{}]",
+                        MIN_TTS_STEPS, MAX_TTS_STEPS
+                    ),
+                },
+            })))
+        }
+        "volume" => Some(CommandResult::Handled(Some(format!(
+            "Volume {}% (/volume <0-100>)",
+            (state.get_tts_volume() * 100.0).round()
+        )))),
+        cmd if cmd.starts_with("volume ") => Some(match parse_percent(&cmd[7..]) {
+            Some(percent) => set_volume(percent, state),
+            None => CommandResult::Handled(Some("Usage: /volume [0-100]".to_string())),
+        }),
         "stop" => Some(CommandResult::Stop),
+        "repeat" => Some(CommandResult::Replay),
+        "save" | "load" | "model" => Some(CommandResult::Handled(Some(format!(
+            "Usage: /{} <name>",
+            cmd
+        )))),
+        // Session names keep their case, so slice the original input
+        cmd if cmd.starts_with("save ") => Some(history_command(
+            input[6..].trim(),
+            CommandResult::SaveHistory,
+        )),
+        cmd if cmd.starts_with("load ") => Some(history_command(
+            input[6..].trim(),
+            CommandResult::LoadHistory,
+        )),
+        "export" => Some(CommandResult::Export {
+            path: default_export_path(),
+        }),
+        cmd if cmd.starts_with("export ") => Some(CommandResult::Export {
+            path: PathBuf::from(input[8..].trim()),
+        }),
+        "clip" => Some(CommandResult::SaveClip {
+            path: default_clip_path(),
+        }),
+        cmd if cmd.starts_with("clip ") => Some(CommandResult::SaveClip {
+            path: PathBuf::from(input[6..].trim()),
+        }),
+        "persona" => Some(CommandResult::LoadPersona(None)),
+        cmd if cmd.starts_with("persona ") => Some(CommandResult::LoadPersona(Some(
+            input[9..].trim().to_string(),
+        ))),
+        cmd if cmd.starts_with("model ") => {
+            Some(CommandResult::SetModel(input[7..].trim().to_string()))
+        }
+        "voice" | "voices" => Some(CommandResult::Voice(None)),
+        cmd if cmd.starts_with("voice ") => {
+            Some(CommandResult::Voice(Some(input[7..].trim().to_string())))
+        }
         "quit" | "exit" => Some(CommandResult::Shutdown),
         "ui" => {
             // Toggle between text and orb modes
-            debug_log("Returning ui_switch:toggle");
+            logger::debug!("Returning ui_switch:toggle");
             Some(CommandResult::Handled(Some("ui_switch:toggle".to_string())))
         }
         cmd if cmd.starts_with("ui ") => {
             let ui_mode = &cmd[3..];
-            debug_log(&format!("Processing /ui command with mode: {}", ui_mode));
+            logger::debug!("Processing /ui command with mode: {}", ui_mode);
             match ui_mode {
                 "text" | "t" => {
-                    debug_log("Returning ui_switch:text");
+                    logger::debug!("Returning ui_switch:text");
                     Some(CommandResult::Handled(Some("ui_switch:text".to_string())))
                 }
                 "orb" | "o" => {
-                    debug_log("Returning ui_switch:orb");
+                    logger::debug!("Returning ui_switch:orb");
                     Some(CommandResult::Handled(Some("ui_switch:orb".to_string())))
                 }
+                "waveform" | "wave" | "w" => {
+                    logger::debug!("Returning ui_switch:waveform");
+                    Some(CommandResult::Handled(Some(
+                        "ui_switch:waveform".to_string(),
+                    )))
+                }
                 _ => Some(CommandResult::Handled(Some(
-                    "Usage: /ui [text|orb] (no args to toggle)".to_string(),
+                    "Usage: /ui [text|orb|waveform] (no args to toggle)".to_string(),
                 ))),
             }
         }
+        "lang" | "language" => Some(CommandResult::Handled(Some(format!(
+            "Language: {} (available: {})",
+            state
+                .language()
+                .as_deref()
+                .map_or("default".to_string(), language_label),
+            if state.languages().is_empty() {
+                "none, see [transcriber.models]".to_string()
+            } else {
+                state.languages().join(", ")
+            }
+        )))),
+        cmd if cmd.starts_with("lang ") || cmd.starts_with("language ") => {
+            let name = cmd.split_once(' ').map_or("", |(_, name)| name);
+            Some(switch_language(name, state))
+        }
         "status" => {
             let status = format!(
                 "Mode: {}, Mic: {}, TTS: {}, Crosstalk: {}, AEC: {}, Wake: {}",
                 state.mode(),
-                if state.mic_muted.load(std::sync::atomic::Ordering::SeqCst) {
+                if state.is_deaf() {
                     "muted"
+                } else if state.mic_muted.load(std::sync::atomic::Ordering::SeqCst) {
+                    "muted (wake word unmutes)"
                 } else {
                     "on"
                 },
@@ -555,10 +940,24 @@ Commands:
   /note - Enter note-taking mode
   /command - Enter command-only mode
   /typing - Enter typing mode (voice-to-keyboard)
-  /ui [text|orb] - Switch UI mode
+  /ui [text|orb|waveform] - Switch UI mode
+  /style [next|prev] - Cycle orb visual style
+  /lowpower - Toggle the low-power orb (Ctrl+L in orb UI)
+  /quality [up|down|4-64] - Show or set TTS denoising steps (Supertonic)
+  /volume [0-100] - Show or set the TTS volume in percent
   /stop - Stop TTS playback
+  /repeat - Speak the last response again
+  /save <name> - Save the conversation to sessions/<name>.json
+  /load <name> - Restore a saved conversation
+  /export [file.md] - Export the conversation to Markdown
+  /clip [file.wav] - Save the last spoken response to a WAV file
+  /model <name> - Switch the LLM model, keeping the conversation
+  /voice <name> - Switch the TTS voice (/voices lists them)
+  /persona [file] - Reload the persona (or load one from a file) and reset the conversation
+  /lang [code|default] - Show or switch the transcription language
   /quit - Exit application
   /status - Show current status
+  /endpoint - Show where LLM requests are sent
   /help or /commands - Show this help
 
 Voice commands:
@@ -569,6 +968,19 @@ Voice commands:
   'enable/disable crosstalk' - Control crosstalk
   'typing mode' - Enter typing mode
   'command mode' - Enter command-only mode
+  'next style' / 'previous style' - Cycle orb visual style
+  'low power mode' - Toggle the low-power orb
+  'repeat that', 'say that again' - Speak the last response again
+  'export conversation' - Export the conversation to Markdown
+  'save last clip' - Save the last spoken response to a WAV file
+  'what can you do', 'list commands' - Hear a short list of voice commands
+  'switch to model <name>' - Switch the LLM model
+  'reload persona' - Re-read the persona and reset the conversation
+  'speak faster' / 'speak slower' / 'normal speed' - Adjust speech speed
+  'higher quality' / 'faster speech' - More or fewer TTS denoising steps
+  'set volume to <n> percent' - Change the TTS volume
+  'switch to <language>' - Transcribe another language, e.g. 'switch to Spanish'
+  'switch back' - Transcribe with the default model again
   'stand down' - Exit application
   
 Typing mode commands:
@@ -586,11 +998,97 @@ Wake word: Say wake phrase when paused to resume"
     }
 }
 
+/// Parse "50", "50%" or "50 percent" as a percentage up to 100
+fn parse_percent(s: &str) -> Option<u32> {
+    let number = s.trim().trim_end_matches("percent").trim_end_matches('%');
+    number.trim().parse().ok().filter(|percent| *percent <= 100)
+}
+
+/// Set the TTS volume to `percent` and confirm it
+fn set_volume(percent: u32, state: &SharedState) -> CommandResult {
+    state.set_tts_volume(percent as f32 / 100.0);
+    CommandResult::Handled(Some(format!("Volume {} percent.", percent)))
+}
+
+/// Display name for a language code, "Spanish" for "es"
+fn language_label(code: &str) -> String {
+    match LANGUAGE_NAMES.iter().find(|(known, _)| *known == code) {
+        Some((_, name)) => {
+            let mut chars = name.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect())
+                .unwrap_or_default()
+        }
+        None => code.to_string(),
+    }
+}
+
+/// Switch the transcription language by code or name ("es", "spanish"), or
+/// back to the default model with "default"
+fn switch_language(name: &str, state: &SharedState) -> CommandResult {
+    let name = name.trim().to_lowercase();
+    if state.languages().is_empty() {
+        return CommandResult::Handled(Some(
+            "No transcription languages configured, see [transcriber.models]".to_string(),
+        ));
+    }
+    if name == "default" {
+        state.set_language(None);
+        return CommandResult::Handled(Some("Transcribing with the default model.".to_string()));
+    }
+    let code = LANGUAGE_NAMES
+        .iter()
+        .find(|(_, known)| *known == name)
+        .map_or(name.as_str(), |(code, _)| code);
+    if code == DEFAULT_MODEL_LANGUAGE && !state.languages().iter().any(|l| l == code) {
+        state.set_language(None);
+        return CommandResult::Handled(Some(format!("Transcribing in {}.", language_label(code))));
+    }
+    CommandResult::Handled(Some(if state.set_language(Some(code)) {
+        logger::info!("Transcription language: {}", code);
+        format!("Transcribing in {}.", language_label(code))
+    } else {
+        format!(
+            "No transcription model for {} (available: {})",
+            name,
+            state.languages().join(", ")
+        )
+    }))
+}
+
+/// Export file name when none is given, `silly-chat-<timestamp>.md`
+fn default_export_path() -> PathBuf {
+    PathBuf::from(format!(
+        "silly-chat-{}.md",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ))
+}
+
+/// Clip file name when none is given, `silly-clip-<timestamp>.wav`
+fn default_clip_path() -> PathBuf {
+    PathBuf::from(format!(
+        "silly-clip-{}.wav",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ))
+}
+
+/// Build a save/load result for a session name, or explain why it's invalid
+fn history_command(name: &str, result: fn(PathBuf) -> CommandResult) -> CommandResult {
+    match crate::chat::session_path(name) {
+        Some(path) => result(path),
+        None => CommandResult::Handled(Some(
+            "Session names may only use letters, digits, '-' and '_'".to_string(),
+        )),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::config::Config;
-    use crate::state::RuntimeState;
+    use crate::state::{DEFAULT_TTS_STEPS, RuntimeState};
+    use std::path::Path;
 
     fn test_state() -> SharedState {
         RuntimeState::new(&Config::default())
@@ -620,6 +1118,26 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_non_english_stop_phrases() {
+        let mut config = Config::default();
+        config.commands.stop_phrases = vec!["Basta".into(), "stopp".into(), "やめて".into()];
+        let processor = CommandProcessor::new(&config);
+        let state = test_state();
+
+        for text in ["¡Basta!", "STOPP.", "やめて。", "やめで"] {
+            assert!(
+                matches!(processor.process(text, &state), CommandResult::Stop),
+                "{}",
+                text
+            );
+        }
+        assert!(matches!(
+            processor.process("bastante", &state),
+            CommandResult::PassThrough(_)
+        ));
+    }
+
     #[test]
     fn test_mode_commands() {
         let config = Config::default();
@@ -645,6 +1163,312 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_command_mode_rejects_passthrough() {
+        let processor = CommandProcessor::new(&Config::default());
+        let state = test_state();
+
+        assert!(matches!(
+            processor.process("what time is it", &state),
+            CommandResult::PassThrough(_)
+        ));
+
+        state.set_mode(AppMode::Command);
+        assert!(matches!(
+            processor.process("what time is it", &state),
+            CommandResult::Handled(Some(ref msg)) if msg == "[Not a command] what time is it"
+        ));
+        // Commands still work
+        assert!(matches!(
+            processor.process("start chat", &state),
+            CommandResult::ModeChange {
+                mode: AppMode::Chat,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_check_mode_change() {
+        let processor = CommandProcessor::new(&Config::default());
+
+        assert!(matches!(
+            processor.check_mode_change("Start chat."),
+            Some(CommandResult::ModeChange {
+                mode: AppMode::Chat,
+                ..
+            })
+        ));
+        // Other commands are left for the note itself
+        assert!(processor.check_mode_change("mute").is_none());
+        assert!(processor.check_mode_change("buy milk and eggs").is_none());
+        // Notes that mention a mode phrase, or nearly say one, stay notes
+        for note in [
+            "update my resume before friday",
+            "pause the meeting at noon",
+            "switch to chat mode later",
+            "transcribed mode",
+        ] {
+            assert!(processor.check_mode_change(note).is_none(), "{}", note);
+        }
+    }
+
+    #[test]
+    fn test_spoken_help() {
+        let processor = CommandProcessor::new(&Config::default());
+        let state = test_state();
+
+        for text in ["What can you do?", "list commands"] {
+            let result = processor.process(text, &state);
+            assert!(
+                matches!(result, CommandResult::Announce(ref msg) if msg == SPOKEN_HELP),
+                "{}",
+                text
+            );
+        }
+        assert!(SPOKEN_HELP.split_whitespace().count() < 30);
+        assert!(!SPOKEN_HELP.contains('/'));
+    }
+
+    #[test]
+    fn test_shutdown_confirmation() {
+        let mut config = Config::default();
+        let state = test_state();
+        assert!(matches!(
+            CommandProcessor::new(&config).process("stand down", &state),
+            CommandResult::Shutdown
+        ));
+
+        config.commands.confirm_shutdown = true;
+        let processor = CommandProcessor::new(&config);
+        let asked = |text: &str| {
+            matches!(processor.process(text, &state),
+                CommandResult::Announce(ref msg) if msg.starts_with("Are you sure"))
+        };
+
+        assert!(asked("Stand down."));
+        assert!(matches!(
+            processor.process("Confirm.", &state),
+            CommandResult::Shutdown
+        ));
+
+        // Anything else cancels, and is handled as usual
+        assert!(asked("standdown"));
+        assert!(matches!(
+            processor.process("what time is it", &state),
+            CommandResult::PassThrough(_)
+        ));
+        assert!(matches!(
+            processor.process("confirm", &state),
+            CommandResult::PassThrough(_)
+        ));
+
+        assert!(asked("exit"));
+        assert!(matches!(
+            processor.process("No.", &state),
+            CommandResult::Announce(ref msg) if msg == "Okay, staying on."
+        ));
+    }
+
+    #[test]
+    fn test_fuzzy_commands() {
+        let mut config = Config::default();
+        let processor = CommandProcessor::new(&config);
+        let state = test_state();
+        let mode = |text: &str| match processor.process(text, &state) {
+            CommandResult::ModeChange { mode, .. } => Some(mode),
+            _ => None,
+        };
+
+        assert!(matches!(
+            processor.process("Mute mike.", &state),
+            CommandResult::Handled(Some(ref msg)) if msg == "Microphone muted."
+        ));
+        assert!(state.mic_muted.load(std::sync::atomic::Ordering::SeqCst));
+        processor.process("Unmute mike", &state);
+        assert!(!state.mic_muted.load(std::sync::atomic::Ordering::SeqCst));
+
+        assert_eq!(mode("Pause conversations."), Some(AppMode::Paused));
+        assert_eq!(mode("Transcribed mode"), Some(AppMode::Transcribe));
+
+        // Single words, commands buried in a sentence and ordinary phrases a
+        // letter or two from a command aren't guessed at
+        for text in [
+            "quite",
+            "I'd like to mute the mic",
+            "start timing",
+            "take a nap",
+            "let's check",
+            "be quick",
+            "repeat what",
+        ] {
+            assert!(matches!(
+                processor.process(text, &state),
+                CommandResult::PassThrough(_)
+            ));
+        }
+
+        config.commands.fuzzy_budget = 0.0;
+        let exact = CommandProcessor::new(&config);
+        assert!(matches!(
+            exact.process("transcribed mode", &state),
+            CommandResult::PassThrough(_)
+        ));
+    }
+
+    #[test]
+    fn test_style_commands() {
+        let config = Config::default();
+        let processor = CommandProcessor::new(&config);
+        let state = test_state();
+
+        let result = processor.process("Change style.", &state);
+        assert!(matches!(
+            result,
+            CommandResult::SetVisualStyle(OrbStyle::Ring)
+        ));
+
+        let result = processor.process("next orb", &state);
+        assert!(matches!(
+            result,
+            CommandResult::SetVisualStyle(OrbStyle::Orbs)
+        ));
+
+        let result = processor.process("previous style", &state);
+        assert!(matches!(
+            result,
+            CommandResult::SetVisualStyle(OrbStyle::Ring)
+        ));
+        assert_eq!(state.orb_style(), OrbStyle::Ring);
+
+        let result = processor.process("Low power mode.", &state);
+        assert!(matches!(
+            result,
+            CommandResult::SetVisualStyle(OrbStyle::Minimal)
+        ));
+        let result = process_slash_command("/lowpower", &state).unwrap();
+        assert!(matches!(
+            result,
+            CommandResult::SetVisualStyle(OrbStyle::Ring)
+        ));
+        let result = processor.process("Battery saver mode", &state);
+        assert!(matches!(
+            result,
+            CommandResult::SetVisualStyle(OrbStyle::Minimal)
+        ));
+        for talk in [
+            "My laptop's battery saver is on.",
+            "It runs in low power mode overnight.",
+        ] {
+            let result = processor.process(talk, &state);
+            assert!(matches!(result, CommandResult::PassThrough(_)), "{}", talk);
+        }
+        assert_eq!(state.orb_style(), OrbStyle::Minimal);
+    }
+
+    #[test]
+    fn test_replay_commands() {
+        let config = Config::default();
+        let processor = CommandProcessor::new(&config);
+        let state = test_state();
+
+        for phrase in ["Repeat that.", "say that again", "repeat"] {
+            let result = processor.process(phrase, &state);
+            assert!(matches!(result, CommandResult::Replay), "{}", phrase);
+        }
+        assert!(matches!(
+            process_slash_command("/repeat", &state),
+            Some(CommandResult::Replay)
+        ));
+        assert!(matches!(
+            processor.process("repeat after me hello", &state),
+            CommandResult::PassThrough(_)
+        ));
+    }
+
+    #[test]
+    fn test_speed_commands() {
+        let config = Config::default();
+        let processor = CommandProcessor::new(&config);
+        let state = test_state();
+        let normal = state.tts_speed();
+
+        processor.process("Speak faster.", &state);
+        assert!((state.tts_speed() - (normal + 0.1)).abs() < 1e-4);
+
+        for _ in 0..30 {
+            processor.process("speak slower", &state);
+        }
+        assert_eq!(state.tts_speed(), 0.5);
+
+        for _ in 0..30 {
+            processor.process("talk faster", &state);
+        }
+        assert_eq!(state.tts_speed(), 2.0);
+
+        processor.process("normal speed", &state);
+        assert_eq!(state.tts_speed(), normal);
+    }
+
+    #[test]
+    fn test_volume_commands() {
+        let config = Config::default();
+        let processor = CommandProcessor::new(&config);
+        let state = test_state();
+        assert_eq!(state.get_tts_volume(), 1.0);
+
+        // Transcripts end in punctuation, which takes the % with it
+        processor.process("Set volume to 50%.", &state);
+        assert_eq!(state.get_tts_volume(), 0.5);
+        processor.process("set the volume to 20 percent", &state);
+        assert_eq!(state.get_tts_volume(), 0.2);
+        assert!(matches!(
+            processor.process("volume 150", &state),
+            CommandResult::PassThrough(_)
+        ));
+        assert_eq!(state.get_tts_volume(), 0.2);
+
+        // Ducking scales the user's volume and restores to it, not to full
+        state.duck_tts();
+        let duck = config.interaction.duck_volume;
+        assert!((state.playback_volume() - 0.2 * duck).abs() < 1e-6);
+        state.restore_tts_volume();
+        assert_eq!(state.playback_volume(), 0.2);
+
+        process_slash_command("/volume 75", &state);
+        assert_eq!(state.get_tts_volume(), 0.75);
+        assert!(matches!(
+            process_slash_command("/volume loud", &state),
+            Some(CommandResult::Handled(Some(usage))) if usage.starts_with("Usage")
+        ));
+    }
+
+    #[test]
+    fn test_quality_commands() {
+        let config = Config::default();
+        let processor = CommandProcessor::new(&config);
+        let state = test_state();
+        assert_eq!(state.tts_steps(), DEFAULT_TTS_STEPS);
+
+        processor.process("Higher quality please.", &state);
+        assert_eq!(state.tts_steps(), DEFAULT_TTS_STEPS * 2);
+        for _ in 0..5 {
+            processor.process("higher quality", &state);
+        }
+        assert_eq!(state.tts_steps(), MAX_TTS_STEPS);
+
+        process_slash_command("/quality 2", &state);
+        assert_eq!(state.tts_steps(), MIN_TTS_STEPS);
+        process_slash_command("/quality 12", &state);
+        assert_eq!(state.tts_steps(), 12);
+        processor.process("faster speech", &state);
+        assert_eq!(state.tts_steps(), 6);
+        assert!(matches!(
+            process_slash_command("/quality best", &state),
+            Some(CommandResult::Handled(Some(ref msg))) if msg.starts_with("Usage")
+        ));
+    }
+
     #[test]
     fn test_passthrough() {
         let config = Config::default();
@@ -668,4 +1492,191 @@ mod tests {
         let result = process_slash_command("not a command", &state);
         assert!(result.is_none());
     }
+
+    #[test]
+    fn test_unmute_on_wake() {
+        let mut config = Config::default();
+        config.interaction.start_muted = true;
+        let state = RuntimeState::new(&config);
+        let status = |state: &SharedState| match process_slash_command("/status", state) {
+            Some(CommandResult::Handled(Some(status))) => status,
+            _ => panic!("/status should report"),
+        };
+        assert!(state.is_deaf());
+        assert!(status(&state).contains("Mic: muted,"));
+
+        config.interaction.unmute_on_wake = true;
+        let state = RuntimeState::new(&config);
+        assert!(!state.is_deaf());
+        assert!(status(&state).contains("Mic: muted (wake word unmutes)"));
+
+        // Nothing can hear the wake word with it switched off
+        process_slash_command("/wake", &state);
+        assert!(state.is_deaf());
+    }
+
+    #[test]
+    fn test_history_commands() {
+        let state = test_state();
+
+        let result = process_slash_command("/save Standup-2", &state);
+        assert!(
+            matches!(result, Some(CommandResult::SaveHistory(ref path)) if path.ends_with("Standup-2.json"))
+        );
+        let result = process_slash_command("/LOAD standup", &state);
+        assert!(matches!(result, Some(CommandResult::LoadHistory(_))));
+
+        for input in ["/save", "/load ../config", "/save a b"] {
+            let result = process_slash_command(input, &state);
+            assert!(
+                matches!(result, Some(CommandResult::Handled(Some(_)))),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_export_commands() {
+        let state = test_state();
+
+        let result = process_slash_command("/export Notes/Standup.md", &state);
+        assert!(
+            matches!(result, Some(CommandResult::Export { ref path }) if path == Path::new("Notes/Standup.md"))
+        );
+        let result = process_slash_command("/export", &state);
+        assert!(matches!(result, Some(CommandResult::Export { ref path })
+            if path.to_string_lossy().starts_with("silly-chat-")
+                && path.extension().is_some_and(|ext| ext == "md")));
+
+        let processor = CommandProcessor::new(&Config::default());
+        assert!(matches!(
+            processor.process("Export the conversation.", &state),
+            CommandResult::Export { .. }
+        ));
+
+        let result = process_slash_command("/clip hello.wav", &state);
+        assert!(
+            matches!(result, Some(CommandResult::SaveClip { ref path }) if path == Path::new("hello.wav"))
+        );
+        assert!(matches!(
+            processor.process("Save that clip.", &state),
+            CommandResult::SaveClip { ref path } if path.extension().is_some_and(|ext| ext == "wav")
+        ));
+    }
+
+    #[test]
+    fn test_model_commands() {
+        let config = Config::default();
+        let processor = CommandProcessor::new(&config);
+        let state = test_state();
+
+        let result = process_slash_command("/model Qwen2.5:7b", &state);
+        assert!(matches!(result, Some(CommandResult::SetModel(ref name)) if name == "Qwen2.5:7b"));
+        assert!(matches!(
+            process_slash_command("/model", &state),
+            Some(CommandResult::Handled(Some(_)))
+        ));
+
+        let result = processor.process("Switch to model llama3.", &state);
+        assert!(matches!(result, CommandResult::SetModel(ref name) if name == "llama3"));
+    }
+
+    #[test]
+    fn test_voice_commands() {
+        let config = Config::default();
+        let processor = CommandProcessor::new(&config);
+        let state = test_state();
+
+        let result = process_slash_command("/voice bf_emma", &state);
+        assert!(matches!(result, Some(CommandResult::Voice(Some(ref name))) if name == "bf_emma"));
+        assert!(matches!(
+            process_slash_command("/voices", &state),
+            Some(CommandResult::Voice(None))
+        ));
+
+        let result = processor.process("Use voice af heart.", &state);
+        assert!(matches!(result, CommandResult::Voice(Some(ref name)) if name == "af_heart"));
+        assert!(matches!(
+            processor.process("List voices", &state),
+            CommandResult::Voice(None)
+        ));
+    }
+
+    #[test]
+    fn test_language_commands() {
+        let processor = CommandProcessor::new(&Config::default());
+        // Without models, "switch to Spanish" is left for the LLM
+        let state = test_state();
+        assert!(matches!(
+            processor.process("Switch to Spanish.", &state),
+            CommandResult::PassThrough(_)
+        ));
+
+        let mut config = Config::default();
+        config.transcriber.models = [("en", "/models/en"), ("es", "/models/es")]
+            .into_iter()
+            .map(|(code, path)| (code.to_string(), path.to_string()))
+            .collect();
+        let state = RuntimeState::new(&config);
+        assert_eq!(state.language(), None);
+
+        assert!(matches!(
+            processor.process("Switch to Spanish.", &state),
+            CommandResult::Handled(Some(ref reply)) if reply == "Transcribing in Spanish."
+        ));
+        assert_eq!(state.language().as_deref(), Some("es"));
+        process_slash_command("/lang EN", &state);
+        assert_eq!(state.language().as_deref(), Some("en"));
+        assert!(matches!(
+            process_slash_command("/lang", &state),
+            Some(CommandResult::Handled(Some(ref status))) if status == "Language: English (available: en, es)"
+        ));
+
+        // No model for French, so the language stays put
+        assert!(matches!(
+            process_slash_command("/lang french", &state),
+            Some(CommandResult::Handled(Some(ref reply))) if reply.starts_with("No transcription model for french")
+        ));
+        assert_eq!(state.language().as_deref(), Some("en"));
+        process_slash_command("/lang default", &state);
+        assert_eq!(state.language(), None);
+
+        // With only a Spanish model, English is the default model
+        config.transcriber.models.remove("en");
+        let state = RuntimeState::new(&config);
+        processor.process("Switch to Spanish.", &state);
+        assert_eq!(state.language().as_deref(), Some("es"));
+        assert!(matches!(
+            processor.process("Switch to English.", &state),
+            CommandResult::Handled(Some(ref reply)) if reply == "Transcribing in English."
+        ));
+        assert_eq!(state.language(), None);
+        processor.process("Transcribe in Spanish.", &state);
+        processor.process("Switch back.", &state);
+        assert_eq!(state.language(), None);
+        processor.process("Switch to Spanish.", &state);
+        processor.process("Switch to default.", &state);
+        assert_eq!(state.language(), None);
+    }
+
+    #[test]
+    fn test_persona_commands() {
+        let config = Config::default();
+        let processor = CommandProcessor::new(&config);
+        let state = test_state();
+
+        assert!(matches!(
+            process_slash_command("/persona", &state),
+            Some(CommandResult::LoadPersona(None))
+        ));
+        let result = process_slash_command("/persona Personas/Pirate.md", &state);
+        assert!(
+            matches!(result, Some(CommandResult::LoadPersona(Some(ref file))) if file == "Personas/Pirate.md")
+        );
+        assert!(matches!(
+            processor.process("Reload persona.", &state),
+            CommandResult::LoadPersona(None)
+        ));
+    }
 }
@@ -1,20 +1,29 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::Path;
+use std::str::FromStr;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Config {
     #[serde(default = "default_name")]
     pub name: String,
     #[serde(default = "default_wake_word")]
-    pub wake_word: String,
+    pub wake_word: WakeWordConfig,
     #[serde(default = "default_wake_timeout")]
     pub wake_timeout_secs: u64,
+    /// How forgiving wake word matching is (0.0 = exact, 1.0 = very loose)
+    #[serde(default = "default_wake_sensitivity")]
+    pub wake_sensitivity: f32,
+    #[serde(default)]
+    pub wake: WakeConfig,
     #[serde(default)]
     pub tts: TtsConfig,
     #[serde(default)]
     pub llm: LlmConfig,
     #[serde(default)]
+    pub chat: ChatConfig,
+    #[serde(default)]
     pub acceleration: AccelerationConfig,
     #[serde(default)]
     pub interaction: InteractionConfig,
@@ -24,6 +33,16 @@ pub struct Config {
     pub ui: UiConfig,
     #[serde(default)]
     pub typing: TypingConfig,
+    #[serde(default)]
+    pub models: ModelsConfig,
+    #[serde(default)]
+    pub transcriber: TranscriberConfig,
+    #[serde(default)]
+    pub audio: AudioConfig,
+    #[serde(default)]
+    pub notes: NotesConfig,
+    #[serde(default)]
+    pub normalize: NormalizeConfig,
 }
 
 impl Default for Config {
@@ -32,22 +51,113 @@ impl Default for Config {
             name: default_name(),
             wake_word: default_wake_word(),
             wake_timeout_secs: default_wake_timeout(),
+            wake_sensitivity: default_wake_sensitivity(),
+            wake: WakeConfig::default(),
             tts: TtsConfig::default(),
             llm: LlmConfig::default(),
+            chat: ChatConfig::default(),
             acceleration: AccelerationConfig::default(),
             interaction: InteractionConfig::default(),
             commands: CommandsConfig::default(),
             ui: UiConfig::default(),
             typing: TypingConfig::default(),
+            models: ModelsConfig::default(),
+            transcriber: TranscriberConfig::default(),
+            audio: AudioConfig::default(),
+            notes: NotesConfig::default(),
+            normalize: NormalizeConfig::default(),
+        }
+    }
+}
+
+// ============================================================================
+// Wake Word Config
+// ============================================================================
+
+/// Wake phrase(s): `wake_word = "Hey Silly"` or `wake_word = ["Hey Silly", "Okay Silly"]`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum WakeWordConfig {
+    Single(String),
+    Multiple(Vec<String>),
+}
+
+impl WakeWordConfig {
+    pub fn phrases(&self) -> Vec<String> {
+        match self {
+            WakeWordConfig::Single(phrase) => vec![phrase.clone()],
+            WakeWordConfig::Multiple(phrases) => phrases.clone(),
+        }
+    }
+}
+
+/// Wake gating
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WakeConfig {
+    /// Seconds of turned-away speech kept before the wake phrase and joined
+    /// to the request (0 = off)
+    #[serde(default = "default_preroll_secs")]
+    pub preroll_secs: f32,
+
+    /// Feedback when the wake word resumes listening
+    #[serde(default)]
+    pub ack: WakeAck,
+
+    /// What `ack = "speech"` says
+    #[serde(default = "default_ack_phrase")]
+    pub ack_phrase: String,
+
+    /// Directory of 16kHz WAV recordings of the wake phrase; while waiting
+    /// for the wake word only utterances that start like one are transcribed
+    #[serde(default)]
+    pub kws_templates: Option<String>,
+
+    /// Keyword spotter match distance (lower is stricter)
+    #[serde(default = "default_kws_threshold")]
+    pub kws_threshold: f32,
+}
+
+fn default_preroll_secs() -> f32 {
+    0.0
+}
+
+fn default_ack_phrase() -> String {
+    "Yes?".to_string()
+}
+
+fn default_kws_threshold() -> f32 {
+    0.25
+}
+
+impl Default for WakeConfig {
+    fn default() -> Self {
+        Self {
+            preroll_secs: default_preroll_secs(),
+            ack: WakeAck::default(),
+            ack_phrase: default_ack_phrase(),
+            kws_templates: None,
+            kws_threshold: default_kws_threshold(),
         }
     }
 }
 
+/// Acknowledgement played when the wake word resumes listening
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum WakeAck {
+    /// Short two-note chime
+    #[default]
+    Chime,
+    /// Speak `wake.ack_phrase`
+    Speech,
+    None,
+}
+
 // ============================================================================
 // UI Config
 // ============================================================================
 
-#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum UiModeConfig {
     /// Text-based terminal UI (default)
@@ -55,9 +165,11 @@ pub enum UiModeConfig {
     Text,
     /// Orb visualization mode
     Orb,
+    /// Scrolling waveform bars
+    Waveform,
 }
 
-#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum OrbStyleConfig {
     /// Simple rotating ring
@@ -69,14 +181,29 @@ pub enum OrbStyleConfig {
     Orbs,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct UiConfig {
-    /// UI mode: "text" or "graphical"
+    /// UI mode: "text", "orb" or "waveform"
     #[serde(default)]
     pub mode: UiModeConfig,
     /// Visual style for graphical mode: "ring", "blob", or "orbs"
     #[serde(default)]
     pub orb_style: OrbStyleConfig,
+    /// Last-used orb style ("blob", "ring", "orbs", "sphere"), saved when cycling styles
+    #[serde(default)]
+    pub default_style: Option<String>,
+    /// Last-used shade pattern, saved when cycling shades
+    #[serde(default)]
+    pub default_shade: Option<String>,
+    /// Pixels per terminal cell in Ctrl+P orb screenshots
+    #[serde(default = "default_screenshot_scale")]
+    pub screenshot_scale: u32,
+    /// Start the orb UI in the low-power minimal style
+    #[serde(default)]
+    pub low_power: bool,
+    /// Redraw rate for the UI loop (1-240)
+    #[serde(default = "default_target_fps")]
+    pub target_fps: u32,
 }
 
 impl Default for UiConfig {
@@ -84,28 +211,324 @@ impl Default for UiConfig {
         Self {
             mode: UiModeConfig::default(),
             orb_style: OrbStyleConfig::default(),
+            default_style: None,
+            default_shade: None,
+            screenshot_scale: default_screenshot_scale(),
+            low_power: false,
+            target_fps: default_target_fps(),
+        }
+    }
+}
+
+fn default_screenshot_scale() -> u32 {
+    4
+}
+
+fn default_target_fps() -> u32 {
+    60
+}
+
+// ============================================================================
+// Models Config
+// ============================================================================
+
+/// Overrides for model locations (defaults resolve via the model search path)
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct ModelsConfig {
+    /// Parakeet transcriber model directory
+    #[serde(default)]
+    pub transcriber_path: Option<String>,
+    /// Silero VAD onnx model
+    #[serde(default)]
+    pub vad_path: Option<String>,
+}
+
+// ============================================================================
+// Audio Config
+// ============================================================================
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AudioConfig {
+    /// Where the assistant listens: "mic", "system" or "app:<name>"
+    #[serde(default)]
+    pub source: CaptureSource,
+
+    /// Microphone to use, matched against device names (case-insensitive,
+    /// exact name first, then substring); unset uses the system default
+    #[serde(default)]
+    pub input_device: Option<String>,
+
+    /// Speaker for TTS, matched like `input_device`; unset uses the system
+    /// default
+    #[serde(default)]
+    pub output_device: Option<String>,
+
+    /// TTS playback volume (0.0-1.0), changed at runtime with "set volume to
+    /// 50 percent" or `/volume`
+    #[serde(default = "default_output_volume")]
+    pub output_volume: f32,
+
+    /// Normalize each utterance's level before transcription (recordings
+    /// keep the original level)
+    #[serde(default)]
+    pub agc: bool,
+
+    /// Speech RMS that AGC brings utterances to
+    #[serde(default = "default_agc_target_rms")]
+    pub target_rms: f32,
+}
+
+fn default_output_volume() -> f32 {
+    1.0
+}
+
+fn default_agc_target_rms() -> f32 {
+    0.1
+}
+
+impl Default for AudioConfig {
+    fn default() -> Self {
+        Self {
+            source: CaptureSource::default(),
+            input_device: None,
+            output_device: None,
+            output_volume: default_output_volume(),
+            agc: false,
+            target_rms: default_agc_target_rms(),
+        }
+    }
+}
+
+impl AudioConfig {
+    /// AGC target level, if AGC is on
+    pub fn agc_target(&self) -> Option<f32> {
+        self.agc.then_some(self.target_rms)
+    }
+}
+
+/// Audio input for the assistant
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum CaptureSource {
+    /// Default input device
+    #[default]
+    Mic,
+    /// Everything the system plays (macOS, requires --features listen)
+    System,
+    /// One application's audio, matched by name (macOS, requires --features listen)
+    App(String),
+}
+
+impl FromStr for CaptureSource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(s.to_string())
+    }
+}
+
+impl TryFrom<String> for CaptureSource {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.trim() {
+            "mic" => Ok(Self::Mic),
+            "system" => Ok(Self::System),
+            other => match other.strip_prefix("app:").map(str::trim) {
+                Some(name) if !name.is_empty() => Ok(Self::App(name.to_string())),
+                _ => Err(format!(
+                    "Invalid audio source '{}', expected \"mic\", \"system\" or \"app:<name>\"",
+                    value
+                )),
+            },
+        }
+    }
+}
+
+impl From<CaptureSource> for String {
+    fn from(source: CaptureSource) -> Self {
+        match source {
+            CaptureSource::Mic => "mic".to_string(),
+            CaptureSource::System => "system".to_string(),
+            CaptureSource::App(name) => format!("app:{}", name),
+        }
+    }
+}
+
+// ============================================================================
+// Notes Config
+// ============================================================================
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NotesConfig {
+    /// File note-taking mode appends to
+    #[serde(default = "default_notes_file")]
+    pub file: String,
+    /// Say "Noted." after each note
+    #[serde(default = "default_notes_ack")]
+    pub ack: bool,
+}
+
+impl Default for NotesConfig {
+    fn default() -> Self {
+        Self {
+            file: default_notes_file(),
+            ack: default_notes_ack(),
+        }
+    }
+}
+
+fn default_notes_file() -> String {
+    "notes.txt".to_string()
+}
+
+fn default_notes_ack() -> bool {
+    true
+}
+
+// ============================================================================
+// Normalize Config
+// ============================================================================
+
+/// Clean-up applied to final transcripts (`normalize::TextNormalizer`)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NormalizeConfig {
+    /// Capitalize the first word of each sentence
+    #[serde(default = "default_normalize_capitalize")]
+    pub capitalize: bool,
+    /// Drop the words in `fillers`
+    #[serde(default = "default_normalize_strip_fillers")]
+    pub strip_fillers: bool,
+    /// Filler words to drop (single words, case-insensitive)
+    #[serde(default = "default_normalize_fillers")]
+    pub fillers: Vec<String>,
+    /// Collapse stuttered articles and pronouns ("the the" -> "the")
+    #[serde(default = "default_normalize_collapse_repeats")]
+    pub collapse_repeats: bool,
+}
+
+impl Default for NormalizeConfig {
+    fn default() -> Self {
+        Self {
+            capitalize: default_normalize_capitalize(),
+            strip_fillers: default_normalize_strip_fillers(),
+            fillers: default_normalize_fillers(),
+            collapse_repeats: default_normalize_collapse_repeats(),
+        }
+    }
+}
+
+fn default_normalize_capitalize() -> bool {
+    true
+}
+
+fn default_normalize_strip_fillers() -> bool {
+    true
+}
+
+fn default_normalize_fillers() -> Vec<String> {
+    ["um", "umm", "uh", "uhm", "erm", "er", "hmm"]
+        .into_iter()
+        .map(String::from)
+        .collect()
+}
+
+fn default_normalize_collapse_repeats() -> bool {
+    true
+}
+
+// ============================================================================
+// Transcriber Config
+// ============================================================================
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TranscriberConfig {
+    /// Live previews below this estimated confidence (0.0 - 1.0) are hidden
+    #[serde(default = "default_min_confidence")]
+    pub min_confidence: f32,
+    /// Buffers whose loudest 30ms frame is below this RMS are never transcribed
+    #[serde(default = "default_silence_floor")]
+    pub silence_floor: f32,
+    /// Transcriber model directory per language code, switched with `/lang`
+    #[serde(default)]
+    pub models: BTreeMap<String, String>,
+    /// Language (a key of `models`) to start with; unset uses the default model
+    #[serde(default)]
+    pub language: Option<String>,
+}
+
+impl Default for TranscriberConfig {
+    fn default() -> Self {
+        Self {
+            min_confidence: default_min_confidence(),
+            silence_floor: default_silence_floor(),
+            models: BTreeMap::new(),
+            language: None,
         }
     }
 }
 
+fn default_min_confidence() -> f32 {
+    0.3
+}
+
+fn default_silence_floor() -> f32 {
+    crate::transcriber::DEFAULT_SILENCE_FLOOR
+}
+
 // ============================================================================
 // Interaction Config
 // ============================================================================
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct InteractionConfig {
     /// Enable processing input while TTS is playing (can be toggled at runtime)
     #[serde(default = "default_crosstalk")]
     pub crosstalk: bool,
 
-    /// Volume level when user speaks during TTS (0.0-1.0)
+    /// Fraction of the TTS volume kept while the user talks over it (0.0-1.0)
     #[serde(default = "default_duck_volume")]
-    #[allow(dead_code)]
     pub duck_volume: f32,
 
     /// Enable acoustic echo cancellation (removes TTS audio from mic input)
     #[serde(default)]
     pub aec: bool,
+
+    /// Trailing silence (ms) before an utterance is considered finished
+    #[serde(default = "default_silence_ms")]
+    pub silence_ms: u32,
+
+    /// Audio (ms) still recorded after an utterance ends so the last word's
+    /// trailing sounds aren't clipped (0 = off)
+    #[serde(default)]
+    pub trailing_pad_ms: u32,
+
+    /// Silero speech probability needed to start an utterance
+    #[serde(default = "default_vad_onset_threshold")]
+    pub vad_onset_threshold: f32,
+
+    /// Silero speech probability below which an utterance is ending
+    /// (kept at or below the onset threshold)
+    #[serde(default = "default_vad_offset_threshold")]
+    pub vad_offset_threshold: f32,
+
+    /// How utterances are delimited: "vad" (default) or "push_to_talk"
+    #[serde(default)]
+    pub mode: InteractionMode,
+
+    /// Switch to paused mode after this many seconds without speech or
+    /// typing (0 = never)
+    #[serde(default)]
+    pub auto_pause_secs: u64,
+
+    /// Start with the mic muted (same as `--muted`)
+    #[serde(default)]
+    pub start_muted: bool,
+
+    /// While muted, keep listening for the wake word and unmute when it's
+    /// heard; otherwise muting stops all audio processing
+    #[serde(default)]
+    pub unmute_on_wake: bool,
 }
 
 impl Default for InteractionConfig {
@@ -114,10 +537,35 @@ impl Default for InteractionConfig {
             crosstalk: default_crosstalk(),
             duck_volume: default_duck_volume(),
             aec: false,
+            silence_ms: default_silence_ms(),
+            trailing_pad_ms: 0,
+            vad_onset_threshold: default_vad_onset_threshold(),
+            vad_offset_threshold: default_vad_offset_threshold(),
+            mode: InteractionMode::default(),
+            auto_pause_secs: 0,
+            start_muted: false,
+            unmute_on_wake: false,
         }
     }
 }
 
+impl InteractionConfig {
+    /// Silero onset/offset thresholds
+    pub fn vad_thresholds(&self) -> crate::vad::Hysteresis {
+        crate::vad::Hysteresis::new(self.vad_onset_threshold, self.vad_offset_threshold)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum InteractionMode {
+    /// Voice activity detection decides when speech starts and ends
+    #[default]
+    Vad,
+    /// Only listen while the push-to-talk hotkey is held (VAD bypassed)
+    PushToTalk,
+}
+
 fn default_crosstalk() -> bool {
     false
 }
@@ -126,13 +574,25 @@ fn default_duck_volume() -> f32 {
     0.2
 }
 
+fn default_silence_ms() -> u32 {
+    crate::audio::DEFAULT_SILENCE_MS
+}
+
+fn default_vad_onset_threshold() -> f32 {
+    crate::vad::VAD_THRESHOLD
+}
+
+fn default_vad_offset_threshold() -> f32 {
+    crate::vad::VAD_THRESHOLD_END
+}
+
 // ============================================================================
 // Typing Config (voice-to-keyboard)
 // ============================================================================
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct TypingConfig {
-    /// Input method: "direct" (default) or "clipboard"
+    /// Input method: "direct" (default), "clipboard" or "dry-run"
     #[serde(default = "default_typing_input_method")]
     pub input_method: String,
 
@@ -152,6 +612,38 @@ pub struct TypingConfig {
     #[serde(default = "default_typing_stop_phrase")]
     #[allow(dead_code)]
     pub stop_phrase: String,
+
+    /// Custom punctuation phrases, e.g. smiley = ":)" or arrow = "->"
+    #[serde(default)]
+    pub punctuation: HashMap<String, String>,
+
+    /// Maximum gap (ms) between the two taps of the double-tap Cmd toggle
+    #[serde(default = "default_typing_double_tap_ms")]
+    pub double_tap_ms: u64,
+
+    /// Prefixes that type the next phrase as words ("literal comma" -> "comma")
+    #[serde(default = "default_typing_literal_prefixes")]
+    pub literal_prefixes: Vec<String>,
+
+    /// Assume the target auto-inserts closing brackets and quotes (code editors)
+    #[serde(default)]
+    pub auto_pair: bool,
+
+    /// Minimum gap (ms) between key events, for apps that drop fast keystrokes
+    #[serde(default)]
+    pub keystroke_delay_ms: u64,
+
+    /// Slow down typing when key events fail, then speed back up
+    #[serde(default)]
+    pub adaptive_delay: bool,
+
+    /// Show a one-line status (typing/paused, last command, undo depth)
+    #[serde(default)]
+    pub status_line: bool,
+
+    /// Put back what the clipboard held after pasting with the clipboard method
+    #[serde(default = "default_typing_restore_clipboard")]
+    pub restore_clipboard: bool,
 }
 
 impl Default for TypingConfig {
@@ -162,10 +654,34 @@ impl Default for TypingConfig {
             undo_buffer_size: default_typing_undo_buffer_size(),
             command_pause_ms: default_typing_command_pause_ms(),
             stop_phrase: default_typing_stop_phrase(),
+            punctuation: HashMap::new(),
+            double_tap_ms: default_typing_double_tap_ms(),
+            literal_prefixes: default_typing_literal_prefixes(),
+            auto_pair: false,
+            keystroke_delay_ms: 0,
+            adaptive_delay: false,
+            status_line: false,
+            restore_clipboard: default_typing_restore_clipboard(),
         }
     }
 }
 
+fn default_typing_restore_clipboard() -> bool {
+    true
+}
+
+fn default_typing_double_tap_ms() -> u64 {
+    350
+}
+
+fn default_typing_literal_prefixes() -> Vec<String> {
+    vec![
+        "literal".to_string(),
+        "spell".to_string(),
+        "type the word".to_string(),
+    ]
+}
+
 fn default_typing_input_method() -> String {
     "direct".to_string()
 }
@@ -190,7 +706,7 @@ fn default_typing_stop_phrase() -> String {
 // Commands Config
 // ============================================================================
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct CommandsConfig {
     /// Enable built-in commands
     #[serde(default = "default_enable_builtin")]
@@ -203,6 +719,14 @@ pub struct CommandsConfig {
     /// Custom command mappings
     #[serde(default)]
     pub custom: Vec<CustomCommand>,
+
+    /// Edits allowed per character when fuzzy matching spoken commands
+    #[serde(default = "default_fuzzy_budget")]
+    pub fuzzy_budget: f32,
+
+    /// Ask "are you sure?" before a spoken shutdown command takes effect
+    #[serde(default)]
+    pub confirm_shutdown: bool,
 }
 
 impl Default for CommandsConfig {
@@ -211,11 +735,13 @@ impl Default for CommandsConfig {
             enable_builtin: default_enable_builtin(),
             stop_phrases: default_stop_phrases(),
             custom: Vec::new(),
+            fuzzy_budget: default_fuzzy_budget(),
+            confirm_shutdown: false,
         }
     }
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct CustomCommand {
     pub phrase: String,
     pub action: String,
@@ -225,6 +751,10 @@ fn default_enable_builtin() -> bool {
     true
 }
 
+fn default_fuzzy_budget() -> f32 {
+    crate::fuzzy::DEFAULT_FUZZY_BUDGET
+}
+
 fn default_stop_phrases() -> Vec<String> {
     vec![
         "stop".to_string(),
@@ -234,7 +764,7 @@ fn default_stop_phrases() -> Vec<String> {
     ]
 }
 
-#[derive(Debug, Deserialize, Default)]
+#[derive(Debug, Serialize, Deserialize, Default)]
 pub struct AccelerationConfig {
     #[serde(default = "default_tts_gpu")]
     pub tts_gpu: bool,
@@ -253,18 +783,21 @@ fn default_vad_gpu() -> bool {
 fn default_name() -> String {
     "Silly".into()
 }
-fn default_wake_word() -> String {
-    "Hey Silly".into()
+fn default_wake_word() -> WakeWordConfig {
+    WakeWordConfig::Single("Hey Silly".into())
 }
 fn default_wake_timeout() -> u64 {
     30
 }
+fn default_wake_sensitivity() -> f32 {
+    0.5
+}
 
 // ============================================================================
 // LLM Config
 // ============================================================================
 
-#[derive(Debug, Deserialize, Clone, Copy, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum PromptFormat {
     #[default]
@@ -273,7 +806,7 @@ pub enum PromptFormat {
     Llama3,  // Llama 3: <|begin_of_text|>...<|eot_id|>
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[serde(tag = "backend")]
 #[allow(dead_code)]
 pub enum LlmConfig {
@@ -299,6 +832,9 @@ pub enum LlmConfig {
     Ollama {
         #[serde(default = "default_ollama_model")]
         model: String,
+        /// Server URL, e.g. "http://gpu-box:11434" (port defaults to 11434)
+        #[serde(default = "default_ollama_host")]
+        host: String,
     },
     #[serde(rename = "kalosm")]
     Kalosm {
@@ -343,6 +879,7 @@ impl Default for LlmConfig {
         {
             LlmConfig::Ollama {
                 model: default_ollama_model(),
+                host: default_ollama_host(),
             }
         }
         #[cfg(all(
@@ -388,6 +925,45 @@ fn default_ollama_model() -> String {
     "mistral:7b-instruct".into()
 }
 
+fn default_ollama_host() -> String {
+    format!("http://localhost:{}", OLLAMA_PORT)
+}
+
+/// Port Ollama listens on unless told otherwise
+const OLLAMA_PORT: u16 = 11434;
+
+/// Normalize an Ollama host: add a missing `http://` scheme and default
+/// port, and drop trailing slashes
+///
+/// The port is only added to plain HTTP hosts; HTTPS means a proxy in front,
+/// which listens on 443.
+fn normalize_ollama_host(host: &str) -> Result<String, String> {
+    let host = host.trim().trim_end_matches('/');
+    let url = if host.contains("://") {
+        host.to_string()
+    } else {
+        format!("http://{}", host)
+    };
+
+    let (scheme, rest) = url.split_once("://").unwrap_or_default();
+    if scheme != "http" && scheme != "https" {
+        return Err(format!("unsupported scheme '{}' in Ollama host", scheme));
+    }
+    let authority = rest.split('/').next().unwrap_or_default();
+    if authority.is_empty() {
+        return Err(format!("no host name in '{}'", url));
+    }
+    // "[::1]" ends in "1]", which isn't a port
+    let has_port = authority
+        .rsplit_once(':')
+        .is_some_and(|(_, port)| !port.is_empty() && port.chars().all(|c| c.is_ascii_digit()));
+    if has_port || scheme == "https" || rest.len() > authority.len() {
+        Ok(url)
+    } else {
+        Ok(format!("{}:{}", url, OLLAMA_PORT))
+    }
+}
+
 fn default_kalosm_model() -> String {
     "qwen-1.5b".into()
 }
@@ -413,9 +989,114 @@ fn expand_env_vars(s: &str) -> String {
     result
 }
 
+/// Keys whose values are never printed
+const SECRET_KEYS: &[&str] = &["api_key"];
+
+/// Redact secrets and undo f32 widening (0.3 would print as
+/// 0.30000001192092896; every float in the config is an f32)
+fn tidy_for_print(value: &mut toml::Value) {
+    match value {
+        toml::Value::Table(table) => {
+            for (key, value) in table.iter_mut() {
+                if SECRET_KEYS.contains(&key.as_str()) {
+                    *value = toml::Value::String("<redacted>".to_string());
+                } else {
+                    tidy_for_print(value);
+                }
+            }
+        }
+        toml::Value::Array(items) => items.iter_mut().for_each(tidy_for_print),
+        toml::Value::Float(f) => {
+            *f = (*f as f32).to_string().parse().unwrap_or(*f);
+        }
+        _ => {}
+    }
+}
+
+/// Reads typed values from `SILLY_*` variables, recording each applied or
+/// rejected override. Unset and empty variables are skipped
+struct EnvOverrides<F> {
+    var: F,
+    report: Vec<Result<String, String>>,
+}
+
+impl<F: Fn(&str) -> Option<String>> EnvOverrides<F> {
+    fn get<T: FromStr>(&mut self, name: &str, key: &str) -> Option<T>
+    where
+        T::Err: std::fmt::Display,
+    {
+        let raw = (self.var)(name).filter(|v| !v.trim().is_empty())?;
+        match raw.trim().parse() {
+            Ok(value) => {
+                self.report
+                    .push(Ok(format!("{} overrides {} = {}", name, key, raw.trim())));
+                Some(value)
+            }
+            Err(e) => {
+                self.report
+                    .push(Err(format!("Ignoring {}={:?}: {}", name, raw, e)));
+                None
+            }
+        }
+    }
+
+    /// Set `target` from `name`; `None` means the setting doesn't exist for
+    /// the configured backend
+    fn apply<T: FromStr>(&mut self, target: Option<&mut T>, name: &str, key: &str)
+    where
+        T::Err: std::fmt::Display,
+    {
+        match target {
+            Some(target) => {
+                if let Some(value) = self.get(name, key) {
+                    *target = value;
+                }
+            }
+            None => {
+                if (self.var)(name).is_some_and(|v| !v.trim().is_empty()) {
+                    self.report.push(Err(format!(
+                        "Ignoring {}: {} isn't used by this llm.backend",
+                        name, key
+                    )));
+                }
+            }
+        }
+    }
+}
+
 impl LlmConfig {
-    /// Resolve preset to base_url if needed, and expand env vars in api_key
+    /// Where LLM requests go, for `/endpoint`
+    pub fn endpoint(&self) -> String {
+        match self {
+            LlmConfig::LlamaCpp {
+                model_path,
+                hf_file,
+                ..
+            } => format!(
+                "llama.cpp, local model {}",
+                model_path.as_deref().unwrap_or(hf_file)
+            ),
+            LlmConfig::Ollama { host, .. } => format!("Ollama at {}", host),
+            LlmConfig::Kalosm { model } => format!("Kalosm, local model {}", model),
+            LlmConfig::OpenAiCompat { base_url, .. } => {
+                format!("OpenAI-compatible API at {}", base_url)
+            }
+        }
+    }
+
+    /// Resolve preset to base_url if needed, expand env vars in api_key, and
+    /// normalize the Ollama host
     pub fn resolve_presets(&mut self) {
+        if let LlmConfig::Ollama { host, .. } = self {
+            match normalize_ollama_host(host) {
+                Ok(normalized) => *host = normalized,
+                Err(e) => {
+                    eprintln!("Warning: {}, using {}", e, default_ollama_host());
+                    *host = default_ollama_host();
+                }
+            }
+        }
+
         if let LlmConfig::OpenAiCompat {
             base_url,
             preset,
@@ -452,11 +1133,82 @@ impl LlmConfig {
     }
 }
 
+// ============================================================================
+// Chat Config
+// ============================================================================
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ChatConfig {
+    /// Save the conversation after each response and restore it on startup
+    #[serde(default)]
+    pub persist: bool,
+    /// Trim the conversation once it grows past this many words
+    #[serde(default = "default_max_context_words")]
+    pub max_context_words: usize,
+    /// How to trim: "drop_oldest" or "summarize"
+    #[serde(default)]
+    pub trim_strategy: TrimStrategy,
+    /// Persona: the system prompt itself or a path to a file holding it,
+    /// with `{name}` replaced by the assistant name
+    #[serde(default)]
+    pub system_prompt: Option<String>,
+    /// Retries when the LLM request fails to connect or times out
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+    /// Seconds without a first token before "Still thinking…" is shown
+    /// (0 = never)
+    #[serde(default = "default_slow_threshold_secs")]
+    pub slow_threshold_secs: u64,
+    /// Play a soft tick along with the "Still thinking…" message
+    #[serde(default = "default_slow_tick")]
+    pub slow_tick: bool,
+}
+
+impl Default for ChatConfig {
+    fn default() -> Self {
+        Self {
+            persist: false,
+            max_context_words: default_max_context_words(),
+            trim_strategy: TrimStrategy::default(),
+            system_prompt: None,
+            max_retries: default_max_retries(),
+            slow_threshold_secs: default_slow_threshold_secs(),
+            slow_tick: default_slow_tick(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TrimStrategy {
+    /// Discard the oldest turns
+    #[default]
+    DropOldest,
+    /// Have the LLM condense the oldest turns into a system note
+    Summarize,
+}
+
+fn default_max_context_words() -> usize {
+    crate::chat::DEFAULT_MAX_CONTEXT_WORDS
+}
+
+fn default_max_retries() -> u32 {
+    crate::chat::DEFAULT_MAX_RETRIES
+}
+
+fn default_slow_threshold_secs() -> u64 {
+    8
+}
+
+fn default_slow_tick() -> bool {
+    true
+}
+
 // ============================================================================
 // TTS Config
 // ============================================================================
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[serde(tag = "engine")]
 #[allow(dead_code)]
 pub enum TtsConfig {
@@ -477,6 +1229,9 @@ pub enum TtsConfig {
         voice_style: String,
         #[serde(default = "default_tts_speed")]
         speed: f32,
+        /// Denoising steps (4-64): more is slower but cleaner
+        #[serde(default = "default_supertonic_steps")]
+        steps: usize,
     },
 }
 
@@ -488,6 +1243,7 @@ impl Default for TtsConfig {
                 onnx_dir: default_supertonic_onnx_dir(),
                 voice_style: default_supertonic_voice_style(),
                 speed: default_tts_speed(),
+                steps: default_supertonic_steps(),
             }
         }
         #[cfg(all(feature = "kokoro", not(feature = "supertonic")))]
@@ -505,6 +1261,23 @@ impl Default for TtsConfig {
     }
 }
 
+impl TtsConfig {
+    /// Configured speech speed, whichever engine is selected
+    pub fn speed(&self) -> f32 {
+        match self {
+            TtsConfig::Kokoro { speed, .. } | TtsConfig::Supertonic { speed, .. } => *speed,
+        }
+    }
+
+    /// Configured denoising steps (only Supertonic has them)
+    pub fn steps(&self) -> usize {
+        match self {
+            TtsConfig::Supertonic { steps, .. } => *steps,
+            TtsConfig::Kokoro { .. } => crate::state::DEFAULT_TTS_STEPS,
+        }
+    }
+}
+
 fn default_kokoro_model() -> String {
     "kokoro-v1.0.onnx".into()
 }
@@ -521,21 +1294,645 @@ fn default_tts_speed() -> f32 {
     1.1
 }
 
+fn default_supertonic_steps() -> usize {
+    crate::state::DEFAULT_TTS_STEPS
+}
+
 impl Config {
-    pub fn load() -> Self {
-        let path = Path::new("config.toml");
+    /// Load config.toml from the working directory (defaults if there's none)
+    ///
+    /// A file that can't be read or parsed is reported like the problems
+    /// `validate` finds, rather than quietly replaced by the defaults.
+    pub fn load() -> Result<Self, Vec<String>> {
+        Self::load_from(Path::new("config.toml"))
+    }
+
+    /// `load`, falling back to the defaults on errors, for settings read again
+    /// after startup has already reported them
+    pub fn load_or_default() -> Self {
+        Self::load().unwrap_or_else(|problems| {
+            crate::logger::error!("{}", problem_report(&problems));
+            Config::default()
+        })
+    }
+
+    fn load_from(path: &Path) -> Result<Self, Vec<String>> {
         let mut config = if path.exists() {
-            fs::read_to_string(path)
-                .ok()
-                .and_then(|s| toml::from_str(&s).ok())
-                .unwrap_or_default()
+            let contents = fs::read_to_string(path)
+                .map_err(|e| vec![format!("can't read {}: {}", path.display(), e)])?;
+            toml::from_str(&contents)
+                .map_err(|e| vec![format!("{} isn't valid: {}", path.display(), e)])?
         } else {
             Config::default()
         };
 
+        for result in config.apply_env_overrides(|name| std::env::var(name).ok()) {
+            match result {
+                Ok(applied) => crate::logger::info!("{}", applied),
+                Err(rejected) => crate::logger::warn!("{}", rejected),
+            }
+        }
+
         // Resolve presets
         config.llm.resolve_presets();
 
-        config
+        Ok(config)
+    }
+
+    /// The resolved config as TOML, defaults included and secrets redacted
+    pub fn to_toml(&self) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let mut value = toml::Value::try_from(self)?;
+        tidy_for_print(&mut value);
+        Ok(toml::to_string(&value)?)
+    }
+
+    /// Apply `SILLY_*` environment variables on top of the file, returning
+    /// each applied override or the reason one was ignored. Runs before
+    /// preset resolution so an overridden Ollama host is normalized too
+    fn apply_env_overrides(
+        &mut self,
+        var: impl Fn(&str) -> Option<String>,
+    ) -> Vec<Result<String, String>> {
+        let mut env = EnvOverrides {
+            var,
+            report: Vec::new(),
+        };
+
+        env.apply(Some(&mut self.name), "SILLY_NAME", "name");
+        if let Some(phrases) = env.get::<String>("SILLY_WAKE_WORD", "wake_word") {
+            let mut phrases: Vec<String> = phrases
+                .split(',')
+                .map(|p| p.trim().to_string())
+                .filter(|p| !p.is_empty())
+                .collect();
+            self.wake_word = if phrases.len() == 1 {
+                WakeWordConfig::Single(phrases.remove(0))
+            } else {
+                WakeWordConfig::Multiple(phrases)
+            };
+        }
+        env.apply(
+            Some(&mut self.wake_timeout_secs),
+            "SILLY_WAKE_TIMEOUT_SECS",
+            "wake_timeout_secs",
+        );
+
+        let speed = match &mut self.tts {
+            TtsConfig::Kokoro { speed, .. } | TtsConfig::Supertonic { speed, .. } => speed,
+        };
+        env.apply(Some(speed), "SILLY_TTS_SPEED", "tts.speed");
+
+        let (model, host, base_url) = match &mut self.llm {
+            LlmConfig::LlamaCpp { .. } => (None, None, None),
+            LlmConfig::Ollama { model, host } => (Some(model), Some(host), None),
+            LlmConfig::Kalosm { model } => (Some(model), None, None),
+            LlmConfig::OpenAiCompat {
+                model, base_url, ..
+            } => (Some(model), None, Some(base_url)),
+        };
+        env.apply(model, "SILLY_LLM_MODEL", "llm.model");
+        env.apply(host, "SILLY_OLLAMA_HOST", "llm.host");
+        env.apply(base_url, "SILLY_LLM_BASE_URL", "llm.base_url");
+
+        env.apply(
+            Some(&mut self.audio.source),
+            "SILLY_AUDIO_SOURCE",
+            "audio.source",
+        );
+        if let Some(path) = env.get("SILLY_TRANSCRIBER_PATH", "models.transcriber_path") {
+            self.models.transcriber_path = Some(path);
+        }
+        if let Some(path) = env.get("SILLY_VAD_PATH", "models.vad_path") {
+            self.models.vad_path = Some(path);
+        }
+
+        env.report
+    }
+
+    /// Check for settings that would fail deep into startup or be silently
+    /// clamped, collecting every problem rather than stopping at the first
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        use crate::state::{MAX_TTS_SPEED, MAX_TTS_STEPS, MIN_TTS_SPEED, MIN_TTS_STEPS};
+
+        let mut problems = Vec::new();
+        let missing = |path: &str| !Path::new(path).exists();
+
+        // TTS: an engine that isn't built in is only replaced by the other one
+        let (engine, built_in) = match &self.tts {
+            TtsConfig::Kokoro { .. } => ("kokoro", cfg!(feature = "kokoro")),
+            TtsConfig::Supertonic { .. } => ("supertonic", cfg!(feature = "supertonic")),
+        };
+        if !built_in && !cfg!(any(feature = "kokoro", feature = "supertonic")) {
+            problems.push(format!(
+                "tts.engine = \"{}\" is not built in (rebuild with --features {})",
+                engine, engine
+            ));
+        }
+        let speed = self.tts.speed();
+        if !(MIN_TTS_SPEED..=MAX_TTS_SPEED).contains(&speed) {
+            problems.push(format!(
+                "tts.speed = {} is out of range ({} to {})",
+                speed, MIN_TTS_SPEED, MAX_TTS_SPEED
+            ));
+        }
+        match &self.tts {
+            TtsConfig::Kokoro { model, voices, .. } => {
+                for (key, path) in [("model", model), ("voices", voices)] {
+                    // Relative paths are looked up in the model directories
+                    if Path::new(path).is_absolute() && missing(path) {
+                        problems.push(format!("tts.{} = \"{}\" does not exist", key, path));
+                    }
+                }
+            }
+            TtsConfig::Supertonic {
+                onnx_dir,
+                voice_style,
+                steps,
+                ..
+            } => {
+                for (key, path) in [("onnx_dir", onnx_dir), ("voice_style", voice_style)] {
+                    if Path::new(path).is_absolute() && missing(path) {
+                        problems.push(format!("tts.{} = \"{}\" does not exist", key, path));
+                    }
+                }
+                if !(MIN_TTS_STEPS..=MAX_TTS_STEPS).contains(steps) {
+                    problems.push(format!(
+                        "tts.steps = {} is out of range ({} to {})",
+                        steps, MIN_TTS_STEPS, MAX_TTS_STEPS
+                    ));
+                }
+            }
+        }
+
+        // LLM backend
+        let (backend, built_in) = match &self.llm {
+            LlmConfig::LlamaCpp { .. } => ("llama-cpp", cfg!(feature = "llama-cpp")),
+            LlmConfig::Ollama { .. } => ("ollama", cfg!(feature = "ollama")),
+            LlmConfig::Kalosm { .. } => ("kalosm", cfg!(feature = "kalosm")),
+            LlmConfig::OpenAiCompat { .. } => ("openai-compat", cfg!(feature = "openai-compat")),
+        };
+        if !built_in {
+            problems.push(format!(
+                "llm.backend = \"{}\" is not built in (rebuild with --features {})",
+                backend, backend
+            ));
+        }
+        if let LlmConfig::LlamaCpp {
+            model_path: Some(path),
+            ..
+        } = &self.llm
+            && missing(path)
+        {
+            problems.push(format!("llm.model_path = \"{}\" does not exist", path));
+        }
+
+        // Model overrides
+        for (key, path) in [
+            ("transcriber_path", &self.models.transcriber_path),
+            ("vad_path", &self.models.vad_path),
+        ] {
+            if let Some(path) = path.as_deref().filter(|p| missing(p)) {
+                problems.push(format!("models.{} = \"{}\" does not exist", key, path));
+            }
+        }
+
+        // Per-language transcriber models
+        for (language, path) in &self.transcriber.models {
+            if missing(path) {
+                problems.push(format!(
+                    "transcriber.models.{} = \"{}\" does not exist",
+                    language, path
+                ));
+            }
+        }
+        if let Some(language) = self
+            .transcriber
+            .language
+            .as_ref()
+            .filter(|l| !self.transcriber.models.contains_key(*l))
+        {
+            problems.push(format!(
+                "transcriber.language = \"{}\" has no model in transcriber.models",
+                language
+            ));
+        }
+
+        // Wake word
+        let phrases = self.wake_word.phrases();
+        if phrases.is_empty() || phrases.iter().any(|p| p.trim().is_empty()) {
+            problems.push("wake_word must be a non-empty phrase (or list of phrases)".to_string());
+        }
+        if !(0.0..=1.0).contains(&self.wake_sensitivity) {
+            problems.push(format!(
+                "wake_sensitivity = {} is out of range (0.0 to 1.0)",
+                self.wake_sensitivity
+            ));
+        }
+
+        // Interaction
+        let interaction = &self.interaction;
+        if !(0.0..=1.0).contains(&interaction.duck_volume) {
+            problems.push(format!(
+                "interaction.duck_volume = {} is out of range (0.0 to 1.0)",
+                interaction.duck_volume
+            ));
+        }
+        for (key, value) in [
+            ("vad_onset_threshold", interaction.vad_onset_threshold),
+            ("vad_offset_threshold", interaction.vad_offset_threshold),
+        ] {
+            if !(0.0..=1.0).contains(&value) {
+                problems.push(format!(
+                    "interaction.{} = {} is out of range (0.0 to 1.0)",
+                    key, value
+                ));
+            }
+        }
+
+        // Audio
+        if !(0.0..=1.0).contains(&self.audio.output_volume) {
+            problems.push(format!(
+                "audio.output_volume = {} is out of range (0.0 to 1.0)",
+                self.audio.output_volume
+            ));
+        }
+        if self.audio.agc && !(self.audio.target_rms > 0.0 && self.audio.target_rms <= 1.0) {
+            problems.push(format!(
+                "audio.target_rms = {} is out of range (above 0.0, up to 1.0)",
+                self.audio.target_rms
+            ));
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(problems)
+        }
+    }
+
+    /// Set `key = "value"` under `[section]` in config.toml, keeping the rest
+    /// of the file (comments included) untouched
+    pub fn save_value(section: &str, key: &str, value: &str) -> std::io::Result<()> {
+        let path = Path::new("config.toml");
+        let contents = if path.exists() {
+            fs::read_to_string(path)?
+        } else {
+            String::new()
+        };
+        fs::write(path, set_toml_value(&contents, section, key, value))
+    }
+
+    /// Remember a UI choice (`[ui] key = "value"`) for the next run; a failed
+    /// save is only logged, since the choice still applies now
+    pub fn remember_ui_choice(key: &str, value: &str) {
+        if let Err(e) = Self::save_value("ui", key, value) {
+            crate::logger::warn!("Failed to save ui.{}: {}", key, e);
+        }
+    }
+}
+
+/// One readable report for the problems found by `Config::validate`
+pub fn problem_report(problems: &[String]) -> String {
+    let mut report = format!(
+        "config.toml has {} problem{}:",
+        problems.len(),
+        if problems.len() == 1 { "" } else { "s" }
+    );
+    for problem in problems {
+        report.push_str("\n  - ");
+        report.push_str(problem);
+    }
+    report
+}
+
+/// The table name of a `[section]` header line, ignoring spacing inside the
+/// brackets and a trailing comment (`[ ui ]  # display` is "ui")
+fn table_header(line: &str) -> Option<&str> {
+    let line = line.split('#').next().unwrap_or_default().trim();
+    let name = line.strip_prefix('[')?.strip_suffix(']')?;
+    // Arrays of tables ("[[x]]") aren't sections we write to
+    if name.starts_with('[') {
+        return None;
+    }
+    Some(name.trim())
+}
+
+/// Replace or insert a string value in a TOML section, creating the section if needed
+fn set_toml_value(contents: &str, section: &str, key: &str, value: &str) -> String {
+    let header = format!("[{}]", section);
+    let entry = format!("{} = \"{}\"", key, value);
+    let mut lines: Vec<String> = contents.lines().map(String::from).collect();
+
+    let Some(start) = lines.iter().position(|l| table_header(l) == Some(section)) else {
+        if lines.last().is_some_and(|l| !l.trim().is_empty()) {
+            lines.push(String::new());
+        }
+        lines.push(header);
+        lines.push(entry);
+        return lines.join("\n") + "\n";
+    };
+
+    let end = lines[start + 1..]
+        .iter()
+        .position(|l| l.trim_start().starts_with('['))
+        .map_or(lines.len(), |i| start + 1 + i);
+
+    let existing = (start + 1..end).find(|&i| {
+        lines[i]
+            .trim_start()
+            .strip_prefix(key)
+            .is_some_and(|rest| rest.trim_start().starts_with('='))
+    });
+
+    match existing {
+        Some(i) => lines[i] = entry,
+        None => {
+            // Insert after the last non-blank line of the section
+            let mut insert_at = end;
+            while insert_at > start + 1 && lines[insert_at - 1].trim().is_empty() {
+                insert_at -= 1;
+            }
+            lines.insert(insert_at, entry);
+        }
+    }
+    lines.join("\n") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wake_word_single_or_list() {
+        let config: Config = toml::from_str(r#"wake_word = "Hey Silly""#).unwrap();
+        assert_eq!(config.wake_word.phrases(), vec!["Hey Silly"]);
+
+        let config: Config = toml::from_str(r#"wake_word = ["Hey Silly", "Okay Silly"]"#).unwrap();
+        assert_eq!(config.wake_word.phrases(), vec!["Hey Silly", "Okay Silly"]);
+
+        let config: Config = toml::from_str("").unwrap();
+        assert_eq!(config.wake_word.phrases(), vec!["Hey Silly"]);
+    }
+
+    #[test]
+    fn test_validate() {
+        let config: Config = toml::from_str("").unwrap();
+        assert_eq!(config.validate(), Ok(()));
+
+        let config: Config = toml::from_str(
+            r#"
+            wake_word = ["Hey Silly", " "]
+            wake_sensitivity = 1.5
+
+            [tts]
+            engine = "supertonic"
+            speed = 5.0
+            steps = 2
+            onnx_dir = "/nonexistent/supertonic/onnx"
+
+            [models]
+            vad_path = "/nonexistent/silero.onnx"
+
+            [interaction]
+            duck_volume = -0.1
+
+            [audio]
+            output_volume = 1.5
+            agc = true
+            target_rms = 0.0
+            "#,
+        )
+        .unwrap();
+        let problems = config.validate().unwrap_err();
+        assert_eq!(
+            problems,
+            vec![
+                "tts.speed = 5 is out of range (0.5 to 2)",
+                "tts.onnx_dir = \"/nonexistent/supertonic/onnx\" does not exist",
+                "tts.steps = 2 is out of range (4 to 64)",
+                "models.vad_path = \"/nonexistent/silero.onnx\" does not exist",
+                "wake_word must be a non-empty phrase (or list of phrases)",
+                "wake_sensitivity = 1.5 is out of range (0.0 to 1.0)",
+                "interaction.duck_volume = -0.1 is out of range (0.0 to 1.0)",
+                "audio.output_volume = 1.5 is out of range (0.0 to 1.0)",
+                "audio.target_rms = 0 is out of range (above 0.0, up to 1.0)",
+            ]
+        );
+        assert!(
+            problem_report(&problems).starts_with("config.toml has 9 problems:\n  - tts.speed")
+        );
+    }
+
+    #[test]
+    fn test_load_reports_malformed_file() {
+        let dir = std::env::temp_dir().join(format!("silly-config-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let missing = dir.join("missing.toml");
+        assert!(Config::load_from(&missing).is_ok());
+
+        let path = dir.join("config.toml");
+        fs::write(&path, "[tts]\nspeed = \"fast\"\n[llm\n").unwrap();
+        let problems = Config::load_from(&path).unwrap_err();
+        fs::remove_dir_all(&dir).ok();
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].contains("config.toml isn't valid"));
+        assert!(problem_report(&problems).starts_with("config.toml has 1 problem:"));
+    }
+
+    #[test]
+    fn test_validate_transcriber_languages() {
+        let config: Config = toml::from_str(
+            r#"
+            [transcriber]
+            language = "fr"
+
+            [transcriber.models]
+            es = "/nonexistent/parakeet-es"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.validate().unwrap_err(),
+            vec![
+                "transcriber.models.es = \"/nonexistent/parakeet-es\" does not exist",
+                "transcriber.language = \"fr\" has no model in transcriber.models",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_backend_features() {
+        let config: Config = toml::from_str(
+            r#"
+            [llm]
+            backend = "llama-cpp"
+            model_path = "/nonexistent/model.gguf"
+            "#,
+        )
+        .unwrap();
+        let problems = config.validate().unwrap_err();
+        assert!(
+            problems.contains(
+                &"llm.model_path = \"/nonexistent/model.gguf\" does not exist".to_string()
+            )
+        );
+        assert_eq!(
+            problems
+                .iter()
+                .any(|p| p.starts_with("llm.backend = \"llama-cpp\" is not built in")),
+            !cfg!(feature = "llama-cpp")
+        );
+    }
+
+    #[test]
+    fn test_set_toml_value() {
+        let contents = "name = \"Silly\"\n\n[ui]\nmode = \"orb\"  # comment\n\n[interaction]\ncrosstalk = true\n";
+
+        // Insert into existing section
+        let updated = set_toml_value(contents, "ui", "default_style", "ring");
+        assert_eq!(
+            updated,
+            "name = \"Silly\"\n\n[ui]\nmode = \"orb\"  # comment\ndefault_style = \"ring\"\n\n[interaction]\ncrosstalk = true\n"
+        );
+
+        // Replace existing value
+        let updated = set_toml_value(&updated, "ui", "default_style", "sphere");
+        let config: Config = toml::from_str(&updated).unwrap();
+        assert_eq!(config.ui.default_style.as_deref(), Some("sphere"));
+        assert_eq!(config.ui.mode, UiModeConfig::Orb);
+        assert!(config.interaction.crosstalk);
+
+        // Create missing section
+        let updated = set_toml_value("name = \"Silly\"\n", "ui", "default_shade", "classic");
+        assert_eq!(
+            updated,
+            "name = \"Silly\"\n\n[ui]\ndefault_shade = \"classic\"\n"
+        );
+        let updated = set_toml_value("", "ui", "default_shade", "lines");
+        assert_eq!(updated, "[ui]\ndefault_shade = \"lines\"\n");
+
+        // Headers with spacing or a comment are still the same table, so no
+        // second [ui] is appended (which would make the file invalid)
+        let contents =
+            "[ ui ]  # display\ndefault_style = \"ring\"\n\n[interaction]\ncrosstalk = true\n";
+        let updated = set_toml_value(contents, "ui", "default_style", "sphere");
+        assert_eq!(
+            updated,
+            "[ ui ]  # display\ndefault_style = \"sphere\"\n\n[interaction]\ncrosstalk = true\n"
+        );
+        let config: Config = toml::from_str(&updated).unwrap();
+        assert_eq!(config.ui.default_style.as_deref(), Some("sphere"));
+    }
+
+    #[test]
+    fn test_normalize_ollama_host() {
+        let ok = |host: &str| normalize_ollama_host(host).unwrap();
+        assert_eq!(ok("http://localhost:11434/"), "http://localhost:11434");
+        assert_eq!(ok("gpu-box"), "http://gpu-box:11434");
+        assert_eq!(ok(" 192.168.1.20:8080 "), "http://192.168.1.20:8080");
+        assert_eq!(
+            ok("https://ollama.example.com/"),
+            "https://ollama.example.com"
+        );
+        assert_eq!(ok("http://[::1]"), "http://[::1]:11434");
+        // A path means a reverse proxy, leave the port alone
+        assert_eq!(
+            ok("https://example.com/ollama"),
+            "https://example.com/ollama"
+        );
+
+        assert!(normalize_ollama_host("").is_err());
+        assert!(normalize_ollama_host("ftp://gpu-box").is_err());
+    }
+
+    #[test]
+    fn test_capture_source() {
+        let parse = |toml: &str| toml::from_str::<AudioConfig>(toml).map(|c| c.source);
+        assert_eq!(parse("").unwrap(), CaptureSource::Mic);
+        assert_eq!(
+            parse(r#"source = "system""#).unwrap(),
+            CaptureSource::System
+        );
+        assert_eq!(
+            parse(r#"source = "app: Zoom""#).unwrap(),
+            CaptureSource::App("Zoom".to_string())
+        );
+        assert!(parse(r#"source = "app:""#).is_err());
+        assert!(parse(r#"source = "speakers""#).is_err());
+    }
+
+    #[test]
+    fn test_to_toml() {
+        let config: Config = toml::from_str(
+            r#"
+            wake_word = ["Hey Silly", "Okay Silly"]
+
+            [llm]
+            backend = "openai-compat"
+            preset = "openai"
+            model = "gpt-4o-mini"
+            api_key = "sk-secret"
+
+            [audio]
+            source = "app:Zoom"
+            "#,
+        )
+        .unwrap();
+        let printed = config.to_toml().unwrap();
+        assert!(!printed.contains("sk-secret"));
+        assert!(printed.contains("api_key = \"<redacted>\""));
+        assert!(printed.contains("wake_sensitivity = 0.5\n"));
+        assert!(printed.contains("vad_onset_threshold = 0.3\n"));
+
+        // Printed output is a valid config that loads back the same
+        let reloaded: Config = toml::from_str(&printed).unwrap();
+        assert_eq!(reloaded.wake_word.phrases(), config.wake_word.phrases());
+        assert_eq!(
+            reloaded.audio.source,
+            CaptureSource::App("Zoom".to_string())
+        );
+        assert_eq!(reloaded.llm.endpoint(), config.llm.endpoint());
+        assert_eq!(reloaded.to_toml().unwrap(), printed);
+    }
+
+    #[test]
+    fn test_env_overrides() {
+        let mut config: Config = toml::from_str(
+            r#"
+            [llm]
+            backend = "ollama"
+            "#,
+        )
+        .unwrap();
+        let env: HashMap<&str, &str> = [
+            ("SILLY_WAKE_WORD", "Hey Silly, Okay Silly"),
+            ("SILLY_TTS_SPEED", "1.25"),
+            ("SILLY_WAKE_TIMEOUT_SECS", "soon"),
+            ("SILLY_OLLAMA_HOST", "gpu-box"),
+            ("SILLY_LLM_BASE_URL", "http://localhost:1234/v1"),
+            ("SILLY_AUDIO_SOURCE", "system"),
+            ("SILLY_NAME", " "),
+        ]
+        .into();
+        let report = config.apply_env_overrides(|name| env.get(name).map(|v| v.to_string()));
+        config.llm.resolve_presets();
+
+        assert_eq!(config.name, "Silly");
+        assert_eq!(config.wake_word.phrases(), vec!["Hey Silly", "Okay Silly"]);
+        assert_eq!(config.wake_timeout_secs, default_wake_timeout());
+        assert!(
+            matches!(config.tts, TtsConfig::Supertonic { speed, .. } | TtsConfig::Kokoro { speed, .. } if speed == 1.25)
+        );
+        assert!(
+            matches!(&config.llm, LlmConfig::Ollama { host, .. } if host == "http://gpu-box:11434")
+        );
+        assert_eq!(config.audio.source, CaptureSource::System);
+
+        assert_eq!(report.iter().filter(|r| r.is_ok()).count(), 4);
+        let rejected: Vec<_> = report.into_iter().filter_map(Result::err).collect();
+        assert_eq!(rejected.len(), 2);
+        assert!(rejected[0].starts_with("Ignoring SILLY_WAKE_TIMEOUT_SECS=\"soon\""));
+        assert!(rejected[1].starts_with("Ignoring SILLY_LLM_BASE_URL"));
     }
 }
@@ -3,15 +3,44 @@
 //! Provides fuzzy matching for wake words and commands to handle
 //! transcription errors and variations in speech.
 
+use unicode_normalization::UnicodeNormalization;
+
+/// Default edits allowed per character of the expected text, about a third
+pub const DEFAULT_FUZZY_BUDGET: f32 = 0.34;
+
 /// Fuzzy match using Levenshtein distance, allows ~30% errors
 pub fn fuzzy_match(expected: &str, actual: &str) -> bool {
+    fuzzy_match_within(expected, actual, DEFAULT_FUZZY_BUDGET)
+}
+
+/// Fuzzy match allowing `budget` edits per character of `expected`, and at
+/// least one unless the budget is zero
+pub fn fuzzy_match_within(expected: &str, actual: &str, budget: f32) -> bool {
     if expected == actual {
         return true;
     }
-    let max_dist = (expected.len() / 3).max(1);
+    if budget <= 0.0 {
+        return false;
+    }
+    let max_dist = ((expected.chars().count() as f32 * budget) as usize).max(1);
     levenshtein(expected, actual) <= max_dist
 }
 
+/// Fuzzy match word by word: the same number of words, each within `budget`
+/// edits per character
+///
+/// Unlike `fuzzy_match_within` there's no minimum of one edit, so short words
+/// must match exactly: "repeat what" isn't "repeat that".
+pub fn fuzzy_match_words(expected: &str, actual: &str, budget: f32) -> bool {
+    let expected: Vec<&str> = expected.split_whitespace().collect();
+    let actual: Vec<&str> = actual.split_whitespace().collect();
+    expected.len() == actual.len()
+        && expected.iter().zip(&actual).all(|(e, a)| {
+            let max_dist = (e.chars().count() as f32 * budget) as usize;
+            levenshtein(e, a) <= max_dist
+        })
+}
+
 /// Calculate Levenshtein distance between two strings
 pub fn levenshtein(a: &str, b: &str) -> usize {
     let a: Vec<char> = a.chars().collect();
@@ -36,10 +65,14 @@ pub fn levenshtein(a: &str, b: &str) -> usize {
     dp[a.len()][b.len()]
 }
 
-/// Clean text for matching: lowercase and remove non-alphabetic characters
+/// Clean text for matching: NFC normalize, lowercase and remove
+/// non-alphabetic characters
+///
+/// Letters from any script are kept, and normalizing first makes an accent
+/// typed as a combining mark match its precomposed form.
 pub fn clean_for_matching(text: &str) -> String {
-    text.to_lowercase()
-        .chars()
+    text.nfc()
+        .flat_map(char::to_lowercase)
         .filter(|c| c.is_alphabetic() || c.is_whitespace())
         .collect()
 }
@@ -69,6 +102,28 @@ mod tests {
         assert!(!fuzzy_match("stop", "start"));
     }
 
+    #[test]
+    fn test_fuzzy_match_words() {
+        assert!(fuzzy_match_words(
+            "transcribe mode",
+            "transcribed mode",
+            0.15
+        ));
+        assert!(fuzzy_match_words(
+            "pause conversation",
+            "pause conversations",
+            0.15
+        ));
+        // Short words get no edits, and words can't be split or merged
+        assert!(!fuzzy_match_words("repeat that", "repeat what", 0.15));
+        assert!(!fuzzy_match_words("start typing", "start timing", 0.15));
+        assert!(!fuzzy_match_words(
+            "transcribe mode",
+            "trans scribe mode",
+            0.15
+        ));
+    }
+
     #[test]
     fn test_levenshtein() {
         assert_eq!(levenshtein("hello", "hello"), 0);
@@ -82,4 +137,21 @@ mod tests {
         assert_eq!(clean_for_matching("Stop."), "stop");
         assert_eq!(clean_for_matching("Hey, there!"), "hey there");
     }
+
+    #[test]
+    fn test_non_english_matching() {
+        assert_eq!(clean_for_matching("¡BASTA!"), "basta");
+        assert_eq!(clean_for_matching("Ça suffit."), "ça suffit");
+        // Combining cedilla composes to the same letter
+        assert_eq!(clean_for_matching("C\u{327}a suffit"), "ça suffit");
+        assert_eq!(clean_for_matching("Стоп!"), "стоп");
+        assert_eq!(clean_for_matching("やめて。"), "やめて");
+
+        assert!(fuzzy_match("ça suffit", "ca suffit"));
+        assert!(fuzzy_match("やめて", "やめで"));
+        // Budget counts characters: 2 edits in a 4-letter word is too many,
+        // even though "стоп" is 8 bytes
+        assert!(fuzzy_match("стоп", "стап"));
+        assert!(!fuzzy_match("стоп", "сдап"));
+    }
 }
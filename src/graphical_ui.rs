@@ -3,6 +3,9 @@
 //! Provides a visual representation of the assistant's state using animated
 //! ASCII art orbs. Supports multiple visual styles: Rings, Blob, and Ring.
 
+use crate::config::Config;
+use crate::line_editor::{LineEdit, LineEditor};
+use crate::logger;
 use crate::render::{OrbStyle, UiEvent, UiMode, UiRenderer};
 use crate::state::AppMode;
 use crate::status_bar::{StatusBarState, StatusDisplayStyle, StatusRenderer};
@@ -10,28 +13,31 @@ use crossterm::event::{self, Event, KeyCode, KeyModifiers};
 use crossterm::style::Color;
 use crossterm::terminal::{self, ClearType};
 use crossterm::{cursor, execute};
-use std::fs::OpenOptions;
+use std::fmt::Write as _;
 use std::io::{self, Write, stdout};
+use std::path::{Path, PathBuf};
 use std::thread;
 use std::time::{Duration, Instant};
-
-fn debug_log(msg: &str) {
-    if let Ok(mut file) = OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open("debug.log")
-    {
-        let _ = writeln!(
-            file,
-            "{}: {}",
-            chrono::Utc::now().format("%H:%M:%S%.3f"),
-            msg
-        );
-    }
-}
+use unicode_width::UnicodeWidthChar;
 
 const TAU: f64 = std::f64::consts::TAU;
 
+/// Redraw interval for the low-power minimal style (~10fps)
+const MINIMAL_FRAME_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How long the orb flashes to Listening when the wake word is heard
+pub const WAKE_FLASH: Duration = Duration::from_millis(800);
+/// Frames between full rewrites of the orb, which repair anything printed over
+/// it outside `draw` (log lines, other threads)
+const FULL_REPAINT_FRAMES: u32 = 150;
+
+/// Time between redraws for a target frame rate (`ui.target_fps`, 1-240)
+///
+/// Animation advances by real elapsed time, so this only changes smoothness.
+pub fn frame_interval(target_fps: u32) -> Duration {
+    Duration::from_secs_f64(1.0 / target_fps.clamp(1, 240) as f64)
+}
+
 // ============================================================================
 // Orb State (maps to assistant states)
 // ============================================================================
@@ -39,6 +45,8 @@ const TAU: f64 = std::f64::consts::TAU;
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum OrbState {
     Idle,
+    /// Paused and waiting for the wake word: a dim, slow Idle
+    Armed,
     Listening,
     Thinking,
     Speaking,
@@ -78,6 +86,30 @@ impl ShadePattern {
         }
     }
 
+    /// Config key for this pattern
+    fn key(&self) -> &'static str {
+        match self {
+            ShadePattern::BrailleAt => "braille_at",
+            ShadePattern::Classic => "classic",
+            ShadePattern::Circles => "circles",
+            ShadePattern::BrailleSolid => "braille_solid",
+            ShadePattern::Lines => "lines",
+            ShadePattern::Particles => "particles",
+        }
+    }
+
+    fn from_key(s: &str) -> Option<ShadePattern> {
+        match s.trim().to_lowercase().as_str() {
+            "braille_at" => Some(ShadePattern::BrailleAt),
+            "classic" => Some(ShadePattern::Classic),
+            "circles" => Some(ShadePattern::Circles),
+            "braille_solid" => Some(ShadePattern::BrailleSolid),
+            "lines" => Some(ShadePattern::Lines),
+            "particles" => Some(ShadePattern::Particles),
+            _ => None,
+        }
+    }
+
     fn next(&self) -> ShadePattern {
         match self {
             ShadePattern::BrailleAt => ShadePattern::Classic,
@@ -97,6 +129,7 @@ impl OrbState {
     /// BPM reference (assuming base animation cycle is ~1 second):
     /// Some initial estimates
     /// - Idle:      0.4 Hz = ~24 BPM (calm, slow breathing)
+    /// - Armed:     slower still, so waiting for the wake word stays quiet
     /// - Listening: 0.7 Hz = ~42 BPM (attentive, moderate pace)
     /// - Thinking:  1.4 Hz = ~84 BPM (active processing)
     /// - Speaking:  1.0 Hz = ~60 BPM (natural speech rhythm)
@@ -104,6 +137,7 @@ impl OrbState {
     fn frequency(&self) -> f64 {
         match self {
             OrbState::Idle => 0.7,
+            OrbState::Armed => 0.35,
             OrbState::Listening => 0.5,
             OrbState::Thinking => 1.0,
             OrbState::Speaking => 0.8,
@@ -153,6 +187,12 @@ impl OrbState {
                 edge: hsl(240.0, 0.95, 0.55), // Deep blue-purple
                 glow: hsl(210.0, 0.7, 0.40),  // Darker blue glow
             },
+            OrbState::Armed => Palette {
+                core: hsl(220.0, 0.5, 0.55),  // Dimmed Idle core
+                mid: hsl(230.0, 0.55, 0.42),  // Muted blue-purple
+                edge: hsl(240.0, 0.6, 0.30),  // Dark blue-purple
+                glow: hsl(210.0, 0.45, 0.22), // Faint glow
+            },
             OrbState::Listening => Palette {
                 core: hsl(160.0, 0.95, 0.80), // Bright cyan-green core
                 mid: hsl(170.0, 0.90, 0.65),  // Vibrant teal
@@ -242,15 +282,31 @@ impl Rgb {
         Rgb(self.0 + other.0, self.1 + other.1, self.2 + other.2)
     }
 
+    fn to_bytes(self) -> [u8; 3] {
+        [
+            (self.0.clamp(0.0, 1.0) * 255.0) as u8,
+            (self.1.clamp(0.0, 1.0) * 255.0) as u8,
+            (self.2.clamp(0.0, 1.0) * 255.0) as u8,
+        ]
+    }
+
     fn to_terminal(self) -> Color {
-        Color::Rgb {
-            r: (self.0.clamp(0.0, 1.0) * 255.0) as u8,
-            g: (self.1.clamp(0.0, 1.0) * 255.0) as u8,
-            b: (self.2.clamp(0.0, 1.0) * 255.0) as u8,
-        }
+        let [r, g, b] = self.to_bytes();
+        Color::Rgb { r, g, b }
     }
 }
 
+/// Write an RGB cell grid to a PNG, drawing each cell as a `scale`x`scale` block
+fn write_png(grid: &[Vec<Rgb>], scale: u32, path: &Path) -> image::ImageResult<()> {
+    let scale = scale.max(1);
+    let height = grid.len() as u32;
+    let width = grid.first().map_or(0, |row| row.len()) as u32;
+    let img = image::RgbImage::from_fn(width * scale, height * scale, |x, y| {
+        image::Rgb(grid[(y / scale) as usize][(x / scale) as usize].to_bytes())
+    });
+    img.save(path)
+}
+
 fn hsl(h: f64, s: f64, l: f64) -> Rgb {
     let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
     let h = h / 60.0;
@@ -432,6 +488,13 @@ impl Orb {
         }
     }
 
+    /// Replace the generated frequency bands with a measured spectrum
+    fn set_audio_bands(&mut self, bands: &[f32; crate::spectrum::NUM_BANDS]) {
+        for (freq, &band) in self.audio_freqs.iter_mut().zip(bands) {
+            *freq = (band as f64).clamp(0.0, 1.0);
+        }
+    }
+
     fn set_secondary_audio(&mut self, level: f64) {
         self.secondary_audio = level.clamp(0.0, 1.0);
     }
@@ -530,6 +593,7 @@ impl Orb {
         // Dynamic core size based on state and audio
         let base_core_size = match self.target_state {
             OrbState::Idle => 0.035,
+            OrbState::Armed => 0.030,
             OrbState::Listening => 0.045,
             OrbState::Thinking => 0.055,
             OrbState::Speaking => 0.050,
@@ -559,7 +623,7 @@ impl Orb {
         // Better ring distribution and audio reactivity
         // =================================================================
         let ring_count = match self.target_state {
-            OrbState::Idle => 3,
+            OrbState::Idle | OrbState::Armed => 3,
             OrbState::Listening => 4,
             OrbState::Thinking => 6, // More rings for complexity
             OrbState::Speaking => 5,
@@ -700,7 +764,7 @@ impl Orb {
         let angle = angle + (t * 0.1).sin() * 2.;
 
         let noise_scale = match self.target_state {
-            OrbState::Idle => 1.5,
+            OrbState::Idle | OrbState::Armed => 1.5,
             OrbState::Listening => 2.0,
             OrbState::Thinking => 3.0,
             OrbState::Speaking => 2.2,
@@ -708,7 +772,7 @@ impl Orb {
         };
 
         let octaves = match self.target_state {
-            OrbState::Idle => 3,
+            OrbState::Idle | OrbState::Armed => 3,
             OrbState::Thinking => 5,
             OrbState::Error => 4,
             _ => 4,
@@ -786,6 +850,7 @@ impl Orb {
         // Create rotating pattern by modulating ring radius based on angle and time
         let rotation_speed = match self.target_state {
             OrbState::Idle => 0.3,
+            OrbState::Armed => 0.15,
             OrbState::Listening => 0.5,
             OrbState::Thinking => 0.8,
             OrbState::Speaking => 0.6,
@@ -806,7 +871,7 @@ impl Orb {
 
         // Ring width varies with audio and state
         let base_width = match self.target_state {
-            OrbState::Idle => 0.06,
+            OrbState::Idle | OrbState::Armed => 0.06,
             OrbState::Listening => 0.08,
             OrbState::Thinking => 0.05,
             OrbState::Speaking => 0.07,
@@ -961,6 +1026,7 @@ impl Orb {
         // STATE-DEPENDENT INTENSITY SCALING
         let state_multiplier = match self.target_state {
             OrbState::Idle => 0.6,
+            OrbState::Armed => 0.4,
             OrbState::Listening => 0.8,
             OrbState::Thinking => 1.2,
             OrbState::Speaking => 1.0,
@@ -989,8 +1055,110 @@ impl Orb {
         (final_intensity, glow_intensity, secondary_intensity)
     }
 
+    // -------------------------------------------------------------------------
+    // Minimal style - low-power pulsing dot
+    //
+    // Only the cells inside the dot's bounding box are visited, with one
+    // distance check each, so a frame costs a tiny fraction of the raycast
+    // styles. State color and audio pulse are kept.
+    // -------------------------------------------------------------------------
+
+    /// Radius of the minimal dot and the rows and columns it covers
+    fn minimal_bounds(
+        &self,
+        width: usize,
+        height: usize,
+    ) -> (f64, std::ops::Range<usize>, std::ops::Range<usize>) {
+        let aspect = 2.0;
+        let max_r = (height as f64).min(width as f64 / aspect) * 0.48;
+        let cx = width as f64 / 2.0;
+        let cy = height as f64 / 2.0;
+
+        let breathe = (self.time * self.current_frequency() * TAU).sin() * 0.05;
+        let r = max_r * (0.25 + breathe + self.smooth_audio * 0.15);
+        if r <= 0.0 {
+            return (r, 0..0, 0..0);
+        }
+
+        let row_range = (cy - r).floor().max(0.0) as usize..((cy + r).ceil() as usize).min(height);
+        let col_range = (cx - r * aspect).floor().max(0.0) as usize
+            ..((cx + r * aspect).ceil() as usize).min(width);
+        (r, row_range, col_range)
+    }
+
+    fn render_minimal_into(&self, buffer: &mut [Vec<(char, Rgb)>], width: usize, height: usize) {
+        let palette = self.current_palette();
+        let shades = self.shade_pattern.chars();
+
+        let aspect = 2.0;
+        let cx = width as f64 / 2.0;
+        let cy = height as f64 / 2.0;
+        let (r, row_range, col_range) = self.minimal_bounds(width, height);
+        for row in row_range {
+            for col in col_range.clone() {
+                let x = (col as f64 - cx) / aspect;
+                let y = row as f64 - cy;
+                let dist = (x * x + y * y).sqrt() / r;
+                if dist > 1.0 {
+                    continue;
+                }
+
+                let intensity = 1.0 - dist * dist;
+                let idx = ((intensity * (shades.len() - 1) as f64).round() as usize)
+                    .clamp(1, shades.len() - 1);
+                let color = palette.sample(dist).scale(0.4 + intensity * 0.6);
+                buffer[row][col] = (shades[idx], color);
+            }
+        }
+    }
+
     fn render(&self, width: usize, height: usize) -> Vec<Vec<(char, Color)>> {
-        let mut buffer = vec![vec![(' ', Color::Reset); width]; height];
+        self.render_cells(width, height)
+            .into_iter()
+            .map(|row| {
+                row.into_iter()
+                    .map(|(ch, rgb)| match ch {
+                        ' ' => (' ', Color::Reset),
+                        _ => (ch, rgb.to_terminal()),
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Per-cell colors of the current frame, black where nothing is drawn
+    fn render_rgb(&self, width: usize, height: usize) -> Vec<Vec<Rgb>> {
+        self.render_cells(width, height)
+            .into_iter()
+            .map(|row| {
+                row.into_iter()
+                    .map(|(ch, rgb)| if ch == ' ' { Rgb(0.0, 0.0, 0.0) } else { rgb })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Shade character and color for each cell, shared by terminal and PNG output
+    fn render_cells(&self, width: usize, height: usize) -> Vec<Vec<(char, Rgb)>> {
+        let mut buffer = Vec::new();
+        self.render_into(&mut buffer, width, height);
+        buffer
+    }
+
+    /// Render into a caller-owned buffer, reusing its rows so steady-state
+    /// frames don't allocate
+    fn render_into(&self, buffer: &mut Vec<Vec<(char, Rgb)>>, width: usize, height: usize) {
+        buffer.resize_with(height, Vec::new);
+        for row in buffer.iter_mut() {
+            row.clear();
+            row.resize(width, (' ', Rgb(0.0, 0.0, 0.0)));
+        }
+
+        if self.style == OrbStyle::Minimal {
+            self.render_minimal_into(buffer, width, height);
+            return;
+        }
+
         let palette = self.current_palette();
 
         let aspect = 2.0; // Slightly adjusted for better proportions
@@ -1016,6 +1184,7 @@ impl Orb {
                     }
                     OrbStyle::Orbs => self.sample_rings2(x, y, max_r),
                     OrbStyle::Sphere => self.sample_sphere(x, y, max_r),
+                    OrbStyle::Minimal => unreachable!("drawn by render_minimal"),
                 };
 
                 // Render all pixels - no minimal contribution filtering
@@ -1068,15 +1237,102 @@ impl Orb {
 
                 let ch = shades[idx];
                 if ch != ' ' {
-                    buffer[row][col] = (ch, final_color.to_terminal());
+                    buffer[row][col] = (ch, final_color);
                 }
             }
         }
+    }
+}
 
-        buffer
+// ============================================================================
+// Terminal output
+// ============================================================================
+
+/// What a terminal cell shows: glyph and foreground color
+type ScreenCell = (char, [u8; 3]);
+
+/// Placeholder for cells whose on-screen content is unknown
+const UNKNOWN_CELL: ScreenCell = ('\0', [0, 0, 0]);
+
+/// Append the escape sequences that turn `screen` into `frame`
+///
+/// Only changed cells are written, with a cursor move unless the cell directly
+/// follows the last one written. With `full` every cell is rewritten (first
+/// frame, after a resize). `screen` is updated to match `frame`.
+fn write_frame(
+    out: &mut String,
+    screen: &mut Vec<Vec<ScreenCell>>,
+    frame: &[Vec<(char, Rgb)>],
+    full: bool,
+) {
+    if full {
+        screen.clear();
+    }
+    screen.resize_with(frame.len(), Vec::new);
+
+    let mut cursor: Option<(usize, usize)> = None;
+    let mut last_color: Option<[u8; 3]> = None;
+    for (ri, (row, shown)) in frame.iter().zip(screen.iter_mut()).enumerate() {
+        shown.resize(row.len(), UNKNOWN_CELL);
+        for (ci, &(ch, color)) in row.iter().enumerate() {
+            let cell = if ch == ' ' {
+                (' ', [0, 0, 0])
+            } else {
+                (ch, color.to_bytes())
+            };
+            if shown[ci] == cell {
+                continue;
+            }
+
+            if cursor != Some((ri, ci)) {
+                let _ = write!(out, "\x1b[{};{}H", ri + 1, ci + 1);
+            }
+            if ch != ' ' && last_color != Some(cell.1) {
+                let [r, g, b] = cell.1;
+                let _ = write!(out, "\x1b[38;2;{};{};{}m", r, g, b);
+                last_color = Some(cell.1);
+            }
+            out.push(ch);
+            shown[ci] = cell;
+            cursor = Some((ri, ci + 1));
+        }
     }
 }
 
+/// `line` cut to `width` columns so it can't wrap and scroll the orb out of
+/// place. Escape sequences don't take up columns and are all kept, so colors
+/// are still reset after the cut.
+fn fit_width(line: &str, width: usize) -> String {
+    let mut fitted = String::with_capacity(line.len());
+    let mut used = 0;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            fitted.push(c);
+            if let Some(next) = chars.next() {
+                fitted.push(next);
+                if next == '[' {
+                    for c in chars.by_ref() {
+                        fitted.push(c);
+                        if ('@'..='~').contains(&c) {
+                            break;
+                        }
+                    }
+                }
+            }
+            continue;
+        }
+        let w = c.width().unwrap_or(0);
+        if used + w <= width {
+            fitted.push(c);
+            used += w;
+        } else {
+            used = width;
+        }
+    }
+    fitted
+}
+
 // ============================================================================
 // GraphicalUi - main UI implementation
 // ============================================================================
@@ -1086,12 +1342,27 @@ pub struct GraphicalUi {
     last_frame: Instant,
     // State from text UI that we also need
     preview: String,
-    input: String,
-    cursor_pos: usize,
+    input: LineEditor,
     responding: bool,
     input_activity: bool,
     keypress_activity: bool,
     status_bar: StatusBarState,
+    /// Latest microphone spectrum, if the audio thread provides one
+    audio_bands: Option<[f32; crate::spectrum::NUM_BANDS]>,
+    /// Orb cells, reused across frames
+    frame: Vec<Vec<(char, Rgb)>>,
+    /// Terminal output, reused across frames
+    out: String,
+    /// What the orb area of the terminal currently shows
+    screen: Vec<Vec<ScreenCell>>,
+    /// Terminal size at the last draw, a change forces a full redraw
+    screen_size: (u16, u16),
+    /// Frames drawn since the orb was last rewritten in full
+    frames_since_full: u32,
+    /// Pixels per cell for PNG screenshots
+    screenshot_scale: u32,
+    /// When the Listening flash for a detected wake word ends
+    wake_flash_until: Option<Instant>,
 }
 
 impl GraphicalUi {
@@ -1108,29 +1379,71 @@ impl GraphicalUi {
         // Orb UI uses text style for cleaner look
         status_bar.display_style = StatusDisplayStyle::Text;
 
+        // Restore the last-used style and shades, falling back to defaults
+        let ui_config = Config::load_or_default().ui;
+        let style = ui_config
+            .default_style
+            .as_deref()
+            .and_then(OrbStyle::from_name)
+            .unwrap_or(OrbStyle::Sphere);
+        let mut orb = Orb::new(style);
+        if let Some(shade) = ui_config
+            .default_shade
+            .as_deref()
+            .and_then(ShadePattern::from_key)
+        {
+            orb.set_shade_pattern(shade);
+        }
+
         Ok(Self {
-            orb: Orb::new(OrbStyle::Sphere),
+            orb,
             last_frame: Instant::now(),
             preview: String::new(),
-            input: String::new(),
-            cursor_pos: 0,
+            input: LineEditor::default(),
             responding: false,
             input_activity: false,
             keypress_activity: false,
             status_bar,
+            audio_bands: None,
+            frame: Vec::new(),
+            out: String::new(),
+            screen: Vec::new(),
+            screen_size: (0, 0),
+            frames_since_full: 0,
+            screenshot_scale: ui_config.screenshot_scale,
+            wake_flash_until: None,
         })
     }
 
-    fn char_to_byte_index(&self, char_idx: usize) -> usize {
-        self.input
-            .char_indices()
-            .nth(char_idx)
-            .map(|(i, _)| i)
-            .unwrap_or(self.input.len())
+    /// Save the current orb frame as `orb-<timestamp>.png`
+    fn save_screenshot(&self) -> Result<PathBuf, Box<dyn std::error::Error + Send + Sync>> {
+        let (tw, th) = terminal::size()?;
+        let w = tw as usize;
+        let h = (th as usize).saturating_sub(3);
+        let path = PathBuf::from(format!(
+            "orb-{}.png",
+            chrono::Local::now().format("%Y%m%d-%H%M%S")
+        ));
+        write_png(&self.orb.render_rgb(w, h), self.screenshot_scale, &path)?;
+        Ok(path)
+    }
+
+    /// State to show when nothing is happening: Armed while paused waiting
+    /// for the wake word, otherwise Idle
+    fn rest_state(&self) -> OrbState {
+        if self.status_bar.mode == AppMode::Paused && self.status_bar.wake_enabled {
+            OrbState::Armed
+        } else {
+            OrbState::Idle
+        }
     }
 
-    fn char_count(&self) -> usize {
-        self.input.chars().count()
+    /// Switch between Idle and Armed when the mode or wake setting changes,
+    /// leaving active states alone
+    fn settle(&mut self) {
+        if matches!(self.orb.target_state, OrbState::Idle | OrbState::Armed) {
+            self.orb.set_state(self.rest_state());
+        }
     }
 }
 
@@ -1156,7 +1469,7 @@ impl UiRenderer for GraphicalUi {
             }
             UiEvent::SpeakingDone => {
                 self.status_bar.status = "Ready".to_string();
-                self.orb.set_state(OrbState::Idle);
+                self.orb.set_state(self.rest_state());
             }
             UiEvent::ResponseChunk(text) => {
                 self.responding = true;
@@ -1169,9 +1482,14 @@ impl UiRenderer for GraphicalUi {
             }
             UiEvent::Idle => {
                 self.status_bar.status = "Idle".to_string();
-                self.orb.set_state(OrbState::Idle);
+                self.orb.set_state(self.rest_state());
                 self.preview.clear();
             }
+            UiEvent::WakeDetected => {
+                self.status_bar.status = "Listening".to_string();
+                self.orb.set_state(OrbState::Listening);
+                self.wake_flash_until = Some(Instant::now() + WAKE_FLASH);
+            }
             UiEvent::Tick => {}
             UiEvent::ContextWords(count) => {
                 self.status_bar.context_words = count;
@@ -1181,7 +1499,7 @@ impl UiRenderer for GraphicalUi {
             }
             UiEvent::Error(msg) => {
                 self.status_bar.status = format!("Error: {}", msg);
-                self.orb.set_state(OrbState::Idle);
+                self.orb.set_state(self.rest_state());
             }
         }
         Ok(())
@@ -1189,9 +1507,22 @@ impl UiRenderer for GraphicalUi {
 
     fn draw(&mut self) -> io::Result<()> {
         let now = Instant::now();
+        if self.orb.style == OrbStyle::Minimal
+            && now.duration_since(self.last_frame) < MINIMAL_FRAME_INTERVAL
+        {
+            return Ok(());
+        }
         let dt = now.duration_since(self.last_frame).as_secs_f64();
         self.last_frame = now;
 
+        // End the wake flash unless the user has started speaking
+        if self.wake_flash_until.is_some_and(|until| now >= until) {
+            self.wake_flash_until = None;
+            if self.orb.target_state == OrbState::Listening && self.preview.is_empty() {
+                self.orb.set_state(self.rest_state());
+            }
+        }
+
         // Update orb with audio levels
         let audio = if self.orb.target_state == OrbState::Listening {
             self.status_bar.audio_level as f64
@@ -1201,6 +1532,11 @@ impl UiRenderer for GraphicalUi {
             0.1
         };
         self.orb.set_audio(audio);
+        if self.orb.target_state == OrbState::Listening {
+            if let Some(bands) = &self.audio_bands {
+                self.orb.set_audio_bands(bands);
+            }
+        }
         self.orb
             .set_secondary_audio(self.status_bar.tts_level as f64);
         self.orb.update(dt);
@@ -1209,77 +1545,63 @@ impl UiRenderer for GraphicalUi {
         let w = tw as usize;
         let h = (th as usize).saturating_sub(3); // Reserve space for status bars
 
-        let buf = self.orb.render(w, h);
-
-        // Build output string
-        let mut out = String::with_capacity(w * h * 24);
-        out.push_str("\x1b[H"); // Home cursor
+        self.orb.render_into(&mut self.frame, w, h);
 
-        let mut last_color: Option<(u8, u8, u8)> = None;
-
-        for (ri, row) in buf.iter().enumerate() {
-            for (ch, color) in row {
-                let rgb = match color {
-                    Color::Rgb { r, g, b } => (*r, *g, *b),
-                    _ => (0, 0, 0),
-                };
-
-                if *ch == ' ' && rgb == (0, 0, 0) {
-                    out.push(' ');
-                } else {
-                    if last_color != Some(rgb) {
-                        out.push_str(&format!("\x1b[38;2;{};{};{}m", rgb.0, rgb.1, rgb.2));
-                        last_color = Some(rgb);
-                    }
-                    out.push(*ch);
-                }
-            }
-            if ri < buf.len() - 1 {
-                out.push_str("\r\n");
-            }
+        // Build output string, rewriting only the orb cells that changed
+        let out = &mut self.out;
+        out.clear();
+        let resized = self.screen_size != (tw, th);
+        if resized {
+            out.push_str("\x1b[2J");
+            self.screen_size = (tw, th);
+        }
+        // Something else may have written to the terminal since the last
+        // full frame, so the cache of what's on screen is refreshed now and then
+        self.frames_since_full += 1;
+        let full = resized || self.frames_since_full >= FULL_REPAINT_FRAMES;
+        if full {
+            self.frames_since_full = 0;
         }
+        write_frame(out, &mut self.screen, &self.frame, full);
 
-        // Reset color and draw status bar
-        out.push_str("\x1b[0m\r\n");
+        // Reset color and redraw the status bar below the orb
+        let _ = write!(out, "\x1b[0m\x1b[{};1H", h + 1);
 
-        // Status line using modular status bar
-        let style_name = match self.orb.style {
-            OrbStyle::Orbs => "Orbs",
-            OrbStyle::Blob => "Blob",
-            OrbStyle::Ring => "Ring",
-            OrbStyle::Sphere => "Sphere",
-        };
+        // Lines stop short of the last column, where the cursor would wait to
+        // wrap and the clear after them would erase the last character
+        let line_width = w.saturating_sub(1);
 
-        let status_line = format!(
-            "{} | Style: {} | Shades: {} | Display: {} | Tab: Switch to Text UI",
+        // Status line using modular status bar
+        let style_name = self.orb.style.name();
+        let status = format!(
+            "{} | Style: {} | Shades: {} | Display: {} | Tab: Switch to Text UI | Shift+Tab: Style",
             self.status_bar
                 .render_status(self.status_bar.display_style, None),
             style_name,
             self.orb.shade_pattern.name(),
             self.status_bar.display_style.name()
         );
+        out.push_str(&fit_width(&status, line_width));
 
-        out.push_str(&status_line);
-
-        // Input line
-        out.push_str("\r\n");
+        // Input line (clearing leftovers from longer lines in earlier frames)
+        out.push_str("\x1b[K\r\n");
 
         // Auto-submit progress bar
-        let timer_bar = self.status_bar.auto_submit_bar();
-        if !timer_bar.is_empty() {
-            out.push_str(&timer_bar);
-        }
+        let mut input_line = self.status_bar.auto_submit_bar();
 
         // Preview text
         if !self.preview.is_empty() {
-            out.push_str(&format!("\x1b[90m{}\x1b[0m ", self.preview));
+            let _ = write!(input_line, "\x1b[90m{}\x1b[0m ", self.preview);
         }
 
         // Input prompt
-        out.push_str(&format!("\x1b[32m>\x1b[0m {}", self.input));
+        let _ = write!(input_line, "\x1b[32m>\x1b[0m {}", self.input.text());
+        out.push_str(&fit_width(&input_line, line_width));
+        out.push_str("\x1b[K");
 
-        print!("{}", out);
-        stdout().flush()?;
+        let mut stdout = stdout();
+        stdout.write_all(out.as_bytes())?;
+        stdout.flush()?;
 
         Ok(())
     }
@@ -1297,42 +1619,34 @@ impl UiRenderer for GraphicalUi {
                 if key.code == KeyCode::Char('m') && key.modifiers.contains(KeyModifiers::CONTROL) {
                     return Ok(Some("/mute".to_string()));
                 }
+                if key.code == KeyCode::Char('l') && key.modifiers.contains(KeyModifiers::CONTROL) {
+                    return Ok(Some("/lowpower".to_string()));
+                }
+                if key.code == KeyCode::Char('p') && key.modifiers.contains(KeyModifiers::CONTROL) {
+                    self.status_bar.status = match self.save_screenshot() {
+                        Ok(path) => format!("Saved {}", path.display()),
+                        Err(e) => format!("Screenshot failed: {}", e),
+                    };
+                    continue;
+                }
 
                 // Tab to switch to text UI mode
                 if key.code == KeyCode::Tab {
-                    debug_log("Tab key pressed, returning /ui text command");
+                    logger::debug!("Tab key pressed, returning /ui text command");
                     return Ok(Some("/ui text".to_string()));
                 }
 
-                // Tab to cycle through orb styles (forward)
-                // Shift+Tab to cycle backward
-                if key.code == KeyCode::Tab {
-                    let current_style = self.orb.style;
-                    let new_style = if key.modifiers.contains(KeyModifiers::SHIFT) {
-                        // Shift+Tab: cycle backward
-                        match current_style {
-                            OrbStyle::Blob => OrbStyle::Sphere,
-                            OrbStyle::Sphere => OrbStyle::Orbs,
-                            OrbStyle::Orbs => OrbStyle::Ring,
-                            OrbStyle::Ring => OrbStyle::Blob,
-                        }
-                    } else {
-                        // Tab: cycle forward
-                        match current_style {
-                            OrbStyle::Blob => OrbStyle::Ring,
-                            OrbStyle::Ring => OrbStyle::Orbs,
-                            OrbStyle::Orbs => OrbStyle::Sphere,
-                            OrbStyle::Sphere => OrbStyle::Blob,
-                        }
-                    };
-                    self.orb.set_style(new_style);
-                    continue;
+                // Shift+Tab to cycle through orb styles (goes through the
+                // command system so voice and keyboard share state and persistence)
+                if key.code == KeyCode::BackTab {
+                    return Ok(Some("/style prev".to_string()));
                 }
 
                 // Backtick to cycle through shade patterns
                 if key.code == KeyCode::Char('`') {
                     let new_pattern = self.orb.shade_pattern.next();
                     self.orb.set_shade_pattern(new_pattern);
+                    Config::remember_ui_choice("default_shade", new_pattern.key());
                     continue;
                 }
 
@@ -1342,85 +1656,17 @@ impl UiRenderer for GraphicalUi {
                     continue;
                 }
 
-                match key.code {
-                    KeyCode::Enter => {
-                        if event::poll(std::time::Duration::from_millis(0))? {
-                            let byte_pos = self.char_to_byte_index(self.cursor_pos);
-                            self.input.insert(byte_pos, '\n');
-                            self.cursor_pos += 1;
-                            self.input_activity = true;
-                            pending_submit = None;
-                        } else {
-                            let text = self.input.trim().to_string();
-                            self.input.clear();
-                            self.cursor_pos = 0;
-                            pending_submit = if !text.is_empty() { Some(text) } else { None };
-                        }
-                    }
-                    KeyCode::Char(c) => {
-                        if key.modifiers.contains(KeyModifiers::CONTROL) {
-                            match c {
-                                'a' => self.cursor_pos = 0,
-                                'e' => self.cursor_pos = self.char_count(),
-                                'k' => {
-                                    if self.cursor_pos < self.char_count() {
-                                        let byte_pos = self.char_to_byte_index(self.cursor_pos);
-                                        self.input.truncate(byte_pos);
-                                        self.input_activity = true;
-                                    }
-                                }
-                                'u' => {
-                                    if self.cursor_pos > 0 {
-                                        let byte_pos = self.char_to_byte_index(self.cursor_pos);
-                                        self.input = self.input[byte_pos..].to_string();
-                                        self.cursor_pos = 0;
-                                        self.input_activity = true;
-                                    }
-                                }
-                                'w' => {
-                                    if self.cursor_pos > 0 {
-                                        let chars: Vec<char> = self.input.chars().collect();
-                                        let mut end = self.cursor_pos;
-
-                                        while end > 0 && chars[end - 1].is_whitespace() {
-                                            end -= 1;
-                                        }
-                                        while end > 0 && !chars[end - 1].is_whitespace() {
-                                            end -= 1;
-                                        }
-
-                                        let start_byte = self.char_to_byte_index(end);
-                                        let end_byte = self.char_to_byte_index(self.cursor_pos);
-                                        self.input.replace_range(start_byte..end_byte, "");
-                                        self.cursor_pos = end;
-                                        self.input_activity = true;
-                                    }
-                                }
-                                _ => {}
-                            }
-                        } else {
-                            let byte_pos = self.char_to_byte_index(self.cursor_pos);
-                            self.input.insert(byte_pos, c);
-                            self.cursor_pos += 1;
-                            self.input_activity = true;
-                        }
-                    }
-                    KeyCode::Backspace if self.cursor_pos > 0 => {
-                        self.cursor_pos -= 1;
-                        let byte_pos = self.char_to_byte_index(self.cursor_pos);
-                        self.input.remove(byte_pos);
+                match self
+                    .input
+                    .handle_key(&key, || event::poll(std::time::Duration::from_millis(0)))?
+                {
+                    LineEdit::Submit(text) => pending_submit = text,
+                    LineEdit::Newline => {
                         self.input_activity = true;
+                        pending_submit = None;
                     }
-                    KeyCode::Delete if self.cursor_pos < self.char_count() => {
-                        let byte_pos = self.char_to_byte_index(self.cursor_pos);
-                        self.input.remove(byte_pos);
-                        self.input_activity = true;
-                    }
-                    KeyCode::Left => self.cursor_pos = self.cursor_pos.saturating_sub(1),
-                    KeyCode::Right if self.cursor_pos < self.char_count() => self.cursor_pos += 1,
-                    KeyCode::Home => self.cursor_pos = 0,
-                    KeyCode::End => self.cursor_pos = self.char_count(),
-                    _ => {}
+                    LineEdit::Changed => self.input_activity = true,
+                    LineEdit::Unchanged => {}
                 }
             }
         }
@@ -1465,10 +1711,16 @@ impl UiRenderer for GraphicalUi {
 
     fn set_wake_enabled(&mut self, enabled: bool) {
         self.status_bar.wake_enabled = enabled;
+        self.settle();
     }
 
     fn set_mode(&mut self, mode: AppMode) {
         self.status_bar.mode = mode;
+        self.settle();
+    }
+
+    fn set_language(&mut self, language: Option<&str>) {
+        self.status_bar.language = language.map(String::from);
     }
 
     fn set_ready(&mut self) {
@@ -1483,6 +1735,14 @@ impl UiRenderer for GraphicalUi {
         self.status_bar.audio_level = level;
     }
 
+    fn set_clipping(&mut self, clipping: bool) {
+        self.status_bar.clipping = clipping;
+    }
+
+    fn set_audio_bands(&mut self, bands: [f32; crate::spectrum::NUM_BANDS]) {
+        self.audio_bands = Some(bands);
+    }
+
     fn set_tts_level(&mut self, level: f32) {
         self.status_bar.tts_level = level;
     }
@@ -1500,25 +1760,15 @@ impl UiRenderer for GraphicalUi {
     }
 
     fn has_pending_input(&self) -> bool {
-        !self.input.trim().is_empty()
+        !self.input.text().trim().is_empty()
     }
 
     fn take_input(&mut self) -> Option<String> {
-        if self.input.is_empty() {
-            None
-        } else {
-            let text = std::mem::take(&mut self.input);
-            self.cursor_pos = 0;
-            Some(text)
-        }
+        self.input.take()
     }
 
     fn append_input(&mut self, text: &str) {
-        if !self.input.is_empty() && !self.input.ends_with(' ') {
-            self.input.push(' ');
-        }
-        self.input.push_str(text);
-        self.cursor_pos = self.char_count();
+        self.input.append(text);
         // Don't set input_activity here - this is for voice input
         // input_activity is only for keyboard input
     }
@@ -1584,6 +1834,7 @@ mod tests {
 
         let states = [
             (OrbState::Idle, "Idle - Calm breathing"),
+            (OrbState::Armed, "Armed - Waiting for wake word"),
             (OrbState::Listening, "Listening - Attentive"),
             (OrbState::Thinking, "Thinking - Processing"),
             (OrbState::Speaking, "Speaking - Responding"),
@@ -1669,6 +1920,7 @@ mod tests {
         // Test all states
         for state in [
             OrbState::Idle,
+            OrbState::Armed,
             OrbState::Listening,
             OrbState::Thinking,
             OrbState::Speaking,
@@ -1700,6 +1952,7 @@ mod tests {
             OrbStyle::Orbs,
             OrbStyle::Blob,
             OrbStyle::Sphere,
+            OrbStyle::Minimal,
         ] {
             let mut orb = Orb::new(style);
             orb.set_state(OrbState::Thinking);
@@ -1716,6 +1969,139 @@ mod tests {
         }
     }
 
+    /// Frame rate changes smoothness only, animation tempo follows real time
+    #[test]
+    fn test_animation_independent_of_fps() {
+        let run = |fps: u32| {
+            let mut orb = Orb::new(OrbStyle::Sphere);
+            orb.set_state(OrbState::Thinking);
+            orb.set_audio(0.8);
+            let dt = frame_interval(fps).as_secs_f64();
+            for _ in 0..fps {
+                orb.update(dt);
+            }
+            orb
+        };
+
+        let smooth = run(120);
+        let choppy = run(15);
+        assert!((smooth.time - choppy.time).abs() < 1e-6);
+        assert!((smooth.transition - choppy.transition).abs() < 1e-6);
+        assert!((smooth.smooth_audio - choppy.smooth_audio).abs() < 1e-6);
+        assert_eq!(frame_interval(0), frame_interval(1));
+    }
+
+    #[test]
+    fn test_render_into_reuses_buffer() {
+        let mut orb = Orb::new(OrbStyle::Ring);
+        orb.update(0.016);
+
+        let mut buffer = Vec::new();
+        orb.render_into(&mut buffer, 40, 12);
+        let rows = buffer.as_ptr();
+        let first_row = buffer[0].as_ptr();
+
+        orb.update(0.016);
+        orb.render_into(&mut buffer, 40, 12);
+        assert_eq!(buffer.as_ptr(), rows);
+        assert_eq!(buffer[0].as_ptr(), first_row);
+
+        // Resizing the terminal reshapes the buffer
+        orb.render_into(&mut buffer, 20, 6);
+        assert_eq!(buffer.len(), 6);
+        assert!(buffer.iter().all(|row| row.len() == 20));
+    }
+
+    #[test]
+    fn test_frame_diff_writes_only_changes() {
+        let mut orb = Orb::new(OrbStyle::Blob);
+        orb.update(0.016);
+        let mut frame = Vec::new();
+        orb.render_into(&mut frame, 80, 24);
+
+        let mut screen = Vec::new();
+        let mut out = String::new();
+        write_frame(&mut out, &mut screen, &frame, true);
+        let full_bytes = out.len();
+
+        // Nothing changed, nothing to write
+        out.clear();
+        write_frame(&mut out, &mut screen, &frame, false);
+        assert!(out.is_empty());
+
+        // A mostly static scene only pays for the changed cells
+        for cell in &mut frame[12][30..40] {
+            *cell = ('#', Rgb(1.0, 0.0, 0.0));
+        }
+        out.clear();
+        write_frame(&mut out, &mut screen, &frame, false);
+        let diff_bytes = out.len();
+        println!(
+            "Full frame: {} bytes, diff: {} bytes",
+            full_bytes, diff_bytes
+        );
+        assert!(out.starts_with("\x1b[13;31H\x1b[38;2;255;0;0m##########"));
+        assert!(diff_bytes * 20 < full_bytes);
+    }
+
+    #[test]
+    fn test_fit_width() {
+        assert_eq!(fit_width("short", 10), "short");
+        assert_eq!(fit_width("a longer line", 8), "a longer");
+        // Escapes are free and kept, even after the cut
+        assert_eq!(
+            fit_width("\x1b[1mbold\x1b[0m and plain", 6),
+            "\x1b[1mbold\x1b[0m a"
+        );
+        assert_eq!(
+            fit_width("\x1b[90mpreview text\x1b[0m > typed", 4),
+            "\x1b[90mprev\x1b[0m"
+        );
+        // Wide characters take two columns
+        assert_eq!(fit_width("📝 12 │ 💬 3", 5), "📝 12");
+        assert_eq!(fit_width("ab📝", 3), "ab");
+    }
+
+    #[test]
+    fn test_png_export() {
+        let mut orb = Orb::new(OrbStyle::Blob);
+        orb.update(0.016);
+
+        let grid = orb.render_rgb(40, 12);
+        assert_eq!(grid.len(), 12);
+        assert_eq!(grid[0].len(), 40);
+
+        let path = std::env::temp_dir().join("silly-orb-test.png");
+        write_png(&grid, 3, &path).unwrap();
+        let img = image::open(&path).unwrap();
+        assert_eq!((img.width(), img.height()), (120, 36));
+        let _ = std::fs::remove_file(&path);
+    }
+
+    /// The low-power style must stay far cheaper than the sphere raycast,
+    /// which samples every cell: the dot only visits its bounding box
+    #[test]
+    fn test_minimal_is_cheaper_than_sphere() {
+        let (width, height) = (120, 40);
+        let mut orb = Orb::new(OrbStyle::Minimal);
+        orb.set_state(OrbState::Listening);
+        for audio in [0.0, 0.5, 1.0] {
+            orb.set_audio(audio);
+            for _ in 0..200 {
+                orb.update(0.016);
+                let (_, rows, cols) = orb.minimal_bounds(width, height);
+                let visited = rows.len() * cols.len();
+                assert!(
+                    visited * 4 < width * height,
+                    "Minimal visits {} of {} cells at audio {}",
+                    visited,
+                    width * height,
+                    audio
+                );
+            }
+        }
+    }
+
     /// Benchmark rendering performance
     #[test]
     #[ignore]
@@ -1750,21 +2136,25 @@ pub fn run_orb_demo() -> io::Result<()> {
     println!("Press Tab to cycle styles, ` (backtick) to cycle shade patterns, Ctrl+C to exit");
 
     let mut ui = GraphicalUi::new()?;
+    let target = frame_interval(Config::load_or_default().ui.target_fps);
 
     let states = [
         (OrbState::Idle, "Idle"),
+        (OrbState::Armed, "Armed"),
         (OrbState::Listening, "Listening"),
         (OrbState::Thinking, "Thinking"),
         (OrbState::Speaking, "Speaking"),
         (OrbState::Error, "Error"),
     ];
 
-    let mut frame_count = 0;
+    let start = Instant::now();
+    let mut next_state_at = 0.0;
     let mut state_index = 0;
     let auto_cycle = true;
 
     loop {
         let now = std::time::Instant::now();
+        let elapsed_secs = now.duration_since(start).as_secs_f64();
 
         // Handle input
         if let Ok(Some(input)) = ui.poll_input() {
@@ -1775,15 +2165,16 @@ pub fn run_orb_demo() -> io::Result<()> {
         }
 
         // Auto-cycle states every 3 seconds
-        if auto_cycle && frame_count % 180 == 0 {
+        if auto_cycle && elapsed_secs >= next_state_at {
             let (state, state_name) = states[state_index];
             ui.orb.set_state(state);
             ui.status_bar.status = format!("{} - Auto Demo", state_name);
             state_index = (state_index + 1) % states.len();
+            next_state_at += 3.0;
         }
 
         // Simulate varying audio levels
-        let t = frame_count as f64 * 0.05;
+        let t = elapsed_secs * 3.0;
         let audio = (0.2 + 0.5 * (t * 0.8).sin() + 0.2 * (t * 1.3).sin())
             .max(0.0)
             .min(1.0);
@@ -1792,18 +2183,11 @@ pub fn run_orb_demo() -> io::Result<()> {
         ui.set_audio_level(audio as f32);
         ui.set_tts_level(tts as f32);
 
-        // Update and draw
-        ui.orb.set_audio(audio);
-        ui.orb.set_secondary_audio(tts);
-        ui.orb.update(1.0 / 60.0);
-
+        // Draw advances the orb by the real time since the last frame
         ui.draw()?;
 
-        frame_count += 1;
-
-        // 60 FPS timing
+        // Frame pacing
         let elapsed = now.elapsed();
-        let target = Duration::from_millis(16);
         if elapsed < target {
             thread::sleep(target - elapsed);
         }
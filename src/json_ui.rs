@@ -0,0 +1,227 @@
+//! Headless JSON lines UI for driving silly from other tools (`--events-json`)
+//!
+//! Every UI event is written to stdout as one JSON object tagged with its
+//! `type`, e.g. `{"type":"preview","data":"hello"}` or `{"type":"thinking"}`.
+//! Lines like `{"input": "what time is it"}` on stdin are submitted as user
+//! input, the same as typing them in the text UI (slash commands included).
+//! Audio and TTS run as usual; mic and TTS levels are not emitted.
+
+use crate::render::{UiEvent, UiMode, UiRenderer};
+use crate::state::AppMode;
+use serde::{Deserialize, Serialize};
+use std::io::{self, BufRead, Write, stdout};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+/// State changes reported through renderer setters rather than `UiEvent`s
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+enum StateEvent<'a> {
+    Message(&'a str),
+    Ready,
+    ResponseWords(usize),
+    MicMuted(bool),
+    TtsEnabled(bool),
+    WakeEnabled(bool),
+    Mode(String),
+    Language(Option<&'a str>),
+}
+
+/// One line of stdin
+#[derive(Debug, Deserialize)]
+struct JsonInput {
+    input: String,
+}
+
+/// Parse a stdin line, `Err` describes why it was rejected
+fn parse_input(line: &str) -> Result<String, String> {
+    serde_json::from_str::<JsonInput>(line)
+        .map(|msg| msg.input)
+        .map_err(|e| format!("Invalid input line: {}", e))
+}
+
+pub struct JsonUi {
+    /// Parsed stdin lines from the reader thread
+    input_rx: Receiver<Result<String, String>>,
+    /// Voice input waiting for auto-submit
+    input: String,
+    mode: AppMode,
+    language: Option<String>,
+}
+
+impl JsonUi {
+    pub fn new() -> io::Result<Self> {
+        let (tx, input_rx) = mpsc::channel();
+        thread::Builder::new()
+            .name("json-input".to_string())
+            .spawn(move || {
+                for line in io::stdin().lock().lines() {
+                    let Ok(line) = line else { break };
+                    if line.trim().is_empty() {
+                        continue;
+                    }
+                    if tx.send(parse_input(&line)).is_err() {
+                        break;
+                    }
+                }
+            })?;
+
+        Ok(Self {
+            input_rx,
+            input: String::new(),
+            mode: AppMode::Chat,
+            language: None,
+        })
+    }
+
+    fn emit<T: Serialize>(&self, event: &T) {
+        let mut out = stdout().lock();
+        if serde_json::to_writer(&mut out, event).is_ok() {
+            let _ = out.write_all(b"\n");
+            let _ = out.flush();
+        }
+    }
+}
+
+impl UiRenderer for JsonUi {
+    fn handle_ui_event(&mut self, event: UiEvent) -> io::Result<()> {
+        if !matches!(event, UiEvent::Tick) {
+            self.emit(&event);
+        }
+        Ok(())
+    }
+
+    fn draw(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn poll_input(&mut self) -> io::Result<Option<String>> {
+        while let Ok(line) = self.input_rx.try_recv() {
+            match line {
+                Ok(text) => return Ok(Some(text)),
+                Err(e) => self.emit(&UiEvent::Error(e)),
+            }
+        }
+        Ok(None)
+    }
+
+    fn restore(&self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn show_message(&mut self, text: &str) {
+        self.emit(&StateEvent::Message(text));
+    }
+
+    fn set_auto_submit_progress(&mut self, _progress: Option<f32>) {}
+
+    fn set_mic_muted(&mut self, muted: bool) {
+        self.emit(&StateEvent::MicMuted(muted));
+    }
+
+    fn set_tts_enabled(&mut self, enabled: bool) {
+        self.emit(&StateEvent::TtsEnabled(enabled));
+    }
+
+    fn set_wake_enabled(&mut self, enabled: bool) {
+        self.emit(&StateEvent::WakeEnabled(enabled));
+    }
+
+    fn set_mode(&mut self, mode: AppMode) {
+        if mode != self.mode {
+            self.mode = mode;
+            self.emit(&StateEvent::Mode(mode.to_string()));
+        }
+    }
+
+    fn set_language(&mut self, language: Option<&str>) {
+        if language != self.language.as_deref() {
+            self.language = language.map(String::from);
+            self.emit(&StateEvent::Language(language));
+        }
+    }
+
+    fn set_ready(&mut self) {
+        self.emit(&StateEvent::Ready);
+    }
+
+    fn set_last_response_words(&mut self, words: usize) {
+        self.emit(&StateEvent::ResponseWords(words));
+    }
+
+    fn set_audio_level(&mut self, _level: f32) {}
+
+    fn set_tts_level(&mut self, _level: f32) {}
+
+    fn has_input_activity(&mut self) -> bool {
+        false
+    }
+
+    fn has_keypress_activity(&mut self) -> bool {
+        false
+    }
+
+    fn has_pending_input(&self) -> bool {
+        !self.input.trim().is_empty()
+    }
+
+    fn take_input(&mut self) -> Option<String> {
+        (!self.input.is_empty()).then(|| std::mem::take(&mut self.input))
+    }
+
+    fn append_input(&mut self, text: &str) {
+        if !self.input.is_empty() && !self.input.ends_with(' ') {
+            self.input.push(' ');
+        }
+        self.input.push_str(text);
+    }
+
+    fn ui_mode(&self) -> UiMode {
+        UiMode::Json
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_json() {
+        let json = |event: &UiEvent| serde_json::to_string(event).unwrap();
+        assert_eq!(
+            json(&UiEvent::Preview("hello".to_string())),
+            r#"{"type":"preview","data":"hello"}"#
+        );
+        assert_eq!(json(&UiEvent::Thinking), r#"{"type":"thinking"}"#);
+        assert_eq!(
+            json(&UiEvent::SwitchUiMode(UiMode::Orb)),
+            r#"{"type":"switch_ui_mode","data":"orb"}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&StateEvent::MicMuted(true)).unwrap(),
+            r#"{"type":"mic_muted","data":true}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&StateEvent::Language(Some("es"))).unwrap(),
+            r#"{"type":"language","data":"es"}"#
+        );
+    }
+
+    #[test]
+    fn test_parse_input() {
+        assert_eq!(
+            parse_input(r#"{"input": "what time is it"}"#),
+            Ok("what time is it".to_string())
+        );
+        assert!(parse_input("what time is it").is_err());
+        assert!(parse_input(r#"{"text": "hi"}"#).is_err());
+    }
+}
@@ -0,0 +1,355 @@
+//! Keyword spotting for the wake word without the transcriber
+//!
+//! While waiting for the wake word every utterance would otherwise go through
+//! the full transcriber just to be thrown away. The spotter compares the start
+//! of each utterance against a few recordings of the wake phrase (templates)
+//! instead: both become log-mel frames that are aligned with dynamic time
+//! warping, a tiny fraction of the cost of a transcription. Only utterances
+//! close enough to a template are transcribed.
+
+use crate::audiofile;
+use crate::logger;
+use realfft::{RealFftPlanner, RealToComplex};
+use std::path::Path;
+use std::sync::Arc;
+
+const SAMPLE_RATE: u32 = 16000;
+/// Analysis window (25ms) and hop (10ms) in samples
+const FRAME: usize = 400;
+const HOP: usize = 160;
+const FFT_SIZE: usize = 512;
+const NUM_MELS: usize = 24;
+const MIN_FREQ: f32 = 80.0;
+const MAX_FREQ: f32 = 7600.0;
+/// Frames this far below the loudest one (20 dB) count as silence when
+/// trimming, so trimming doesn't depend on mic gain
+const SILENCE_RATIO: f32 = 0.1;
+/// Templates shorter than this many frames (0.2s) are ignored
+const MIN_TEMPLATE_FRAMES: usize = 20;
+/// How much faster or slower than a template the phrase may be spoken
+const MAX_STRETCH: f32 = 1.6;
+
+type Frame = [f32; NUM_MELS];
+
+/// Log-mel features of 16kHz audio
+struct Features {
+    fft: Arc<dyn RealToComplex<f32>>,
+    window: Vec<f32>,
+    /// Triangular mel filters as (first FFT bin, weights)
+    filters: Vec<(usize, Vec<f32>)>,
+}
+
+impl Features {
+    fn new() -> Self {
+        let fft = RealFftPlanner::<f32>::new().plan_fft_forward(FFT_SIZE);
+        let window = (0..FRAME)
+            .map(|i| {
+                let phase = 2.0 * std::f32::consts::PI * i as f32 / FRAME as f32;
+                0.5 - 0.5 * phase.cos()
+            })
+            .collect();
+
+        let mel = |hz: f32| 2595.0 * (1.0 + hz / 700.0).log10();
+        let hz = |mel: f32| 700.0 * (10f32.powf(mel / 2595.0) - 1.0);
+        let bin_hz = SAMPLE_RATE as f32 / FFT_SIZE as f32;
+        let (low, high) = (mel(MIN_FREQ), mel(MAX_FREQ));
+        let edges: Vec<f32> = (0..NUM_MELS + 2)
+            .map(|i| hz(low + (high - low) * i as f32 / (NUM_MELS + 1) as f32) / bin_hz)
+            .collect();
+        let filters = edges
+            .windows(3)
+            .map(|e| {
+                let start = e[0].ceil() as usize;
+                let end = (e[2].floor() as usize).max(start);
+                let weights = (start..=end)
+                    .map(|bin| {
+                        let bin = bin as f32;
+                        if bin <= e[1] {
+                            (bin - e[0]) / (e[1] - e[0])
+                        } else {
+                            (e[2] - bin) / (e[2] - e[1])
+                        }
+                        .max(0.0)
+                    })
+                    .collect();
+                (start, weights)
+            })
+            .collect();
+
+        Self {
+            fft,
+            window,
+            filters,
+        }
+    }
+
+    /// Frames of `samples` from the first non-silent one, and up to the last
+    /// non-silent one when `trim_end` is set
+    fn extract(&self, samples: &[f32], trim_end: bool) -> Vec<Frame> {
+        let count = if samples.len() < FRAME {
+            0
+        } else {
+            (samples.len() - FRAME) / HOP + 1
+        };
+        let energies: Vec<f32> = (0..count)
+            .map(|i| {
+                let frame = &samples[i * HOP..i * HOP + FRAME];
+                frame.iter().map(|s| s * s).sum::<f32>() / FRAME as f32
+            })
+            .collect();
+        let loudest = energies.iter().copied().fold(0.0, f32::max);
+        let threshold = loudest * SILENCE_RATIO * SILENCE_RATIO;
+        let Some(first) = energies.iter().position(|&e| e > threshold) else {
+            return Vec::new();
+        };
+        let last = if trim_end {
+            energies
+                .iter()
+                .rposition(|&e| e > threshold)
+                .unwrap_or(first)
+        } else {
+            count - 1
+        };
+
+        let mut input = self.fft.make_input_vec();
+        let mut spectrum = self.fft.make_output_vec();
+        (first..=last)
+            .map(|i| {
+                let frame = &samples[i * HOP..i * HOP + FRAME];
+                for (dst, (s, w)) in input.iter_mut().zip(frame.iter().zip(&self.window)) {
+                    *dst = s * w;
+                }
+                input[FRAME..].fill(0.0);
+                let _ = self.fft.process(&mut input, &mut spectrum);
+
+                let mut mels = [0.0; NUM_MELS];
+                for (mel, (start, weights)) in mels.iter_mut().zip(&self.filters) {
+                    let power: f32 = weights
+                        .iter()
+                        .zip(&spectrum[*start..])
+                        .map(|(w, c)| w * c.norm_sqr())
+                        .sum();
+                    *mel = (power + 1e-10).ln();
+                }
+                // Remove the frame's overall level so only the spectral shape counts
+                let mean = mels.iter().sum::<f32>() / NUM_MELS as f32;
+                mels.iter_mut().for_each(|m| *m -= mean);
+                mels
+            })
+            .collect()
+    }
+}
+
+/// Cosine distance between two frames (0 = same shape, up to 2)
+fn frame_distance(a: &Frame, b: &Frame) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm =
+        a.iter().map(|x| x * x).sum::<f32>().sqrt() * b.iter().map(|y| y * y).sum::<f32>().sqrt();
+    if norm <= f32::EPSILON {
+        return 1.0;
+    }
+    1.0 - dot / norm
+}
+
+/// Average frame distance along the best alignment of `template` with the
+/// start of `utterance`, which may run on past the end of the match
+fn prefix_distance(template: &[Frame], utterance: &[Frame]) -> f32 {
+    let n = template.len();
+    let min_len = ((n as f32 / MAX_STRETCH) as usize).max(1);
+    let max_len = ((n as f32 * MAX_STRETCH) as usize).min(utterance.len());
+    if n == 0 || max_len < min_len {
+        return f32::INFINITY;
+    }
+
+    // Accumulated cost over the last three template frames. Each step moves
+    // both sequences on by one, or one of them by two, which keeps the
+    // alignment between half and double speed.
+    let mut rows = [
+        vec![f32::INFINITY; max_len],
+        vec![f32::INFINITY; max_len],
+        vec![f32::INFINITY; max_len],
+    ];
+    for (i, t) in template.iter().enumerate() {
+        let [older, prev, row] = &mut rows;
+        for j in 0..max_len {
+            let best = if i == 0 && j == 0 {
+                0.0
+            } else if j == 0 {
+                f32::INFINITY
+            } else {
+                let skip_utterance = if j > 1 { prev[j - 2] } else { f32::INFINITY };
+                prev[j - 1].min(skip_utterance).min(older[j - 1])
+            };
+            row[j] = best + frame_distance(t, &utterance[j]);
+        }
+        rows.rotate_left(1);
+    }
+
+    // Average over the path, whose length lies between the two sequences'
+    let last = &rows[1];
+    (min_len - 1..max_len)
+        .map(|j| last[j] * 2.0 / (n + j + 1) as f32)
+        .fold(f32::INFINITY, f32::min)
+}
+
+/// Matches the start of utterances against recordings of the wake phrase
+pub struct KeywordSpotter {
+    features: Features,
+    templates: Vec<Vec<Frame>>,
+    threshold: f32,
+}
+
+impl KeywordSpotter {
+    /// Spotter over templates already in memory (16kHz mono)
+    pub fn new(templates: &[Vec<f32>], threshold: f32) -> Self {
+        let features = Features::new();
+        let templates = templates
+            .iter()
+            .map(|t| features.extract(t, true))
+            .filter(|t| t.len() >= MIN_TEMPLATE_FRAMES)
+            .collect();
+        Self {
+            features,
+            templates,
+            threshold,
+        }
+    }
+
+    /// Load every 16kHz WAV in `dir` as a template
+    pub fn load_dir(
+        dir: &Path,
+        threshold: f32,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let mut paths: Vec<_> = std::fs::read_dir(dir)?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|p| {
+                p.extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("wav"))
+            })
+            .collect();
+        paths.sort();
+
+        let mut recordings = Vec::new();
+        for path in &paths {
+            let (samples, sample_rate) = audiofile::read_wav(path)?;
+            if sample_rate != SAMPLE_RATE {
+                return Err(format!(
+                    "{} is {}Hz, wake templates must be 16kHz",
+                    path.display(),
+                    sample_rate
+                )
+                .into());
+            }
+            recordings.push(samples);
+        }
+
+        let spotter = Self::new(&recordings, threshold);
+        if spotter.templates.is_empty() {
+            return Err(format!("no usable WAV recordings in {}", dir.display()).into());
+        }
+        Ok(spotter)
+    }
+
+    pub fn template_count(&self) -> usize {
+        self.templates.len()
+    }
+
+    /// Distance from the closest template (lower is closer)
+    pub fn distance(&self, audio: &[f32]) -> f32 {
+        let frames = self.features.extract(audio, false);
+        self.templates
+            .iter()
+            .map(|t| prefix_distance(t, &frames))
+            .fold(f32::INFINITY, f32::min)
+    }
+
+    /// Whether `audio` starts with something close to a template
+    pub fn detect(&self, audio: &[f32]) -> bool {
+        let distance = self.distance(audio);
+        logger::debug!(
+            "Keyword spotter distance {:.3} (threshold {:.3})",
+            distance,
+            self.threshold
+        );
+        distance <= self.threshold
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A vowel-like tone whose pitch glides from `from` to `to` Hz
+    fn glide(from: f32, to: f32, secs: f32) -> Vec<f32> {
+        let rate = SAMPLE_RATE as f32;
+        let len = (secs * rate) as usize;
+        let mut phase = 0.0f32;
+        (0..len)
+            .map(|i| {
+                let pitch = from + (to - from) * i as f32 / len as f32;
+                phase += 2.0 * std::f32::consts::PI * pitch / rate;
+                (1..=6)
+                    .map(|h| (phase * h as f32).sin() / h as f32)
+                    .sum::<f32>()
+                    * 0.1
+            })
+            .collect()
+    }
+
+    /// Hiss, like an "s"
+    fn hiss(secs: f32) -> Vec<f32> {
+        let mut seed = 12345u32;
+        (0..(secs * SAMPLE_RATE as f32) as usize)
+            .map(|_| {
+                seed = seed.wrapping_mul(1664525).wrapping_add(1013904223);
+                (seed >> 8) as f32 / (1 << 24) as f32 * 0.1 - 0.05
+            })
+            .collect()
+    }
+
+    fn silence(secs: f32) -> Vec<f32> {
+        vec![0.0; (secs * SAMPLE_RATE as f32) as usize]
+    }
+
+    #[test]
+    fn test_spots_template_at_start() {
+        let template = [
+            hiss(0.2),
+            glide(200.0, 600.0, 0.3),
+            glide(600.0, 250.0, 0.3),
+        ]
+        .concat();
+        let spotter = KeywordSpotter::new(std::slice::from_ref(&template), 0.2);
+        assert_eq!(spotter.template_count(), 1);
+
+        // Same phrase, slower and quieter, after a pause and followed by more speech
+        let slower = [
+            hiss(0.24),
+            glide(200.0, 600.0, 0.36),
+            glide(600.0, 250.0, 0.36),
+        ]
+        .concat();
+        let utterance = [
+            silence(0.2),
+            slower.iter().map(|s| s * 0.3).collect(),
+            glide(300.0, 300.0, 1.0),
+        ]
+        .concat();
+        let other = [glide(150.0, 150.0, 0.6), hiss(0.6)].concat();
+        assert!(spotter.distance(&utterance) < spotter.distance(&other));
+        assert!(spotter.detect(&utterance));
+
+        // Different sounds, or the phrase somewhere other than the start
+        assert!(!spotter.detect(&other));
+        assert!(!spotter.detect(&[glide(150.0, 150.0, 1.0), template].concat()));
+        assert!(!spotter.detect(&silence(1.0)));
+        assert!(!spotter.detect(&[]));
+    }
+
+    #[test]
+    fn test_short_templates_ignored() {
+        let spotter = KeywordSpotter::new(&[glide(200.0, 400.0, 0.1), silence(1.0)], 0.2);
+        assert_eq!(spotter.template_count(), 0);
+        assert!(!spotter.detect(&glide(200.0, 400.0, 0.1)));
+    }
+}
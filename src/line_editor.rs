@@ -0,0 +1,232 @@
+//! Single-line input editing shared by the orb and waveform UIs
+//!
+//! Emacs-style keys (Ctrl+A/E/K/U/W), arrows, Home/End, Backspace and Delete.
+//! An Enter with more key events already queued is part of a paste and adds a
+//! newline instead of submitting.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::io;
+
+/// What a key did to the line
+#[derive(Debug, PartialEq)]
+pub enum LineEdit {
+    /// Nothing changed, or only the cursor moved
+    Unchanged,
+    /// The text changed
+    Changed,
+    /// A pasted newline was inserted, so an Enter earlier in the paste
+    /// doesn't submit
+    Newline,
+    /// Enter submitted the line: its trimmed text, `None` when blank
+    Submit(Option<String>),
+}
+
+#[derive(Default)]
+pub struct LineEditor {
+    text: String,
+    /// Cursor position in characters
+    cursor: usize,
+}
+
+impl LineEditor {
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Apply a key; `paste_pending` is asked on Enter whether more input is
+    /// already queued behind it
+    pub fn handle_key(
+        &mut self,
+        key: &KeyEvent,
+        paste_pending: impl FnOnce() -> io::Result<bool>,
+    ) -> io::Result<LineEdit> {
+        let edit = match key.code {
+            KeyCode::Enter if paste_pending()? => {
+                self.insert('\n');
+                LineEdit::Newline
+            }
+            KeyCode::Enter => {
+                let text = self.text.trim().to_string();
+                self.clear();
+                LineEdit::Submit(if text.is_empty() { None } else { Some(text) })
+            }
+            KeyCode::Char(c) if key.modifiers.contains(KeyModifiers::CONTROL) => match c {
+                'a' => self.move_to(0),
+                'e' => self.move_to(self.char_count()),
+                'k' if self.cursor < self.char_count() => {
+                    let byte_pos = self.byte_index(self.cursor);
+                    self.text.truncate(byte_pos);
+                    LineEdit::Changed
+                }
+                'u' if self.cursor > 0 => {
+                    let byte_pos = self.byte_index(self.cursor);
+                    self.text.replace_range(..byte_pos, "");
+                    self.cursor = 0;
+                    LineEdit::Changed
+                }
+                'w' if self.cursor > 0 => {
+                    let chars: Vec<char> = self.text.chars().collect();
+                    let mut start = self.cursor;
+                    while start > 0 && chars[start - 1].is_whitespace() {
+                        start -= 1;
+                    }
+                    while start > 0 && !chars[start - 1].is_whitespace() {
+                        start -= 1;
+                    }
+                    let range = self.byte_index(start)..self.byte_index(self.cursor);
+                    self.text.replace_range(range, "");
+                    self.cursor = start;
+                    LineEdit::Changed
+                }
+                _ => LineEdit::Unchanged,
+            },
+            KeyCode::Char(c) => {
+                self.insert(c);
+                LineEdit::Changed
+            }
+            KeyCode::Backspace if self.cursor > 0 => {
+                self.cursor -= 1;
+                self.text.remove(self.byte_index(self.cursor));
+                LineEdit::Changed
+            }
+            KeyCode::Delete if self.cursor < self.char_count() => {
+                self.text.remove(self.byte_index(self.cursor));
+                LineEdit::Changed
+            }
+            KeyCode::Left => self.move_to(self.cursor.saturating_sub(1)),
+            KeyCode::Right => self.move_to((self.cursor + 1).min(self.char_count())),
+            KeyCode::Home => self.move_to(0),
+            KeyCode::End => self.move_to(self.char_count()),
+            _ => LineEdit::Unchanged,
+        };
+        Ok(edit)
+    }
+
+    /// Add voice input to the end of the line, space-separated
+    pub fn append(&mut self, text: &str) {
+        if !self.text.is_empty() && !self.text.ends_with(' ') {
+            self.text.push(' ');
+        }
+        self.text.push_str(text);
+        self.cursor = self.char_count();
+    }
+
+    /// Take the line, leaving it empty (`None` if it already was)
+    pub fn take(&mut self) -> Option<String> {
+        if self.text.is_empty() {
+            None
+        } else {
+            self.cursor = 0;
+            Some(std::mem::take(&mut self.text))
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.text.clear();
+        self.cursor = 0;
+    }
+
+    fn insert(&mut self, c: char) {
+        let byte_pos = self.byte_index(self.cursor);
+        self.text.insert(byte_pos, c);
+        self.cursor += 1;
+    }
+
+    fn move_to(&mut self, cursor: usize) -> LineEdit {
+        self.cursor = cursor;
+        LineEdit::Unchanged
+    }
+
+    fn byte_index(&self, char_idx: usize) -> usize {
+        self.text
+            .char_indices()
+            .nth(char_idx)
+            .map(|(i, _)| i)
+            .unwrap_or(self.text.len())
+    }
+
+    fn char_count(&self) -> usize {
+        self.text.chars().count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(editor: &mut LineEditor, code: KeyCode, modifiers: KeyModifiers) -> LineEdit {
+        editor
+            .handle_key(&KeyEvent::new(code, modifiers), || Ok(false))
+            .unwrap()
+    }
+
+    fn type_text(editor: &mut LineEditor, text: &str) {
+        for c in text.chars() {
+            press(editor, KeyCode::Char(c), KeyModifiers::NONE);
+        }
+    }
+
+    #[test]
+    fn test_editing_keys() {
+        let mut editor = LineEditor::default();
+        type_text(&mut editor, "héllo world");
+        assert_eq!(
+            press(&mut editor, KeyCode::Char('w'), KeyModifiers::CONTROL),
+            LineEdit::Changed
+        );
+        assert_eq!(editor.text(), "héllo ");
+
+        press(&mut editor, KeyCode::Home, KeyModifiers::NONE);
+        press(&mut editor, KeyCode::Right, KeyModifiers::NONE);
+        press(&mut editor, KeyCode::Right, KeyModifiers::NONE);
+        assert_eq!(
+            press(&mut editor, KeyCode::Backspace, KeyModifiers::NONE),
+            LineEdit::Changed
+        );
+        assert_eq!(editor.text(), "hllo ");
+        press(&mut editor, KeyCode::Delete, KeyModifiers::NONE);
+        assert_eq!(editor.text(), "hlo ");
+
+        press(&mut editor, KeyCode::Char('k'), KeyModifiers::CONTROL);
+        assert_eq!(editor.text(), "h");
+        // Nothing after the cursor left to kill
+        assert_eq!(
+            press(&mut editor, KeyCode::Char('k'), KeyModifiers::CONTROL),
+            LineEdit::Unchanged
+        );
+
+        type_text(&mut editor, "ey there");
+        press(&mut editor, KeyCode::Left, KeyModifiers::NONE);
+        press(&mut editor, KeyCode::Char('u'), KeyModifiers::CONTROL);
+        assert_eq!(editor.text(), "e");
+        press(&mut editor, KeyCode::Char('e'), KeyModifiers::CONTROL);
+        type_text(&mut editor, "nd");
+        assert_eq!(editor.text(), "end");
+    }
+
+    #[test]
+    fn test_submit_and_paste() {
+        let mut editor = LineEditor::default();
+        type_text(&mut editor, "  first");
+        let pasted = editor
+            .handle_key(&KeyEvent::from(KeyCode::Enter), || Ok(true))
+            .unwrap();
+        assert_eq!(pasted, LineEdit::Newline);
+        type_text(&mut editor, "second ");
+        assert_eq!(
+            press(&mut editor, KeyCode::Enter, KeyModifiers::NONE),
+            LineEdit::Submit(Some("first\nsecond".to_string()))
+        );
+        assert_eq!(editor.text(), "");
+        assert_eq!(
+            press(&mut editor, KeyCode::Enter, KeyModifiers::NONE),
+            LineEdit::Submit(None)
+        );
+
+        editor.append("from voice");
+        type_text(&mut editor, "!");
+        editor.append("more");
+        assert_eq!(editor.take().as_deref(), Some("from voice! more"));
+        assert_eq!(editor.take(), None);
+    }
+}
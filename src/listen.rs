@@ -1,9 +1,12 @@
-use crate::capture::{TARGET_RATE, resample};
+use crate::audiofile::{read_audio, resample};
+use crate::capture::TARGET_RATE;
+use crate::config::{self, Config};
 use crate::model_manager;
+use crate::normalize::TextNormalizer;
 pub use crate::pipeline::{AudioSource, run_multi_source, run_pipeline_with_options};
+pub use crate::subtitle::SubtitleFormat;
 use crate::transcriber::Transcriber;
-use std::fs::File;
-use std::io::{Read, Write};
+use std::io::Write;
 use std::path::PathBuf;
 
 pub fn list_apps() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
@@ -88,20 +91,16 @@ pub fn run_listen(
     output: PathBuf,
     _debug_wav: Option<PathBuf>,
     save_ogg: Option<PathBuf>,
+    format: SubtitleFormat,
+    vad_log: Option<PathBuf>,
+    interim: bool,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    run_pipeline_with_options(source, output, save_ogg)
+    run_pipeline_with_options(source, output, save_ogg, format, vad_log, interim)
 }
 
 pub fn transcribe_wav(path: PathBuf) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
-
-    let (samples, sample_rate) = if ext == "ogg" {
-        println!("Loading OGG: {:?}", path);
-        load_ogg_file(&path)?
-    } else {
-        println!("Loading WAV: {:?}", path);
-        load_wav_file(&path)?
-    };
+    println!("Loading: {:?}", path);
+    let (samples, sample_rate) = read_audio(&path)?;
 
     println!(
         "Sample rate: {}Hz, {} samples ({:.1}s)",
@@ -112,14 +111,16 @@ pub fn transcribe_wav(path: PathBuf) -> Result<(), Box<dyn std::error::Error + S
 
     let samples = if sample_rate as usize != TARGET_RATE {
         println!("Resampling {}Hz -> {}Hz", sample_rate, TARGET_RATE);
-        resample(&samples, sample_rate as usize, TARGET_RATE)
+        resample(&samples, sample_rate as usize, TARGET_RATE)?
     } else {
         samples
     };
 
     println!("Loading transcription model...");
-    let parakeet_path = model_manager::resolve_model_path(model_manager::PARAKEET_DIR);
-    let mut transcriber = Transcriber::new(&parakeet_path.to_string_lossy())?;
+    let config = Config::load().map_err(|p| config::problem_report(&p))?;
+    let parakeet_path = model_manager::transcriber_model_path(&config);
+    let mut transcriber = Transcriber::new(&parakeet_path.to_string_lossy())?
+        .with_normalizer(TextNormalizer::new(&config.normalize));
 
     println!("Transcribing...\n");
     let start = std::time::Instant::now();
@@ -137,55 +138,3 @@ pub fn transcribe_wav(path: PathBuf) -> Result<(), Box<dyn std::error::Error + S
 
     Ok(())
 }
-
-fn load_wav_file(
-    path: &PathBuf,
-) -> Result<(Vec<f32>, u32), Box<dyn std::error::Error + Send + Sync>> {
-    let mut file = File::open(path)?;
-    let mut header = [0u8; 44];
-    file.read_exact(&mut header)?;
-
-    let sample_rate = u32::from_le_bytes([header[24], header[25], header[26], header[27]]);
-    let bits_per_sample = u16::from_le_bytes([header[34], header[35]]);
-    let data_size = u32::from_le_bytes([header[40], header[41], header[42], header[43]]);
-
-    let mut data = vec![0u8; data_size as usize];
-    file.read_exact(&mut data)?;
-
-    let samples: Vec<f32> = if bits_per_sample == 16 {
-        data.chunks_exact(2)
-            .map(|c| i16::from_le_bytes([c[0], c[1]]) as f32 / 32768.0)
-            .collect()
-    } else if bits_per_sample == 32 {
-        data.chunks_exact(4)
-            .map(|c| f32::from_le_bytes([c[0], c[1], c[2], c[3]]))
-            .collect()
-    } else {
-        return Err(format!("Unsupported bits per sample: {}", bits_per_sample).into());
-    };
-
-    Ok((samples, sample_rate))
-}
-
-fn load_ogg_file(
-    path: &PathBuf,
-) -> Result<(Vec<f32>, u32), Box<dyn std::error::Error + Send + Sync>> {
-    use lewton::inside_ogg::OggStreamReader;
-
-    let file = File::open(path)?;
-    let mut reader = OggStreamReader::new(file)?;
-
-    let sample_rate = reader.ident_hdr.audio_sample_rate;
-    let channels = reader.ident_hdr.audio_channels as usize;
-
-    let mut samples = Vec::new();
-    while let Some(packet) = reader.read_dec_packet_itl()? {
-        for chunk in packet.chunks(channels) {
-            let mono: f32 =
-                chunk.iter().map(|&s| s as f32 / 32768.0).sum::<f32>() / channels as f32;
-            samples.push(mono);
-        }
-    }
-
-    Ok((samples, sample_rate))
-}
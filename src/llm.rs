@@ -2,17 +2,23 @@
 
 #[cfg(feature = "llama-cpp")]
 use crate::config::PromptFormat;
+use serde::{Deserialize, Serialize};
 #[cfg(feature = "llama-cpp")]
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 /// Chat message for conversation history
-#[derive(Clone)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Message {
     pub role: Role,
     pub content: String,
+    /// When the turn was added to the conversation, kept for exports only
+    #[serde(skip)]
+    pub time: Option<chrono::DateTime<chrono::Local>>,
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 #[allow(dead_code)]
 pub enum Role {
     System,
@@ -20,6 +26,44 @@ pub enum Role {
     Assistant,
 }
 
+/// A request that failed before anything was generated and is worth retrying:
+/// the server couldn't be reached, timed out, or was busy
+#[derive(Debug)]
+pub struct TransientError(pub String);
+
+impl std::fmt::Display for TransientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for TransientError {}
+
+/// Whether `e` or one of its sources is a failure to reach the server
+#[cfg(feature = "ollama")]
+fn is_connection_error(e: &(dyn std::error::Error + 'static)) -> bool {
+    use std::io::ErrorKind;
+
+    let mut source = Some(e);
+    while let Some(err) = source {
+        let kind = err.downcast_ref::<std::io::Error>().map(|io| io.kind());
+        if matches!(
+            kind,
+            Some(
+                ErrorKind::ConnectionRefused
+                    | ErrorKind::ConnectionReset
+                    | ErrorKind::ConnectionAborted
+                    | ErrorKind::NotConnected
+                    | ErrorKind::TimedOut
+            )
+        ) {
+            return true;
+        }
+        source = err.source();
+    }
+    false
+}
+
 /// Trait for LLM backends
 pub trait LlmBackend: Send {
     /// Generate streaming response, calling on_token for each token
@@ -27,7 +71,26 @@ pub trait LlmBackend: Send {
         &mut self,
         messages: &[Message],
         on_token: &mut dyn FnMut(&str),
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        self.generate_cancellable(messages, &AtomicBool::new(false), on_token)
+    }
+
+    /// Like `generate`, but stops between tokens once `cancel` is set and
+    /// returns what was generated so far
+    fn generate_cancellable(
+        &mut self,
+        messages: &[Message],
+        cancel: &AtomicBool,
+        on_token: &mut dyn FnMut(&str),
     ) -> Result<String, Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Replace the system prompt sent ahead of the conversation
+    fn set_system_prompt(&mut self, prompt: &str);
+
+    /// Switch to another model, keeping the conversation
+    fn set_model(&mut self, _name: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        Err("This LLM backend can't switch models at runtime".into())
+    }
 }
 
 // ============================================================================
@@ -111,7 +174,7 @@ pub mod llama {
 
             for msg in messages {
                 let role = match msg.role {
-                    Role::System => continue,
+                    Role::System => "system",
                     Role::User => "user",
                     Role::Assistant => "assistant",
                 };
@@ -133,6 +196,11 @@ pub mod llama {
             let mut first_user = true;
             for msg in messages {
                 match msg.role {
+                    // Mistral has no system role, notes join the first instruction
+                    Role::System if first_user => {
+                        prompt.push_str(&msg.content);
+                        prompt.push_str("\n\n");
+                    }
                     Role::System => {}
                     Role::User => {
                         if first_user {
@@ -162,7 +230,7 @@ pub mod llama {
 
             for msg in messages {
                 let role = match msg.role {
-                    Role::System => continue,
+                    Role::System => "system",
                     Role::User => "user",
                     Role::Assistant => "assistant",
                 };
@@ -177,9 +245,10 @@ pub mod llama {
     }
 
     impl LlmBackend for LlamaCppBackend {
-        fn generate(
+        fn generate_cancellable(
             &mut self,
             messages: &[Message],
+            cancel: &AtomicBool,
             on_token: &mut dyn FnMut(&str),
         ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
             let prompt = self.format_prompt(messages);
@@ -216,7 +285,7 @@ pub mod llama {
             let n_len = 1024i32;
             let mut decoder = encoding_rs::UTF_8.new_decoder();
 
-            while n_cur < n_len {
+            while n_cur < n_len && !cancel.load(Ordering::SeqCst) {
                 let token = sampler.sample(&ctx, batch.n_tokens() - 1);
                 sampler.accept(token);
 
@@ -245,6 +314,10 @@ pub mod llama {
 
             Ok(full_response)
         }
+
+        fn set_system_prompt(&mut self, prompt: &str) {
+            self.system_prompt = prompt.to_string();
+        }
     }
 
     /// Download model from HuggingFace Hub
@@ -277,24 +350,44 @@ pub mod ollama {
 
     pub struct OllamaBackend {
         client: Ollama,
+        host: String,
         model: String,
         system_prompt: String,
     }
 
     impl OllamaBackend {
-        pub fn new(model: &str, system_prompt: &str) -> Self {
-            Self {
-                client: Ollama::default(),
+        pub fn new(
+            model: &str,
+            host: &str,
+            system_prompt: &str,
+        ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+            let client = Ollama::try_new(host)
+                .map_err(|e| format!("Invalid Ollama host '{}': {}", host, e))?;
+            Ok(Self {
+                client,
+                host: host.to_string(),
                 model: model.to_string(),
                 system_prompt: system_prompt.to_string(),
-            }
+            })
+        }
+
+        /// Make sure the server answers, so a wrong host fails at startup
+        pub async fn check_connection(
+            &self,
+        ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+            self.client
+                .list_local_models()
+                .await
+                .map_err(|e| format!("Ollama unreachable at {}: {}", self.host, e))?;
+            Ok(())
         }
     }
 
     impl LlmBackend for OllamaBackend {
-        fn generate(
+        fn generate_cancellable(
             &mut self,
             messages: &[Message],
+            cancel: &AtomicBool,
             on_token: &mut dyn FnMut(&str),
         ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
             // Build message history
@@ -317,10 +410,22 @@ pub mod ollama {
 
             let client = &self.client;
             let result = rt.block_on(async {
-                let mut stream = client.send_chat_messages_stream(request).await?;
+                let mut stream = client.send_chat_messages_stream(request).await.map_err(
+                    |e| -> Box<dyn std::error::Error + Send + Sync> {
+                        if is_connection_error(&e) {
+                            Box::new(TransientError(e.to_string()))
+                        } else {
+                            e.into()
+                        }
+                    },
+                )?;
                 let mut full_response = String::new();
 
                 while let Some(Ok(chunk)) = stream.next().await {
+                    // Dropping the stream closes the connection
+                    if cancel.load(Ordering::SeqCst) {
+                        break;
+                    }
                     let content = &chunk.message.content;
                     on_token(content);
                     full_response.push_str(content);
@@ -331,6 +436,24 @@ pub mod ollama {
 
             Ok(result)
         }
+
+        fn set_system_prompt(&mut self, prompt: &str) {
+            self.system_prompt = prompt.to_string();
+        }
+
+        fn set_model(
+            &mut self,
+            name: &str,
+        ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+            // Check the model exists so a typo fails here, not on the next question
+            let rt = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()?;
+            rt.block_on(self.client.show_model_info(name.to_string()))
+                .map_err(|e| format!("Unknown Ollama model '{}': {}", name, e))?;
+            self.model = name.to_string();
+            Ok(())
+        }
     }
 }
 
@@ -340,7 +463,7 @@ pub mod ollama {
 
 #[cfg(feature = "openai-compat")]
 pub mod openai_compat {
-    use super::{LlmBackend, Message, Role};
+    use super::{AtomicBool, LlmBackend, Message, Ordering, Role, TransientError};
     use reqwest::blocking::Client;
     use serde::{Deserialize, Serialize};
     use std::io::{BufRead, BufReader};
@@ -390,6 +513,7 @@ pub mod openai_compat {
         client: Client,
         base_url: String,
         model: String,
+        system_prompt: String,
         api_key: Option<String>,
         temperature: Option<f32>,
         top_p: Option<f32>,
@@ -417,6 +541,7 @@ pub mod openai_compat {
                 client,
                 base_url: base_url.trim_end_matches('/').to_string(),
                 model,
+                system_prompt: String::new(),
                 api_key,
                 temperature,
                 top_p,
@@ -427,15 +552,66 @@ pub mod openai_compat {
         }
     }
 
+    /// Collect content deltas from an SSE stream until `[DONE]`, the end of
+    /// the stream, or `cancel`
+    fn read_sse(
+        lines: impl Iterator<Item = std::io::Result<String>>,
+        cancel: &AtomicBool,
+        on_token: &mut dyn FnMut(&str),
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let mut full_response = String::new();
+
+        for line in lines {
+            let line = line?;
+
+            if line.is_empty() {
+                continue;
+            }
+
+            if line == "data: [DONE]" {
+                break;
+            }
+
+            if let Some(json_str) = line.strip_prefix("data: ") {
+                match serde_json::from_str::<ChatChunk>(json_str) {
+                    Ok(chunk) => {
+                        if let Some(choice) = chunk.choices.first() {
+                            if let Some(content) = &choice.delta.content {
+                                on_token(content);
+                                full_response.push_str(content);
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        eprintln!("Warning: Failed to parse chunk: {}", e);
+                    }
+                }
+            }
+
+            if cancel.load(Ordering::SeqCst) {
+                break;
+            }
+        }
+
+        Ok(full_response)
+    }
+
     impl LlmBackend for OpenAiCompatBackend {
-        fn generate(
+        fn generate_cancellable(
             &mut self,
             messages: &[Message],
+            cancel: &AtomicBool,
             on_token: &mut dyn FnMut(&str),
         ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
-            // Build message array
-            let chat_messages: Vec<ChatMessage> = messages
+            // Build message array, system prompt first
+            let system = (!self.system_prompt.is_empty()).then(|| Message {
+                role: Role::System,
+                content: self.system_prompt.clone(),
+                time: None,
+            });
+            let chat_messages: Vec<ChatMessage> = system
                 .iter()
+                .chain(messages)
                 .map(|msg| {
                     let role = match msg.role {
                         Role::System => "system",
@@ -471,49 +647,94 @@ pub mod openai_compat {
                 req = req.header("Authorization", format!("Bearer {}", key));
             }
 
-            let response = req.send()?;
+            let response = req
+                .send()
+                .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> {
+                    if e.is_connect() || e.is_timeout() {
+                        Box::new(TransientError(e.to_string()))
+                    } else {
+                        e.into()
+                    }
+                })?;
 
             if !response.status().is_success() {
                 let status = response.status();
                 let error_text = response
                     .text()
                     .unwrap_or_else(|_| "Unknown error".to_string());
-                return Err(format!("API error {}: {}", status, error_text).into());
+                let message = format!("API error {}: {}", status, error_text);
+                // A busy or restarting server may recover, a bad request won't
+                if status.is_server_error() {
+                    return Err(Box::new(TransientError(message)));
+                }
+                return Err(message.into());
             }
 
-            // Parse SSE stream
-            let reader = BufReader::new(response);
-            let mut full_response = String::new();
+            // Returning early drops the response, which closes the connection
+            read_sse(BufReader::new(response).lines(), cancel, on_token)
+        }
 
-            for line in reader.lines() {
-                let line = line?;
+        fn set_system_prompt(&mut self, prompt: &str) {
+            self.system_prompt = prompt.to_string();
+        }
 
-                if line.is_empty() {
-                    continue;
-                }
+        fn set_model(
+            &mut self,
+            name: &str,
+        ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+            // Unknown models are reported by the server on the next request
+            self.model = name.to_string();
+            Ok(())
+        }
+    }
 
-                if line == "data: [DONE]" {
-                    break;
-                }
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use std::cell::Cell;
+
+        #[test]
+        fn test_sse_stops_on_cancel() {
+            let chunk = |text: &str| {
+                Ok(format!(
+                    r#"data: {{"choices":[{{"delta":{{"content":"{}"}},"finish_reason":null}}]}}"#,
+                    text
+                ))
+            };
+            let served = Cell::new(0);
+            // A slow stream that would go on well past the stop
+            let lines = (0..100).map(|i| {
+                served.set(served.get() + 1);
+                chunk(&format!("word{} ", i))
+            });
 
-                if let Some(json_str) = line.strip_prefix("data: ") {
-                    match serde_json::from_str::<ChatChunk>(json_str) {
-                        Ok(chunk) => {
-                            if let Some(choice) = chunk.choices.first() {
-                                if let Some(content) = &choice.delta.content {
-                                    on_token(content);
-                                    full_response.push_str(content);
-                                }
-                            }
-                        }
-                        Err(e) => {
-                            eprintln!("Warning: Failed to parse chunk: {}", e);
-                        }
-                    }
+            let cancel = AtomicBool::new(false);
+            let mut tokens = 0;
+            let response = read_sse(lines, &cancel, &mut |_| {
+                tokens += 1;
+                if tokens == 3 {
+                    cancel.store(true, Ordering::SeqCst);
                 }
-            }
+            })
+            .unwrap();
 
-            Ok(full_response)
+            assert_eq!(response, "word0 word1 word2 ");
+            assert_eq!(served.get(), 3);
+        }
+
+        #[test]
+        fn test_sse_reads_to_done() {
+            let lines = [
+                r#"data: {"choices":[{"delta":{"content":"Hi"},"finish_reason":null}]}"#,
+                "",
+                "data: [DONE]",
+                r#"data: {"choices":[{"delta":{"content":"late"},"finish_reason":null}]}"#,
+            ]
+            .into_iter()
+            .map(|line| Ok(line.to_string()));
+
+            let response = read_sse(lines, &AtomicBool::new(false), &mut |_| {}).unwrap();
+            assert_eq!(response, "Hi");
         }
     }
 }
@@ -561,9 +782,10 @@ pub mod kalosm_backend {
     }
 
     impl LlmBackend for KalosmBackend {
-        fn generate(
+        fn generate_cancellable(
             &mut self,
             messages: &[Message],
+            cancel: &AtomicBool,
             on_token: &mut dyn FnMut(&str),
         ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
             let mut prompt = format!("System: {}\n\n", self.system_prompt);
@@ -585,6 +807,9 @@ pub mod kalosm_backend {
                 let mut stream = self.model.complete(&prompt);
                 let mut full_response = String::new();
                 while let Some(token) = stream.next().await {
+                    if cancel.load(Ordering::SeqCst) {
+                        break;
+                    }
                     let t = token.to_string();
                     on_token(&t);
                     full_response.push_str(&t);
@@ -594,5 +819,9 @@ pub mod kalosm_backend {
 
             Ok(result)
         }
+
+        fn set_system_prompt(&mut self, prompt: &str) {
+            self.system_prompt = prompt.to_string();
+        }
     }
 }
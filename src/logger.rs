@@ -0,0 +1,147 @@
+//! Minimal leveled logger
+//!
+//! Messages go to stderr and, with `--log-file`, to a file with timestamps.
+//! Fullscreen UIs call `quiet_terminal()` so log lines can't corrupt the
+//! screen; from then on logging is file-only (`debug.log` unless a file was
+//! given). Use the `error!`, `warn!`, `info!` and `debug!` macros.
+
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Mutex;
+
+/// Log file used when the terminal is quieted and no `--log-file` was given
+const DEFAULT_LOG_FILE: &str = "debug.log";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+}
+
+impl Level {
+    fn label(self) -> &'static str {
+        match self {
+            Level::Error => "ERROR",
+            Level::Warn => "WARN",
+            Level::Info => "INFO",
+            Level::Debug => "DEBUG",
+        }
+    }
+}
+
+impl FromStr for Level {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "error" => Ok(Level::Error),
+            "warn" | "warning" => Ok(Level::Warn),
+            "info" => Ok(Level::Info),
+            "debug" => Ok(Level::Debug),
+            _ => Err(format!(
+                "Invalid log level '{}', expected error, warn, info or debug",
+                s
+            )),
+        }
+    }
+}
+
+struct Logger {
+    level: Level,
+    file: Option<File>,
+    stderr: bool,
+}
+
+static LOGGER: Mutex<Logger> = Mutex::new(Logger {
+    level: Level::Info,
+    file: None,
+    stderr: true,
+});
+
+fn open_log(path: &Path) -> std::io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+/// Set the level and optional log file (appended to)
+pub fn init(level: Level, file: Option<&Path>) -> std::io::Result<()> {
+    let file = file.map(open_log).transpose()?;
+    let mut logger = LOGGER.lock().unwrap_or_else(|e| e.into_inner());
+    logger.level = level;
+    logger.file = file;
+    Ok(())
+}
+
+/// Stop writing to stderr, falling back to `debug.log` if no file is set
+pub fn quiet_terminal() {
+    let mut logger = LOGGER.lock().unwrap_or_else(|e| e.into_inner());
+    logger.stderr = false;
+    if logger.file.is_none() {
+        logger.file = open_log(Path::new(DEFAULT_LOG_FILE)).ok();
+    }
+}
+
+pub fn log(level: Level, args: fmt::Arguments) {
+    let mut logger = LOGGER.lock().unwrap_or_else(|e| e.into_inner());
+    if level > logger.level {
+        return;
+    }
+    if logger.stderr {
+        eprintln!("{}", args);
+    }
+    if let Some(file) = logger.file.as_mut() {
+        let _ = writeln!(
+            file,
+            "{} {:5} {}",
+            chrono::Local::now().format("%H:%M:%S%.3f"),
+            level.label(),
+            args
+        );
+    }
+}
+
+macro_rules! log_error {
+    ($($arg:tt)*) => {
+        $crate::logger::log($crate::logger::Level::Error, format_args!($($arg)*))
+    };
+}
+
+macro_rules! log_warn {
+    ($($arg:tt)*) => {
+        $crate::logger::log($crate::logger::Level::Warn, format_args!($($arg)*))
+    };
+}
+
+macro_rules! log_info {
+    ($($arg:tt)*) => {
+        $crate::logger::log($crate::logger::Level::Info, format_args!($($arg)*))
+    };
+}
+
+macro_rules! log_debug {
+    ($($arg:tt)*) => {
+        $crate::logger::log($crate::logger::Level::Debug, format_args!($($arg)*))
+    };
+}
+
+// Re-exported under short names (`logger::warn!`); `warn` itself would clash
+// with the built-in lint attribute
+#[allow(unused_imports)]
+pub(crate) use {log_debug as debug, log_error as error, log_info as info, log_warn as warn};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_level() {
+        assert_eq!("warn".parse(), Ok(Level::Warn));
+        assert_eq!("DEBUG".parse(), Ok(Level::Debug));
+        assert!("loud".parse::<Level>().is_err());
+        assert!(Level::Error < Level::Debug);
+    }
+}
@@ -1,6 +1,8 @@
 #[cfg(feature = "aec")]
 mod aec;
 mod audio;
+mod audiofile;
+mod bench;
 #[cfg(feature = "listen")]
 mod capture;
 mod chat;
@@ -8,33 +10,48 @@ mod command;
 mod config;
 mod fuzzy;
 mod graphical_ui;
+mod json_ui;
+mod kws;
+mod line_editor;
 #[cfg(feature = "listen")]
 mod listen;
 mod llm;
+mod logger;
 mod model_manager;
+mod normalize;
 #[cfg(feature = "listen")]
 mod pipeline;
+mod recorder;
 mod render;
 mod rephrase;
 mod repl;
 #[cfg(feature = "listen")]
+mod resample;
+#[cfg(feature = "listen")]
 mod segmenter;
+mod sentence;
 mod session;
+mod spectrum;
 mod state;
 mod stats;
 mod status_bar;
 #[cfg(feature = "listen")]
+mod subtitle;
+#[cfg(feature = "listen")]
 mod summarize;
 #[cfg(feature = "supertonic")]
 mod supertonic;
 mod test_ui;
 mod transcriber;
 mod tts;
+mod tts_markup;
 mod tui;
 #[cfg(feature = "typing")]
 mod typing;
 mod vad;
+mod vad_eval;
 mod wake;
+mod waveform_ui;
 
 use command::{CommandProcessor, CommandResult};
 use config::{Config, LlmConfig, OrbStyleConfig, TtsConfig, UiModeConfig};
@@ -44,8 +61,6 @@ use state::RuntimeState;
 
 use clap::{Parser, Subcommand};
 use std::error::Error;
-use std::fs::OpenOptions;
-use std::io::Write;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -65,21 +80,176 @@ fn resolve_tts_path(path: &str) -> String {
     }
 }
 
-fn debug_log(msg: &str) {
-    if let Ok(mut file) = OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open("debug.log")
-    {
-        let _ = writeln!(
-            file,
-            "{}: {}",
-            chrono::Utc::now().format("%H:%M:%S%.3f"),
-            msg
-        );
+/// Drive `RuntimeState::ptt_held` from the global Ctrl+Space hotkey
+#[cfg(feature = "typing")]
+fn start_push_to_talk(state: &state::SharedState) {
+    let hotkeys = typing::HotkeyConfig {
+        enable_double_tap_cmd: false,
+        ..Default::default()
+    };
+    match typing::start_hotkey_listener(hotkeys) {
+        Ok((rx, _running)) => {
+            let state = Arc::clone(state);
+            thread::spawn(move || {
+                while let Ok(event) = rx.recv() {
+                    match event {
+                        typing::HotkeyEvent::PushToTalkStart => {
+                            state.ptt_held.store(true, Ordering::SeqCst)
+                        }
+                        typing::HotkeyEvent::PushToTalkEnd => {
+                            state.ptt_held.store(false, Ordering::SeqCst)
+                        }
+                        typing::HotkeyEvent::Toggle => {}
+                    }
+                }
+            });
+            logger::info!("Push-to-talk: hold Ctrl+Space to talk");
+        }
+        Err(e) => {
+            logger::warn!("Push-to-talk hotkey failed ({}), using VAD", e);
+            state.ptt_mode.store(false, Ordering::SeqCst);
+        }
+    }
+}
+
+#[cfg(not(feature = "typing"))]
+fn start_push_to_talk(state: &state::SharedState) {
+    logger::warn!("Push-to-talk needs the global hotkey (build with --features typing), using VAD");
+    state.ptt_mode.store(false, Ordering::SeqCst);
+}
+
+/// Listen to system or app audio (`audio.source`) instead of the mic, feeding
+/// the same VAD frames into `audio_tx`
+#[cfg(feature = "listen")]
+fn start_system_capture(
+    source: &config::CaptureSource,
+    audio_tx: mpsc::Sender<Vec<f32>>,
+    running: Arc<AtomicBool>,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let app = match source {
+        config::CaptureSource::App(name) => Some(name.clone()),
+        _ => None,
+    };
+    let (capture_tx, capture_rx) = flume::unbounded();
+    thread::spawn(move || audio::forward_frames(capture_rx, audio_tx));
+    // The stream lives on the capture thread; it reports whether it started
+    let (started_tx, started_rx) = mpsc::channel();
+    thread::spawn(move || {
+        let stream = match pipeline::start_system_tap(capture_tx, None, app) {
+            Ok(stream) => stream,
+            Err(e) => {
+                let _ = started_tx.send(Err(e));
+                return;
+            }
+        };
+        let _ = started_tx.send(Ok(()));
+        while running.load(Ordering::SeqCst) {
+            thread::sleep(std::time::Duration::from_millis(100));
+        }
+        let _ = stream.stop_capture();
+    });
+    started_rx
+        .recv()
+        .map_err(|_| "Audio capture thread exited before starting")?
+}
+
+#[cfg(not(feature = "listen"))]
+fn start_system_capture(
+    source: &config::CaptureSource,
+    _audio_tx: mpsc::Sender<Vec<f32>>,
+    _running: Arc<AtomicBool>,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    Err(format!(
+        "audio.source = {:?} needs system audio capture (build with --features listen)",
+        source
+    )
+    .into())
+}
+
+/// Log a finished (or stopped) response to the session recording, if any
+fn record_response(recorder: &Option<recorder::SharedRecorder>, response: &mut String) {
+    let response = std::mem::take(response);
+    if let Some(recorder) = recorder {
+        if response.is_empty() {
+            return;
+        }
+        if let Err(e) = recorder.lock().unwrap().record_response(&response) {
+            logger::warn!("Session recording failed: {}", e);
+        }
+    }
+}
+
+/// Switch the orb style and remember it as `ui.default_style` for the next run
+///
+/// The low-power style isn't saved, so the full style is restored on restart
+/// unless `ui.low_power` is set.
+fn apply_visual_style(ui_renderer: &mut dyn UiRenderer, style: OrbStyle) {
+    ui_renderer.set_visual_style(style);
+    if style == OrbStyle::Minimal {
+        return;
+    }
+    Config::remember_ui_choice("default_style", &style.name().to_lowercase());
+}
+
+/// Switch modes and show the announcement; leaving note-taking has the
+/// session report how many notes were taken
+fn change_mode(
+    mode: state::AppMode,
+    announcement: Option<String>,
+    runtime_state: &RuntimeState,
+    ui_renderer: &mut dyn UiRenderer,
+    session_tx: &tokio::sync::mpsc::UnboundedSender<session::SessionCommand>,
+) {
+    if runtime_state.mode() == state::AppMode::NoteTaking && mode != state::AppMode::NoteTaking {
+        let _ = session_tx.send(session::SessionCommand::EndNotes);
+    }
+    runtime_state.set_mode(mode);
+    ui_renderer.set_mode(mode);
+    if let Some(msg) = announcement {
+        ui_renderer.show_message(&msg);
+    }
+}
+
+/// Handle `/persona [file]` and "reload persona": re-read the persona and
+/// have the session start over with it
+///
+/// A file argument must exist, and becomes the persona reloaded from then on.
+fn reload_persona(
+    persona: &mut Option<String>,
+    file: Option<String>,
+    name: &str,
+    session_tx: &tokio::sync::mpsc::UnboundedSender<session::SessionCommand>,
+    ui_renderer: &mut dyn UiRenderer,
+) {
+    if let Some(file) = file {
+        if !std::path::Path::new(&file).is_file() {
+            ui_renderer.show_message(&format!("Persona file not found: {}", file));
+            return;
+        }
+        *persona = Some(file);
+    }
+    match chat::resolve_persona(persona.as_deref(), name) {
+        Ok(prompt) => {
+            let _ = session_tx.send(session::SessionCommand::SetSystemPrompt(prompt));
+        }
+        Err(e) => ui_renderer.show_message(&e),
     }
 }
 
+/// Create the renderer for a UI mode
+fn create_ui_renderer(mode: UiMode, orb_style: OrbStyle) -> std::io::Result<Box<dyn UiRenderer>> {
+    Ok(match mode {
+        UiMode::Text => Box::new(tui::Tui::new()?),
+        UiMode::Orb => {
+            let mut gui = graphical_ui::GraphicalUi::new()?;
+            gui.set_visual_style(orb_style);
+            Box::new(gui)
+        }
+        UiMode::Waveform => Box::new(waveform_ui::WaveformUi::new()?),
+        UiMode::Json => Box::new(json_ui::JsonUi::new()?),
+    })
+}
+
 #[derive(Parser)]
 #[command(name = "silly")]
 struct Cli {
@@ -94,6 +264,10 @@ struct Cli {
     #[arg(long)]
     no_tts: bool,
 
+    /// Start with the mic muted (see `interaction.unmute_on_wake`)
+    #[arg(long)]
+    muted: bool,
+
     /// Use orb visualization UI instead of text UI
     #[arg(long, short = 'o')]
     orb: bool,
@@ -102,6 +276,15 @@ struct Cli {
     #[arg(long, short = 't')]
     text: bool,
 
+    /// Use scrolling waveform UI instead of text UI
+    #[arg(long, short = 'w')]
+    waveform: bool,
+
+    /// Headless mode: print UI events as JSON lines on stdout and read
+    /// {"input": "..."} lines from stdin
+    #[arg(long)]
+    events_json: bool,
+
     /// Visual style for graphical UI: orbs, blob, or ring
     #[arg(long, value_parser = ["orbs", "blob", "ring"])]
     orb_style: Option<String>,
@@ -110,6 +293,24 @@ struct Cli {
     #[cfg(feature = "aec")]
     #[arg(long)]
     debug_aec: Option<String>,
+
+    /// Append log messages to this file (fullscreen UIs log only to the file,
+    /// debug.log by default)
+    #[arg(long, value_name = "FILE")]
+    log_file: Option<PathBuf>,
+
+    /// Log verbosity: error, warn, info or debug
+    #[arg(long, value_parser = ["error", "warn", "info", "debug"], default_value = "info")]
+    log_level: String,
+
+    /// Save each utterance as a WAV and log transcripts and responses to this directory
+    #[arg(long, value_name = "DIR")]
+    save_session: Option<PathBuf>,
+
+    /// Feed a WAV, OGG, MP3 or FLAC recording through the assistant instead of the mic
+    #[cfg(feature = "listen")]
+    #[arg(long, value_name = "FILE")]
+    replay: Option<PathBuf>,
 }
 
 #[derive(Subcommand)]
@@ -133,6 +334,9 @@ enum Command {
         /// Output file for transcription
         #[arg(short, long, default_value = "transcript.txt")]
         output: PathBuf,
+        /// Output format for the transcript file
+        #[arg(long, value_parser = ["txt", "srt", "vtt", "json"], default_value = "txt")]
+        format: String,
         /// List available applications
         #[arg(long)]
         list: bool,
@@ -145,6 +349,12 @@ enum Command {
         /// Multi-source mode: capture from two sources with attribution
         #[arg(long)]
         multi: bool,
+        /// Write per-frame VAD decisions to a CSV file for tuning
+        #[arg(long)]
+        vad_log: Option<PathBuf>,
+        /// Show a live preview of speech in progress (single source only)
+        #[arg(long)]
+        interim: bool,
     },
     /// Record audio to OGG file (no transcription)
     #[cfg(feature = "listen")]
@@ -174,13 +384,41 @@ enum Command {
         #[arg(short, long)]
         input: Option<PathBuf>,
     },
-    /// Transcribe a WAV file (for debugging)
+    /// Transcribe an audio file: WAV, OGG, MP3 or FLAC (for debugging)
     #[cfg(feature = "listen")]
     TranscribeWav {
-        /// Input WAV file
+        /// Input audio file
         #[arg(short, long)]
         input: PathBuf,
     },
+    /// Benchmark transcription: latency percentiles and realtime factor
+    Bench {
+        /// Audio file to transcribe (default: 5s of synthetic audio)
+        #[arg(long)]
+        file: Option<PathBuf>,
+        /// Number of timed runs
+        #[arg(long, default_value = "10")]
+        iters: usize,
+    },
+    /// Score a VAD engine against labeled speech regions (precision/recall, onset latency)
+    VadCheck {
+        /// Audio file to run the VAD over
+        #[arg(long)]
+        wav: PathBuf,
+        /// CSV of labeled speech regions, one `start_secs,end_secs` per line
+        #[arg(long)]
+        labels: PathBuf,
+        /// VAD engine to evaluate
+        #[arg(long, value_parser = ["silero", "energy", "adaptive"], default_value = "silero")]
+        engine: String,
+    },
+    /// List audio input and output devices
+    Devices,
+    /// Inspect the effective configuration
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
     /// Quick test of LLM backend
     Probe {
         /// Question to ask
@@ -189,9 +427,12 @@ enum Command {
     /// Voice-to-keyboard: type speech into active application
     #[cfg(feature = "typing")]
     Typing {
-        /// Input method: "direct" (default) or "clipboard"
+        /// Input method: "direct" (default), "clipboard" or "dry-run"
         #[arg(long, default_value = "direct")]
         input_method: String,
+        /// Print what would be typed instead of sending keystrokes
+        #[arg(long)]
+        dry_run: bool,
         /// Disable feedback sounds/visual
         #[arg(long)]
         no_feedback: bool,
@@ -207,11 +448,18 @@ enum Command {
     },
 }
 
+#[derive(Subcommand)]
+enum ConfigAction {
+    /// Print the resolved config (file, SILLY_* overrides and defaults) as TOML, secrets redacted
+    Print,
+}
+
 const TARGET_RATE: usize = 16000;
 
 #[hotpath::main]
 fn main() -> Result<(), Box<dyn Error + Send + Sync>> {
     let cli = Cli::parse();
+    logger::init(cli.log_level.parse()?, cli.log_file.as_deref())?;
 
     // Handle sync commands before starting async runtime
     #[cfg(feature = "listen")]
@@ -223,6 +471,18 @@ fn main() -> Result<(), Box<dyn Error + Send + Sync>> {
         return rephrase::run_rephrase(text.clone(), input.clone());
     }
 
+    if let Some(Command::Config {
+        action: ConfigAction::Print,
+    }) = &cli.command
+    {
+        let config = Config::load().map_err(|p| config::problem_report(&p))?;
+        if let Err(problems) = config.validate() {
+            logger::warn!("{}", config::problem_report(&problems));
+        }
+        print!("{}", config.to_toml()?);
+        return Ok(());
+    }
+
     tokio::runtime::Builder::new_multi_thread()
         .enable_all()
         .build()?
@@ -241,17 +501,27 @@ async fn async_main_with_cli(cli: Cli) -> Result<(), Box<dyn Error + Send + Sync
         Some(Command::Listen {
             source,
             output,
+            format,
             list,
             debug_wav,
             save_ogg,
             multi,
+            vad_log,
+            interim,
         }) => {
             if *list {
                 return listen::list_apps();
             }
+            let format = listen::SubtitleFormat::from_str(format);
             if *multi {
                 let (src1, src2) = listen::pick_sources_multi()?;
-                return listen::run_multi_source(src1, src2, output.clone());
+                return listen::run_multi_source(
+                    src1,
+                    src2,
+                    output.clone(),
+                    format,
+                    vad_log.clone(),
+                );
             }
             let src = match source {
                 Some(s) if s == "mic" => listen::AudioSource::Mic,
@@ -259,7 +529,15 @@ async fn async_main_with_cli(cli: Cli) -> Result<(), Box<dyn Error + Send + Sync
                 Some(s) => listen::AudioSource::App(s.clone()),
                 None => listen::pick_source_interactive()?,
             };
-            return listen::run_listen(src, output.clone(), debug_wav.clone(), save_ogg.clone());
+            return listen::run_listen(
+                src,
+                output.clone(),
+                debug_wav.clone(),
+                save_ogg.clone(),
+                format,
+                vad_log.clone(),
+                *interim,
+            );
         }
         #[cfg(feature = "listen")]
         Some(Command::Record {
@@ -285,12 +563,30 @@ async fn async_main_with_cli(cli: Cli) -> Result<(), Box<dyn Error + Send + Sync
         Some(Command::TranscribeWav { input }) => {
             return listen::transcribe_wav(input.clone());
         }
+        Some(Command::Bench { file, iters }) => {
+            return bench::run_bench(file.clone(), *iters);
+        }
+        Some(Command::VadCheck {
+            wav,
+            labels,
+            engine,
+        }) => {
+            return vad_eval::run_vad_check(wav, labels, engine);
+        }
+        Some(Command::Devices) => {
+            let config = Config::load().map_err(|p| config::problem_report(&p))?;
+            return audio::run_devices(
+                config.audio.input_device.as_deref(),
+                config.audio.output_device.as_deref(),
+            );
+        }
         Some(Command::Probe { prompt }) => {
             return run_probe(prompt).await;
         }
         #[cfg(feature = "typing")]
         Some(Command::Typing {
             input_method,
+            dry_run,
             no_feedback,
             verbose,
             commands,
@@ -300,30 +596,44 @@ async fn async_main_with_cli(cli: Cli) -> Result<(), Box<dyn Error + Send + Sync
                 typing::CommandParser::print_help();
                 return Ok(());
             }
-            return run_typing_mode(
-                input_method.clone(),
-                !no_feedback,
-                *verbose,
-                *command_pause_ms,
-            )
-            .await;
+            let input_method = if *dry_run {
+                "dry-run".to_string()
+            } else {
+                input_method.clone()
+            };
+            return run_typing_mode(input_method, !no_feedback, *verbose, *command_pause_ms).await;
         }
         None => {}
     }
 
     // Load config early for acceleration settings
-    let config = Config::load();
+    let config = match Config::load().and_then(|config| config.validate().map(|_| config)) {
+        Ok(config) => config,
+        Err(problems) => {
+            eprintln!("{}", config::problem_report(&problems));
+            std::process::exit(1);
+        }
+    };
 
     // Ensure required models are downloaded
     let _model_dir =
         model_manager::ensure_models(&config).map_err(|e| format!("Model setup failed: {}", e))?;
 
     // Resolve model paths
-    let vad_model_path = model_manager::resolve_model_path(model_manager::VAD_MODEL);
-    let parakeet_model_path = model_manager::resolve_model_path(model_manager::PARAKEET_DIR);
+    let vad_model_path = model_manager::vad_model_path(&config);
+    let parakeet_model_path = model_manager::transcriber_model_path(&config);
+    let language_model_paths: std::collections::BTreeMap<String, PathBuf> = config
+        .transcriber
+        .models
+        .iter()
+        .map(|(language, path)| (language.clone(), PathBuf::from(path)))
+        .collect();
 
     // Create shared runtime state
     let runtime_state = RuntimeState::new(&config);
+    if runtime_state.ptt_mode.load(Ordering::SeqCst) {
+        start_push_to_talk(&runtime_state);
+    }
 
     // Apply CLI flags to runtime state
     if cli.no_stt {
@@ -333,9 +643,14 @@ async fn async_main_with_cli(cli: Cli) -> Result<(), Box<dyn Error + Send + Sync
     if cli.no_tts {
         runtime_state.tts_enabled.store(false, Ordering::SeqCst);
     }
+    if cli.muted {
+        runtime_state.mic_muted.store(true, Ordering::SeqCst);
+    }
 
     // Create command processor
     let command_processor = CommandProcessor::new(&config);
+    // Final transcripts are cleaned up per mode, since typing needs the raw words
+    let normalizer = normalize::TextNormalizer::new(&config.normalize);
 
     // Shared stats for performance tracking
     let stats = stats::new_shared();
@@ -344,7 +659,9 @@ async fn async_main_with_cli(cli: Cli) -> Result<(), Box<dyn Error + Send + Sync
     let stats_session = Arc::clone(&stats);
 
     // Legacy flags for backward compatibility (VAD processor and transcribe mode)
-    let mic_muted = Arc::new(AtomicBool::new(cli.no_stt));
+    let mic_muted = Arc::new(AtomicBool::new(
+        runtime_state.mic_muted.load(Ordering::SeqCst),
+    ));
     let tts_enabled = Arc::new(AtomicBool::new(!cli.no_tts));
     let wake_enabled = Arc::new(AtomicBool::new(!cli.no_stt));
 
@@ -356,7 +673,7 @@ async fn async_main_with_cli(cli: Cli) -> Result<(), Box<dyn Error + Send + Sync
             // Ensure runtime state has AEC enabled (--debug-aec implies AEC on)
             runtime_state.aec_enabled.store(true, Ordering::SeqCst);
             let (tx, rx) = mpsc::channel::<aec::RenderFrame>();
-            eprintln!(
+            logger::info!(
                 "AEC: Enabled{}",
                 if cli.debug_aec.is_some() {
                     " (debug mode)"
@@ -398,8 +715,36 @@ async fn async_main_with_cli(cli: Cli) -> Result<(), Box<dyn Error + Send + Sync
         }
     });
 
-    // Start audio capture thread
-    let _stream = audio::start_capture(audio_tx)?;
+    // Cleared on SIGTERM/SIGINT/SIGHUP or when the main loop exits; capture
+    // then stops so the VAD and transcriber threads drain and can be joined
+    let running = Arc::new(AtomicBool::new(true));
+    let running_signal = Arc::clone(&running);
+    ctrlc::set_handler(move || running_signal.store(false, Ordering::SeqCst))?;
+
+    // Start audio capture thread (or replay a recording in its place)
+    #[cfg(feature = "listen")]
+    let replay = cli
+        .replay
+        .as_deref()
+        .map(audiofile::read_audio_16k)
+        .transpose()?;
+    #[cfg(not(feature = "listen"))]
+    let replay: Option<Vec<f32>> = None;
+    let stream = if let Some(samples) = replay {
+        audio::start_replay(samples, audio_tx, Arc::clone(&running));
+        None
+    } else {
+        match &config.audio.source {
+            config::CaptureSource::Mic => Some(audio::start_capture(
+                audio_tx,
+                config.audio.input_device.as_deref(),
+            )?),
+            source => {
+                start_system_capture(source, audio_tx, Arc::clone(&running))?;
+                None
+            }
+        }
+    };
 
     // TTS level monitor thread - send updates when TTS is playing
     let runtime_state_tts = Arc::clone(&runtime_state);
@@ -419,6 +764,7 @@ async fn async_main_with_cli(cli: Cli) -> Result<(), Box<dyn Error + Send + Sync
 
     // Start VAD processing thread with crosstalk support
     let vad_path_for_thread = vad_model_path.to_string_lossy().to_string();
+    let vad_thresholds = config.interaction.vad_thresholds();
     let vad_handle = thread::spawn(move || {
         let use_gpu_vad = config.acceleration.vad_gpu;
 
@@ -435,42 +781,42 @@ async fn async_main_with_cli(cli: Cli) -> Result<(), Box<dyn Error + Send + Sync
 
             match vad_result {
                 Ok(v) => {
-                    eprintln!(
+                    logger::info!(
                         "VAD: Silero enabled (crosstalk: {})",
                         runtime_state_vad.crosstalk_enabled.load(Ordering::SeqCst)
                     );
-                    Some(v)
+                    Some(v.with_thresholds(vad_thresholds))
                 }
                 Err(e) => {
-                    eprintln!("Silero VAD failed ({}), using energy-based", e);
-                    Some(VadEngine::energy())
+                    logger::warn!("Silero VAD failed ({}), using adaptive energy-based", e);
+                    Some(VadEngine::energy_adaptive(vad::ADAPTIVE_ENERGY_FACTOR))
                 }
             }
         } else {
-            eprintln!("VAD model not found, using energy-based");
-            Some(VadEngine::energy())
+            logger::warn!("VAD model not found, using adaptive energy-based");
+            Some(VadEngine::energy_adaptive(vad::ADAPTIVE_ENERGY_FACTOR))
         };
 
         // Use the crosstalk-enabled VAD processor (with optional AEC)
         let vad_engine = vad.unwrap_or_else(|| {
-            eprintln!("Failed to initialize VAD engine");
-            VadEngine::energy()
+            logger::error!("Failed to initialize VAD engine");
+            VadEngine::energy_adaptive(vad::ADAPTIVE_ENERGY_FACTOR)
         });
 
         #[cfg(feature = "aec")]
         {
             let aec = aec_render_rx.and_then(|rx| match aec::AecProcessor::new(rx) {
                 Ok(proc) => {
-                    eprintln!("AEC: Processor initialized");
+                    logger::info!("AEC: Processor initialized");
                     Some(if let Some(ref prefix) = aec_debug_prefix {
-                        eprintln!("AEC: Debug output to {}_*.wav", prefix);
+                        logger::info!("AEC: Debug output to {}_*.wav", prefix);
                         proc.with_debug(prefix)
                     } else {
                         proc
                     })
                 }
                 Err(e) => {
-                    eprintln!("AEC: Failed to create processor: {}", e);
+                    logger::warn!("AEC: Failed to create processor: {}", e);
                     None
                 }
             });
@@ -482,6 +828,8 @@ async fn async_main_with_cli(cli: Cli) -> Result<(), Box<dyn Error + Send + Sync
                 runtime_state_vad,
                 display_tx_audio,
                 aec,
+                config.interaction.silence_ms,
+                config.interaction.trailing_pad_ms,
             );
         }
 
@@ -493,48 +841,200 @@ async fn async_main_with_cli(cli: Cli) -> Result<(), Box<dyn Error + Send + Sync
             Some(vad_engine),
             runtime_state_vad,
             display_tx_audio,
+            config.interaction.silence_ms,
+            config.interaction.trailing_pad_ms,
         );
     });
 
+    // Wake gate for the final transcriber, screening audio with the keyword
+    // spotter when templates are configured
+    let mut wake_gate =
+        wake::WakeWord::from_sensitivity(&config.wake_word.phrases(), config.wake_sensitivity);
+    if let Some(dir) = &config.wake.kws_templates {
+        match kws::KeywordSpotter::load_dir(std::path::Path::new(dir), config.wake.kws_threshold) {
+            Ok(spotter) => {
+                logger::info!(
+                    "Wake keyword spotting with {} templates from {}",
+                    spotter.template_count(),
+                    dir
+                );
+                wake_gate = wake_gate.with_spotter(spotter);
+            }
+            Err(e) => logger::warn!(
+                "Keyword spotting off, matching the wake word in transcripts: {}",
+                e
+            ),
+        }
+    }
+
     // Preview transcription thread
-    let parakeet_path_preview = parakeet_model_path.to_string_lossy().to_string();
+    let mut preview_models =
+        transcriber::LanguageModels::new(parakeet_model_path.clone(), language_model_paths.clone());
+    let min_confidence = config.transcriber.min_confidence;
+    // With keyword spotting, nothing is transcribed until the wake word fires
+    let spotting = wake_gate.has_spotter();
+    let runtime_state_preview = Arc::clone(&runtime_state);
     let preview_handle = thread::spawn(move || {
-        let mut transcriber = match transcriber::Transcriber::new(&parakeet_path_preview) {
-            Ok(t) => t,
-            Err(e) => {
-                eprintln!("Preview transcriber failed: {}", e);
-                return;
-            }
+        let load_preview = |path: &std::path::Path| {
+            let mut transcriber = transcriber::Transcriber::new(path)?;
+            let elapsed = transcriber.warmup();
+            logger::info!(
+                "Preview transcriber ({}) warmed up in {}ms",
+                path.display(),
+                elapsed.as_millis()
+            );
+            Ok::<_, Box<dyn std::error::Error + Send + Sync>>(transcriber)
         };
+        if let Err(e) =
+            preview_models.get(runtime_state_preview.language().as_deref(), load_preview)
+        {
+            logger::error!("Preview transcriber failed: {}", e);
+            return;
+        }
 
+        // Preview buffers are snapshots of the growing utterance; only feed the
+        // samples we haven't seen yet, and reset when a new utterance starts.
+        let mut consumed = 0usize;
+        let mut last_sample = 0.0f32;
+        let mut preview_text = String::new();
+        let mut active_language = runtime_state_preview.language();
         while let Ok(samples) = preview_rx.recv() {
-            if samples.len() >= 8000 {
-                if let Ok(text) = transcriber.transcribe(&samples) {
-                    if !text.is_empty() {
-                        let _ = display_tx.send(DisplayEvent::Preview(text));
+            if spotting && runtime_state_preview.wake_only() {
+                consumed = 0;
+                continue;
+            }
+            let language = runtime_state_preview.language();
+            if language != active_language {
+                active_language = language;
+                consumed = 0;
+            }
+            if let Err(e) = preview_models.get(active_language.as_deref(), load_preview) {
+                // Fall back to the default model, as the final thread does,
+                // rather than retrying the load on every buffer
+                logger::error!(
+                    "No preview transcriber for language {}: {}",
+                    active_language.as_deref().unwrap_or("default"),
+                    e
+                );
+                runtime_state_preview.set_language(None);
+                active_language = None;
+                let _ = display_tx.send(DisplayEvent::LanguageFallback);
+            }
+            let transcriber = match preview_models.get(active_language.as_deref(), load_preview) {
+                Ok(t) => t,
+                Err(_) => continue,
+            };
+            let same_utterance =
+                consumed > 0 && samples.len() >= consumed && samples[consumed - 1] == last_sample;
+            if !same_utterance {
+                transcriber.reset();
+                preview_text.clear();
+                consumed = 0;
+            }
+            let new_samples = &samples[consumed..];
+            consumed = samples.len();
+            last_sample = samples.last().copied().unwrap_or(0.0);
+
+            if let Ok((words, confidence)) = transcriber.transcribe_incremental(new_samples) {
+                // Drop likely noise (e.g. hypotheses over a mid-sentence pause)
+                if words.is_empty() || confidence < min_confidence {
+                    continue;
+                }
+                for word in words {
+                    if !preview_text.is_empty() {
+                        preview_text.push(' ');
                     }
+                    preview_text.push_str(&word.text);
                 }
+                let _ = display_tx.send(DisplayEvent::Preview(preview_text.clone()));
             }
         }
     });
 
+    // Session recording (--save-session)
+    let recorder = match &cli.save_session {
+        Some(dir) => Some(
+            recorder::SessionRecorder::new(dir)
+                .map_err(|e| format!("Can't record session to {}: {}", dir.display(), e))?
+                .shared(),
+        ),
+        None => None,
+    };
+    let recorder_final = recorder.clone();
+
     // Final transcription thread
-    let parakeet_path_final = parakeet_model_path.to_string_lossy().to_string();
+    let mut final_models =
+        transcriber::LanguageModels::new(parakeet_model_path, language_model_paths);
+    let silence_floor = config.transcriber.silence_floor;
+    let agc_target = config.audio.agc_target();
+    let runtime_state_final = Arc::clone(&runtime_state);
+    let mut preroll = wake::Preroll::new(config.wake.preroll_secs);
     let final_handle = thread::spawn(move || {
-        let mut transcriber = match transcriber::Transcriber::with_stats(
-            &parakeet_path_final,
-            Some(stats_transcribe),
-        ) {
-            Ok(t) => t,
-            Err(e) => {
-                eprintln!("Final transcriber failed: {}", e);
-                return;
-            }
+        let load_final = |path: &std::path::Path| {
+            let mut transcriber =
+                transcriber::Transcriber::with_stats(path, Some(Arc::clone(&stats_transcribe)))?
+                    .with_silence_floor(silence_floor)
+                    .with_agc(agc_target);
+            let elapsed = transcriber.warmup();
+            logger::info!(
+                "Transcriber ({}) warmed up in {}ms",
+                path.display(),
+                elapsed.as_millis()
+            );
+            Ok::<_, Box<dyn std::error::Error + Send + Sync>>(transcriber)
         };
+        if let Err(e) = final_models.get(runtime_state_final.language().as_deref(), load_final) {
+            logger::error!("Final transcriber failed: {}", e);
+            return;
+        }
 
         while let Ok(samples) = final_rx.recv() {
-            if let Ok(text) = transcriber.transcribe(&samples) {
+            // What the keyword spotter rejects is never transcribed, so it
+            // can't be part of the preroll either
+            if runtime_state_final.wake_only() && !wake_gate.detect_from_audio(&samples) {
+                continue;
+            }
+            let language = runtime_state_final.language();
+            if let Err(e) = final_models.get(language.as_deref(), load_final) {
+                // Fall back to the default model rather than going deaf
+                logger::error!(
+                    "No transcriber for language {}: {}",
+                    language.as_deref().unwrap_or("default"),
+                    e
+                );
+                runtime_state_final.set_language(None);
+                let _ = display_tx2.send(DisplayEvent::LanguageFallback);
+            }
+            let transcriber =
+                match final_models.get(runtime_state_final.language().as_deref(), load_final) {
+                    Ok(t) => t,
+                    Err(_) => continue,
+                };
+            if let Ok(mut text) = transcriber.transcribe_slice(&samples) {
                 if !text.is_empty() {
+                    // While only the wake word gets through, keep what's turned
+                    // away and join the last few seconds of it to the request
+                    if runtime_state_final.awaiting_wake() {
+                        let now = std::time::Instant::now();
+                        let len = std::time::Duration::from_secs_f32(
+                            samples.len() as f32 / TARGET_RATE as f32,
+                        );
+                        match wake_gate.detect(&text) {
+                            None => preroll.push(text.clone(), len, now),
+                            Some(m) => {
+                                let pre_text = preroll.take(now, len);
+                                if !pre_text.is_empty() {
+                                    logger::debug!("Pre-wake speech: {:?}", pre_text);
+                                    text = wake::join_preroll(&m, &pre_text);
+                                }
+                            }
+                        }
+                    }
+                    if let Some(ref recorder) = recorder_final {
+                        if let Err(e) = recorder.lock().unwrap().record_utterance(&samples, &text) {
+                            logger::warn!("Session recording failed: {}", e);
+                        }
+                    }
                     let _ = display_tx2.send(DisplayEvent::Final(text));
                 }
             }
@@ -543,7 +1043,7 @@ async fn async_main_with_cli(cli: Cli) -> Result<(), Box<dyn Error + Send + Sync
 
     // Initialize TTS (config already loaded above)
     let use_gpu_tts = config.acceleration.tts_gpu;
-    let tts_engine: tts::Tts = match config.tts {
+    let mut tts_engine: tts::Tts = match config.tts {
         #[cfg(feature = "kokoro")]
         TtsConfig::Kokoro {
             model,
@@ -552,17 +1052,17 @@ async fn async_main_with_cli(cli: Cli) -> Result<(), Box<dyn Error + Send + Sync
         } => {
             let model_resolved = resolve_tts_path(&model);
             let voices_resolved = resolve_tts_path(&voices);
-            eprintln!("TTS: Kokoro (speed: {})", speed);
+            logger::info!("TTS: Kokoro (speed: {})", speed);
             let engine = tts::KokoroEngine::new(&model_resolved, &voices_resolved, speed).await;
             tts::Tts::with_stats(Box::new(engine), stats_tts)
         }
         #[cfg(not(feature = "kokoro"))]
         TtsConfig::Kokoro { .. } => {
-            eprintln!("Warning: Kokoro not enabled. Build with --features kokoro");
+            logger::warn!("Warning: Kokoro not enabled. Build with --features kokoro");
             // Fallback to Supertonic if available
             #[cfg(feature = "supertonic")]
             {
-                eprintln!("Falling back to Supertonic TTS");
+                logger::warn!("Falling back to Supertonic TTS");
                 let onnx_path = model_manager::resolve_model_path("supertonic/onnx");
                 let voice_path =
                     model_manager::resolve_model_path("supertonic/voice_styles/M1.json");
@@ -570,17 +1070,18 @@ async fn async_main_with_cli(cli: Cli) -> Result<(), Box<dyn Error + Send + Sync
                     &onnx_path.to_string_lossy(),
                     &voice_path.to_string_lossy(),
                     1.1,
+                    state::DEFAULT_TTS_STEPS,
                     use_gpu_tts,
                 )
                 .unwrap_or_else(|e| {
-                    eprintln!("Failed to initialize Supertonic TTS: {}", e);
+                    logger::error!("Failed to initialize Supertonic TTS: {}", e);
                     panic!("No working TTS engine available");
                 });
                 tts::Tts::with_stats(Box::new(engine), stats_tts)
             }
             #[cfg(not(feature = "supertonic"))]
             {
-                panic!("Kokoro not enabled. Build with --features kokoro");
+                return Err("Kokoro not enabled. Build with --features kokoro".into());
             }
         }
         #[cfg(feature = "supertonic")]
@@ -588,25 +1089,37 @@ async fn async_main_with_cli(cli: Cli) -> Result<(), Box<dyn Error + Send + Sync
             onnx_dir,
             voice_style,
             speed,
+            steps,
         } => {
             let onnx_resolved = resolve_tts_path(&onnx_dir);
             let voice_resolved = resolve_tts_path(&voice_style);
-            eprintln!("TTS: Supertonic (speed: {}, GPU: {})", speed, use_gpu_tts);
-            let engine =
-                tts::SupertonicEngine::new(&onnx_resolved, &voice_resolved, speed, use_gpu_tts)
-                    .map_err(|e| {
-                        eprintln!("Failed to load Supertonic TTS: {}", e);
-                        "Supertonic TTS initialization failed"
-                    })?;
+            let steps = steps.clamp(state::MIN_TTS_STEPS, state::MAX_TTS_STEPS);
+            logger::info!(
+                "TTS: Supertonic (speed: {}, steps: {}, GPU: {})",
+                speed,
+                steps,
+                use_gpu_tts && supertonic::GPU_AVAILABLE
+            );
+            let engine = tts::SupertonicEngine::new(
+                &onnx_resolved,
+                &voice_resolved,
+                speed,
+                steps,
+                use_gpu_tts,
+            )
+            .map_err(|e| {
+                logger::error!("Failed to load Supertonic TTS: {}", e);
+                "Supertonic TTS initialization failed"
+            })?;
             tts::Tts::with_stats(Box::new(engine), stats_tts)
         }
         #[cfg(not(feature = "supertonic"))]
         TtsConfig::Supertonic { .. } => {
-            eprintln!("Warning: Supertonic not enabled. Build with --features supertonic");
+            logger::warn!("Warning: Supertonic not enabled. Build with --features supertonic");
             // Fallback to Kokoro if available
             #[cfg(feature = "kokoro")]
             {
-                eprintln!("Falling back to Kokoro TTS");
+                logger::warn!("Falling back to Kokoro TTS");
                 let model_path = model_manager::resolve_model_path("kokoro-v1.0.onnx");
                 let voices_path = model_manager::resolve_model_path("voices-v1.0.bin");
                 let engine = tts::KokoroEngine::new(
@@ -619,13 +1132,24 @@ async fn async_main_with_cli(cli: Cli) -> Result<(), Box<dyn Error + Send + Sync
             }
             #[cfg(not(feature = "kokoro"))]
             {
-                panic!("Supertonic not enabled. Build with --features supertonic");
+                return Err("Supertonic not enabled. Build with --features supertonic".into());
             }
         }
     };
+    match tts_engine.warmup() {
+        Ok(elapsed) => logger::info!("TTS warmed up in {}ms", elapsed.as_millis()),
+        Err(e) => logger::warn!("TTS warmup failed: {}", e),
+    }
 
     // Initialize LLM backend
-    let system_prompt = chat::system_prompt(&config.name);
+    let mut persona = config.chat.system_prompt.clone();
+    let system_prompt =
+        chat::resolve_persona(persona.as_deref(), &config.name).unwrap_or_else(|e| {
+            logger::warn!("Warning: {}, using the default persona", e);
+            persona = None;
+            chat::system_prompt(&config.name)
+        });
+    let llm_endpoint = config.llm.endpoint();
     let llm_backend: Box<dyn llm::LlmBackend> = match config.llm {
         #[cfg(feature = "llama-cpp")]
         LlmConfig::LlamaCpp {
@@ -655,15 +1179,17 @@ async fn async_main_with_cli(cli: Cli) -> Result<(), Box<dyn Error + Send + Sync
         }
         #[cfg(not(feature = "llama-cpp"))]
         LlmConfig::LlamaCpp { .. } => {
-            panic!("llama-cpp not enabled. Build with --features llama-cpp");
+            return Err("llama-cpp not enabled. Build with --features llama-cpp".into());
         }
         #[cfg(feature = "ollama")]
-        LlmConfig::Ollama { model } => {
-            Box::new(llm::ollama::OllamaBackend::new(&model, &system_prompt))
+        LlmConfig::Ollama { model, host } => {
+            let backend = llm::ollama::OllamaBackend::new(&model, &host, &system_prompt)?;
+            backend.check_connection().await?;
+            Box::new(backend)
         }
         #[cfg(not(feature = "ollama"))]
         LlmConfig::Ollama { .. } => {
-            panic!("Ollama not enabled. Build with --features ollama");
+            return Err("Ollama not enabled. Build with --features ollama".into());
         }
         #[cfg(feature = "openai-compat")]
         LlmConfig::OpenAiCompat {
@@ -676,19 +1202,26 @@ async fn async_main_with_cli(cli: Cli) -> Result<(), Box<dyn Error + Send + Sync
             presence_penalty,
             frequency_penalty,
             ..
-        } => Box::new(llm::openai_compat::OpenAiCompatBackend::new(
-            base_url.clone(),
-            model.clone(),
-            api_key.clone(),
-            temperature,
-            top_p,
-            max_tokens,
-            presence_penalty,
-            frequency_penalty,
-        )?),
+        } => {
+            let mut backend: Box<dyn llm::LlmBackend> =
+                Box::new(llm::openai_compat::OpenAiCompatBackend::new(
+                    base_url.clone(),
+                    model.clone(),
+                    api_key.clone(),
+                    temperature,
+                    top_p,
+                    max_tokens,
+                    presence_penalty,
+                    frequency_penalty,
+                )?);
+            backend.set_system_prompt(&system_prompt);
+            backend
+        }
         #[cfg(not(feature = "openai-compat"))]
         LlmConfig::OpenAiCompat { .. } => {
-            panic!("OpenAI-compatible backend not enabled. Build with --features openai-compat");
+            return Err(
+                "OpenAI-compatible backend not enabled. Build with --features openai-compat".into(),
+            );
         }
         #[cfg(feature = "kalosm")]
         LlmConfig::Kalosm { ref model } => {
@@ -708,12 +1241,20 @@ async fn async_main_with_cli(cli: Cli) -> Result<(), Box<dyn Error + Send + Sync
         }
         #[cfg(not(feature = "kalosm"))]
         LlmConfig::Kalosm { .. } => {
-            panic!("Kalosm not enabled. Build with --features kalosm");
+            return Err("Kalosm not enabled. Build with --features kalosm".into());
         }
     };
 
-    let llm_chat = chat::Chat::new(llm_backend);
-    let wake_word = wake::WakeWord::new(&config.wake_word);
+    let llm_chat = chat::Chat::new(llm_backend)
+        .with_context_budget(config.chat.max_context_words, config.chat.trim_strategy)
+        .with_max_retries(config.chat.max_retries);
+    let persist_path = if config.chat.persist {
+        chat::session_path(chat::LAST_SESSION)
+    } else {
+        None
+    };
+    let wake_word =
+        wake::WakeWord::from_sensitivity(&config.wake_word.phrases(), config.wake_sensitivity);
 
     // Session manager channels
     let (session_tx, session_rx) =
@@ -730,7 +1271,12 @@ async fn async_main_with_cli(cli: Cli) -> Result<(), Box<dyn Error + Send + Sync
         session_event_tx,
     )
     .with_aec_tx(aec_render_tx)
-    .with_stats(stats_session);
+    .with_stats(stats_session)
+    .with_persist_path(persist_path.clone())
+    .with_notes(config.notes.clone())
+    .with_output_device(config.audio.output_device.clone())
+    .with_wake_ack(config.wake.ack, &config.wake.ack_phrase)
+    .with_slow_response(config.chat.slow_threshold_secs, config.chat.slow_tick);
 
     #[cfg(not(feature = "aec"))]
     let session_mgr = session::SessionManager::new(
@@ -739,22 +1285,35 @@ async fn async_main_with_cli(cli: Cli) -> Result<(), Box<dyn Error + Send + Sync
         Arc::clone(&runtime_state),
         session_event_tx,
     )
-    .with_stats(stats_session);
+    .with_stats(stats_session)
+    .with_persist_path(persist_path.clone())
+    .with_notes(config.notes.clone())
+    .with_output_device(config.audio.output_device.clone())
+    .with_wake_ack(config.wake.ack, &config.wake.ack_phrase)
+    .with_slow_response(config.chat.slow_threshold_secs, config.chat.slow_tick);
 
     // Spawn session manager on dedicated thread (LLM inference is blocking)
-    let _session_handle = std::thread::spawn(move || {
+    let session_handle = std::thread::spawn(move || {
         session_mgr.run_sync(session_rx);
     });
 
     let (ui, ui_rx) = Ui::new();
 
     // Determine UI mode from CLI flags or config
-    let ui_mode = if cli.text {
-        UiModeConfig::Text
+    let ui_mode = if cli.events_json {
+        UiMode::Json
+    } else if cli.text {
+        UiMode::Text
     } else if cli.orb {
-        UiModeConfig::Orb
+        UiMode::Orb
+    } else if cli.waveform {
+        UiMode::Waveform
     } else {
-        config.ui.mode
+        match config.ui.mode {
+            UiModeConfig::Text => UiMode::Text,
+            UiModeConfig::Orb => UiMode::Orb,
+            UiModeConfig::Waveform => UiMode::Waveform,
+        }
     };
 
     // Determine orb style
@@ -762,25 +1321,51 @@ async fn async_main_with_cli(cli: Cli) -> Result<(), Box<dyn Error + Send + Sync
         Some("ring") => OrbStyle::Ring,
         Some("blob") => OrbStyle::Blob,
         Some("orbs") => OrbStyle::Orbs,
-        _ => match config.ui.orb_style {
-            OrbStyleConfig::Ring => OrbStyle::Ring,
-            OrbStyleConfig::Blob => OrbStyle::Blob,
-            OrbStyleConfig::Orbs => OrbStyle::Orbs,
+        _ => match config
+            .ui
+            .default_style
+            .as_deref()
+            .and_then(OrbStyle::from_name)
+        {
+            // Last-used style, saved when cycling styles
+            Some(style) => style,
+            None => match config.ui.orb_style {
+                OrbStyleConfig::Ring => OrbStyle::Ring,
+                OrbStyleConfig::Blob => OrbStyle::Blob,
+                OrbStyleConfig::Orbs => OrbStyle::Orbs,
+            },
         },
     };
 
+    runtime_state.set_orb_style(orb_style);
+    if config.ui.low_power {
+        runtime_state.set_orb_style(OrbStyle::Minimal);
+    }
+    let orb_style = runtime_state.orb_style();
+
+    // Keep log lines from drawing over fullscreen UIs
+    if ui_mode != UiMode::Json {
+        logger::quiet_terminal();
+    }
+
     // Initialize UI based on mode
-    let mut ui_renderer: Box<dyn UiRenderer> = match ui_mode {
-        UiModeConfig::Text => Box::new(tui::Tui::new()?),
-        UiModeConfig::Orb => {
-            let mut gui = graphical_ui::GraphicalUi::new()?;
-            gui.set_visual_style(orb_style);
-            Box::new(gui)
-        }
-    };
+    let mut ui_renderer = create_ui_renderer(ui_mode, orb_style)?;
+    ui_renderer.set_mic_muted(runtime_state.mic_muted.load(Ordering::SeqCst));
+    ui_renderer.set_tts_enabled(runtime_state.tts_enabled.load(Ordering::SeqCst));
+    ui_renderer.set_wake_enabled(runtime_state.wake_enabled.load(Ordering::SeqCst));
+    ui_renderer.set_language(runtime_state.language().as_deref());
+    if runtime_state.mic_muted.load(Ordering::SeqCst) && !cli.no_stt {
+        ui_renderer.show_message(if runtime_state.is_deaf() {
+            "Mic muted, /mute to unmute"
+        } else {
+            "Mic muted, say the wake word to unmute"
+        });
+    }
     ui_renderer.draw()?;
 
     let mut last_interaction: Option<std::time::Instant> = None;
+    // Streamed response text for the session recording
+    let mut recorded_response = String::new();
     let wake_timeout = std::time::Duration::from_secs(config.wake_timeout_secs);
 
     let auto_submit_delay = std::time::Duration::from_millis(2000);
@@ -795,10 +1380,19 @@ async fn async_main_with_cli(cli: Cli) -> Result<(), Box<dyn Error + Send + Sync
             config.typing.undo_buffer_size,
             config.typing.feedback,
             config.typing.command_pause_ms,
+            typing::KeyPacing {
+                delay_ms: config.typing.keystroke_delay_ms,
+                adaptive: config.typing.adaptive_delay,
+            },
         ) {
-            Ok(proc) => Some(proc),
+            Ok(proc) => Some(
+                proc.with_custom_punctuation(config.typing.punctuation.clone())
+                    .with_literal_prefixes(config.typing.literal_prefixes.clone())
+                    .with_auto_pair(config.typing.auto_pair)
+                    .with_restore_clipboard(config.typing.restore_clipboard),
+            ),
             Err(e) => {
-                eprintln!("Warning: Failed to initialize typing processor: {}", e);
+                logger::warn!("Warning: Failed to initialize typing processor: {}", e);
                 None
             }
         }
@@ -810,6 +1404,14 @@ async fn async_main_with_cli(cli: Cli) -> Result<(), Box<dyn Error + Send + Sync
     let keypress_mute_duration = std::time::Duration::from_secs(1);
     let mut keypress_mute_until: Option<std::time::Instant> = None;
 
+    // Pause after this long without speech or typing (0 = never)
+    let auto_pause_secs = config.interaction.auto_pause_secs;
+
+    // Restore the last conversation before greeting
+    if let Some(path) = persist_path.filter(|p| p.exists()) {
+        let _ = session_tx.send(session::SessionCommand::LoadHistory(path));
+    }
+
     // Initial greeting
     let _ = session_tx.send(session::SessionCommand::Greet);
 
@@ -817,58 +1419,52 @@ async fn async_main_with_cli(cli: Cli) -> Result<(), Box<dyn Error + Send + Sync
     let (async_ui_tx, mut async_ui_rx) = tokio::sync::mpsc::unbounded_channel();
     let ui_rx_bridge = std::thread::spawn(move || {
         while let Ok(event) = ui_rx.recv() {
-            debug_log(&format!("UI bridge received event: {:?}", event));
+            logger::debug!("UI bridge received event: {:?}", event);
             if async_ui_tx.send(event).is_err() {
-                debug_log("Failed to send event through async channel");
+                logger::debug!("Failed to send event through async channel");
                 break;
             }
-            debug_log("Event sent through async channel");
+            logger::debug!("Event sent through async channel");
         }
-        debug_log("UI bridge thread exiting");
+        logger::debug!("UI bridge thread exiting");
     });
 
+    // Redraw on a fixed schedule, independent of how often other events arrive
+    let frame_interval = graphical_ui::frame_interval(config.ui.target_fps);
+    let mut next_frame = tokio::time::Instant::now() + frame_interval;
+
     loop {
         tokio::select! {
             // UI events from Ui sender
             Some(event) = async_ui_rx.recv() => {
                 // Check for UI mode switching events
                 if let UiEvent::SwitchUiMode(new_mode) = &event {
-                    debug_log(&format!("Received SwitchUiMode event: {:?}", new_mode));
+                    logger::debug!("Received SwitchUiMode event: {:?}", new_mode);
                     let current_mode = ui_renderer.ui_mode();
-                    debug_log(&format!("Current UI mode: {:?}", current_mode));
-                    if *new_mode != current_mode {
-                        debug_log(&format!("Switching UI mode from {:?} to {:?}", current_mode, new_mode));
+                    logger::debug!("Current UI mode: {:?}", current_mode);
+                    // The JSON UI has no terminal to switch to
+                    if *new_mode != current_mode && current_mode != UiMode::Json {
+                        logger::debug!("Switching UI mode from {:?} to {:?}", current_mode, new_mode);
                         // Restore terminal state from old UI
                         ui_renderer.restore()?;
 
                         // Create new UI renderer
-                        ui_renderer = match *new_mode {
-                            UiMode::Text => {
-                                debug_log("Creating new text UI");
-                                let new_tui = Box::new(tui::Tui::new()?);
-                                debug_log("Text UI created successfully");
-                                new_tui
-                            }
-                            UiMode::Orb => {
-                                debug_log("Creating new orb UI");
-                                let mut gui = graphical_ui::GraphicalUi::new()?;
-                                gui.set_visual_style(orb_style);
-                                debug_log("Orb UI created successfully");
-                                Box::new(gui)
-                            }
-                        };
+                        logger::debug!("Creating new {:?} UI", new_mode);
+                        ui_renderer = create_ui_renderer(*new_mode, runtime_state.orb_style())?;
+                        logger::debug!("UI created successfully");
 
                         // Sync state with new UI
                         ui_renderer.set_mic_muted(runtime_state.mic_muted.load(Ordering::SeqCst));
                         ui_renderer.set_tts_enabled(runtime_state.tts_enabled.load(Ordering::SeqCst));
                         ui_renderer.set_wake_enabled(runtime_state.wake_enabled.load(Ordering::SeqCst));
+                        ui_renderer.set_language(runtime_state.language().as_deref());
                         ui_renderer.set_mode(runtime_state.mode());
 
                         // Force an immediate draw to ensure UI is visible
                         ui_renderer.draw()?;
-                        debug_log("UI switch completed");
+                        logger::debug!("UI switch completed");
                     } else {
-                        debug_log("UI mode already matches, no switch needed");
+                        logger::debug!("UI mode already matches, no switch needed");
                     }
                 } else {
                     ui_renderer.handle_ui_event(event)?;
@@ -883,10 +1479,14 @@ async fn async_main_with_cli(cli: Cli) -> Result<(), Box<dyn Error + Send + Sync
                     }
                     session::SessionEvent::Chunk(text) => {
                         ui.append_response(&text);
+                        if recorder.is_some() {
+                            recorded_response.push_str(&text);
+                        }
                     }
                     session::SessionEvent::ResponseEnd { response_words } => {
                         ui.end_response();
                         ui_renderer.set_last_response_words(response_words);
+                        record_response(&recorder, &mut recorded_response);
                     }
                     session::SessionEvent::Speaking => {
                         ui.set_speaking();
@@ -898,9 +1498,23 @@ async fn async_main_with_cli(cli: Cli) -> Result<(), Box<dyn Error + Send + Sync
                     session::SessionEvent::ContextWords(words) => {
                         ui.set_context_words(words);
                     }
-                    session::SessionEvent::Ready => {
+                    session::SessionEvent::Cancelled => {
+                        ui.end_response();
+                        ui.set_idle();
+                        record_response(&recorder, &mut recorded_response);
+                    }
+                    session::SessionEvent::Retrying { attempt, max } => {
+                        ui_renderer.show_message(&format!("Retrying… ({}/{})", attempt, max));
+                    }
+                    session::SessionEvent::Ready | session::SessionEvent::WakeAcknowledged => {
                         ui_renderer.set_ready();
                     }
+                    session::SessionEvent::SlowResponse => {
+                        ui_renderer.show_message("Still thinking… say stop to cancel");
+                    }
+                    session::SessionEvent::Message(msg) => {
+                        ui_renderer.show_message(&msg);
+                    }
                     session::SessionEvent::Error(e) => {
                         ui.show_error(&e);
                         ui.set_idle();
@@ -915,12 +1529,20 @@ async fn async_main_with_cli(cli: Cli) -> Result<(), Box<dyn Error + Send + Sync
             // Audio transcription events - mode-aware handling
             Some(event) = async_display_rx.recv() => {
                 match event {
-                    DisplayEvent::AudioLevel(level) => {
-                        ui_renderer.set_audio_level(level);
+                    DisplayEvent::AudioLevel { rms, clipping } => {
+                        ui_renderer.set_audio_level(rms);
+                        ui_renderer.set_clipping(clipping);
+                    }
+                    DisplayEvent::AudioBands(bands) => {
+                        ui_renderer.set_audio_bands(bands);
                     }
                     DisplayEvent::TtsLevel(level) => {
                         ui_renderer.set_tts_level(level);
                     }
+                    DisplayEvent::LanguageFallback => {
+                        ui_renderer.set_language(runtime_state.language().as_deref());
+                        ui_renderer.show_message("Language model failed to load, using the default model");
+                    }
                     DisplayEvent::Preview(text) => {
                         // Use mode-aware transcript handling
                         let _result = repl::handle_transcript_with_mode(
@@ -930,6 +1552,7 @@ async fn async_main_with_cli(cli: Cli) -> Result<(), Box<dyn Error + Send + Sync
                             wake_timeout,
                             &runtime_state,
                             &command_processor,
+                            &normalizer,
                             &ui,
                         );
                         // Preview events mean user is still speaking - cancel auto-submit timer
@@ -938,6 +1561,9 @@ async fn async_main_with_cli(cli: Cli) -> Result<(), Box<dyn Error + Send + Sync
                         auto_submit_deadline = None;
                     }
                     DisplayEvent::Final(text) => {
+                        runtime_state.update_last_interaction();
+                        let was_muted = runtime_state.mic_muted.load(Ordering::SeqCst);
+                        let was_paused = runtime_state.mode() == state::AppMode::Paused;
                         // Use mode-aware transcript handling
                         let result = repl::handle_transcript_with_mode(
                             TranscriptEvent::Final(text),
@@ -946,8 +1572,25 @@ async fn async_main_with_cli(cli: Cli) -> Result<(), Box<dyn Error + Send + Sync
                             wake_timeout,
                             &runtime_state,
                             &command_processor,
+                            &normalizer,
                             &ui,
                         );
+                        // The wake word unmutes with interaction.unmute_on_wake
+                        let unmuted = was_muted && !runtime_state.mic_muted.load(Ordering::SeqCst);
+                        if unmuted {
+                            mic_muted.store(false, Ordering::SeqCst);
+                            ui_renderer.set_mic_muted(false);
+                        }
+                        // Let the user know the wake word was heard (wake.ack)
+                        let resumed = was_paused
+                            && matches!(
+                                result,
+                                TranscriptResult::ModeChange { mode: state::AppMode::Chat, .. }
+                            );
+                        if unmuted || resumed {
+                            ui.wake_detected();
+                            let _ = session_tx.send(session::SessionCommand::AcknowledgeWake);
+                        }
 
                         match result {
                             TranscriptResult::SendToLlm(input_text) => {
@@ -962,12 +1605,8 @@ async fn async_main_with_cli(cli: Cli) -> Result<(), Box<dyn Error + Send + Sync
                                 ui_renderer.show_message(&format!("[Transcribed] {}", text));
                             }
                             TranscriptResult::AppendNote(text) => {
-                                // Note-taking mode: append to notes file
-                                if let Err(e) = repl::append_to_notes(&text) {
-                                    ui_renderer.show_message(&format!("Failed to save note: {}", e));
-                                } else {
-                                    ui_renderer.show_message(&format!("[Note saved] {}", text));
-                                }
+                                // Note-taking mode: the session appends it to notes.file
+                                let _ = session_tx.send(session::SessionCommand::AppendNote(text));
                             }
                             TranscriptResult::TypeText(text) => {
                                 // Typing mode: type into active application
@@ -1016,19 +1655,43 @@ async fn async_main_with_cli(cli: Cli) -> Result<(), Box<dyn Error + Send + Sync
                                 ui_renderer.set_mic_muted(runtime_state.mic_muted.load(Ordering::SeqCst));
                                 ui_renderer.set_tts_enabled(runtime_state.tts_enabled.load(Ordering::SeqCst));
                                 ui_renderer.set_wake_enabled(runtime_state.wake_enabled.load(Ordering::SeqCst));
+                                ui_renderer.set_language(runtime_state.language().as_deref());
+                            }
+                            TranscriptResult::Announce(msg) => {
+                                ui_renderer.show_message(&msg);
+                                let _ = session_tx.send(session::SessionCommand::Announce(msg));
                             }
                             TranscriptResult::Stop => {
+                                // The session thread is busy streaming, so flag the stop directly
+                                runtime_state.request_cancel();
                                 let _ = session_tx.send(session::SessionCommand::Cancel);
                             }
                             TranscriptResult::Shutdown => {
                                 break;
                             }
                             TranscriptResult::ModeChange { mode, announcement } => {
-                                runtime_state.set_mode(mode);
-                                ui_renderer.set_mode(mode);
-                                if let Some(msg) = announcement {
-                                    ui_renderer.show_message(&msg);
-                                }
+                                change_mode(mode, announcement, &runtime_state, ui_renderer.as_mut(), &session_tx);
+                            }
+                            TranscriptResult::SetVisualStyle(style) => {
+                                apply_visual_style(ui_renderer.as_mut(), style);
+                            }
+                            TranscriptResult::Replay => {
+                                let _ = session_tx.send(session::SessionCommand::Replay);
+                            }
+                            TranscriptResult::SetModel(name) => {
+                                let _ = session_tx.send(session::SessionCommand::SetModel(name));
+                            }
+                            TranscriptResult::Voice(name) => {
+                                let _ = session_tx.send(session::SessionCommand::Voice(name));
+                            }
+                            TranscriptResult::Export(path) => {
+                                let _ = session_tx.send(session::SessionCommand::Export(path));
+                            }
+                            TranscriptResult::SaveClip(path) => {
+                                let _ = session_tx.send(session::SessionCommand::SaveClip(path));
+                            }
+                            TranscriptResult::LoadPersona(file) => {
+                                reload_persona(&mut persona, file, &config.name, &session_tx, ui_renderer.as_mut());
                             }
                             TranscriptResult::None => {
                                 // No action needed
@@ -1038,20 +1701,33 @@ async fn async_main_with_cli(cli: Cli) -> Result<(), Box<dyn Error + Send + Sync
                 }
             }
             // Periodic: keyboard input, deadline check, redraw
-            _ = tokio::time::sleep(std::time::Duration::from_millis(16)) => {
+            _ = tokio::time::sleep_until(next_frame) => {
+                // Termination signal: leave through the normal cleanup below
+                if should_exit(&running, None) {
+                    break;
+                }
+
+                // Skip frames we fell behind on rather than bursting to catch up
+                next_frame += frame_interval;
+                let now = tokio::time::Instant::now();
+                if next_frame < now {
+                    next_frame = now + frame_interval;
+                }
+
                 // Poll keyboard input - drain all available events before redrawing
                 let mut should_break = false;
                 loop {
                     match ui_renderer.poll_input()? {
                         None => {
-                            debug_log("Main: No keyboard input available");
+                            logger::debug!("Main: No keyboard input available");
                             break;
                         }
                         Some(line) => {
-                            debug_log(&format!("Main: Keyboard input received: {}", line));
+                            logger::debug!("Main: Keyboard input received: {}", line);
+                            runtime_state.update_last_interaction();
 
                             // Handle Ctrl+C
-                            if line == "\x03" {
+                            if should_exit(&running, Some(&line)) {
                                 should_break = true;
                                 break;
                             }
@@ -1060,29 +1736,35 @@ async fn async_main_with_cli(cli: Cli) -> Result<(), Box<dyn Error + Send + Sync
                             if let Some(cmd_result) = command::process_slash_command(&line, &runtime_state) {
                                 match cmd_result {
                                     CommandResult::Handled(Some(msg)) => {
-                                        debug_log(&format!("Command result: {}", msg));
+                                        logger::debug!("Command result: {}", msg);
                                         // Check for UI switching commands
                                         if msg.starts_with("ui_switch:") {
                                             let new_mode = &msg[10..];
-                                            debug_log(&format!("UI switch requested to: {}", new_mode));
+                                            logger::debug!("UI switch requested to: {}", new_mode);
                                             match new_mode {
                                                 "text" => {
-                                                    debug_log("Requesting switch to text UI");
+                                                    logger::debug!("Requesting switch to text UI");
                                                     ui.request_ui_mode_switch(UiMode::Text);
                                                     ui_renderer.show_message("Switching to text UI...");
                                                 }
                                                 "orb" => {
-                                                    debug_log("Requesting switch to orb UI");
+                                                    logger::debug!("Requesting switch to orb UI");
                                                     ui.request_ui_mode_switch(UiMode::Orb);
                                                     ui_renderer.show_message("Switching to orb UI...");
                                                 }
+                                                "waveform" => {
+                                                    logger::debug!("Requesting switch to waveform UI");
+                                                    ui.request_ui_mode_switch(UiMode::Waveform);
+                                                    ui_renderer.show_message("Switching to waveform UI...");
+                                                }
                                                 "toggle" => {
                                                     let current = ui_renderer.ui_mode();
                                                     let new = match current {
                                                         UiMode::Text => UiMode::Orb,
-                                                        UiMode::Orb => UiMode::Text,
+                                                        UiMode::Orb | UiMode::Waveform => UiMode::Text,
+                                                        UiMode::Json => UiMode::Json,
                                                     };
-                                                    debug_log(&format!("Toggling UI from {:?} to {:?}", current, new));
+                                                    logger::debug!("Toggling UI from {:?} to {:?}", current, new);
                                                     ui.request_ui_mode_switch(new);
                                                 }
                                                 _ => {
@@ -1094,7 +1776,13 @@ async fn async_main_with_cli(cli: Cli) -> Result<(), Box<dyn Error + Send + Sync
                                         }
                                     }
                                     CommandResult::Handled(None) => {}
+                                    CommandResult::Announce(msg) => {
+                                        ui_renderer.show_message(&msg);
+                                        let _ = session_tx.send(session::SessionCommand::Announce(msg));
+                                    }
                                     CommandResult::Stop => {
+                                        // The session thread is busy streaming, so flag the stop directly
+                                        runtime_state.request_cancel();
                                         let _ = session_tx.send(session::SessionCommand::Cancel);
                                     }
                                     CommandResult::Shutdown => {
@@ -1102,11 +1790,34 @@ async fn async_main_with_cli(cli: Cli) -> Result<(), Box<dyn Error + Send + Sync
                                         break;
                                     }
                                     CommandResult::ModeChange { mode, announcement } => {
-                                        runtime_state.set_mode(mode);
-                                        ui_renderer.set_mode(mode);
-                                        if let Some(msg) = announcement {
-                                            ui_renderer.show_message(&msg);
-                                        }
+                                        change_mode(mode, announcement, &runtime_state, ui_renderer.as_mut(), &session_tx);
+                                    }
+                                    CommandResult::SetVisualStyle(style) => {
+                                        apply_visual_style(ui_renderer.as_mut(), style);
+                                    }
+                                    CommandResult::Replay => {
+                                        let _ = session_tx.send(session::SessionCommand::Replay);
+                                    }
+                                    CommandResult::SaveHistory(path) => {
+                                        let _ = session_tx.send(session::SessionCommand::SaveHistory(path));
+                                    }
+                                    CommandResult::LoadHistory(path) => {
+                                        let _ = session_tx.send(session::SessionCommand::LoadHistory(path));
+                                    }
+                                    CommandResult::Export { path } => {
+                                        let _ = session_tx.send(session::SessionCommand::Export(path));
+                                    }
+                                    CommandResult::SaveClip { path } => {
+                                        let _ = session_tx.send(session::SessionCommand::SaveClip(path));
+                                    }
+                                    CommandResult::SetModel(name) => {
+                                        let _ = session_tx.send(session::SessionCommand::SetModel(name));
+                                    }
+                                    CommandResult::Voice(name) => {
+                                        let _ = session_tx.send(session::SessionCommand::Voice(name));
+                                    }
+                                    CommandResult::LoadPersona(file) => {
+                                        reload_persona(&mut persona, file, &config.name, &session_tx, ui_renderer.as_mut());
                                     }
                                     CommandResult::PassThrough(_) => {}
                                 }
@@ -1117,6 +1828,7 @@ async fn async_main_with_cli(cli: Cli) -> Result<(), Box<dyn Error + Send + Sync
                                 ui_renderer.set_mic_muted(runtime_state.mic_muted.load(Ordering::SeqCst));
                                 ui_renderer.set_tts_enabled(runtime_state.tts_enabled.load(Ordering::SeqCst));
                                 ui_renderer.set_wake_enabled(runtime_state.wake_enabled.load(Ordering::SeqCst));
+                                ui_renderer.set_language(runtime_state.language().as_deref());
                                 keypress_mute_until = None;
                                 continue;
                             }
@@ -1128,10 +1840,17 @@ async fn async_main_with_cli(cli: Cli) -> Result<(), Box<dyn Error + Send + Sync
                                 continue;
                             }
 
+                            if line == "/endpoint" {
+                                ui_renderer.show_message(&llm_endpoint);
+                                continue;
+                            }
+
                             // Process through command processor for voice-style commands
                             let cmd_result = command_processor.process(&line, &runtime_state);
                             match cmd_result {
                                 CommandResult::Stop => {
+                                    // The session thread is busy streaming, so flag the stop directly
+                                    runtime_state.request_cancel();
                                     let _ = session_tx.send(session::SessionCommand::Cancel);
                                     continue;
                                 }
@@ -1154,12 +1873,45 @@ async fn async_main_with_cli(cli: Cli) -> Result<(), Box<dyn Error + Send + Sync
                                     wake_enabled.store(runtime_state.wake_enabled.load(Ordering::SeqCst), Ordering::SeqCst);
                                     continue;
                                 }
+                                CommandResult::Announce(msg) => {
+                                    ui_renderer.show_message(&msg);
+                                    let _ = session_tx.send(session::SessionCommand::Announce(msg));
+                                    continue;
+                                }
                                 CommandResult::ModeChange { mode, announcement } => {
-                                    runtime_state.set_mode(mode);
-                                    ui_renderer.set_mode(mode);
-                                    if let Some(msg) = announcement {
-                                        ui_renderer.show_message(&msg);
-                                    }
+                                    change_mode(mode, announcement, &runtime_state, ui_renderer.as_mut(), &session_tx);
+                                    continue;
+                                }
+                                CommandResult::SetVisualStyle(style) => {
+                                    apply_visual_style(ui_renderer.as_mut(), style);
+                                    continue;
+                                }
+                                CommandResult::Replay => {
+                                    let _ = session_tx.send(session::SessionCommand::Replay);
+                                    continue;
+                                }
+                                CommandResult::SetModel(name) => {
+                                    let _ = session_tx.send(session::SessionCommand::SetModel(name));
+                                    continue;
+                                }
+                                CommandResult::Voice(name) => {
+                                    let _ = session_tx.send(session::SessionCommand::Voice(name));
+                                    continue;
+                                }
+                                CommandResult::Export { path } => {
+                                    let _ = session_tx.send(session::SessionCommand::Export(path));
+                                    continue;
+                                }
+                                CommandResult::SaveClip { path } => {
+                                    let _ = session_tx.send(session::SessionCommand::SaveClip(path));
+                                    continue;
+                                }
+                                CommandResult::LoadPersona(file) => {
+                                    reload_persona(&mut persona, file, &config.name, &session_tx, ui_renderer.as_mut());
+                                    continue;
+                                }
+                                // Only produced by slash commands, handled above
+                                CommandResult::SaveHistory(_) | CommandResult::LoadHistory(_) => {
                                     continue;
                                 }
                                 CommandResult::PassThrough(text) => {
@@ -1183,42 +1935,32 @@ async fn async_main_with_cli(cli: Cli) -> Result<(), Box<dyn Error + Send + Sync
                 while let Ok(ui_event) = async_ui_rx.try_recv() {
                     // Check for UI mode switching events in the periodic branch too
                     if let UiEvent::SwitchUiMode(new_mode) = &ui_event {
-                        debug_log(&format!("Received SwitchUiMode event in periodic branch: {:?}", new_mode));
+                        logger::debug!("Received SwitchUiMode event in periodic branch: {:?}", new_mode);
                         let current_mode = ui_renderer.ui_mode();
-                        debug_log(&format!("Current UI mode: {:?}", current_mode));
-                        if *new_mode != current_mode {
-                            debug_log(&format!("Switching UI mode from {:?} to {:?}", current_mode, new_mode));
+                        logger::debug!("Current UI mode: {:?}", current_mode);
+                        // The JSON UI has no terminal to switch to
+                        if *new_mode != current_mode && current_mode != UiMode::Json {
+                            logger::debug!("Switching UI mode from {:?} to {:?}", current_mode, new_mode);
                             // Restore terminal state from old UI
                             ui_renderer.restore()?;
 
                             // Create new UI renderer
-                            ui_renderer = match *new_mode {
-                                UiMode::Text => {
-                                    debug_log("Creating new text UI");
-                                    let new_tui = Box::new(tui::Tui::new()?);
-                                    debug_log("Text UI created successfully");
-                                    new_tui
-                                }
-                                UiMode::Orb => {
-                                    debug_log("Creating new orb UI");
-                                    let mut gui = graphical_ui::GraphicalUi::new()?;
-                                    gui.set_visual_style(orb_style);
-                                    debug_log("Orb UI created successfully");
-                                    Box::new(gui)
-                                }
-                            };
+                            logger::debug!("Creating new {:?} UI", new_mode);
+                            ui_renderer = create_ui_renderer(*new_mode, runtime_state.orb_style())?;
+                            logger::debug!("UI created successfully");
 
                             // Sync state with new UI
                             ui_renderer.set_mic_muted(runtime_state.mic_muted.load(Ordering::SeqCst));
                             ui_renderer.set_tts_enabled(runtime_state.tts_enabled.load(Ordering::SeqCst));
                             ui_renderer.set_wake_enabled(runtime_state.wake_enabled.load(Ordering::SeqCst));
+                            ui_renderer.set_language(runtime_state.language().as_deref());
                             ui_renderer.set_mode(runtime_state.mode());
 
                             // Force an immediate draw to ensure UI is visible
                             ui_renderer.draw()?;
-                            debug_log("UI switch completed in periodic branch");
+                            logger::debug!("UI switch completed in periodic branch");
                         } else {
-                            debug_log("UI mode already matches, no switch needed");
+                            logger::debug!("UI mode already matches, no switch needed");
                         }
                     } else {
                         ui_renderer.handle_ui_event(ui_event)?;
@@ -1227,6 +1969,7 @@ async fn async_main_with_cli(cli: Cli) -> Result<(), Box<dyn Error + Send + Sync
 
                 // Temporarily mute mic on any keypress
                 if ui_renderer.has_keypress_activity() {
+                    runtime_state.update_last_interaction();
                     mic_muted.store(true, Ordering::SeqCst);
                     runtime_state.mic_muted.store(true, Ordering::SeqCst);
                     keypress_mute_until = Some(std::time::Instant::now() + keypress_mute_duration);
@@ -1244,9 +1987,29 @@ async fn async_main_with_cli(cli: Cli) -> Result<(), Box<dyn Error + Send + Sync
                 // Cancel auto-submit timer on keyboard input only
                 // (Voice input timer is managed by Final event handler)
                 if ui_renderer.has_input_activity() {
+                    runtime_state.update_last_interaction();
                     auto_submit_deadline = None;
                 }
 
+                // Auto-pause when nobody has spoken or typed for a while
+                if auto_pause_secs > 0 && runtime_state.mode() != state::AppMode::Paused {
+                    if runtime_state.tts_playing.load(Ordering::SeqCst)
+                        || runtime_state.llm_generating.load(Ordering::SeqCst)
+                    {
+                        // Idle time starts once the response is done
+                        runtime_state.update_last_interaction();
+                    } else if runtime_state.idle_secs() >= auto_pause_secs {
+                        change_mode(
+                            state::AppMode::Paused,
+                            Some("Going idle - say the wake word to resume".to_string()),
+                            &runtime_state,
+                            ui_renderer.as_mut(),
+                            &session_tx,
+                        );
+                        let _ = session_tx.send(session::SessionCommand::Announce("Going idle.".to_string()));
+                    }
+                }
+
                 // Update auto-submit progress bar
                 if let Some(deadline) = auto_submit_deadline {
                     let now = tokio::time::Instant::now();
@@ -1285,22 +2048,56 @@ async fn async_main_with_cli(cli: Cli) -> Result<(), Box<dyn Error + Send + Sync
     drop(ui_renderer);
     drop(ui_rx_bridge);
 
-    let _ = vad_handle.join();
-    let _ = preview_handle.join();
-    let _ = final_handle.join();
+    drop(stream);
+    stop_workers(
+        &running,
+        &runtime_state,
+        session_tx,
+        vec![session_handle, vad_handle, preview_handle, final_handle],
+    );
 
     Ok(())
 }
 
+/// Whether the main loop should leave for cleanup: the Ctrl+C handler has
+/// cleared `running`, or Ctrl+C arrived as keyboard `input` (raw mode)
+fn should_exit(running: &AtomicBool, input: Option<&str>) -> bool {
+    !running.load(Ordering::SeqCst) || input == Some("\x03")
+}
+
+/// Stop capture so the audio threads run dry, and let the session thread
+/// finish what it's writing (history, recordings) once its queue closes,
+/// then wait for all of them
+fn stop_workers(
+    running: &AtomicBool,
+    state: &state::SharedState,
+    session_tx: tokio::sync::mpsc::UnboundedSender<session::SessionCommand>,
+    workers: Vec<thread::JoinHandle<()>>,
+) {
+    running.store(false, Ordering::SeqCst);
+    state.request_cancel();
+    drop(session_tx);
+    for worker in workers {
+        let _ = worker.join();
+    }
+}
+
 enum DisplayEvent {
     Preview(String),
     Final(String),
-    AudioLevel(f32),
+    /// Smoothed input level, and whether the mic has been clipping
+    AudioLevel {
+        rms: f32,
+        clipping: bool,
+    },
+    AudioBands([f32; spectrum::NUM_BANDS]),
     TtsLevel(f32),
+    /// A language's transcriber failed to load, so it's back on the default
+    LanguageFallback,
 }
 
 async fn run_probe(prompt: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
-    let config = Config::load();
+    let config = Config::load().map_err(|p| config::problem_report(&p))?;
     let system_prompt = chat::system_prompt(&config.name);
 
     let mut backend: Box<dyn llm::LlmBackend> = match &config.llm {
@@ -1326,9 +2123,11 @@ async fn run_probe(prompt: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
             *frequency_penalty,
         )?),
         #[cfg(feature = "ollama")]
-        LlmConfig::Ollama { model } => {
-            Box::new(llm::ollama::OllamaBackend::new(model, &system_prompt))
-        }
+        LlmConfig::Ollama { model, host } => Box::new(llm::ollama::OllamaBackend::new(
+            model,
+            host,
+            &system_prompt,
+        )?),
         _ => {
             eprintln!("Probe requires openai-compat or ollama backend");
             return Ok(());
@@ -1339,10 +2138,12 @@ async fn run_probe(prompt: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
         llm::Message {
             role: llm::Role::System,
             content: system_prompt,
+            time: None,
         },
         llm::Message {
             role: llm::Role::User,
             content: prompt.to_string(),
+            time: None,
         },
     ];
 
@@ -1366,21 +2167,27 @@ async fn run_transcribe_mode() -> Result<(), Box<dyn Error + Send + Sync>> {
     let (preview_tx, _) = mpsc::sync_channel::<Arc<[f32]>>(1); // unused but required
     let (display_tx, display_rx) = mpsc::channel::<DisplayEvent>();
 
-    let _stream = audio::start_capture(audio_tx)?;
+    let config = Config::load().map_err(|p| config::problem_report(&p))?;
+    let _stream = audio::start_capture(audio_tx, config.audio.input_device.as_deref())?;
 
     let tts_playing = Arc::new(AtomicBool::new(false));
     let tts_playing_vad = Arc::clone(&tts_playing);
     let mic_muted = Arc::new(AtomicBool::new(false));
     let mic_muted_vad = Arc::clone(&mic_muted);
 
-    let vad_path = model_manager::resolve_model_path(model_manager::VAD_MODEL)
+    let vad_path = model_manager::vad_model_path(&config)
         .to_string_lossy()
         .to_string();
+    let silence_ms = config.interaction.silence_ms;
+    let trailing_pad_ms = config.interaction.trailing_pad_ms;
+    let vad_thresholds = config.interaction.vad_thresholds();
     thread::spawn(move || {
         let vad = if std::path::Path::new(&vad_path).exists() {
-            VadEngine::silero(&vad_path, TARGET_RATE).ok()
+            VadEngine::silero(&vad_path, TARGET_RATE)
+                .ok()
+                .map(|v| v.with_thresholds(vad_thresholds))
         } else {
-            Some(VadEngine::energy())
+            Some(VadEngine::energy_adaptive(vad::ADAPTIVE_ENERGY_FACTOR))
         };
         audio::run_vad_processor(
             audio_rx,
@@ -1390,19 +2197,28 @@ async fn run_transcribe_mode() -> Result<(), Box<dyn Error + Send + Sync>> {
             tts_playing_vad,
             mic_muted_vad,
             display_tx,
+            silence_ms,
+            trailing_pad_ms,
+            None,
         );
     });
 
-    let parakeet_path = model_manager::resolve_model_path(model_manager::PARAKEET_DIR)
+    let parakeet_path = model_manager::transcriber_model_path(&config)
         .to_string_lossy()
         .to_string();
+    let silence_floor = config.transcriber.silence_floor;
+    let agc_target = config.audio.agc_target();
+    let normalizer = normalize::TextNormalizer::new(&config.normalize);
     thread::spawn(move || {
         let mut transcriber = match transcriber::Transcriber::new(&parakeet_path) {
-            Ok(t) => t,
+            Ok(t) => t
+                .with_silence_floor(silence_floor)
+                .with_agc(agc_target)
+                .with_normalizer(normalizer),
             Err(_) => return,
         };
         while let Ok(samples) = final_rx.recv() {
-            if let Ok(text) = transcriber.transcribe(&samples) {
+            if let Ok(text) = transcriber.transcribe_slice(&samples) {
                 if !text.is_empty() {
                     println!("{}", text);
                 }
@@ -1430,7 +2246,10 @@ async fn run_typing_mode(
     verbose: bool,
     command_pause_ms: u32,
 ) -> Result<(), Box<dyn Error + Send + Sync>> {
-    use typing::{HotkeyConfig, HotkeyEvent, InputMethod, ProcessResult, TypingProcessor};
+    use typing::{
+        HotkeyConfig, HotkeyEvent, InputMethod, KeyPacing, ProcessResult, StatusLine, TypingMode,
+        TypingProcessor,
+    };
 
     eprintln!("═══════════════════════════════════════════════════════════════");
     eprintln!("                    SILLY TYPING MODE");
@@ -1449,15 +2268,27 @@ async fn run_typing_mode(
     eprintln!("═══════════════════════════════════════════════════════════════");
     eprintln!();
 
+    let config = Config::load().map_err(|p| config::problem_report(&p))?;
+
     // Initialize typing processor (must stay on main thread - Enigo isn't Send)
     let method = InputMethod::from_str(&input_method);
-    let mut processor = TypingProcessor::new(method, 50, feedback, command_pause_ms)
+    let pacing = KeyPacing {
+        delay_ms: config.typing.keystroke_delay_ms,
+        adaptive: config.typing.adaptive_delay,
+    };
+    let mut processor = TypingProcessor::new(method, 50, feedback, command_pause_ms, pacing)
         .map_err(|e| format!("Failed to initialize typing: {}", e))?
-        .with_verbose(verbose);
+        .with_verbose(verbose)
+        .with_custom_punctuation(config.typing.punctuation.clone())
+        .with_literal_prefixes(config.typing.literal_prefixes.clone())
+        .with_auto_pair(config.typing.auto_pair)
+        .with_restore_clipboard(config.typing.restore_clipboard);
 
     // Start global hotkey listener
-    let (hotkey_rx, hotkey_running) = typing::start_hotkey_listener(HotkeyConfig::default())
-        .map_err(|e| format!("Failed to start hotkey listener: {}", e))?;
+    let (hotkey_rx, hotkey_running) = typing::start_hotkey_listener(
+        HotkeyConfig::default().with_double_tap_ms(config.typing.double_tap_ms),
+    )
+    .map_err(|e| format!("Failed to start hotkey listener: {}", e))?;
 
     // Set up audio pipeline
     let (audio_tx, audio_rx) = mpsc::channel::<Vec<f32>>();
@@ -1468,7 +2299,7 @@ async fn run_typing_mode(
     // Channel to send transcribed text from transcriber thread to main thread
     let (text_tx, text_rx) = mpsc::channel::<String>();
 
-    let _stream = audio::start_capture(audio_tx)?;
+    let _stream = audio::start_capture(audio_tx, config.audio.input_device.as_deref())?;
 
     let tts_playing = Arc::new(AtomicBool::new(false));
     let mic_muted = Arc::new(AtomicBool::new(false));
@@ -1477,14 +2308,23 @@ async fn run_typing_mode(
     let mic_muted_vad = Arc::clone(&mic_muted);
 
     // VAD processor thread
-    let vad_path = model_manager::resolve_model_path(model_manager::VAD_MODEL)
+    let vad_path = model_manager::vad_model_path(&config)
         .to_string_lossy()
         .to_string();
+    let silence_ms = config.interaction.silence_ms;
+    let trailing_pad_ms = config.interaction.trailing_pad_ms;
+    let vad_thresholds = config.interaction.vad_thresholds();
+    // In push-to-talk mode audio is only captured while Ctrl+Space is held
+    let ptt_held = (config.interaction.mode == config::InteractionMode::PushToTalk)
+        .then(|| Arc::new(AtomicBool::new(false)));
+    let ptt_held_vad = ptt_held.clone();
     thread::spawn(move || {
         let vad = if std::path::Path::new(&vad_path).exists() {
-            VadEngine::silero(&vad_path, TARGET_RATE).ok()
+            VadEngine::silero(&vad_path, TARGET_RATE)
+                .ok()
+                .map(|v| v.with_thresholds(vad_thresholds))
         } else {
-            Some(VadEngine::energy())
+            Some(VadEngine::energy_adaptive(vad::ADAPTIVE_ENERGY_FACTOR))
         };
         audio::run_vad_processor(
             audio_rx,
@@ -1494,6 +2334,9 @@ async fn run_typing_mode(
             tts_playing_vad,
             mic_muted_vad,
             display_tx,
+            silence_ms,
+            trailing_pad_ms,
+            ptt_held_vad,
         );
     });
 
@@ -1501,12 +2344,14 @@ async fn run_typing_mode(
     let running = Arc::new(AtomicBool::new(true));
     let running_transcribe = Arc::clone(&running);
 
-    let parakeet_path = model_manager::resolve_model_path(model_manager::PARAKEET_DIR)
+    let parakeet_path = model_manager::transcriber_model_path(&config)
         .to_string_lossy()
         .to_string();
+    let silence_floor = config.transcriber.silence_floor;
+    let agc_target = config.audio.agc_target();
     thread::spawn(move || {
         let mut transcriber = match transcriber::Transcriber::new(&parakeet_path) {
-            Ok(t) => t,
+            Ok(t) => t.with_silence_floor(silence_floor).with_agc(agc_target),
             Err(e) => {
                 eprintln!("Failed to initialize transcriber: {}", e);
                 return;
@@ -1516,7 +2361,7 @@ async fn run_typing_mode(
         while running_transcribe.load(Ordering::SeqCst) {
             match final_rx.recv_timeout(std::time::Duration::from_millis(100)) {
                 Ok(samples) => {
-                    if let Ok(text) = transcriber.transcribe(&samples) {
+                    if let Ok(text) = transcriber.transcribe_slice(&samples) {
                         if !text.is_empty() {
                             if text_tx.send(text).is_err() {
                                 break;
@@ -1539,11 +2384,14 @@ async fn run_typing_mode(
     // Track state for hotkey control
     let mut typing_enabled = true; // Toggle state (double-tap Cmd)
     let mut ptt_active = false; // Push-to-talk state (Ctrl+Space)
+    let mut status = StatusLine::new(config.typing.status_line);
+    status.draw();
 
     // Main loop - process transcribed text with TypingProcessor (on main thread)
     while running.load(Ordering::SeqCst) {
         // Check for hotkey events (non-blocking)
         while let Ok(event) = hotkey_rx.try_recv() {
+            status.clear();
             match event {
                 HotkeyEvent::Toggle => {
                     typing_enabled = !typing_enabled;
@@ -1559,10 +2407,16 @@ async fn run_typing_mode(
                     if typing_enabled {
                         ptt_active = true;
                         mic_muted_main.store(false, Ordering::SeqCst);
+                        if let Some(ref held) = ptt_held {
+                            held.store(true, Ordering::SeqCst);
+                        }
                         eprint!("[PTT] ");
                     }
                 }
                 HotkeyEvent::PushToTalkEnd => {
+                    if let Some(ref held) = ptt_held {
+                        held.store(false, Ordering::SeqCst);
+                    }
                     if ptt_active {
                         ptt_active = false;
                         // Don't mute if typing is enabled (continuous mode)
@@ -1571,6 +2425,8 @@ async fn run_typing_mode(
                     }
                 }
             }
+            status.set_mode(TypingMode::from_state(typing_enabled, ptt_active));
+            status.draw();
         }
 
         // Only process transcriptions when typing is active
@@ -1578,6 +2434,7 @@ async fn run_typing_mode(
         if active {
             match text_rx.recv_timeout(std::time::Duration::from_millis(50)) {
                 Ok(text) => {
+                    status.clear();
                     match processor.process_segment(&text, 300) {
                         Ok(ProcessResult::Stop) => {
                             eprintln!("\nStopping typing mode...");
@@ -1601,6 +2458,9 @@ async fn run_typing_mode(
                             eprintln!("Typing error: {}", e);
                         }
                     }
+                    status.update(processor.last_command(), processor.undo_count());
+                    status.set_mode(TypingMode::from_state(typing_enabled, ptt_active));
+                    status.draw();
                 }
                 Err(mpsc::RecvTimeoutError::Timeout) => {}
                 Err(mpsc::RecvTimeoutError::Disconnected) => break,
@@ -1613,7 +2473,75 @@ async fn run_typing_mode(
 
     // Stop hotkey listener
     hotkey_running.store(false, Ordering::SeqCst);
+    status.clear();
 
     eprintln!("\nTyping mode ended.");
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stop_workers_joins_after_ctrl_c() {
+        let state = RuntimeState::new(&Config::default());
+        let running = Arc::new(AtomicBool::new(true));
+
+        // Stand-ins for the session thread (runs until its queue closes) and
+        // a capture thread (runs until `running` is cleared)
+        let (session_tx, mut session_rx) = tokio::sync::mpsc::unbounded_channel();
+        let session = thread::spawn(move || while session_rx.blocking_recv().is_some() {});
+        let running_capture = Arc::clone(&running);
+        let capture = thread::spawn(move || {
+            while running_capture.load(Ordering::SeqCst) {
+                thread::sleep(std::time::Duration::from_millis(10));
+            }
+        });
+        let _ = session_tx.send(session::SessionCommand::Cancel);
+
+        // The main loop's per-frame check, then its cleanup
+        let (done_tx, done_rx) = mpsc::channel();
+        let state_stop = Arc::clone(&state);
+        let running_loop = Arc::clone(&running);
+        thread::spawn(move || {
+            while !should_exit(&running_loop, None) {
+                thread::sleep(std::time::Duration::from_millis(10));
+            }
+            stop_workers(
+                &running_loop,
+                &state_stop,
+                session_tx,
+                vec![session, capture],
+            );
+            let _ = done_tx.send(());
+        });
+        assert!(
+            done_rx
+                .recv_timeout(std::time::Duration::from_millis(100))
+                .is_err(),
+            "stopped before Ctrl+C"
+        );
+
+        // What the Ctrl+C handler does
+        running.store(false, Ordering::SeqCst);
+        assert!(
+            done_rx
+                .recv_timeout(std::time::Duration::from_secs(2))
+                .is_ok(),
+            "worker threads didn't finish"
+        );
+        assert!(state.is_cancel_requested());
+    }
+
+    #[test]
+    fn test_should_exit() {
+        let running = AtomicBool::new(true);
+        assert!(!should_exit(&running, None));
+        assert!(!should_exit(&running, Some("hello")));
+        // In raw mode Ctrl+C is a key, not a signal
+        assert!(should_exit(&running, Some("\x03")));
+        running.store(false, Ordering::SeqCst);
+        assert!(should_exit(&running, None));
+    }
+}
@@ -107,6 +107,22 @@ pub fn resolve_model_path(relative: &str) -> PathBuf {
     download_dir().join(relative)
 }
 
+/// Transcriber model directory: `models.transcriber_path` if set, else the default.
+pub fn transcriber_model_path(config: &Config) -> PathBuf {
+    match &config.models.transcriber_path {
+        Some(path) => PathBuf::from(path),
+        None => resolve_model_path(PARAKEET_DIR),
+    }
+}
+
+/// VAD model file: `models.vad_path` if set, else the default.
+pub fn vad_model_path(config: &Config) -> PathBuf {
+    match &config.models.vad_path {
+        Some(path) => PathBuf::from(path),
+        None => resolve_model_path(VAD_MODEL),
+    }
+}
+
 /// Check whether a model file/directory exists in any search location.
 #[cfg(feature = "model-download")]
 fn model_exists(relative: &str) -> bool {
@@ -292,7 +308,8 @@ mod downloader {
         let mut needed: Vec<(&str, DownloadFn)> = Vec::new();
 
         // --- VAD ---
-        if !model_exists(VAD_MODEL) {
+        // Custom model paths are the user's responsibility; only fetch defaults
+        if config.models.vad_path.is_none() && !model_exists(VAD_MODEL) {
             needed.push((
                 "Silero VAD v4",
                 Box::new(|base: &Path| download_file(VAD_URL, &base.join(VAD_MODEL))),
@@ -301,7 +318,9 @@ mod downloader {
 
         // --- Parakeet STT ---
         // Check for the directory with at least the encoder model
-        if !model_exists(&format!("{}/encoder-model.int8.onnx", PARAKEET_DIR)) {
+        if config.models.transcriber_path.is_none()
+            && !model_exists(&format!("{}/encoder-model.int8.onnx", PARAKEET_DIR))
+        {
             needed.push((
                 "Parakeet STT (int8)",
                 Box::new(|base: &Path| download_and_extract_targz(PARAKEET_TARGZ_URL, base)),
@@ -0,0 +1,215 @@
+//! Transcript clean-up (`[normalize]`)
+//!
+//! Parakeet sometimes leaves sentences uncapitalized, transcribes hesitations
+//! ("um", "uh") and repeats stuttered words ("the the"). Final transcripts go
+//! through a `TextNormalizer` before they're shown, saved or sent to the LLM.
+//! Live previews and typing mode are left raw: code mode's "and and" and
+//! dictated repeats like "had had" must reach the keyboard as spoken.
+
+use crate::config::NormalizeConfig;
+
+/// Words a stutter repeats that are never doubled on purpose. Others can be:
+/// "had had", "that that", "gave her her keys"
+const STUTTER_WORDS: &[&str] = &[
+    "a", "an", "the", "i", "he", "she", "we", "they", "my", "your", "his", "our", "their", "of",
+    "to",
+];
+
+pub struct TextNormalizer {
+    capitalize: bool,
+    /// Lowercased filler words, empty when fillers are kept
+    fillers: Vec<String>,
+    collapse_repeats: bool,
+}
+
+impl TextNormalizer {
+    pub fn new(config: &NormalizeConfig) -> Self {
+        let fillers = if config.strip_fillers {
+            config
+                .fillers
+                .iter()
+                .map(|f| f.trim().to_lowercase())
+                .filter(|f| !f.is_empty())
+                .collect()
+        } else {
+            Vec::new()
+        };
+        Self {
+            capitalize: config.capitalize,
+            fillers,
+            collapse_repeats: config.collapse_repeats,
+        }
+    }
+
+    pub fn normalize(&self, text: &str) -> String {
+        let mut words: Vec<String> = Vec::new();
+        // A dropped filler opened the sentence, so the next word does instead
+        let mut capitalize_next = false;
+        for token in text.split_whitespace() {
+            let key = core(token).to_lowercase();
+            if self.fillers.contains(&key) {
+                // "and, um." keeps its full stop: "and."
+                if let Some((prev, end)) = words.last_mut().zip(sentence_end(token)) {
+                    let kept = prev.trim_end_matches([',', ';', ':']).len();
+                    prev.truncate(kept);
+                    if sentence_end(prev).is_none() {
+                        prev.push(end);
+                    }
+                }
+                capitalize_next |= token.starts_with(char::is_uppercase);
+                continue;
+            }
+
+            let repeated = self.collapse_repeats
+                && STUTTER_WORDS.contains(&key.as_str())
+                && words.last().is_some_and(|prev| {
+                    trailing(prev).is_empty() && core(prev).to_lowercase() == key
+                });
+            if repeated {
+                // Keep the first word's casing and the second's punctuation
+                if let Some(prev) = words.last_mut() {
+                    prev.push_str(trailing(token));
+                }
+                continue;
+            }
+
+            words.push(if capitalize_next {
+                capitalize_first(token)
+            } else {
+                token.to_string()
+            });
+            capitalize_next = false;
+        }
+
+        if self.capitalize {
+            let mut sentence_start = true;
+            for word in &mut words {
+                if sentence_start {
+                    *word = capitalize_first(word);
+                }
+                sentence_start = sentence_end(word).is_some();
+            }
+        }
+        words.join(" ")
+    }
+}
+
+/// The word without surrounding punctuation ("'Hello," -> "Hello")
+fn core(token: &str) -> &str {
+    token.trim_matches(|c: char| !c.is_alphanumeric())
+}
+
+/// Punctuation after the last letter or digit
+fn trailing(token: &str) -> &str {
+    let end = token.trim_end_matches(|c: char| !c.is_alphanumeric()).len();
+    &token[end..]
+}
+
+/// The `.`, `?` or `!` a token ends its sentence with, if any
+fn sentence_end(token: &str) -> Option<char> {
+    trailing(token)
+        .chars()
+        .find(|c| matches!(c, '.' | '?' | '!'))
+}
+
+/// Uppercase the first letter, skipping leading quotes and brackets
+fn capitalize_first(token: &str) -> String {
+    match token.char_indices().find(|(_, c)| c.is_alphanumeric()) {
+        Some((i, c)) if c.is_lowercase() => format!(
+            "{}{}{}",
+            &token[..i],
+            c.to_uppercase(),
+            &token[i + c.len_utf8()..]
+        ),
+        _ => token.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn normalizer(capitalize: bool, strip_fillers: bool, collapse_repeats: bool) -> TextNormalizer {
+        TextNormalizer::new(&NormalizeConfig {
+            capitalize,
+            strip_fillers,
+            collapse_repeats,
+            ..NormalizeConfig::default()
+        })
+    }
+
+    fn check(normalizer: &TextNormalizer, cases: &[(&str, &str)]) {
+        for (before, after) in cases {
+            assert_eq!(
+                normalizer.normalize(before),
+                *after,
+                "normalizing {:?}",
+                before
+            );
+        }
+    }
+
+    #[test]
+    fn test_capitalize_sentences() {
+        check(
+            &normalizer(true, false, false),
+            &[
+                ("hello there. how are you?", "Hello there. How are you?"),
+                ("wow! \"really\" it works", "Wow! \"Really\" it works"),
+                ("3 apples. ok", "3 apples. Ok"),
+                ("Already Fine.", "Already Fine."),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_strip_fillers() {
+        check(
+            &normalizer(false, true, false),
+            &[
+                ("Um, I think, uh, we should go.", "I think, we should go."),
+                ("so umm the meeting", "so the meeting"),
+                ("turn it off and, uh.", "turn it off and."),
+                ("Uh. Fine", "Fine"),
+                // Words that merely contain a filler stay
+                ("summer underway", "summer underway"),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_collapse_repeats() {
+        check(
+            &normalizer(false, false, true),
+            &[
+                ("the the cat", "the cat"),
+                ("The the cat sat on a a mat.", "The cat sat on a mat."),
+                ("I I I think", "I think"),
+                ("back to to the start", "back to the start"),
+                // Words that can be doubled on purpose stay
+                ("she had had enough", "she had had enough"),
+                ("I know that that works", "I know that that works"),
+                ("gave her her keys", "gave her her keys"),
+                // Punctuation between them means the repeat was meant
+                ("no, no, no", "no, no, no"),
+                ("the, the one", "the, the one"),
+                ("call 1 1 2", "call 1 1 2"),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_default_config() {
+        check(
+            &TextNormalizer::new(&NormalizeConfig::default()),
+            &[
+                ("um the the plan is done. next", "The plan is done. Next"),
+                ("", ""),
+            ],
+        );
+        check(
+            &normalizer(false, false, false),
+            &[("um the the plan", "um the the plan")],
+        );
+    }
+}
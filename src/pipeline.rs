@@ -1,24 +1,49 @@
+use crate::audiofile::OggWriter;
 use crate::capture::{TARGET_RATE, capture_mic, capture_system};
+use crate::config::{self, Config};
 use crate::model_manager;
-use crate::segmenter::{AudioSegment, SegmenterConfig, run_segmenter};
-use crate::transcriber::Transcriber;
+use crate::normalize::TextNormalizer;
+use crate::segmenter::{AudioSegment, SegmenterConfig, open_vad_log, run_segmenter};
+use crate::subtitle::{SubtitleFormat, SubtitleWriter, format_line};
+use crate::transcriber::{Transcriber, WordHyp, interpolate_words};
 use crate::vad::VadEngine;
 use flume::{Receiver, Sender};
 use std::fs::File;
 use std::io::{BufWriter, Write};
-use std::num::NonZero;
-use std::path::PathBuf;
-use std::sync::Arc;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 use std::thread;
-use vorbis_rs::VorbisEncoderBuilder;
+use std::time::{Duration, Instant};
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, serde::Serialize)]
 pub struct Transcript {
     pub start: f32,
     pub end: f32,
     pub text: String,
     pub source: Option<String>,
+    /// Per-word timings, interpolated across the segment
+    pub words: Vec<WordHyp>,
+    /// Interim text for speech still in progress, replaced by a later transcript
+    #[serde(skip)]
+    pub partial: bool,
+}
+
+impl Transcript {
+    /// Build a transcript for a segment, spreading its duration across the words
+    fn from_segment(segment: &AudioSegment, text: &str, source: Option<String>) -> Option<Self> {
+        let start = segment.start_secs();
+        let words = interpolate_words(text, start, start + segment.duration_secs());
+        let (first, last) = (words.first()?, words.last()?);
+        Some(Self {
+            start: first.start,
+            end: last.end,
+            text: text.to_string(),
+            source,
+            words,
+            partial: segment.partial,
+        })
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -59,15 +84,14 @@ pub fn run_transcriber_with_source(
     while running.load(Ordering::SeqCst) {
         match rx.recv_timeout(std::time::Duration::from_millis(100)) {
             Ok(segment) => {
+                // A newer segment is already waiting, this preview is stale
+                if segment.partial && !rx.is_empty() {
+                    continue;
+                }
                 if let Ok(text) = transcriber.transcribe(&segment.samples) {
-                    let text = text.trim();
-                    if !text.is_empty() {
-                        let _ = tx.send(Transcript {
-                            start: segment.start_secs(),
-                            end: segment.start_secs() + segment.duration_secs(),
-                            text: text.to_string(),
-                            source: source.clone(),
-                        });
+                    if let Some(t) = Transcript::from_segment(&segment, text.trim(), source.clone())
+                    {
+                        let _ = tx.send(t);
                     }
                 }
             }
@@ -77,16 +101,10 @@ pub fn run_transcriber_with_source(
     }
 
     // Drain remaining
-    for segment in rx.drain() {
+    for segment in rx.drain().filter(|s| !s.partial) {
         if let Ok(text) = transcriber.transcribe(&segment.samples) {
-            let text = text.trim();
-            if !text.is_empty() {
-                let _ = tx.send(Transcript {
-                    start: segment.start_secs(),
-                    end: segment.start_secs() + segment.duration_secs(),
-                    text: text.to_string(),
-                    source: source.clone(),
-                });
+            if let Some(t) = Transcript::from_segment(&segment, text.trim(), source.clone()) {
+                let _ = tx.send(t);
             }
         }
     }
@@ -94,51 +112,120 @@ pub fn run_transcriber_with_source(
     Ok(())
 }
 
+/// How often speech in progress is re-transcribed with `--interim`
+const INTERIM_SECS: f32 = 1.0;
+
+/// How long transcripts are held so ones from a slower transcriber can be
+/// slotted in before them
+const REORDER_WINDOW: Duration = Duration::from_millis(500);
+
+/// Holds transcripts for a short window and releases them by start time, so
+/// two sources transcribed in parallel read chronologically even when their
+/// transcribers finish out of order
+struct ReorderBuffer {
+    window: Duration,
+    /// Pending transcripts with their arrival time, sorted by start
+    pending: Vec<(Instant, Transcript)>,
+}
+
+impl ReorderBuffer {
+    fn new(window: Duration) -> Self {
+        Self {
+            window,
+            pending: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, transcript: Transcript, now: Instant) {
+        let at = self
+            .pending
+            .partition_point(|(_, t)| t.start <= transcript.start);
+        self.pending.insert(at, (now, transcript));
+    }
+
+    /// Release the earliest transcripts once they've been held for the window
+    fn ready(&mut self, now: Instant) -> Vec<Transcript> {
+        let held = self
+            .pending
+            .iter()
+            .take_while(|(arrived, _)| now.duration_since(*arrived) >= self.window)
+            .count();
+        self.pending.drain(..held).map(|(_, t)| t).collect()
+    }
+
+    fn flush(&mut self) -> Vec<Transcript> {
+        self.pending.drain(..).map(|(_, t)| t).collect()
+    }
+}
+
 pub fn run_writer(
     rx: Receiver<Transcript>,
     output: PathBuf,
     running: Arc<AtomicBool>,
+    format: SubtitleFormat,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let file = File::create(&output)?;
-    let mut writer = BufWriter::new(file);
-
-    let format_line = |t: &Transcript| -> String {
-        match &t.source {
-            Some(src) => format!("[{:.2}-{:.2}] [{}] {}", t.start, t.end, src, t.text),
-            None => format!("[{:.2}-{:.2}] {}", t.start, t.end, t.text),
-        }
-    };
+    let mut writer = SubtitleWriter::new(BufWriter::new(file), format)?;
+    let mut buffer = ReorderBuffer::new(REORDER_WINDOW);
+
+    // Final lines clear any partial still showing before printing over it
+    let mut emit =
+        |transcripts: Vec<Transcript>| -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+            for t in transcripts {
+                println!("\r\x1b[2K{}", format_line(&t));
+                writer.write(&t)?;
+            }
+            Ok(())
+        };
 
     while running.load(Ordering::SeqCst) {
-        match rx.recv_timeout(std::time::Duration::from_millis(100)) {
-            Ok(t) => {
-                let line = format_line(&t);
-                println!("{}", line);
-                writeln!(writer, "{}", line)?;
-                writer.flush()?;
+        match rx.recv_timeout(Duration::from_millis(100)) {
+            // Partials are only shown, on a line the next one rewrites
+            Ok(t) if t.partial => {
+                print!("\r\x1b[2K{} …", format_line(&t));
+                std::io::stdout().flush()?;
             }
-            Err(flume::RecvTimeoutError::Timeout) => continue,
+            Ok(t) => buffer.push(t, Instant::now()),
+            Err(flume::RecvTimeoutError::Timeout) => {}
             Err(flume::RecvTimeoutError::Disconnected) => break,
         }
+        emit(buffer.ready(Instant::now()))?;
     }
 
     // Drain remaining
-    for t in rx.drain() {
-        let line = format_line(&t);
-        println!("{}", line);
-        writeln!(writer, "{}", line)?;
+    for t in rx.drain().filter(|t| !t.partial) {
+        buffer.push(t, Instant::now());
     }
+    emit(buffer.flush())?;
 
-    writer.flush()?;
+    writer.finish()?;
     println!("\nSaved to: {}", output.display());
     Ok(())
 }
 
+/// Toggle `paused` each time `p` is entered on stdin, so a private stretch
+/// can be left out of the transcript without stopping the recording
+fn spawn_pause_toggle(paused: Arc<AtomicBool>) {
+    thread::spawn(move || {
+        for line in std::io::stdin().lines() {
+            let Ok(line) = line else { break };
+            if !line.trim().eq_ignore_ascii_case("p") {
+                continue;
+            }
+            if paused.fetch_xor(true, Ordering::SeqCst) {
+                println!("▶ resumed");
+            } else {
+                println!("⏸ paused (type p + Enter to resume)");
+            }
+        }
+    });
+}
+
 pub fn run_pipeline(
     source: AudioSource,
     output: PathBuf,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    run_pipeline_with_options(source, output, None)
+    run_pipeline_with_options(source, output, None, SubtitleFormat::Txt, None, false)
 }
 
 /// Record audio to OGG only, no transcription
@@ -153,11 +240,18 @@ pub fn run_record_only(
     let (ogg_tx, ogg_rx) = flume::bounded::<Vec<f32>>(100);
 
     let running_capture = running.clone();
+    let input_device = Config::load()
+        .map_err(|p| config::problem_report(&p))?
+        .audio
+        .input_device;
     let capture_handle = thread::spawn(move || {
         let result = match source {
-            AudioSource::Mic => {
-                capture_mic_with_tap(flume::bounded(1).0, Some(ogg_tx), running_capture)
-            }
+            AudioSource::Mic => capture_mic_with_tap(
+                flume::bounded(1).0,
+                Some(ogg_tx),
+                running_capture,
+                input_device,
+            ),
             AudioSource::System => {
                 capture_system_with_tap(flume::bounded(1).0, Some(ogg_tx), running_capture, None)
             }
@@ -187,19 +281,29 @@ pub fn run_pipeline_with_options(
     source: AudioSource,
     output: PathBuf,
     save_ogg: Option<PathBuf>,
+    format: SubtitleFormat,
+    vad_log: Option<PathBuf>,
+    interim: bool,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let running = Arc::new(AtomicBool::new(true));
     let r = running.clone();
     ctrlc::set_handler(move || r.store(false, Ordering::SeqCst))?;
 
+    let vad_log = vad_log.map(|p| open_vad_log(&p)).transpose()?;
+
     // Load models first (before spawning threads)
     println!("Loading VAD...");
-    let vad_path = model_manager::resolve_model_path(model_manager::VAD_MODEL);
-    let vad = VadEngine::silero(&vad_path.to_string_lossy(), TARGET_RATE)?;
+    let config = Config::load().map_err(|p| config::problem_report(&p))?;
+    let vad_path = model_manager::vad_model_path(&config);
+    let vad = VadEngine::silero(&vad_path.to_string_lossy(), TARGET_RATE)?
+        .with_thresholds(config.interaction.vad_thresholds());
 
     println!("Loading transcriber...");
-    let parakeet_path = model_manager::resolve_model_path(model_manager::PARAKEET_DIR);
-    let transcriber = Transcriber::new(&parakeet_path.to_string_lossy())?;
+    let parakeet_path = model_manager::transcriber_model_path(&config);
+    let transcriber = Transcriber::new(&parakeet_path.to_string_lossy())?
+        .with_silence_floor(config.transcriber.silence_floor)
+        .with_agc(config.audio.agc_target())
+        .with_normalizer(TextNormalizer::new(&config.normalize));
 
     // Channels
     let (audio_tx, audio_rx) = flume::bounded::<Vec<f32>>(100);
@@ -215,9 +319,12 @@ pub fn run_pipeline_with_options(
     // Spawn threads
     let running_capture = running.clone();
     let ogg_sender = ogg_tx.as_ref().map(|(tx, _)| tx.clone());
+    let input_device = config.audio.input_device.clone();
     let capture_handle = thread::spawn(move || {
         let result = match source {
-            AudioSource::Mic => capture_mic_with_tap(audio_tx, ogg_sender, running_capture),
+            AudioSource::Mic => {
+                capture_mic_with_tap(audio_tx, ogg_sender, running_capture, input_device)
+            }
             AudioSource::System => {
                 capture_system_with_tap(audio_tx, ogg_sender, running_capture, None)
             }
@@ -243,14 +350,22 @@ pub fn run_pipeline_with_options(
         None
     };
 
+    let paused = Arc::new(AtomicBool::new(false));
+    spawn_pause_toggle(paused.clone());
+    let mut segmenter_config = SegmenterConfig::default().with_pause(paused);
+    if interim {
+        segmenter_config = segmenter_config.with_interim(INTERIM_SECS);
+    }
+
     let running_seg = running.clone();
     let segmenter_handle = thread::spawn(move || {
         if let Err(e) = run_segmenter(
             audio_rx,
             segment_tx,
             vad,
-            SegmenterConfig::default(),
+            segmenter_config,
             running_seg,
+            vad_log,
         ) {
             eprintln!("Segmenter error: {}", e);
         }
@@ -264,8 +379,8 @@ pub fn run_pipeline_with_options(
     });
 
     // Writer runs on main thread
-    println!("Recording... Press Ctrl+C to stop.\n");
-    run_writer(transcript_rx, output, running.clone())?;
+    println!("Recording... Type p + Enter to pause/resume, Ctrl+C to stop.\n");
+    run_writer(transcript_rx, output, running.clone(), format)?;
 
     // Wait for threads
     let _ = capture_handle.join();
@@ -283,22 +398,11 @@ fn run_ogg_writer(
     path: PathBuf,
     running: Arc<AtomicBool>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let file = File::create(&path)?;
-    let mut encoder = VorbisEncoderBuilder::new(
-        NonZero::new(TARGET_RATE as u32).unwrap(),
-        NonZero::new(1).unwrap(),
-        file,
-    )?
-    .build()?;
-
-    let mut total_samples = 0usize;
+    let mut writer = OggWriter::create(&path, TARGET_RATE as u32)?;
 
     while running.load(Ordering::SeqCst) {
         match rx.recv_timeout(std::time::Duration::from_millis(100)) {
-            Ok(samples) => {
-                total_samples += samples.len();
-                encoder.encode_audio_block([&samples[..]])?;
-            }
+            Ok(samples) => writer.write(&samples)?,
             Err(flume::RecvTimeoutError::Timeout) => continue,
             Err(flume::RecvTimeoutError::Disconnected) => break,
         }
@@ -306,13 +410,10 @@ fn run_ogg_writer(
 
     // Drain remaining
     for samples in rx.drain() {
-        total_samples += samples.len();
-        encoder.encode_audio_block([&samples[..]])?;
+        writer.write(&samples)?;
     }
 
-    encoder.finish()?;
-
-    let duration = total_samples as f32 / TARGET_RATE as f32;
+    let duration = writer.finish()?;
     let size = std::fs::metadata(&path)?.len();
     println!(
         "OGG saved: {} ({:.1}s, {:.1} KB)",
@@ -328,18 +429,19 @@ fn capture_mic_with_tap(
     tx: Sender<Vec<f32>>,
     ogg_tx: Option<Sender<Vec<f32>>>,
     running: Arc<AtomicBool>,
+    device_name: Option<String>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    use crate::capture::resample;
-    use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+    use crate::resample::StreamResampler;
+    use cpal::traits::{DeviceTrait, StreamTrait};
 
-    let host = cpal::default_host();
-    let device = host.default_input_device().ok_or("No input device")?;
+    let device = crate::audio::input_device(device_name.as_deref())?;
     let supported = device.default_input_config()?;
     let sample_rate = u32::from(supported.sample_rate()) as usize;
     let channels = supported.channels() as usize;
 
     println!("Mic: {}Hz {}ch", sample_rate, channels);
 
+    let mut resampler = StreamResampler::new(sample_rate, TARGET_RATE)?;
     let stream = device.build_input_stream(
         &supported.config(),
         move |data: &[f32], _| {
@@ -350,7 +452,10 @@ fn capture_mic_with_tap(
                     .map(|c| c.iter().sum::<f32>() / channels as f32)
                     .collect()
             };
-            let resampled = resample(&mono, sample_rate, TARGET_RATE);
+            let resampled = resampler.process(&mono);
+            if resampled.is_empty() {
+                return;
+            }
             if let Some(ref ogg) = ogg_tx {
                 let _ = ogg.send(resampled.clone());
             }
@@ -368,13 +473,32 @@ fn capture_mic_with_tap(
     Ok(())
 }
 
-fn capture_system_with_tap(
+pub(crate) fn capture_system_with_tap(
     tx: Sender<Vec<f32>>,
     ogg_tx: Option<Sender<Vec<f32>>>,
     running: Arc<AtomicBool>,
     app_filter: Option<String>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    use crate::capture::resample;
+    let stream = start_system_tap(tx, ogg_tx, app_filter)?;
+
+    while running.load(Ordering::SeqCst) {
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    }
+
+    let _ = stream.stop_capture();
+    Ok(())
+}
+
+/// Start capturing system (or one app's) audio into `tx`, resampled to 16kHz
+///
+/// Capture runs until the returned stream is stopped, so callers can tell a
+/// stream that never started apart from one that ended.
+pub(crate) fn start_system_tap(
+    tx: Sender<Vec<f32>>,
+    ogg_tx: Option<Sender<Vec<f32>>>,
+    app_filter: Option<String>,
+) -> Result<screencapturekit::prelude::SCStream, Box<dyn std::error::Error + Send + Sync>> {
+    use crate::resample::StreamResampler;
     use screencapturekit::prelude::*;
 
     const CAPTURE_SAMPLE_RATE: usize = 48000;
@@ -410,6 +534,7 @@ fn capture_system_with_tap(
         .with_channel_count(1);
 
     let mut stream = SCStream::new(&filter, &config);
+    let resampler = Mutex::new(StreamResampler::new(CAPTURE_SAMPLE_RATE, TARGET_RATE)?);
 
     stream.add_output_handler(
         move |sample: CMSampleBuffer, of_type: SCStreamOutputType| {
@@ -426,7 +551,13 @@ fn capture_system_with_tap(
                         .chunks_exact(4)
                         .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
                         .collect();
-                    let resampled = resample(&samples, CAPTURE_SAMPLE_RATE, TARGET_RATE);
+                    let Ok(mut resampler) = resampler.lock() else {
+                        return;
+                    };
+                    let resampled = resampler.process(&samples);
+                    if resampled.is_empty() {
+                        continue;
+                    }
                     if let Some(ref ogg) = ogg_tx {
                         let _ = ogg.send(resampled.clone());
                     }
@@ -438,13 +569,17 @@ fn capture_system_with_tap(
     );
 
     stream.start_capture()?;
+    Ok(stream)
+}
 
-    while running.load(Ordering::SeqCst) {
-        std::thread::sleep(std::time::Duration::from_millis(100));
-    }
-
-    let _ = stream.stop_capture();
-    Ok(())
+/// Insert `-n` before the extension, e.g. `vad.csv` -> `vad-1.csv`
+fn numbered_path(path: &Path, n: usize) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let name = match path.extension() {
+        Some(ext) => format!("{}-{}.{}", stem, n, ext.to_string_lossy()),
+        None => format!("{}-{}", stem, n),
+    };
+    path.with_file_name(name)
 }
 
 /// Run two audio sources in parallel with merged, attributed transcripts
@@ -452,23 +587,43 @@ pub fn run_multi_source(
     source1: AudioSource,
     source2: AudioSource,
     output: PathBuf,
+    format: SubtitleFormat,
+    vad_log: Option<PathBuf>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let running = Arc::new(AtomicBool::new(true));
     let r = running.clone();
     ctrlc::set_handler(move || r.store(false, Ordering::SeqCst))?;
 
+    // One VAD log per source: <stem>-1.<ext> and <stem>-2.<ext>
+    let (vad_log1, vad_log2) = match &vad_log {
+        Some(path) => (
+            Some(open_vad_log(&numbered_path(path, 1))?),
+            Some(open_vad_log(&numbered_path(path, 2))?),
+        ),
+        None => (None, None),
+    };
+
     // Load models (need 2 VADs, 2 transcribers)
     println!("Loading VAD models...");
-    let vad_path = model_manager::resolve_model_path(model_manager::VAD_MODEL);
+    let config = Config::load().map_err(|p| config::problem_report(&p))?;
+    let vad_path = model_manager::vad_model_path(&config);
     let vad_str = vad_path.to_string_lossy();
-    let vad1 = VadEngine::silero(&vad_str, TARGET_RATE)?;
-    let vad2 = VadEngine::silero(&vad_str, TARGET_RATE)?;
+    let vad1 = VadEngine::silero(&vad_str, TARGET_RATE)?
+        .with_thresholds(config.interaction.vad_thresholds());
+    let vad2 = VadEngine::silero(&vad_str, TARGET_RATE)?
+        .with_thresholds(config.interaction.vad_thresholds());
 
     println!("Loading transcriber models...");
-    let parakeet_path = model_manager::resolve_model_path(model_manager::PARAKEET_DIR);
+    let parakeet_path = model_manager::transcriber_model_path(&config);
     let parakeet_str = parakeet_path.to_string_lossy();
-    let transcriber1 = Transcriber::new(&parakeet_str)?;
-    let transcriber2 = Transcriber::new(&parakeet_str)?;
+    let transcriber1 = Transcriber::new(&parakeet_str)?
+        .with_silence_floor(config.transcriber.silence_floor)
+        .with_agc(config.audio.agc_target())
+        .with_normalizer(TextNormalizer::new(&config.normalize));
+    let transcriber2 = Transcriber::new(&parakeet_str)?
+        .with_silence_floor(config.transcriber.silence_floor)
+        .with_agc(config.audio.agc_target())
+        .with_normalizer(TextNormalizer::new(&config.normalize));
 
     // Shared transcript channel (both pipelines write here)
     let (transcript_tx, transcript_rx) = flume::bounded::<Transcript>(20);
@@ -476,6 +631,10 @@ pub fn run_multi_source(
     let label1 = source1.label();
     let label2 = source2.label();
 
+    // One pause toggle for both sources
+    let paused = Arc::new(AtomicBool::new(false));
+    spawn_pause_toggle(paused.clone());
+
     // Pipeline 1
     let (audio_tx1, audio_rx1) = flume::bounded::<Vec<f32>>(100);
     let (segment_tx1, segment_rx1) = flume::bounded::<AudioSegment>(10);
@@ -483,9 +642,10 @@ pub fn run_multi_source(
 
     let running1 = running.clone();
     let source1_clone = source1.clone();
+    let input_device1 = config.audio.input_device.clone();
     let capture1 = thread::spawn(move || {
         let result = match source1_clone {
-            AudioSource::Mic => capture_mic_with_tap(audio_tx1, None, running1),
+            AudioSource::Mic => capture_mic_with_tap(audio_tx1, None, running1, input_device1),
             AudioSource::System => capture_system_with_tap(audio_tx1, None, running1, None),
             AudioSource::App(name) => {
                 capture_system_with_tap(audio_tx1, None, running1, Some(name))
@@ -497,13 +657,15 @@ pub fn run_multi_source(
     });
 
     let running1_seg = running.clone();
+    let paused1 = paused.clone();
     let seg1 = thread::spawn(move || {
         if let Err(e) = run_segmenter(
             audio_rx1,
             segment_tx1,
             vad1,
-            SegmenterConfig::default(),
+            SegmenterConfig::default().with_pause(paused1),
             running1_seg,
+            vad_log1,
         ) {
             eprintln!("Segmenter 1 error: {}", e);
         }
@@ -528,9 +690,10 @@ pub fn run_multi_source(
 
     let running2 = running.clone();
     let source2_clone = source2.clone();
+    let input_device2 = config.audio.input_device.clone();
     let capture2 = thread::spawn(move || {
         let result = match source2_clone {
-            AudioSource::Mic => capture_mic_with_tap(audio_tx2, None, running2),
+            AudioSource::Mic => capture_mic_with_tap(audio_tx2, None, running2, input_device2),
             AudioSource::System => capture_system_with_tap(audio_tx2, None, running2, None),
             AudioSource::App(name) => {
                 capture_system_with_tap(audio_tx2, None, running2, Some(name))
@@ -542,13 +705,15 @@ pub fn run_multi_source(
     });
 
     let running2_seg = running.clone();
+    let paused2 = paused.clone();
     let seg2 = thread::spawn(move || {
         if let Err(e) = run_segmenter(
             audio_rx2,
             segment_tx2,
             vad2,
-            SegmenterConfig::default(),
+            SegmenterConfig::default().with_pause(paused2),
             running2_seg,
+            vad_log2,
         ) {
             eprintln!("Segmenter 2 error: {}", e);
         }
@@ -569,11 +734,11 @@ pub fn run_multi_source(
 
     // Writer on main thread
     println!(
-        "Recording from [{}] and [{}]... Press Ctrl+C to stop.\n",
+        "Recording from [{}] and [{}]... Type p + Enter to pause/resume, Ctrl+C to stop.\n",
         source1.label(),
         source2.label()
     );
-    run_writer(transcript_rx, output, running.clone())?;
+    run_writer(transcript_rx, output, running.clone(), format)?;
 
     // Wait for threads
     let _ = capture1.join();
@@ -585,3 +750,55 @@ pub fn run_multi_source(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transcript(start: f32, source: &str) -> Transcript {
+        Transcript {
+            start,
+            end: start + 1.0,
+            text: format!("at {}", start),
+            source: Some(source.to_string()),
+            words: Vec::new(),
+            partial: false,
+        }
+    }
+
+    fn starts(transcripts: &[Transcript]) -> Vec<f32> {
+        transcripts.iter().map(|t| t.start).collect()
+    }
+
+    #[test]
+    fn test_reorder_buffer_sorts_within_window() {
+        let t0 = Instant::now();
+        let ms = |n| t0 + Duration::from_millis(n);
+        let mut buffer = ReorderBuffer::new(Duration::from_millis(500));
+
+        // The mic transcriber finishes first, system audio catches up 200ms later
+        buffer.push(transcript(3.0, "mic"), ms(0));
+        buffer.push(transcript(5.0, "mic"), ms(100));
+        buffer.push(transcript(2.0, "system"), ms(200));
+        buffer.push(transcript(4.0, "system"), ms(300));
+        assert!(buffer.ready(ms(400)).is_empty());
+
+        // 2.0 arrived last among the first pair, so nothing is released until it's held
+        assert!(buffer.ready(ms(600)).is_empty());
+        assert_eq!(starts(&buffer.ready(ms(700))), [2.0, 3.0]);
+        assert_eq!(starts(&buffer.ready(ms(800))), [4.0, 5.0]);
+        assert!(buffer.flush().is_empty());
+    }
+
+    #[test]
+    fn test_reorder_buffer_flush_keeps_order() {
+        let now = Instant::now();
+        let mut buffer = ReorderBuffer::new(Duration::from_millis(500));
+        buffer.push(transcript(1.5, "system"), now);
+        buffer.push(transcript(0.5, "mic"), now);
+        buffer.push(transcript(1.0, "mic"), now);
+        let flushed = buffer.flush();
+        assert_eq!(starts(&flushed), [0.5, 1.0, 1.5]);
+        assert_eq!(flushed[0].source.as_deref(), Some("mic"));
+    }
+}
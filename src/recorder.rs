@@ -0,0 +1,131 @@
+//! Session recording for diagnosing misheard input (`--save-session <dir>`)
+//!
+//! Each finalized utterance is written as `utterance-NNNN.wav` (16kHz mono),
+//! and `session.jsonl` gets one line per transcript and per LLM response:
+//!
+//! ```text
+//! {"utterance":1,"wav":"utterance-0001.wav","transcript":"hey silly what time is it"}
+//! {"utterance":1,"response":"It's three o'clock."}
+//! ```
+//!
+//! Responses are tagged with the latest utterance, so typed input or an
+//! utterance that didn't reach the LLM shows up as a transcript without one.
+
+use crate::audiofile::write_wav;
+use serde::Serialize;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+const SAMPLE_RATE: u32 = 16000;
+const LOG_FILE: &str = "session.jsonl";
+
+#[derive(Serialize)]
+#[serde(untagged)]
+enum Entry<'a> {
+    Transcript {
+        utterance: usize,
+        wav: &'a str,
+        transcript: &'a str,
+    },
+    Response {
+        utterance: usize,
+        response: &'a str,
+    },
+}
+
+pub struct SessionRecorder {
+    dir: PathBuf,
+    log: BufWriter<File>,
+    utterances: usize,
+}
+
+pub type SharedRecorder = Arc<Mutex<SessionRecorder>>;
+
+impl SessionRecorder {
+    /// Create `dir` if needed and append to its log
+    pub fn new(dir: &Path) -> std::io::Result<Self> {
+        fs::create_dir_all(dir)?;
+        let log = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(dir.join(LOG_FILE))?;
+        // Continue numbering after any utterances already in the directory
+        let utterances = fs::read_dir(dir)?
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| {
+                entry
+                    .file_name()
+                    .to_string_lossy()
+                    .starts_with("utterance-")
+            })
+            .count();
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            log: BufWriter::new(log),
+            utterances,
+        })
+    }
+
+    pub fn shared(self) -> SharedRecorder {
+        Arc::new(Mutex::new(self))
+    }
+
+    /// Save a finalized utterance and its transcript
+    pub fn record_utterance(&mut self, samples: &[f32], transcript: &str) -> std::io::Result<()> {
+        self.utterances += 1;
+        let wav = format!("utterance-{:04}.wav", self.utterances);
+        write_wav(&self.dir.join(&wav), samples, SAMPLE_RATE)?;
+        self.write(&Entry::Transcript {
+            utterance: self.utterances,
+            wav: &wav,
+            transcript,
+        })
+    }
+
+    /// Log the LLM response to the latest utterance
+    pub fn record_response(&mut self, response: &str) -> std::io::Result<()> {
+        self.write(&Entry::Response {
+            utterance: self.utterances,
+            response,
+        })
+    }
+
+    fn write(&mut self, entry: &Entry) -> std::io::Result<()> {
+        serde_json::to_writer(&mut self.log, entry)?;
+        self.log.write_all(b"\n")?;
+        self.log.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_session() {
+        let dir = std::env::temp_dir().join(format!("silly-session-{}", std::process::id()));
+        let mut recorder = SessionRecorder::new(&dir).unwrap();
+        recorder
+            .record_utterance(&[0.1; 1600], "what time is it")
+            .unwrap();
+        recorder.record_response("It's noon.").unwrap();
+
+        let log = fs::read_to_string(dir.join(LOG_FILE)).unwrap();
+        let lines: Vec<&str> = log.lines().collect();
+        assert_eq!(
+            lines,
+            [
+                r#"{"utterance":1,"wav":"utterance-0001.wav","transcript":"what time is it"}"#,
+                r#"{"utterance":1,"response":"It's noon."}"#,
+            ]
+        );
+        assert!(dir.join("utterance-0001.wav").exists());
+
+        // Reopening continues the numbering
+        let recorder = SessionRecorder::new(&dir).unwrap();
+        assert_eq!(recorder.utterances, 1);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -1,26 +1,12 @@
 //! UI event types and sender for cross-thread communication
 
+use crate::logger;
 use crate::state::AppMode;
-use std::fs::OpenOptions;
+use serde::Serialize;
 use std::io;
-use std::io::Write;
-
-fn debug_log(msg: &str) {
-    if let Ok(mut file) = OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open("debug.log")
-    {
-        let _ = writeln!(
-            file,
-            "{}: {}",
-            chrono::Utc::now().format("%H:%M:%S%.3f"),
-            msg
-        );
-    }
-}
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
 pub enum UiEvent {
     Preview(String),
     Final(String),
@@ -30,6 +16,8 @@ pub enum UiEvent {
     ResponseChunk(String),
     ResponseEnd,
     Idle,
+    /// The wake word was heard while waiting for it
+    WakeDetected,
     Tick,
     ContextWords(usize),
     SwitchUiMode(UiMode),
@@ -37,7 +25,8 @@ pub enum UiEvent {
 }
 
 /// UI mode selection
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "lowercase")]
 #[allow(dead_code)]
 pub enum UiMode {
     /// Text-based terminal UI (default)
@@ -45,20 +34,83 @@ pub enum UiMode {
     Text,
     /// Orb visualization mode
     Orb,
+    /// Scrolling waveform bars
+    Waveform,
+    /// Headless JSON lines on stdin/stdout (`--events-json`)
+    Json,
 }
 
 /// Visual style for the graphical orb UI
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(u8)]
 pub enum OrbStyle {
     /// Volumetric noise blob
     #[default]
-    Blob,
+    Blob = 0,
     /// Simple rotating ring
-    Ring,
+    Ring = 1,
     /// Concentric glowing orbs (horizontal ellipses)
-    Orbs,
+    Orbs = 2,
     /// Particle sphere with displacement and noise
-    Sphere,
+    Sphere = 3,
+    /// Low-power pulsing dot, redrawn at ~10fps (not part of the style cycle)
+    Minimal = 4,
+}
+
+impl OrbStyle {
+    /// Next style in the cycle (Blob -> Ring -> Orbs -> Sphere -> Blob)
+    pub fn next(self) -> Self {
+        match self {
+            OrbStyle::Blob => OrbStyle::Ring,
+            OrbStyle::Ring => OrbStyle::Orbs,
+            OrbStyle::Orbs => OrbStyle::Sphere,
+            OrbStyle::Sphere | OrbStyle::Minimal => OrbStyle::Blob,
+        }
+    }
+
+    /// Previous style in the cycle
+    pub fn prev(self) -> Self {
+        match self {
+            OrbStyle::Blob | OrbStyle::Minimal => OrbStyle::Sphere,
+            OrbStyle::Sphere => OrbStyle::Orbs,
+            OrbStyle::Orbs => OrbStyle::Ring,
+            OrbStyle::Ring => OrbStyle::Blob,
+        }
+    }
+
+    /// Parse a style name as used in config ("blob", "ring", "orbs", "sphere", "minimal")
+    pub fn from_name(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "blob" => Some(OrbStyle::Blob),
+            "ring" => Some(OrbStyle::Ring),
+            "orbs" => Some(OrbStyle::Orbs),
+            "sphere" => Some(OrbStyle::Sphere),
+            "minimal" => Some(OrbStyle::Minimal),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            OrbStyle::Blob => "Blob",
+            OrbStyle::Ring => "Ring",
+            OrbStyle::Orbs => "Orbs",
+            OrbStyle::Sphere => "Sphere",
+            OrbStyle::Minimal => "Minimal",
+        }
+    }
+}
+
+impl From<u8> for OrbStyle {
+    fn from(v: u8) -> Self {
+        match v {
+            1 => OrbStyle::Ring,
+            2 => OrbStyle::Orbs,
+            3 => OrbStyle::Sphere,
+            4 => OrbStyle::Minimal,
+            _ => OrbStyle::Blob,
+        }
+    }
 }
 
 /// Trait for UI renderers - allows swapping between text and graphical UI
@@ -99,6 +151,9 @@ pub trait UiRenderer: Send {
     /// Set current application mode
     fn set_mode(&mut self, mode: AppMode);
 
+    /// Set the transcription language indicator (`None` for the default model)
+    fn set_language(&mut self, language: Option<&str>);
+
     /// Set the ready state
     fn set_ready(&mut self);
 
@@ -108,6 +163,16 @@ pub trait UiRenderer: Send {
     /// Set current audio input level (0.0-1.0)
     fn set_audio_level(&mut self, level: f32);
 
+    /// Flag sustained input clipping (too much mic gain)
+    fn set_clipping(&mut self, _clipping: bool) {
+        // Default no-op for UIs without a status bar
+    }
+
+    /// Set current audio input spectrum, one level (0.0-1.0) per band
+    fn set_audio_bands(&mut self, _bands: [f32; crate::spectrum::NUM_BANDS]) {
+        // Default no-op for text UI
+    }
+
     /// Set current TTS output level (0.0-1.0)
     fn set_tts_level(&mut self, level: f32);
 
@@ -169,6 +234,10 @@ impl Ui {
         let _ = self.tx.send(UiEvent::Idle);
     }
 
+    pub fn wake_detected(&self) {
+        let _ = self.tx.send(UiEvent::WakeDetected);
+    }
+
     pub fn show_error(&self, msg: &str) {
         let _ = self.tx.send(UiEvent::Error(msg.to_string()));
     }
@@ -198,11 +267,8 @@ impl Ui {
     }
 
     pub fn request_ui_mode_switch(&self, mode: UiMode) {
-        debug_log(&format!(
-            "request_ui_mode_switch called with mode: {:?}",
-            mode
-        ));
+        logger::debug!("request_ui_mode_switch called with mode: {:?}", mode);
         let _ = self.tx.send(UiEvent::SwitchUiMode(mode));
-        debug_log("SwitchUiMode event sent");
+        logger::debug!("SwitchUiMode event sent");
     }
 }
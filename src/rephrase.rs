@@ -1,4 +1,4 @@
-use crate::config::{Config, LlmConfig};
+use crate::config::{self, Config, LlmConfig};
 use crate::llm::{LlmBackend, Message, Role};
 use std::fs;
 use std::io::{self, Read, Write, stdout};
@@ -56,7 +56,7 @@ pub fn run_rephrase(
         return Ok(());
     }
 
-    let config = Config::load();
+    let config = Config::load().map_err(|p| config::problem_report(&p))?;
     let mut backend = create_backend(&config.llm, REPHRASE_SYSTEM)?;
 
     eprintln!("Rephrasing...\n");
@@ -67,6 +67,7 @@ pub fn run_rephrase(
             "Rephrase the following text. Correct spelling and grammar, check facts, and provide multiple tones.\n\n{}",
             content
         ),
+        time: None,
     }];
 
     backend.generate(&messages, &mut |token| {
@@ -114,10 +115,11 @@ fn create_backend(
             Err("llama-cpp not enabled. Build with --features llama-cpp".into())
         }
         #[cfg(feature = "ollama")]
-        LlmConfig::Ollama { model } => Ok(Box::new(crate::llm::ollama::OllamaBackend::new(
+        LlmConfig::Ollama { model, host } => Ok(Box::new(crate::llm::ollama::OllamaBackend::new(
             model,
+            host,
             _system_prompt,
-        ))),
+        )?)),
         #[cfg(not(feature = "ollama"))]
         LlmConfig::Ollama { .. } => Err("Ollama not enabled. Build with --features ollama".into()),
         #[cfg(feature = "openai-compat")]
@@ -1,9 +1,11 @@
 //! REPL input handling - keyboard and voice input processing
 
 use crate::command::{CommandProcessor, CommandResult};
-use crate::render::Ui;
+use crate::normalize::TextNormalizer;
+use crate::render::{OrbStyle, Ui};
 use crate::state::{AppMode, SharedState};
 use crate::wake::WakeWord;
+use std::path::PathBuf;
 use std::time::{Duration, Instant};
 
 /// Events from audio transcription pipeline
@@ -24,6 +26,8 @@ pub enum TranscriptResult {
     TypeText(String),
     /// Command was handled (with optional response message)
     CommandHandled(Option<String>),
+    /// Command was handled with a reply to speak
+    Announce(String),
     /// Stop command (cancel TTS)
     Stop,
     /// Mode change command
@@ -33,6 +37,20 @@ pub enum TranscriptResult {
     },
     /// Shutdown requested
     Shutdown,
+    /// Orb visual style change
+    SetVisualStyle(OrbStyle),
+    /// Replay the last spoken response
+    Replay,
+    /// Switch the LLM model
+    SetModel(String),
+    /// Switch the TTS voice, or list the voices
+    Voice(Option<String>),
+    /// Reload the persona, optionally from a new file
+    LoadPersona(Option<String>),
+    /// Export the conversation to Markdown
+    Export(PathBuf),
+    /// Save the last spoken response to a WAV file
+    SaveClip(PathBuf),
     /// No action needed
     None,
 }
@@ -63,7 +81,7 @@ pub fn handle_transcript(
                 text
             } else {
                 match wake_word.detect(&text) {
-                    Some(cmd) => cmd,
+                    Some(m) => m.rest,
                     None => return None,
                 }
             };
@@ -85,6 +103,8 @@ pub fn handle_transcript(
 /// - Transcribe: STT only, no LLM processing
 /// - NoteTaking: Append to notes file
 /// - Command: Only processes commands, no LLM
+/// - Typing: Types the text; the only mode whose text isn't normalized
+#[allow(clippy::too_many_arguments)]
 pub fn handle_transcript_with_mode(
     event: TranscriptEvent,
     wake_word: &WakeWord,
@@ -92,12 +112,18 @@ pub fn handle_transcript_with_mode(
     _wake_timeout: Duration,
     state: &SharedState,
     command_processor: &CommandProcessor,
+    normalizer: &TextNormalizer,
     ui: &Ui,
 ) -> TranscriptResult {
     let mode = state.mode();
     let _wake_enabled = state.wake_enabled.load(std::sync::atomic::Ordering::SeqCst);
 
+    // Audio only arrives while muted with `interaction.unmute_on_wake`, and
+    // then the wake word is the only thing that counts
+    let muted = state.mic_muted.load(std::sync::atomic::Ordering::SeqCst);
+
     match event {
+        TranscriptEvent::Preview(_) if muted => TranscriptResult::None,
         TranscriptEvent::Preview(text) => {
             ui.set_preview(text);
             TranscriptResult::None
@@ -108,9 +134,53 @@ pub fn handle_transcript_with_mode(
             if text.is_empty() {
                 return TranscriptResult::None;
             }
+            // Typing mode gets the raw words: "equals equals" is code, and
+            // "had had" may be meant
+            let text = if mode == AppMode::Typing {
+                text
+            } else {
+                normalizer.normalize(&text)
+            };
+
+            if muted {
+                let Some(m) = wake_word.detect(&text) else {
+                    return TranscriptResult::None;
+                };
+                crate::logger::debug!("Wake phrase matched while muted: {:?}", m.phrase);
+                state
+                    .mic_muted
+                    .store(false, std::sync::atomic::Ordering::SeqCst);
+                state.update_last_interaction();
+                // The wake word also resumes, as it does unmuted; handling the
+                // rest in Paused mode would want the wake word a second time
+                if mode == AppMode::Paused {
+                    return TranscriptResult::ModeChange {
+                        mode: AppMode::Chat,
+                        announcement: Some(format!("Resumed. {}", m.rest)),
+                    };
+                }
+                if m.rest.is_empty() {
+                    return TranscriptResult::CommandHandled(Some("Unmuted".to_string()));
+                }
+                // Anything after the wake word is handled as if spoken unmuted
+                return handle_transcript_with_mode(
+                    TranscriptEvent::Final(m.rest),
+                    wake_word,
+                    _last_interaction,
+                    _wake_timeout,
+                    state,
+                    command_processor,
+                    normalizer,
+                    ui,
+                );
+            }
 
-            // First, check if this is a command (in all modes except Transcribe/NoteTaking)
-            let should_check_commands = !matches!(mode, AppMode::Transcribe | AppMode::NoteTaking);
+            // First, check if this is a command (in all modes except Transcribe,
+            // NoteTaking and Typing, where only mode changes are recognized)
+            let should_check_commands = !matches!(
+                mode,
+                AppMode::Transcribe | AppMode::NoteTaking | AppMode::Typing
+            );
 
             if should_check_commands {
                 let cmd_result = command_processor.process(&text, state);
@@ -118,9 +188,25 @@ pub fn handle_transcript_with_mode(
                     CommandResult::Stop => return TranscriptResult::Stop,
                     CommandResult::Shutdown => return TranscriptResult::Shutdown,
                     CommandResult::Handled(msg) => return TranscriptResult::CommandHandled(msg),
+                    CommandResult::Announce(msg) => return TranscriptResult::Announce(msg),
                     CommandResult::ModeChange { mode, announcement } => {
                         return TranscriptResult::ModeChange { mode, announcement };
                     }
+                    CommandResult::SetVisualStyle(style) => {
+                        return TranscriptResult::SetVisualStyle(style);
+                    }
+                    CommandResult::Replay => return TranscriptResult::Replay,
+                    CommandResult::SetModel(name) => return TranscriptResult::SetModel(name),
+                    CommandResult::Voice(name) => return TranscriptResult::Voice(name),
+                    CommandResult::Export { path } => return TranscriptResult::Export(path),
+                    CommandResult::SaveClip { path } => return TranscriptResult::SaveClip(path),
+                    CommandResult::LoadPersona(file) => {
+                        return TranscriptResult::LoadPersona(file);
+                    }
+                    // Only produced by slash commands
+                    CommandResult::SaveHistory(_) | CommandResult::LoadHistory(_) => {
+                        return TranscriptResult::None;
+                    }
                     CommandResult::PassThrough(text) => {
                         // Not a command, continue with mode-specific handling
                         match mode {
@@ -132,35 +218,45 @@ pub fn handle_transcript_with_mode(
                             AppMode::Paused => {
                                 // Paused mode: requires wake word to resume
                                 match wake_word.detect(&text) {
-                                    Some(cmd) => {
+                                    Some(m) => {
                                         // Wake word detected - resume conversation and process command
+                                        crate::logger::debug!(
+                                            "Wake phrase matched: {:?}",
+                                            m.phrase
+                                        );
                                         state.update_last_interaction();
                                         // Auto-switch to Chat mode
                                         TranscriptResult::ModeChange {
                                             mode: AppMode::Chat,
-                                            announcement: Some(format!("Resumed. {}", cmd)),
+                                            announcement: Some(format!("Resumed. {}", m.rest)),
                                         }
                                     }
                                     None => TranscriptResult::None,
                                 }
                             }
-                            AppMode::Command => {
-                                // Command mode: if not a command, show message
-                                TranscriptResult::CommandHandled(Some(format!(
-                                    "[Not a command] {}",
-                                    text
-                                )))
-                            }
                             _ => TranscriptResult::None,
                         }
                     }
                 }
             } else {
-                // Transcribe, NoteTaking, and Typing modes don't process commands
+                // Transcribe, NoteTaking and Typing modes don't process commands
                 match mode {
                     AppMode::Transcribe => TranscriptResult::TranscribeOnly(text),
-                    AppMode::NoteTaking => TranscriptResult::AppendNote(text),
-                    AppMode::Typing => TranscriptResult::TypeText(text),
+                    // Mode commands still work, so "start chat" ends note-taking
+                    // or dictation; anything else is the user's words
+                    AppMode::NoteTaking | AppMode::Typing => {
+                        match command_processor.check_mode_change(&text) {
+                            Some(CommandResult::ModeChange {
+                                mode: new_mode,
+                                announcement,
+                            }) if new_mode != mode => TranscriptResult::ModeChange {
+                                mode: new_mode,
+                                announcement,
+                            },
+                            _ if mode == AppMode::Typing => TranscriptResult::TypeText(text),
+                            _ => TranscriptResult::AppendNote(text),
+                        }
+                    }
                     _ => TranscriptResult::None,
                 }
             }
@@ -168,15 +264,12 @@ pub fn handle_transcript_with_mode(
     }
 }
 
-/// Append text to the notes file
-pub fn append_to_notes(text: &str) -> std::io::Result<()> {
+/// Append text to the notes file (`notes.file`)
+pub fn append_to_notes(path: &str, text: &str) -> std::io::Result<()> {
     use std::fs::OpenOptions;
     use std::io::Write;
 
-    let mut file = OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open("notes.txt")?;
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
 
     // Add timestamp
     let timestamp = chrono::Local::now().format("%Y-%m-%d %H:%M:%S");
@@ -184,3 +277,134 @@ pub fn append_to_notes(text: &str) -> std::io::Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::render::UiEvent;
+    use crate::state::RuntimeState;
+
+    struct Harness {
+        wake_word: WakeWord,
+        state: SharedState,
+        commands: CommandProcessor,
+        normalizer: TextNormalizer,
+        ui: Ui,
+        _events: flume::Receiver<UiEvent>,
+    }
+
+    impl Harness {
+        fn new(mode: AppMode) -> Self {
+            let config = Config::default();
+            let (ui, events) = Ui::new();
+            let state = RuntimeState::new(&config);
+            state.set_mode(mode);
+            Self {
+                wake_word: WakeWord::new(&["hey silly"]),
+                state,
+                commands: CommandProcessor::new(&config),
+                normalizer: TextNormalizer::new(&config.normalize),
+                ui,
+                _events: events,
+            }
+        }
+
+        fn hear(&self, text: &str) -> TranscriptResult {
+            handle_transcript_with_mode(
+                TranscriptEvent::Final(text.to_string()),
+                &self.wake_word,
+                None,
+                Duration::from_secs(30),
+                &self.state,
+                &self.commands,
+                &self.normalizer,
+                &self.ui,
+            )
+        }
+    }
+
+    #[test]
+    fn test_typing_mode_gets_raw_text() {
+        let harness = Harness::new(AppMode::Typing);
+        let TranscriptResult::TypeText(text) = harness.hear("x equals equals y") else {
+            panic!("expected text to type");
+        };
+        assert_eq!(text, "x equals equals y");
+
+        #[cfg(feature = "typing")]
+        {
+            use crate::typing::{InputMethod, KeyPacing, TypingProcessor};
+            let mut typing =
+                TypingProcessor::new(InputMethod::DryRun, 50, false, 100, KeyPacing::default())
+                    .unwrap();
+            typing.process_segment("code mode on", 500).unwrap();
+            typing.process_segment(&text, 0).unwrap();
+            assert_eq!(typing.dry_run_transcript(), ["type \"x == y\""]);
+        }
+
+        // Other modes still get the clean-up
+        let harness = Harness::new(AppMode::Chat);
+        let TranscriptResult::SendToLlm(text) = harness.hear("um the the plan") else {
+            panic!("expected text for the LLM");
+        };
+        assert_eq!(text, "The plan");
+    }
+
+    #[test]
+    fn test_wake_while_muted_and_paused() {
+        let harness = Harness::new(AppMode::Paused);
+        let muted = || {
+            harness
+                .state
+                .mic_muted
+                .load(std::sync::atomic::Ordering::SeqCst)
+        };
+        for (heard, announcement) in [
+            ("Hey Silly, what's the time", "Resumed. what's the time"),
+            ("Hey Silly", "Resumed. "),
+        ] {
+            harness
+                .state
+                .mic_muted
+                .store(true, std::sync::atomic::Ordering::SeqCst);
+            assert!(matches!(
+                harness.hear(heard),
+                TranscriptResult::ModeChange { mode: AppMode::Chat, announcement: Some(ref a) }
+                    if a == announcement
+            ));
+            assert!(!muted());
+        }
+
+        // Muted without the wake word, nothing gets through
+        harness
+            .state
+            .mic_muted
+            .store(true, std::sync::atomic::Ordering::SeqCst);
+        assert!(matches!(
+            harness.hear("what's the time"),
+            TranscriptResult::None
+        ));
+        assert!(muted());
+    }
+
+    #[test]
+    fn test_typing_mode_only_hears_mode_changes() {
+        let harness = Harness::new(AppMode::Typing);
+        for dictated in [
+            "then switch to command mode in the editor",
+            "the laptop has a battery saver",
+            "resume the upload",
+        ] {
+            let TranscriptResult::TypeText(text) = harness.hear(dictated) else {
+                panic!("expected {:?} to be typed", dictated);
+            };
+            assert_eq!(text, dictated);
+        }
+
+        let TranscriptResult::ModeChange { mode, .. } = harness.hear("Start chat.") else {
+            panic!("expected a mode change");
+        };
+        assert_eq!(mode, AppMode::Chat);
+    }
+}
@@ -0,0 +1,193 @@
+//! Band-limited sample rate conversion for captured and loaded audio
+//!
+//! Backed by rubato's FFT resampler, which low-pass filters before decimating
+//! so content above the target Nyquist doesn't fold back into the speech band.
+
+use rubato::{FftFixedIn, Resampler};
+
+/// Input samples fed to rubato per call
+const RESAMPLE_CHUNK: usize = 1024;
+
+/// Incremental resampler for capture callbacks that deliver arbitrary block sizes
+///
+/// Input is buffered until a full chunk is available, so output lags the input
+/// by up to one chunk plus the filter delay. Equal rates pass through untouched.
+pub struct StreamResampler {
+    resampler: Option<FftFixedIn<f32>>,
+    in_buf: Vec<f32>,
+}
+
+impl StreamResampler {
+    pub fn new(
+        from_rate: usize,
+        to_rate: usize,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let resampler = if from_rate == to_rate {
+            None
+        } else {
+            Some(FftFixedIn::<f32>::new(
+                from_rate,
+                to_rate,
+                RESAMPLE_CHUNK,
+                1,
+                1,
+            )?)
+        };
+        Ok(Self {
+            resampler,
+            in_buf: Vec::with_capacity(RESAMPLE_CHUNK),
+        })
+    }
+
+    /// Feed samples, returning whatever output is ready (possibly empty)
+    pub fn process(&mut self, samples: &[f32]) -> Vec<f32> {
+        let Some(resampler) = self.resampler.as_mut() else {
+            return samples.to_vec();
+        };
+
+        self.in_buf.extend_from_slice(samples);
+
+        let mut out = Vec::new();
+        while self.in_buf.len() >= RESAMPLE_CHUNK {
+            let chunk: Vec<f32> = self.in_buf.drain(..RESAMPLE_CHUNK).collect();
+            if let Ok(resampled) = resampler.process(&[&chunk], None) {
+                out.extend_from_slice(&resampled[0]);
+            }
+        }
+        out
+    }
+
+    /// Push buffered input through, padding with silence to fill the last chunk
+    pub fn flush(&mut self) -> Vec<f32> {
+        let Some(resampler) = self.resampler.as_mut() else {
+            return Vec::new();
+        };
+
+        let tail = std::mem::take(&mut self.in_buf);
+        resampler
+            .process_partial(Some(&[&tail]), None)
+            .map(|mut out| out.swap_remove(0))
+            .unwrap_or_default()
+    }
+
+    /// Output samples of filter delay before the first input sample appears
+    fn output_delay(&self) -> usize {
+        self.resampler.as_ref().map_or(0, |r| r.output_delay())
+    }
+}
+
+/// Resample a complete buffer (e.g. a loaded file), compensating for filter delay
+pub fn resample(
+    samples: &[f32],
+    from_rate: usize,
+    to_rate: usize,
+) -> Result<Vec<f32>, Box<dyn std::error::Error + Send + Sync>> {
+    if from_rate == to_rate {
+        return Ok(samples.to_vec());
+    }
+
+    let mut resampler = StreamResampler::new(from_rate, to_rate)?;
+    let delay = resampler.output_delay();
+    let expected = (samples.len() as f64 * to_rate as f64 / from_rate as f64) as usize;
+
+    let mut out = resampler.process(samples);
+    while out.len() < delay + expected {
+        let tail = resampler.flush();
+        if tail.is_empty() {
+            break;
+        }
+        out.extend_from_slice(&tail);
+    }
+
+    Ok(out.into_iter().skip(delay).take(expected).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::PI;
+
+    /// The linear interpolator this module replaced, kept as a baseline
+    fn resample_linear(samples: &[f32], from_rate: usize, to_rate: usize) -> Vec<f32> {
+        let ratio = to_rate as f64 / from_rate as f64;
+        let new_len = (samples.len() as f64 * ratio) as usize;
+        (0..new_len)
+            .map(|i| {
+                let src_idx = i as f64 / ratio;
+                let idx = src_idx as usize;
+                let frac = src_idx - idx as f64;
+                if idx + 1 < samples.len() {
+                    samples[idx] * (1.0 - frac as f32) + samples[idx + 1] * frac as f32
+                } else {
+                    samples.get(idx).copied().unwrap_or(0.0)
+                }
+            })
+            .collect()
+    }
+
+    fn sine(freq: f32, rate: usize, secs: f32) -> Vec<f32> {
+        (0..(rate as f32 * secs) as usize)
+            .map(|i| (2.0 * PI * freq * i as f32 / rate as f32).sin() * 0.5)
+            .collect()
+    }
+
+    /// Goertzel magnitude of `freq`, normalised to amplitude
+    fn tone_level(samples: &[f32], freq: f32, rate: usize) -> f32 {
+        let coeff = 2.0 * (2.0 * PI * freq / rate as f32).cos();
+        let (mut s1, mut s2) = (0.0f32, 0.0f32);
+        for &x in samples {
+            let s0 = x + coeff * s1 - s2;
+            s2 = s1;
+            s1 = s0;
+        }
+        let power = s1 * s1 + s2 * s2 - coeff * s1 * s2;
+        2.0 * power.max(0.0).sqrt() / samples.len() as f32
+    }
+
+    #[test]
+    fn test_passthrough_at_equal_rates() {
+        let input = sine(440.0, 16000, 0.1);
+        assert_eq!(resample(&input, 16000, 16000).unwrap(), input);
+    }
+
+    #[test]
+    fn test_in_band_tone_preserved() {
+        let input = sine(1000.0, 48000, 1.0);
+        let out = resample(&input, 48000, 16000).unwrap();
+        assert_eq!(out.len(), 16000);
+        // Skip the edges where the filter ramps up and down
+        let level = tone_level(&out[1600..14400], 1000.0, 16000);
+        assert!((level - 0.5).abs() < 0.05, "1kHz level {}", level);
+    }
+
+    #[test]
+    fn test_less_aliasing_than_linear() {
+        // 11kHz is above the 8kHz output Nyquist and folds down to 5kHz
+        let input = sine(11000.0, 48000, 1.0);
+        let linear = resample_linear(&input, 48000, 16000);
+        let sinc = resample(&input, 48000, 16000).unwrap();
+
+        let linear_alias = tone_level(&linear[1600..14400], 5000.0, 16000);
+        let sinc_alias = tone_level(&sinc[1600..14400], 5000.0, 16000);
+
+        assert!(linear_alias > 0.1, "linear alias {}", linear_alias);
+        assert!(
+            sinc_alias < linear_alias / 100.0,
+            "sinc alias {} vs linear {}",
+            sinc_alias,
+            linear_alias
+        );
+    }
+
+    #[test]
+    fn test_streaming_matches_block_sizes() {
+        let input = sine(1000.0, 48000, 0.5);
+        let mut resampler = StreamResampler::new(48000, 16000).unwrap();
+        let mut out = Vec::new();
+        for block in input.chunks(480) {
+            out.extend(resampler.process(block));
+        }
+        out.extend(resampler.flush());
+        assert!(out.len() >= input.len() / 3);
+    }
+}
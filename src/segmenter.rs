@@ -1,17 +1,25 @@
 use crate::vad::VadEngine;
 use flume::{Receiver, Sender};
-use std::io::Write;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 
 const VAD_FRAME_SAMPLES: usize = 480;
 const TARGET_RATE: usize = 16000;
+/// Flush the VAD log every ~3s of audio
+const VAD_LOG_FLUSH_FRAMES: usize = 100;
+/// How far back from the max length to look for a place to split
+const SPLIT_SEARCH_SECS: usize = 3;
 
 #[derive(Clone, Debug)]
 pub struct AudioSegment {
     pub samples: Vec<f32>,
     pub start_sample: usize,
     pub end_sample: usize,
+    /// Speech still in progress, sent for an interim preview
+    pub partial: bool,
 }
 
 impl AudioSegment {
@@ -30,28 +38,113 @@ impl AudioSegment {
 
 pub struct SegmenterConfig {
     pub silence_ms: u32,
+    /// Continuous speech longer than this is split even without a pause
     pub max_segment_secs: u32,
+    /// Length of the quietest stretch a forced split is centred on
+    pub force_split_on_silence_ms: u32,
+    /// While set, audio is discarded but the clock keeps running, so
+    /// timestamps after resuming match real elapsed time
+    pub paused: Option<Arc<AtomicBool>>,
+    /// Send the speech so far as a partial segment every this many seconds
+    pub interim_secs: Option<f32>,
 }
 
 impl Default for SegmenterConfig {
     fn default() -> Self {
         Self {
-            silence_ms: 500,
+            silence_ms: crate::audio::DEFAULT_SILENCE_MS,
             max_segment_secs: 30,
+            force_split_on_silence_ms: 150,
+            paused: None,
+            interim_secs: None,
         }
     }
 }
 
+impl SegmenterConfig {
+    pub fn with_pause(mut self, paused: Arc<AtomicBool>) -> Self {
+        self.paused = Some(paused);
+        self
+    }
+
+    pub fn with_interim(mut self, secs: f32) -> Self {
+        self.interim_secs = Some(secs);
+        self
+    }
+
+    fn is_paused(&self) -> bool {
+        self.paused
+            .as_ref()
+            .is_some_and(|p| p.load(Ordering::SeqCst))
+    }
+}
+
+/// Send the buffered speech as a segment
+fn emit_segment(
+    tx: &Sender<AudioSegment>,
+    speech_buf: &mut Vec<f32>,
+    start_sample: usize,
+    end_sample: usize,
+) {
+    let duration = speech_buf.len() as f32 / TARGET_RATE as f32;
+    println!("[{:.1}s]", duration);
+
+    let segment = AudioSegment {
+        samples: std::mem::take(speech_buf),
+        start_sample,
+        end_sample,
+        partial: false,
+    };
+    let _ = tx.send(segment);
+}
+
+/// Where to cut an over-long segment: the middle of the quietest run of
+/// `window_frames` frames within the last `search` samples, so the cut lands
+/// between words rather than mid-word
+fn quietest_split(samples: &[f32], window_frames: usize, search: usize) -> usize {
+    let energies: Vec<f32> = samples
+        .chunks(VAD_FRAME_SAMPLES)
+        .map(|frame| frame.iter().map(|s| s * s).sum())
+        .collect();
+    let first = energies
+        .len()
+        .saturating_sub(search / VAD_FRAME_SAMPLES)
+        .max(1);
+    if energies.len() < first + window_frames {
+        return samples.len();
+    }
+
+    let best = (first..=energies.len() - window_frames)
+        .min_by(|&a, &b| {
+            let sum = |i: usize| energies[i..i + window_frames].iter().sum::<f32>();
+            sum(a).total_cmp(&sum(b))
+        })
+        .unwrap_or(first);
+    (best + window_frames / 2) * VAD_FRAME_SAMPLES
+}
+
+/// Create a CSV file for per-frame VAD decisions, with header row
+pub fn open_vad_log(path: &Path) -> std::io::Result<BufWriter<File>> {
+    let mut log = BufWriter::new(File::create(path)?);
+    writeln!(log, "time_secs,rms,prob,is_speech,in_speech")?;
+    Ok(log)
+}
+
 pub fn run_segmenter(
     rx: Receiver<Vec<f32>>,
     tx: Sender<AudioSegment>,
     mut vad: VadEngine,
     config: SegmenterConfig,
     running: Arc<AtomicBool>,
+    mut vad_log: Option<BufWriter<File>>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let silence_threshold_frames =
-        (config.silence_ms as usize * TARGET_RATE) / (1000 * VAD_FRAME_SAMPLES);
+    let silence_threshold_frames = crate::audio::silence_ms_to_frames(config.silence_ms);
     let max_samples = config.max_segment_secs as usize * TARGET_RATE;
+    let split_window_frames = crate::audio::silence_ms_to_frames(config.force_split_on_silence_ms);
+    let split_search = SPLIT_SEARCH_SECS * TARGET_RATE;
+    let interim_samples = config
+        .interim_secs
+        .map(|secs| (secs * TARGET_RATE as f32) as usize);
 
     let mut vad_buf: Vec<f32> = Vec::new();
     let mut speech_buf: Vec<f32> = Vec::new();
@@ -60,6 +153,9 @@ pub fn run_segmenter(
     let mut total_samples: usize = 0;
     let mut speech_start_sample: usize = 0;
     let mut first_audio = true;
+    let mut logged_frames: usize = 0;
+    // Length of speech_buf when the last partial was sent
+    let mut interim_len: usize = 0;
 
     while running.load(Ordering::SeqCst) {
         match rx.recv_timeout(std::time::Duration::from_millis(100)) {
@@ -76,7 +172,22 @@ pub fn run_segmenter(
 
         while vad_buf.len() >= VAD_FRAME_SAMPLES {
             let chunk: Vec<f32> = vad_buf.drain(..VAD_FRAME_SAMPLES).collect();
-            let is_speech = vad.is_speech(&chunk, in_speech);
+
+            if config.is_paused() {
+                // Close off speech in progress, then drop audio until resumed
+                if in_speech {
+                    emit_segment(&tx, &mut speech_buf, speech_start_sample, total_samples);
+                    in_speech = false;
+                    silence_frames = 0;
+                    vad.reset();
+                }
+                total_samples += VAD_FRAME_SAMPLES;
+                continue;
+            }
+
+            let decision = vad.classify(&chunk, in_speech);
+            let is_speech = decision.is_speech;
+            let frame_start = total_samples;
 
             if is_speech {
                 if !in_speech {
@@ -91,18 +202,13 @@ pub fn run_segmenter(
                 silence_frames += 1;
                 speech_buf.extend_from_slice(&chunk);
 
-                if silence_frames >= silence_threshold_frames as u32
-                    || speech_buf.len() >= max_samples
-                {
-                    let duration = speech_buf.len() as f32 / TARGET_RATE as f32;
-                    println!("[{:.1}s]", duration);
-
-                    let segment = AudioSegment {
-                        samples: std::mem::take(&mut speech_buf),
-                        start_sample: speech_start_sample,
-                        end_sample: total_samples + VAD_FRAME_SAMPLES,
-                    };
-                    let _ = tx.send(segment);
+                if silence_frames >= silence_threshold_frames as u32 {
+                    emit_segment(
+                        &tx,
+                        &mut speech_buf,
+                        speech_start_sample,
+                        total_samples + VAD_FRAME_SAMPLES,
+                    );
 
                     in_speech = false;
                     silence_frames = 0;
@@ -110,19 +216,245 @@ pub fn run_segmenter(
                 }
             }
 
+            if in_speech && speech_buf.len() >= max_samples {
+                // No pause long enough: cut at the quietest point near the
+                // cap and keep going with the rest
+                let split = quietest_split(&speech_buf, split_window_frames, split_search);
+                let rest = speech_buf.split_off(split);
+                let split_sample = speech_start_sample + split;
+                emit_segment(&tx, &mut speech_buf, speech_start_sample, split_sample);
+                speech_buf = rest;
+                speech_start_sample = split_sample;
+                interim_len = 0;
+            }
+
+            if !in_speech {
+                interim_len = 0;
+            } else if let Some(interval) = interim_samples {
+                // Skip a preview while the transcriber is still busy, the
+                // next one will cover this audio too
+                if speech_buf.len() >= interim_len + interval && tx.is_empty() {
+                    interim_len = speech_buf.len();
+                    let _ = tx.send(AudioSegment {
+                        samples: speech_buf.clone(),
+                        start_sample: speech_start_sample,
+                        end_sample: total_samples + VAD_FRAME_SAMPLES,
+                        partial: true,
+                    });
+                }
+            }
+
+            if let Some(log) = vad_log.as_mut() {
+                let prob = decision
+                    .prob
+                    .map(|p| format!("{:.4}", p))
+                    .unwrap_or_default();
+                let result = writeln!(
+                    log,
+                    "{:.3},{:.5},{},{},{}",
+                    frame_start as f32 / TARGET_RATE as f32,
+                    decision.rms,
+                    prob,
+                    is_speech as u8,
+                    in_speech as u8
+                );
+                logged_frames += 1;
+                let result = result.and_then(|_| {
+                    if logged_frames % VAD_LOG_FLUSH_FRAMES == 0 {
+                        log.flush()
+                    } else {
+                        Ok(())
+                    }
+                });
+                if let Err(e) = result {
+                    eprintln!("VAD log error, disabling: {}", e);
+                    vad_log = None;
+                }
+            }
+
             total_samples += VAD_FRAME_SAMPLES;
         }
     }
 
+    if let Some(mut log) = vad_log {
+        log.flush()?;
+    }
+
     // Flush remaining
     if !speech_buf.is_empty() && speech_buf.len() >= TARGET_RATE / 2 {
         let segment = AudioSegment {
             samples: speech_buf,
             start_sample: speech_start_sample,
             end_sample: total_samples,
+            partial: false,
         };
         let _ = tx.send(segment);
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vad_log_rows() {
+        let path = std::env::temp_dir().join("silly-vad-log-test.csv");
+        let log = open_vad_log(&path).unwrap();
+
+        let (audio_tx, audio_rx) = flume::unbounded();
+        let (segment_tx, _segment_rx) = flume::unbounded();
+        for _ in 0..5 {
+            audio_tx.send(vec![0.0; VAD_FRAME_SAMPLES]).unwrap();
+        }
+        drop(audio_tx);
+
+        run_segmenter(
+            audio_rx,
+            segment_tx,
+            VadEngine::energy(),
+            SegmenterConfig::default(),
+            Arc::new(AtomicBool::new(true)),
+            Some(log),
+        )
+        .unwrap();
+
+        let csv = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "time_secs,rms,prob,is_speech,in_speech");
+        assert_eq!(lines.len(), 6);
+        assert_eq!(lines[2], "0.030,0.00000,,0,0");
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_pause_drops_audio_but_keeps_time() {
+        let (audio_tx, audio_rx) = flume::bounded(0);
+        let (segment_tx, segment_rx) = flume::unbounded();
+        let paused = Arc::new(AtomicBool::new(true));
+        let config = SegmenterConfig::default().with_pause(Arc::clone(&paused));
+        let segmenter = std::thread::spawn(move || {
+            run_segmenter(
+                audio_rx,
+                segment_tx,
+                VadEngine::energy(),
+                config,
+                Arc::new(AtomicBool::new(true)),
+                None,
+            )
+        });
+
+        // 1s of speech while paused, then a silent frame: the rendezvous
+        // channel only accepts it once the speech has been processed
+        let speech = || vec![0.1; VAD_FRAME_SAMPLES];
+        for _ in 0..33 {
+            audio_tx.send(speech()).unwrap();
+        }
+        audio_tx.send(vec![0.0; VAD_FRAME_SAMPLES]).unwrap();
+        paused.store(false, Ordering::SeqCst);
+
+        for _ in 0..33 {
+            audio_tx.send(speech()).unwrap();
+        }
+        for _ in 0..30 {
+            audio_tx.send(vec![0.0; VAD_FRAME_SAMPLES]).unwrap();
+        }
+        drop(audio_tx);
+        segmenter.join().unwrap().unwrap();
+
+        let segments: Vec<AudioSegment> = segment_rx.drain().collect();
+        assert_eq!(segments.len(), 1);
+        // Starts after the paused second plus the silent frame
+        assert_eq!(segments[0].start_sample, 34 * VAD_FRAME_SAMPLES);
+    }
+
+    #[test]
+    fn test_interim_partials_precede_final_segment() {
+        let (audio_tx, audio_rx) = flume::unbounded();
+        let (segment_tx, segment_rx) = flume::unbounded();
+        for _ in 0..66 {
+            audio_tx.send(vec![0.1; VAD_FRAME_SAMPLES]).unwrap();
+        }
+        for _ in 0..30 {
+            audio_tx.send(vec![0.0; VAD_FRAME_SAMPLES]).unwrap();
+        }
+        drop(audio_tx);
+
+        run_segmenter(
+            audio_rx,
+            segment_tx,
+            VadEngine::energy(),
+            SegmenterConfig::default().with_interim(0.5),
+            Arc::new(AtomicBool::new(true)),
+            None,
+        )
+        .unwrap();
+
+        // Nothing consumes the partial, so no second one is queued behind it
+        let segments: Vec<AudioSegment> = segment_rx.drain().collect();
+        assert_eq!(segments.len(), 2);
+        assert!(segments[0].partial);
+        assert!(segments[0].duration_secs() >= 0.5 && segments[0].duration_secs() < 0.6);
+        assert!(!segments[1].partial);
+        assert_eq!(segments[0].start_sample, segments[1].start_sample);
+        assert!(segments[1].samples.starts_with(&segments[0].samples));
+    }
+
+    #[test]
+    fn test_long_speech_splits_at_quietest_point() {
+        let (audio_tx, audio_rx) = flume::unbounded();
+        let (segment_tx, segment_rx) = flume::unbounded();
+
+        // 25s of continuous "speech": a tone with a short dip between words
+        // every second and one deeper dip at 13.5s
+        let frames_per_sec = TARGET_RATE / VAD_FRAME_SAMPLES;
+        let deep_dip = 27 * frames_per_sec / 2;
+        for frame in 0..25 * frames_per_sec {
+            let amp = if (deep_dip..deep_dip + 5).contains(&frame) {
+                0.005
+            } else if frame % frames_per_sec < 5 {
+                0.03
+            } else {
+                0.1
+            };
+            let samples: Vec<f32> = (0..VAD_FRAME_SAMPLES)
+                .map(|i| {
+                    let t = (frame * VAD_FRAME_SAMPLES + i) as f32 / TARGET_RATE as f32;
+                    amp * (t * 200.0 * std::f32::consts::TAU).sin()
+                })
+                .collect();
+            audio_tx.send(samples).unwrap();
+        }
+        for _ in 0..30 {
+            audio_tx.send(vec![0.0; VAD_FRAME_SAMPLES]).unwrap();
+        }
+        drop(audio_tx);
+
+        run_segmenter(
+            audio_rx,
+            segment_tx,
+            VadEngine::energy(),
+            SegmenterConfig {
+                max_segment_secs: 15,
+                ..SegmenterConfig::default()
+            },
+            Arc::new(AtomicBool::new(true)),
+            None,
+        )
+        .unwrap();
+
+        let segments: Vec<AudioSegment> = segment_rx.drain().collect();
+        assert_eq!(segments.len(), 2);
+        // Cut in the middle of the deep dip, not at the 15s cap
+        assert_eq!(segments[0].end_sample, (deep_dip + 2) * VAD_FRAME_SAMPLES);
+        assert_eq!(segments[1].start_sample, segments[0].end_sample);
+        for segment in &segments {
+            assert_eq!(
+                segment.samples.len(),
+                segment.end_sample - segment.start_sample
+            );
+            assert!(segment.duration_secs() > 5.0 && segment.duration_secs() <= 15.0);
+        }
+    }
+}
@@ -0,0 +1,186 @@
+//! Sentence splitting for streaming LLM output into TTS
+//!
+//! Tokens are buffered until a sentence boundary is seen: `.`, `!` or `?`
+//! (plus any closing quotes/brackets) followed by whitespace. Abbreviations and
+//! initialisms don't end a sentence, and very short sentences are merged into
+//! the next so TTS isn't handed single words.
+
+/// Default minimum words before a sentence is released
+pub const DEFAULT_MIN_WORDS: usize = 3;
+
+/// Abbreviations that end in a period but rarely end a sentence
+const ABBREVIATIONS: &[&str] = &[
+    "dr.", "mr.", "mrs.", "ms.", "prof.", "sr.", "jr.", "st.", "vs.", "etc.", "e.g.", "i.e.",
+    "approx.", "fig.", "inc.", "ltd.",
+];
+
+pub struct SentenceSplitter {
+    buffer: String,
+    min_words: usize,
+}
+
+impl Default for SentenceSplitter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SentenceSplitter {
+    pub fn new() -> Self {
+        Self {
+            buffer: String::new(),
+            min_words: DEFAULT_MIN_WORDS,
+        }
+    }
+
+    pub fn with_min_words(mut self, min_words: usize) -> Self {
+        self.min_words = min_words.max(1);
+        self
+    }
+
+    /// Add streamed text, returning any sentences that are now complete
+    pub fn push(&mut self, text: &str) -> Vec<String> {
+        self.buffer.push_str(text);
+
+        let mut sentences = Vec::new();
+        let mut start = 0;
+        while let Some(end) = self.next_boundary(start) {
+            let candidate = self.buffer[..end].trim();
+            if candidate.split_whitespace().count() >= self.min_words {
+                sentences.push(candidate.to_string());
+                self.buffer.drain(..end);
+                start = 0;
+            } else {
+                // Too short on its own, keep it and look for the next boundary
+                start = end;
+            }
+        }
+        sentences
+    }
+
+    /// Flush whatever is left at the end of the response
+    pub fn finish(&mut self) -> Option<String> {
+        let rest = self.buffer.trim().to_string();
+        self.buffer.clear();
+        (!rest.is_empty()).then_some(rest)
+    }
+
+    /// Byte offset just past the next sentence boundary at or after `from`
+    fn next_boundary(&self, from: usize) -> Option<usize> {
+        let buf = &self.buffer;
+        let mut search = from;
+        while let Some(pos) = buf[search..].find(['.', '!', '?']) {
+            let punct = search + pos;
+            // Consume the run of terminal punctuation and closing quotes/brackets
+            let end = punct
+                + buf[punct..]
+                    .find(|c: char| !matches!(c, '.' | '!' | '?' | '"' | '\'' | ')' | ']'))
+                    .unwrap_or(buf.len() - punct);
+
+            // Need to see what follows before committing (e.g. "3.14", "e.g.")
+            let next = buf[end..].chars().next()?;
+            if next.is_whitespace() && !is_abbreviation(&buf[..end]) {
+                return Some(end);
+            }
+            search = end.max(punct + 1);
+        }
+        None
+    }
+}
+
+/// Whether the last word of `text` is an abbreviation or initialism
+fn is_abbreviation(text: &str) -> bool {
+    let word = text.split_whitespace().last().unwrap_or("");
+    let word = word
+        .trim_start_matches(['"', '\'', '(', '['])
+        .to_lowercase();
+    if !word.ends_with('.') {
+        return false;
+    }
+    if ABBREVIATIONS.contains(&word.as_str()) {
+        return true;
+    }
+    // Initialisms like "U.S." or a single initial like "J."
+    let letters = word.trim_end_matches('.');
+    letters
+        .split('.')
+        .all(|part| part.chars().count() == 1 && part.chars().all(char::is_alphabetic))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split_all(splitter: &mut SentenceSplitter, tokens: &[&str]) -> Vec<String> {
+        let mut out: Vec<String> = tokens.iter().flat_map(|t| splitter.push(t)).collect();
+        out.extend(splitter.finish());
+        out
+    }
+
+    #[test]
+    fn test_splits_streamed_tokens() {
+        let mut splitter = SentenceSplitter::new();
+        let out = split_all(
+            &mut splitter,
+            &[
+                "The sky ",
+                "is blue. ",
+                "Grass is",
+                " green! What",
+                " else is new?",
+            ],
+        );
+        assert_eq!(
+            out,
+            vec!["The sky is blue.", "Grass is green!", "What else is new?"]
+        );
+    }
+
+    #[test]
+    fn test_abbreviations_do_not_split() {
+        let mut splitter = SentenceSplitter::new();
+        let out = split_all(
+            &mut splitter,
+            &["Ask Dr. Smith about fruit, e.g. apples and pears. Then rest."],
+        );
+        assert_eq!(
+            out,
+            vec![
+                "Ask Dr. Smith about fruit, e.g. apples and pears.",
+                "Then rest."
+            ]
+        );
+
+        let mut splitter = SentenceSplitter::new();
+        let out = split_all(
+            &mut splitter,
+            &["The U.S. economy grew. It was 3.5 percent."],
+        );
+        assert_eq!(out, vec!["The U.S. economy grew.", "It was 3.5 percent."]);
+    }
+
+    #[test]
+    fn test_short_sentences_merge() {
+        let mut splitter = SentenceSplitter::new();
+        assert!(splitter.push("Hi! ").is_empty());
+        let out = splitter.push("How are you today? ");
+        assert_eq!(out, vec!["Hi! How are you today?"]);
+        assert_eq!(splitter.finish(), None);
+    }
+
+    #[test]
+    fn test_waits_for_following_whitespace() {
+        let mut splitter = SentenceSplitter::new().with_min_words(1);
+        assert!(splitter.push("Pi is 3.").is_empty());
+        assert!(splitter.push("14 roughly.").is_empty());
+        assert_eq!(splitter.push(" Okay"), vec!["Pi is 3.14 roughly."]);
+        assert_eq!(splitter.finish().as_deref(), Some("Okay"));
+    }
+
+    #[test]
+    fn test_closing_quotes_stay_with_sentence() {
+        let mut splitter = SentenceSplitter::new().with_min_words(1);
+        let out = split_all(&mut splitter, &["He said \"stop right there!\" Then left."]);
+        assert_eq!(out, vec!["He said \"stop right there!\"", "Then left."]);
+    }
+}
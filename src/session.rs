@@ -1,11 +1,18 @@
 //! Session manager - handles LLM, TTS, and audio playback
 
+use crate::audiofile::write_wav;
 use crate::chat::Chat;
+use crate::config::{NotesConfig, WakeAck};
+use crate::sentence::SentenceSplitter;
 use crate::state::SharedState;
 use crate::stats::{LlmTimer, SharedStats};
-use crate::tts::Tts;
+use crate::tts::{self, Tts, TtsController};
+use crate::tts_markup;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::atomic::Ordering;
+use std::sync::mpsc as std_mpsc;
+use std::time::Duration;
 use tokio::sync::mpsc;
 
 #[cfg(feature = "aec")]
@@ -15,17 +22,56 @@ pub enum SessionCommand {
     UserInput(String),
     Greet,
     Cancel,
+    /// Speak the last response again without querying the LLM
+    Replay,
+    /// Write the conversation history to a file
+    SaveHistory(PathBuf),
+    /// Replace the conversation history with a saved one
+    LoadHistory(PathBuf),
+    /// Write the conversation to a Markdown file
+    Export(PathBuf),
+    /// Write the last spoken response to a WAV file
+    SaveClip(PathBuf),
+    /// Append an utterance to the notes file (note-taking mode)
+    AppendNote(String),
+    /// Note-taking mode ended, report how many notes were taken
+    EndNotes,
+    /// Speak a short announcement (if TTS is enabled) without touching history
+    Announce(String),
+    /// Switch the LLM model, keeping the history
+    SetModel(String),
+    /// Switch the TTS voice, or list the voices if none is given
+    Voice(Option<String>),
+    /// Replace the system prompt and reset the history
+    SetSystemPrompt(String),
+    /// Play the wake acknowledgement (`wake.ack`)
+    AcknowledgeWake,
 }
 
 #[derive(Clone, Debug)]
 pub enum SessionEvent {
     Thinking,
     Chunk(String),
-    ResponseEnd { response_words: usize },
+    ResponseEnd {
+        response_words: usize,
+    },
     Speaking,
     SpeakingDone,
     ContextWords(usize),
+    /// Generation was stopped before the response finished
+    Cancelled,
+    /// The LLM request failed to start and is being retried
+    Retrying {
+        attempt: u32,
+        max: u32,
+    },
     Ready,
+    /// No token has arrived within `chat.slow_threshold_secs`
+    SlowResponse,
+    /// The wake acknowledgement has played, the assistant is listening
+    WakeAcknowledged,
+    /// Informational message for the user
+    Message(String),
     Error(String),
 }
 
@@ -35,6 +81,22 @@ pub struct SessionManager {
     event_tx: mpsc::UnboundedSender<SessionEvent>,
     stats: Option<SharedStats>,
     state: SharedState,
+    /// Last assistant response, kept for replay
+    last_response: Option<String>,
+    /// Where to save the history after each response (`chat.persist`)
+    persist_path: Option<PathBuf>,
+    notes: NotesConfig,
+    /// Notes taken since entering note-taking mode
+    notes_taken: usize,
+    /// Report a slow response after this long without a first token
+    slow_threshold: Option<Duration>,
+    /// Tick when reporting a slow response
+    slow_tick: bool,
+    /// Speaker to play TTS on (`audio.output_device`)
+    output_device: Option<String>,
+    wake_ack: WakeAck,
+    /// Preloaded wake acknowledgement audio and its sample rate
+    wake_ack_audio: Option<(Vec<f32>, u32)>,
     #[cfg(feature = "aec")]
     aec_tx: Option<AecRenderTx>,
 }
@@ -52,6 +114,15 @@ impl SessionManager {
             event_tx,
             stats: None,
             state,
+            last_response: None,
+            persist_path: None,
+            notes: NotesConfig::default(),
+            notes_taken: 0,
+            slow_threshold: None,
+            slow_tick: false,
+            output_device: None,
+            wake_ack: WakeAck::None,
+            wake_ack_audio: None,
             #[cfg(feature = "aec")]
             aec_tx: None,
         }
@@ -68,6 +139,50 @@ impl SessionManager {
         self
     }
 
+    pub fn with_persist_path(mut self, path: Option<PathBuf>) -> Self {
+        self.persist_path = path;
+        self
+    }
+
+    pub fn with_notes(mut self, notes: NotesConfig) -> Self {
+        self.notes = notes;
+        self
+    }
+
+    pub fn with_output_device(mut self, device: Option<String>) -> Self {
+        self.output_device = device;
+        self
+    }
+
+    /// Preload the wake acknowledgement: the chime, or `phrase` synthesized
+    /// once up front so it plays without delay
+    pub fn with_wake_ack(mut self, ack: WakeAck, phrase: &str) -> Self {
+        let chime = || (tts::wake_chime(), tts::CHIME_SAMPLE_RATE);
+        self.wake_ack_audio = match ack {
+            WakeAck::None => None,
+            WakeAck::Chime => Some(chime()),
+            WakeAck::Speech => match self.tts.synthesize(phrase) {
+                Ok(clip) => Some(clip),
+                Err(e) => {
+                    let _ = self.event_tx.send(SessionEvent::Message(format!(
+                        "Can't synthesize the wake acknowledgement, using the chime: {}",
+                        e
+                    )));
+                    Some(chime())
+                }
+            },
+        };
+        self.wake_ack = ack;
+        self
+    }
+
+    /// Report slow responses after `threshold_secs` (0 = never)
+    pub fn with_slow_response(mut self, threshold_secs: u64, tick: bool) -> Self {
+        self.slow_threshold = (threshold_secs > 0).then(|| Duration::from_secs(threshold_secs));
+        self.slow_tick = tick;
+        self
+    }
+
     pub fn run_sync(mut self, mut cmd_rx: mpsc::UnboundedReceiver<SessionCommand>) {
         while let Some(cmd) = cmd_rx.blocking_recv() {
             match cmd {
@@ -80,6 +195,138 @@ impl SessionManager {
                 SessionCommand::Cancel => {
                     // Nothing to cancel if idle
                 }
+                SessionCommand::Replay => {
+                    let text = self
+                        .last_response
+                        .clone()
+                        .unwrap_or_else(|| "Nothing to repeat.".to_string());
+                    self.replay(&text, self.last_response.is_some());
+                }
+                SessionCommand::SaveHistory(path) => {
+                    let event = match self.chat.save_history(&path) {
+                        Ok(()) => SessionEvent::Message(format!(
+                            "Saved conversation to {}",
+                            path.display()
+                        )),
+                        Err(e) => {
+                            SessionEvent::Error(format!("Failed to save {}: {}", path.display(), e))
+                        }
+                    };
+                    let _ = self.event_tx.send(event);
+                }
+                SessionCommand::Export(path) => {
+                    let event = match self.chat.to_markdown(chrono::Local::now()) {
+                        None => SessionEvent::Message("Nothing to export yet".to_string()),
+                        Some(md) => match std::fs::write(&path, md) {
+                            Ok(()) => SessionEvent::Message(format!(
+                                "Exported conversation to {}",
+                                path.display()
+                            )),
+                            Err(e) => SessionEvent::Error(format!(
+                                "Failed to export {}: {}",
+                                path.display(),
+                                e
+                            )),
+                        },
+                    };
+                    let _ = self.event_tx.send(event);
+                }
+                SessionCommand::SaveClip(path) => {
+                    let event = match self.tts.last_clip() {
+                        None => SessionEvent::Message("No recent audio.".to_string()),
+                        Some((samples, rate)) => match write_wav(&path, samples, rate) {
+                            Ok(()) => {
+                                SessionEvent::Message(format!("Saved clip to {}", path.display()))
+                            }
+                            Err(e) => SessionEvent::Error(format!(
+                                "Failed to save {}: {}",
+                                path.display(),
+                                e
+                            )),
+                        },
+                    };
+                    let _ = self.event_tx.send(event);
+                }
+                SessionCommand::AppendNote(text) => {
+                    match crate::repl::append_to_notes(&self.notes.file, &text) {
+                        Ok(()) => {
+                            self.notes_taken += 1;
+                            let _ = self
+                                .event_tx
+                                .send(SessionEvent::Message(format!("[Note saved] {}", text)));
+                            if self.notes.ack {
+                                self.speak_if_enabled("Noted.");
+                            }
+                        }
+                        Err(e) => {
+                            let _ = self
+                                .event_tx
+                                .send(SessionEvent::Error(format!("Failed to save note: {}", e)));
+                        }
+                    }
+                }
+                SessionCommand::EndNotes => {
+                    let count = std::mem::take(&mut self.notes_taken);
+                    if count > 0 {
+                        let summary =
+                            format!("Took {} note{}.", count, if count == 1 { "" } else { "s" });
+                        let _ = self.event_tx.send(SessionEvent::Message(summary.clone()));
+                        self.speak_if_enabled(&summary);
+                    }
+                }
+                SessionCommand::Announce(text) => {
+                    self.speak_if_enabled(&text);
+                }
+                SessionCommand::AcknowledgeWake => {
+                    self.acknowledge_wake();
+                }
+                SessionCommand::SetModel(name) => {
+                    let event = match self.chat.set_model(&name) {
+                        Ok(()) => SessionEvent::Message(format!("Switched to model {}", name)),
+                        Err(e) => SessionEvent::Error(e.to_string()),
+                    };
+                    let _ = self.event_tx.send(event);
+                }
+                SessionCommand::Voice(Some(name)) => {
+                    let event = match self.tts.set_voice(&name) {
+                        Ok(()) => SessionEvent::Message(format!("Switched to voice {}", name)),
+                        Err(e) => SessionEvent::Error(e.to_string()),
+                    };
+                    let _ = self.event_tx.send(event);
+                }
+                SessionCommand::Voice(None) => {
+                    let voices = self.tts.voices();
+                    let msg = if voices.is_empty() {
+                        "The current TTS engine has a single voice".to_string()
+                    } else {
+                        format!("Voices: {}", voices.join(", "))
+                    };
+                    let _ = self.event_tx.send(SessionEvent::Message(msg));
+                }
+                SessionCommand::SetSystemPrompt(prompt) => {
+                    self.chat.set_system_prompt(&prompt);
+                    let _ = self.event_tx.send(SessionEvent::Message(
+                        "Persona loaded, conversation reset".to_string(),
+                    ));
+                    let _ = self.event_tx.send(SessionEvent::ContextWords(0));
+                }
+                SessionCommand::LoadHistory(path) => match self.chat.load_history(&path) {
+                    Ok(words) => {
+                        let _ = self.event_tx.send(SessionEvent::Message(format!(
+                            "Restored {} words from {}",
+                            words,
+                            path.display()
+                        )));
+                        let _ = self.event_tx.send(SessionEvent::ContextWords(words));
+                    }
+                    Err(e) => {
+                        let _ = self.event_tx.send(SessionEvent::Error(format!(
+                            "Failed to load {}: {}",
+                            path.display(),
+                            e
+                        )));
+                    }
+                },
             }
         }
     }
@@ -93,69 +340,100 @@ impl SessionManager {
 
         self.chat.history_push_user(message);
 
-        // Create TTS controller with state (and optional AEC channel)
-        let (stream, controller) = match Tts::create_controller(Arc::clone(&self.state)) {
-            Ok((s, c)) => {
-                #[cfg(feature = "aec")]
-                let c = if let Some(ref tx) = self.aec_tx {
-                    c.with_aec_tx(tx.clone())
-                } else {
-                    c
-                };
-                (s, c)
-            }
-            Err(e) => {
-                let _ = self.event_tx.send(SessionEvent::Error(e.to_string()));
-                self.state.tts_playing.store(false, Ordering::SeqCst);
-                self.state.set_tts_level(0.0);
-                return;
-            }
+        let Some((stream, controller)) = self.create_controller() else {
+            return;
         };
 
-        let mut buffer = String::new();
+        let mut splitter = SentenceSplitter::new();
+        // Markup is spoken, not shown
+        let mut display = tts_markup::Stripper::default();
         let mut speaking_sent = false;
         let mut llm_timer = self.stats.as_ref().map(|s| LlmTimer::new(Arc::clone(s)));
         let mut full_response = String::new();
 
         let event_tx = self.event_tx.clone();
         let state = Arc::clone(&self.state);
+        let (sentence_tx, sentence_rx) = std_mpsc::channel::<String>();
+        // Dropped on the first token (or when generation ends), which stops
+        // the slow response watchdog
+        let (first_token_tx, first_token_rx) = std_mpsc::channel::<()>();
+        let mut first_token_tx = Some(first_token_tx);
+        let slow_threshold = self.slow_threshold;
+        let slow_tick = self.slow_tick;
+
+        // Synthesize sentences on a separate thread so TTS runs while the LLM
+        // keeps generating; the channel keeps them in order
+        let result = std::thread::scope(|scope| {
+            let tts = &mut self.tts;
+            let controller = &controller;
+            scope.spawn(move || speak_sentences(tts, controller, sentence_rx, true));
+
+            if let Some(threshold) = slow_threshold {
+                let event_tx = event_tx.clone();
+                let state = Arc::clone(&state);
+                scope.spawn(move || {
+                    if let Err(std_mpsc::RecvTimeoutError::Timeout) =
+                        first_token_rx.recv_timeout(threshold)
+                    {
+                        let _ = event_tx.send(SessionEvent::SlowResponse);
+                        if slow_tick && state.tts_enabled.load(Ordering::SeqCst) {
+                            controller.tick();
+                        }
+                    }
+                });
+            }
 
-        // Generate with streaming callback
-        let result = self.chat.generate(|token| {
-            if let Some(ref mut timer) = llm_timer {
-                timer.mark_first_token();
+            // Generate with streaming callback, stopping early on cancel
+            let on_retry = |attempt, max| {
+                let _ = event_tx.send(SessionEvent::Retrying { attempt, max });
+            };
+            let result = self
+                .chat
+                .generate(&state.cancel_requested, on_retry, |token| {
+                    if let Some(ref mut timer) = llm_timer {
+                        timer.mark_first_token();
+                    }
+                    first_token_tx.take();
+                    let shown = display.push(token);
+                    if !shown.is_empty() {
+                        let _ = event_tx.send(SessionEvent::Chunk(shown));
+                    }
+                    full_response.push_str(token);
+
+                    // Queue complete sentences to TTS as soon as they're ready
+                    for sentence in splitter.push(token) {
+                        if state.tts_enabled.load(Ordering::SeqCst) {
+                            if !speaking_sent {
+                                let _ = event_tx.send(SessionEvent::Speaking);
+                                speaking_sent = true;
+                            }
+                            let _ = sentence_tx.send(sentence);
+                        }
+                    }
+                });
+
+            drop(first_token_tx);
+            let shown = display.finish();
+            if !shown.is_empty() {
+                let _ = event_tx.send(SessionEvent::Chunk(shown));
             }
-            let _ = event_tx.send(SessionEvent::Chunk(token.to_string()));
-            full_response.push_str(token);
-            buffer.push_str(token);
-
-            // Queue complete sentences to TTS - improved sentence detection
-            let mut start_pos = 0;
-            while let Some(pos) = buffer[start_pos..].find(|c| c == '.' || c == '!' || c == '?') {
-                let actual_pos = start_pos + pos;
-                // Check if this is a sentence ending or just punctuation in the middle of text
-                let sentence_end = actual_pos + 1;
-                let sentence_content = &buffer[start_pos..sentence_end].trim();
-
-                // Skip if it's just a single character (e.g., "U.S.A." or "Dr.")
-                if sentence_content
-                    .chars()
-                    .all(|c| c.is_ascii_alphabetic() || c == '.')
-                {
-                    start_pos = sentence_end;
-                    continue;
-                }
-
-                if !sentence_content.is_empty() && state.tts_enabled.load(Ordering::SeqCst) {
-                    if !speaking_sent {
-                        let _ = event_tx.send(SessionEvent::Speaking);
-                        speaking_sent = true;
+
+            // Flush remaining
+            if result.is_ok() {
+                if let Some(remaining) = splitter.finish() {
+                    if state.tts_enabled.load(Ordering::SeqCst) {
+                        if !speaking_sent {
+                            let _ = event_tx.send(SessionEvent::Speaking);
+                        }
+                        let _ = sentence_tx.send(remaining);
                     }
-                    let _ = self.tts.queue_to_controller(sentence_content, &controller);
                 }
-                buffer = buffer[sentence_end..].to_string();
-                start_pos = 0;
+            } else {
+                // Don't synthesize anything still queued
+                state.request_cancel();
             }
+            drop(sentence_tx);
+            result
         });
 
         // Record LLM stats
@@ -168,6 +446,7 @@ impl SessionManager {
             let _ = self.event_tx.send(SessionEvent::Error(e.to_string()));
             self.chat.history_pop();
             controller.stop();
+            self.state.clear_cancel();
             Tts::finish_controller(stream, controller);
             self.state.tts_playing.store(false, Ordering::SeqCst);
             self.state.set_tts_level(0.0);
@@ -175,26 +454,142 @@ impl SessionManager {
             return;
         }
 
-        // Flush remaining
-        let remaining = buffer.trim();
-        if !remaining.is_empty() && self.state.tts_enabled.load(Ordering::SeqCst) {
-            if !speaking_sent {
-                let _ = self.event_tx.send(SessionEvent::Speaking);
+        // Stopped mid-generation: keep what was already said, then go idle
+        if self.state.is_cancel_requested() {
+            if full_response.trim().is_empty() {
+                self.chat.history_pop();
+            } else {
+                self.chat
+                    .history_push_assistant(&tts_markup::strip(&full_response));
             }
-            let _ = self.tts.queue_to_controller(remaining, &controller);
+            controller.stop();
+            self.state.clear_cancel();
+            Tts::finish_controller(stream, controller);
+            self.state.tts_playing.store(false, Ordering::SeqCst);
+            self.state.set_tts_level(0.0);
+            let _ = self.event_tx.send(SessionEvent::Cancelled);
+            let _ = self.event_tx.send(SessionEvent::Ready);
+            return;
         }
 
-        self.chat.history_push_assistant(&full_response);
+        self.chat
+            .history_push_assistant(&tts_markup::strip(&full_response));
+        // Kept with its markup, so a replay sounds the same
+        if !full_response.trim().is_empty() {
+            self.last_response = Some(full_response.trim().to_string());
+        }
 
         let response_words = full_response.split_whitespace().count();
         let _ = self
             .event_tx
             .send(SessionEvent::ResponseEnd { response_words });
+
+        // Trim while the response plays, a summary needs another LLM call
+        if let Err(e) = self.chat.trim_context() {
+            let _ = self.event_tx.send(SessionEvent::Message(format!(
+                "Context summary failed, dropped oldest turns instead: {}",
+                e
+            )));
+        }
         let _ = self
             .event_tx
             .send(SessionEvent::ContextWords(self.chat.context_words()));
 
-        // Wait for TTS to finish with cancel support
+        // Reported as a message, an error here would reset the UI mid-response
+        if let Some(ref path) = self.persist_path {
+            if let Err(e) = self.chat.save_history(path) {
+                let _ = self.event_tx.send(SessionEvent::Message(format!(
+                    "Failed to save {}: {}",
+                    path.display(),
+                    e
+                )));
+            }
+        }
+
+        self.wait_for_playback(stream, controller);
+    }
+
+    /// Speak a short acknowledgement unless TTS is turned off
+    ///
+    /// Not recorded, so `/clip` still saves the last response.
+    fn speak_if_enabled(&mut self, text: &str) {
+        if self.state.tts_enabled.load(Ordering::SeqCst) {
+            self.replay(text, false);
+        }
+    }
+
+    /// Play the preloaded wake acknowledgement (speech only if TTS is on),
+    /// then report that the assistant is listening
+    fn acknowledge_wake(&mut self) {
+        let speech_off =
+            self.wake_ack == WakeAck::Speech && !self.state.tts_enabled.load(Ordering::SeqCst);
+        if let Some((audio, sample_rate)) = self.wake_ack_audio.clone().filter(|_| !speech_off) {
+            self.state.clear_cancel();
+            // Marked as playing so the mic doesn't transcribe it
+            self.state.tts_playing.store(true, Ordering::SeqCst);
+            if let Some((stream, controller)) = self.create_controller() {
+                controller.play(audio, sample_rate);
+                while controller.is_playing() {
+                    if controller.is_cancel_requested() {
+                        controller.stop();
+                        self.state.clear_cancel();
+                        break;
+                    }
+                    std::thread::sleep(Duration::from_millis(50));
+                }
+                Tts::finish_controller(stream, controller);
+            }
+            self.state.tts_playing.store(false, Ordering::SeqCst);
+            self.state.set_tts_level(0.0);
+        }
+        let _ = self.event_tx.send(SessionEvent::WakeAcknowledged);
+    }
+
+    /// Re-synthesize cached text, sentence by sentence, recording it as the
+    /// last clip if `record` is set
+    fn replay(&mut self, text: &str, record: bool) {
+        self.state.clear_cancel();
+        self.state.tts_playing.store(true, Ordering::SeqCst);
+
+        let Some((stream, controller)) = self.create_controller() else {
+            return;
+        };
+
+        let _ = self.event_tx.send(SessionEvent::Speaking);
+        let mut splitter = SentenceSplitter::new();
+        let (sentence_tx, sentence_rx) = std_mpsc::channel();
+        for sentence in splitter.push(text).into_iter().chain(splitter.finish()) {
+            let _ = sentence_tx.send(sentence);
+        }
+        drop(sentence_tx);
+        speak_sentences(&mut self.tts, &controller, sentence_rx, record);
+
+        self.wait_for_playback(stream, controller);
+    }
+
+    /// Create TTS controller with state (and optional AEC channel)
+    fn create_controller(&self) -> Option<(rodio::OutputStream, TtsController)> {
+        match Tts::create_controller(Arc::clone(&self.state), self.output_device.as_deref()) {
+            Ok((s, c)) => {
+                #[cfg(feature = "aec")]
+                let c = if let Some(ref tx) = self.aec_tx {
+                    c.with_aec_tx(tx.clone())
+                } else {
+                    c
+                };
+                Some((s, c))
+            }
+            Err(e) => {
+                let _ = self.event_tx.send(SessionEvent::Error(e.to_string()));
+                self.state.tts_playing.store(false, Ordering::SeqCst);
+                self.state.set_tts_level(0.0);
+                None
+            }
+        }
+    }
+
+    /// Wait for TTS to finish with cancel support, then report the session idle
+    fn wait_for_playback(&self, stream: rodio::OutputStream, controller: TtsController) {
         // Poll for completion with cancel check
         while controller.is_playing() {
             // Check for cancel request
@@ -203,8 +598,6 @@ impl SessionManager {
                 self.state.clear_cancel();
                 break;
             }
-            // Update volume based on state (for ducking)
-            controller.update_volume();
             std::thread::sleep(std::time::Duration::from_millis(50));
         }
         Tts::finish_controller(stream, controller);
@@ -215,3 +608,33 @@ impl SessionManager {
         self.state.set_tts_level(0.0);
     }
 }
+
+/// Synthesize queued sentences in order until the sender is dropped, as a
+/// new clip if `record` is set.
+///
+/// Once a stop is requested the sink is flushed and the rest of the queue is
+/// drained without being synthesized.
+fn speak_sentences(
+    tts: &mut Tts,
+    controller: &TtsController,
+    rx: std_mpsc::Receiver<String>,
+    record: bool,
+) {
+    if record {
+        tts.start_clip();
+    } else {
+        tts.skip_clip();
+    }
+    let mut markup = tts_markup::Parser::new();
+    for sentence in rx {
+        if controller.is_cancel_requested() {
+            if controller.is_playing() {
+                controller.stop();
+            }
+            continue;
+        }
+        tts.set_speed(controller.state.tts_speed());
+        tts.set_steps(controller.state.tts_steps());
+        let _ = tts.queue_to_controller(&sentence, &mut markup, controller);
+    }
+}
@@ -0,0 +1,156 @@
+//! Microphone spectrum bands for the orb visualizer
+//!
+//! Each update takes the most recent ~16ms of audio, applies a Hann window and
+//! an FFT, and takes the peak level of log-spaced bands between roughly the
+//! voice fundamental and the Nyquist limit, so vowels and formants move
+//! different bands.
+
+use realfft::num_complex::Complex;
+use realfft::{RealFftPlanner, RealToComplex};
+use std::sync::Arc;
+
+/// Number of bands sent to the UI
+pub const NUM_BANDS: usize = 8;
+
+/// Samples per analysis window (16ms at 16kHz)
+const WINDOW: usize = 256;
+/// Lowest band edge (Hz)
+const MIN_FREQ: f32 = 80.0;
+/// Band power (dBFS) shown as empty
+const FLOOR_DB: f32 = -70.0;
+/// Band power (dBFS) shown as full
+const CEILING_DB: f32 = -20.0;
+
+pub struct SpectrumAnalyzer {
+    fft: Arc<dyn RealToComplex<f32>>,
+    window: Vec<f32>,
+    input: Vec<f32>,
+    spectrum: Vec<Complex<f32>>,
+    /// FFT bin range `start..end` for each band
+    band_bins: [(usize, usize); NUM_BANDS],
+}
+
+impl SpectrumAnalyzer {
+    pub fn new(sample_rate: u32) -> Self {
+        let fft = RealFftPlanner::<f32>::new().plan_fft_forward(WINDOW);
+        let window = (0..WINDOW)
+            .map(|i| {
+                let phase = 2.0 * std::f32::consts::PI * i as f32 / WINDOW as f32;
+                0.5 - 0.5 * phase.cos()
+            })
+            .collect();
+        let input = fft.make_input_vec();
+        let spectrum = fft.make_output_vec();
+
+        // Log-spaced edges from MIN_FREQ to Nyquist, at least one bin per band
+        let nyquist = sample_rate as f32 / 2.0;
+        let bin_hz = sample_rate as f32 / WINDOW as f32;
+        let last_bin = spectrum.len() - 1;
+        let edge = |i: usize| MIN_FREQ * (nyquist / MIN_FREQ).powf(i as f32 / NUM_BANDS as f32);
+        let mut band_bins = [(0, 0); NUM_BANDS];
+        let mut next = ((MIN_FREQ / bin_hz).round() as usize).max(1);
+        for (i, bins) in band_bins.iter_mut().enumerate() {
+            let start = next.min(last_bin);
+            let end = if i + 1 == NUM_BANDS {
+                last_bin + 1
+            } else {
+                ((edge(i + 1) / bin_hz).round() as usize)
+                    .max(start + 1)
+                    .min(last_bin)
+            };
+            *bins = (start, end);
+            next = end;
+        }
+
+        Self {
+            fft,
+            window,
+            input,
+            spectrum,
+            band_bins,
+        }
+    }
+
+    /// Band levels (0.0 - 1.0) of the most recent window of `samples`
+    ///
+    /// Shorter input is zero-padded at the front.
+    pub fn bands(&mut self, samples: &[f32]) -> [f32; NUM_BANDS] {
+        let recent = &samples[samples.len().saturating_sub(WINDOW)..];
+        let pad = WINDOW - recent.len();
+        self.input[..pad].fill(0.0);
+        for (i, (&s, out)) in recent.iter().zip(&mut self.input[pad..]).enumerate() {
+            *out = s * self.window[pad + i];
+        }
+
+        if self
+            .fft
+            .process(&mut self.input, &mut self.spectrum)
+            .is_err()
+        {
+            return [0.0; NUM_BANDS];
+        }
+
+        // Hann window sums to WINDOW/2, so a full-scale sine peaks at 1.0
+        let norm = 2.0 / (WINDOW as f32 / 2.0);
+        let mut levels = [0.0; NUM_BANDS];
+        for (level, &(start, end)) in levels.iter_mut().zip(&self.band_bins) {
+            let peak = self.spectrum[start..end]
+                .iter()
+                .map(|c| c.norm() * norm)
+                .fold(0.0f32, f32::max);
+            let db = 20.0 * peak.max(1e-10).log10();
+            *level = ((db - FLOOR_DB) / (CEILING_DB - FLOOR_DB)).clamp(0.0, 1.0);
+        }
+        levels
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine(freq: f32, amplitude: f32) -> Vec<f32> {
+        (0..480)
+            .map(|i| amplitude * (2.0 * std::f32::consts::PI * freq * i as f32 / 16000.0).sin())
+            .collect()
+    }
+
+    fn loudest(bands: &[f32; NUM_BANDS]) -> usize {
+        (0..NUM_BANDS)
+            .max_by(|&a, &b| bands[a].total_cmp(&bands[b]))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_bands_cover_spectrum() {
+        let analyzer = SpectrumAnalyzer::new(16000);
+        let bins = analyzer.band_bins;
+        assert!(bins[0].0 >= 1);
+        assert_eq!(bins[NUM_BANDS - 1].1, WINDOW / 2 + 1);
+        for pair in bins.windows(2) {
+            assert_eq!(pair[0].1, pair[1].0);
+        }
+        assert!(bins.iter().all(|&(start, end)| end > start));
+    }
+
+    #[test]
+    fn test_silence_is_empty() {
+        let mut analyzer = SpectrumAnalyzer::new(16000);
+        assert_eq!(analyzer.bands(&[0.0; 480]), [0.0; NUM_BANDS]);
+        assert_eq!(analyzer.bands(&[]), [0.0; NUM_BANDS]);
+    }
+
+    #[test]
+    fn test_tone_lands_in_its_band() {
+        let mut analyzer = SpectrumAnalyzer::new(16000);
+        let low = analyzer.bands(&sine(200.0, 0.3));
+        let high = analyzer.bands(&sine(4000.0, 0.3));
+        assert!(loudest(&low) < loudest(&high));
+        assert!(low[loudest(&low)] > 0.9);
+
+        // Quieter input gives lower levels in the same band
+        let quiet = analyzer.bands(&sine(4000.0, 0.003));
+        assert_eq!(loudest(&quiet), loudest(&high));
+        assert!(quiet[loudest(&quiet)] < high[loudest(&high)]);
+    }
+}
@@ -6,10 +6,22 @@
 #![allow(dead_code)] // Many fields/methods will be used in future phases
 
 use std::fmt;
-use std::sync::Arc;
-use std::sync::atomic::{AtomicBool, AtomicU8, AtomicU32, AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU8, AtomicU32, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 
-use crate::config::Config;
+use crate::config::{Config, InteractionMode};
+use crate::render::OrbStyle;
+
+/// Allowed TTS speed range for live adjustment
+pub const MIN_TTS_SPEED: f32 = 0.5;
+pub const MAX_TTS_SPEED: f32 = 2.0;
+/// Step used by the "speak faster" / "speak slower" commands
+pub const TTS_SPEED_STEP: f32 = 0.1;
+
+/// Supertonic denoising steps: more is slower but cleaner
+pub const DEFAULT_TTS_STEPS: usize = 5;
+pub const MIN_TTS_STEPS: usize = 4;
+pub const MAX_TTS_STEPS: usize = 64;
 
 /// Application modes
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -82,8 +94,10 @@ pub struct RuntimeState {
     // ========================================================================
     // Audio state
     // ========================================================================
-    /// Microphone is muted (no audio processing)
+    /// Microphone is muted (nothing reaches the LLM)
     pub mic_muted: AtomicBool,
+    /// While muted, keep listening for the wake word to unmute
+    pub unmute_on_wake: AtomicBool,
     /// Current microphone RMS level (0.0-1.0)
     pub mic_level: AtomicF32,
 
@@ -94,12 +108,20 @@ pub struct RuntimeState {
     pub tts_enabled: AtomicBool,
     /// TTS is currently playing audio
     pub tts_playing: AtomicBool,
-    /// Current TTS volume (0.0-1.0)
+    /// Playback volume set by the user (0.0-1.0)
     pub tts_volume: AtomicF32,
+    /// User speech is ducking playback to `duck_volume`
+    pub tts_ducked: AtomicBool,
     /// Current TTS output RMS level (0.0-1.0)
     pub tts_level: AtomicF32,
     /// Duck volume level from config
     duck_volume: AtomicF32,
+    /// Current speech speed, read before each synthesized chunk
+    pub tts_speed: AtomicF32,
+    /// Speech speed from config, restored by "normal speed"
+    default_tts_speed: AtomicF32,
+    /// Denoising steps (Supertonic), read before each synthesized chunk
+    pub tts_steps: AtomicUsize,
 
     // ========================================================================
     // Interaction state
@@ -116,6 +138,12 @@ pub struct RuntimeState {
     pub last_interaction_ms: AtomicU64,
     /// Wake timeout in seconds
     wake_timeout_secs: AtomicU64,
+    /// Push-to-talk mode: VAD is bypassed, audio is only captured while held
+    pub ptt_mode: AtomicBool,
+    /// Push-to-talk key is currently held
+    pub ptt_held: AtomicBool,
+    /// When a shutdown was asked for and awaits confirmation (Unix ms, 0 if none)
+    shutdown_requested_ms: AtomicU64,
 
     // ========================================================================
     // Mode state
@@ -123,6 +151,22 @@ pub struct RuntimeState {
     /// Current application mode (stored as u8)
     mode: AtomicU8,
 
+    // ========================================================================
+    // UI state
+    // ========================================================================
+    /// Current orb visual style (stored as u8)
+    orb_style: AtomicU8,
+    /// Style to return to when leaving low-power mode (stored as u8)
+    full_orb_style: AtomicU8,
+
+    // ========================================================================
+    // Transcription state
+    // ========================================================================
+    /// Languages with a transcriber model (`transcriber.models`), sorted
+    languages: Vec<String>,
+    /// Active transcription language, `None` for the default model
+    language: Mutex<Option<String>>,
+
     // ========================================================================
     // LLM state
     // ========================================================================
@@ -136,32 +180,63 @@ pub struct RuntimeState {
     pub cancel_requested: AtomicBool,
 }
 
+fn unix_ms() -> u64 {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64
+}
+
 impl RuntimeState {
     /// Create new RuntimeState initialized from config
     pub fn new(config: &Config) -> Arc<Self> {
+        let tts_speed = config.tts.speed().clamp(MIN_TTS_SPEED, MAX_TTS_SPEED);
         Arc::new(Self {
             // Audio
-            mic_muted: AtomicBool::new(false),
+            mic_muted: AtomicBool::new(config.interaction.start_muted),
+            unmute_on_wake: AtomicBool::new(config.interaction.unmute_on_wake),
             mic_level: AtomicF32::new(0.0),
 
             // TTS
             tts_enabled: AtomicBool::new(true),
             tts_playing: AtomicBool::new(false),
-            tts_volume: AtomicF32::new(1.0),
+            tts_volume: AtomicF32::new(config.audio.output_volume.clamp(0.0, 1.0)),
+            tts_ducked: AtomicBool::new(false),
             tts_level: AtomicF32::new(0.0),
             duck_volume: AtomicF32::new(config.interaction.duck_volume),
+            tts_speed: AtomicF32::new(tts_speed),
+            default_tts_speed: AtomicF32::new(tts_speed),
+            tts_steps: AtomicUsize::new(config.tts.steps().clamp(MIN_TTS_STEPS, MAX_TTS_STEPS)),
 
             // Interaction
             crosstalk_enabled: AtomicBool::new(config.interaction.crosstalk),
             aec_enabled: AtomicBool::new(config.interaction.aec),
             wake_enabled: AtomicBool::new(true),
             in_conversation: AtomicBool::new(false),
-            last_interaction_ms: AtomicU64::new(0),
+            last_interaction_ms: AtomicU64::new(unix_ms()),
             wake_timeout_secs: AtomicU64::new(config.wake_timeout_secs),
+            ptt_mode: AtomicBool::new(config.interaction.mode == InteractionMode::PushToTalk),
+            ptt_held: AtomicBool::new(false),
+            shutdown_requested_ms: AtomicU64::new(0),
 
             // Mode - start in Chat mode by default
             mode: AtomicU8::new(AppMode::Chat as u8),
 
+            // UI
+            orb_style: AtomicU8::new(OrbStyle::default() as u8),
+            full_orb_style: AtomicU8::new(OrbStyle::default() as u8),
+
+            // Transcription
+            languages: config.transcriber.models.keys().cloned().collect(),
+            language: Mutex::new(
+                config
+                    .transcriber
+                    .language
+                    .clone()
+                    .filter(|l| config.transcriber.models.contains_key(l)),
+            ),
+
             // LLM
             llm_generating: AtomicBool::new(false),
 
@@ -184,13 +259,89 @@ impl RuntimeState {
         self.mode.store(mode as u8, Ordering::SeqCst);
     }
 
+    // ========================================================================
+    // UI helpers
+    // ========================================================================
+
+    /// Get current orb visual style
+    pub fn orb_style(&self) -> OrbStyle {
+        OrbStyle::from(self.orb_style.load(Ordering::SeqCst))
+    }
+
+    /// Set orb visual style
+    pub fn set_orb_style(&self, style: OrbStyle) {
+        self.orb_style.store(style as u8, Ordering::SeqCst);
+        if style != OrbStyle::Minimal {
+            self.full_orb_style.store(style as u8, Ordering::SeqCst);
+        }
+    }
+
+    /// Switch between the low-power orb and the last full style, returning the new style
+    pub fn toggle_low_power(&self) -> OrbStyle {
+        let style = if self.orb_style() == OrbStyle::Minimal {
+            OrbStyle::from(self.full_orb_style.load(Ordering::SeqCst))
+        } else {
+            OrbStyle::Minimal
+        };
+        self.set_orb_style(style);
+        style
+    }
+
+    // ========================================================================
+    // Transcription helpers
+    // ========================================================================
+
+    /// Languages that have a transcriber model
+    pub fn languages(&self) -> &[String] {
+        &self.languages
+    }
+
+    /// Active transcription language, `None` while on the default model
+    pub fn language(&self) -> Option<String> {
+        self.language
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Switch the transcription language (`None` for the default model).
+    /// Returns false, leaving it unchanged, if the language has no model
+    pub fn set_language(&self, language: Option<&str>) -> bool {
+        if language.is_some_and(|l| !self.languages.iter().any(|known| known == l)) {
+            return false;
+        }
+        *self.language.lock().unwrap_or_else(|e| e.into_inner()) = language.map(String::from);
+        true
+    }
+
     // ========================================================================
     // Audio processing helpers
     // ========================================================================
 
-    /// Check if audio should be processed (not muted, and either crosstalk or TTS not playing)
+    /// Muted with nothing listening for the wake word, so audio can be
+    /// dropped before the VAD. With `unmute_on_wake` (and the wake word
+    /// enabled) a muted mic is still transcribed, but only the wake word acts
+    pub fn is_deaf(&self) -> bool {
+        self.mic_muted.load(Ordering::SeqCst)
+            && !(self.unmute_on_wake.load(Ordering::SeqCst)
+                && self.wake_enabled.load(Ordering::SeqCst))
+    }
+
+    /// Only the wake word gets through: paused, or muted but listening for it
+    pub fn awaiting_wake(&self) -> bool {
+        self.mode() == AppMode::Paused || self.wake_only()
+    }
+
+    /// Muted but listening: nothing but the wake word can act, so audio the
+    /// keyword spotter rejects needn't be transcribed. Paused mode still takes
+    /// spoken commands ("resume", "unmute") without the wake word.
+    pub fn wake_only(&self) -> bool {
+        self.mic_muted.load(Ordering::SeqCst) && !self.is_deaf()
+    }
+
+    /// Check if audio should be processed (not deaf, and either crosstalk or TTS not playing)
     pub fn should_process_audio(&self) -> bool {
-        !self.mic_muted.load(Ordering::SeqCst)
+        !self.is_deaf()
             && (self.crosstalk_enabled.load(Ordering::SeqCst)
                 || !self.tts_playing.load(Ordering::SeqCst))
     }
@@ -211,20 +362,30 @@ impl RuntimeState {
 
     /// Duck TTS volume (reduce to configured duck_volume)
     pub fn duck_tts(&self) {
-        let duck = self.duck_volume.load(Ordering::SeqCst);
-        self.tts_volume.store(duck, Ordering::SeqCst);
+        self.tts_ducked.store(true, Ordering::SeqCst);
     }
 
-    /// Restore TTS volume to full
+    /// Restore TTS volume after ducking
     pub fn restore_tts_volume(&self) {
-        self.tts_volume.store(1.0, Ordering::SeqCst);
+        self.tts_ducked.store(false, Ordering::SeqCst);
     }
 
-    /// Get current TTS volume
+    /// Get the user's TTS volume
     pub fn get_tts_volume(&self) -> f32 {
         self.tts_volume.load(Ordering::SeqCst)
     }
 
+    /// Gain applied to TTS samples: the user's volume, scaled down to
+    /// `duck_volume` while ducked
+    pub fn playback_volume(&self) -> f32 {
+        let duck = if self.tts_ducked.load(Ordering::SeqCst) {
+            self.duck_volume.load(Ordering::SeqCst)
+        } else {
+            1.0
+        };
+        self.get_tts_volume() * duck
+    }
+
     /// Set TTS volume
     pub fn set_tts_volume(&self, volume: f32) {
         self.tts_volume
@@ -241,6 +402,50 @@ impl RuntimeState {
         self.tts_level.load(Ordering::SeqCst)
     }
 
+    // ========================================================================
+    // TTS speed helpers
+    // ========================================================================
+
+    /// Get current TTS speed
+    pub fn tts_speed(&self) -> f32 {
+        self.tts_speed.load(Ordering::SeqCst)
+    }
+
+    /// Set TTS speed, clamped to 0.5-2.0 and rounded to 0.1. Returns the new speed
+    pub fn set_tts_speed(&self, speed: f32) -> f32 {
+        let speed = ((speed * 10.0).round() / 10.0).clamp(MIN_TTS_SPEED, MAX_TTS_SPEED);
+        self.tts_speed.store(speed, Ordering::SeqCst);
+        speed
+    }
+
+    /// Nudge TTS speed by `delta`. Returns the new speed
+    pub fn adjust_tts_speed(&self, delta: f32) -> f32 {
+        self.set_tts_speed(self.tts_speed() + delta)
+    }
+
+    /// Restore the configured TTS speed. Returns the new speed
+    pub fn reset_tts_speed(&self) -> f32 {
+        self.set_tts_speed(self.default_tts_speed.load(Ordering::SeqCst))
+    }
+
+    pub fn tts_steps(&self) -> usize {
+        self.tts_steps.load(Ordering::SeqCst)
+    }
+
+    /// Set denoising steps, clamped to 4-64. Returns the new count
+    pub fn set_tts_steps(&self, steps: usize) -> usize {
+        let steps = steps.clamp(MIN_TTS_STEPS, MAX_TTS_STEPS);
+        self.tts_steps.store(steps, Ordering::SeqCst);
+        steps
+    }
+
+    /// Double (higher quality) or halve (faster) the denoising steps.
+    /// Returns the new count
+    pub fn scale_tts_steps(&self, higher: bool) -> usize {
+        let steps = self.tts_steps();
+        self.set_tts_steps(if higher { steps * 2 } else { steps / 2 })
+    }
+
     // ========================================================================
     // Cancellation helpers
     // ========================================================================
@@ -287,6 +492,25 @@ impl RuntimeState {
         now - last < timeout_secs * 1000
     }
 
+    /// Seconds since the last interaction
+    pub fn idle_secs(&self) -> u64 {
+        let last = self.last_interaction_ms.load(Ordering::SeqCst);
+        unix_ms().saturating_sub(last) / 1000
+    }
+
+    /// Start waiting for the user to confirm a shutdown
+    pub fn request_shutdown_confirmation(&self) {
+        self.shutdown_requested_ms
+            .store(unix_ms(), Ordering::SeqCst);
+    }
+
+    /// Clear a pending shutdown request, returns whether one was made within
+    /// the last `window_secs`
+    pub fn take_shutdown_request(&self, window_secs: u64) -> bool {
+        let requested = self.shutdown_requested_ms.swap(0, Ordering::SeqCst);
+        requested != 0 && unix_ms().saturating_sub(requested) < window_secs * 1000
+    }
+
     /// Update conversation state based on timeout
     pub fn update_conversation_state(&self) {
         let in_timeout = self.is_in_wake_timeout();
@@ -351,6 +575,7 @@ impl fmt::Debug for RuntimeState {
             )
             .field("aec_enabled", &self.aec_enabled.load(Ordering::SeqCst))
             .field("wake_enabled", &self.wake_enabled.load(Ordering::SeqCst))
+            .field("language", &self.language())
             .field(
                 "in_conversation",
                 &self.in_conversation.load(Ordering::SeqCst),
@@ -369,3 +594,27 @@ impl fmt::Debug for RuntimeState {
 
 /// Type alias for shared state
 pub type SharedState = Arc<RuntimeState>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wake_only_when_muted_and_listening() {
+        let mut config = Config::default();
+        config.interaction.unmute_on_wake = true;
+        let state = RuntimeState::new(&config);
+
+        // Paused still hears commands, so it isn't screened for the wake word
+        state.set_mode(AppMode::Paused);
+        assert!(state.awaiting_wake());
+        assert!(!state.wake_only());
+
+        state.mic_muted.store(true, Ordering::SeqCst);
+        assert!(state.wake_only());
+
+        // Muted without unmute_on_wake nothing is transcribed at all
+        state.unmute_on_wake.store(false, Ordering::SeqCst);
+        assert!(!state.wake_only());
+    }
+}
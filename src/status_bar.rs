@@ -46,10 +46,14 @@ pub struct StatusBarState {
     pub spin_frame: usize,
     pub audio_level: f32,
     pub tts_level: f32,
+    /// Mic input has been clipping
+    pub clipping: bool,
     pub mic_muted: bool,
     pub tts_enabled: bool,
     pub wake_enabled: bool,
     pub mode: AppMode,
+    /// Transcription language code, `None` on the default model
+    pub language: Option<String>,
     pub context_words: usize,
     pub last_response_words: usize,
     pub auto_submit_progress: Option<f32>,
@@ -64,10 +68,12 @@ impl Default for StatusBarState {
             spin_frame: 0,
             audio_level: 0.0,
             tts_level: 0.0,
+            clipping: false,
             mic_muted: false,
             tts_enabled: true,
             wake_enabled: true,
             mode: AppMode::Chat,
+            language: None,
             context_words: 0,
             last_response_words: 0,
             auto_submit_progress: None,
@@ -120,12 +126,27 @@ impl StatusBarState {
         }
     }
 
+    /// Generate the transcription language tag shown after the mode
+    pub fn language_string(&self, style: StatusDisplayStyle) -> String {
+        match (&self.language, style) {
+            (None, _) => String::new(),
+            (Some(code), StatusDisplayStyle::Emoji) => format!(" 🌐 {}", code.to_uppercase()),
+            (Some(code), StatusDisplayStyle::Text) => format!(" [{}]", code.to_uppercase()),
+        }
+    }
+
     /// Generate the toggles string (mic, tts, wake) with configurable style
     pub fn toggles_string(&self, style: StatusDisplayStyle) -> String {
         match style {
             StatusDisplayStyle::Emoji => format!(
                 "{}{}{}",
-                if self.mic_muted { "🔇" } else { "🎙" },
+                if self.mic_muted {
+                    "🔇"
+                } else if self.clipping {
+                    "\x1b[31m🎙 CLIP\x1b[90m"
+                } else {
+                    "🎙"
+                },
                 if self.tts_enabled { "🔊" } else { "🔈" },
                 if self.wake_enabled { "👂" } else { "💤" },
             ),
@@ -133,6 +154,8 @@ impl StatusBarState {
                 "{}{}{}",
                 if self.mic_muted {
                     "\x1b[31m[MIC OFF]\x1b[0m"
+                } else if self.clipping {
+                    "\x1b[1;31m[MIC CLIP]\x1b[0m"
                 } else {
                     "\x1b[32m[MIC]\x1b[0m"
                 },
@@ -186,21 +209,28 @@ impl StatusBarState {
         let toggles = self.toggles_string(style);
         let tts_viz = self.tts_viz_string();
         let mode_str = self.mode_string();
+        let language = self.language_string(style);
 
         let status_content = match style {
             StatusDisplayStyle::Emoji => format!(
-                "{}{} │ {} │ {}{} │ 📝 {} │ 💬 {}",
+                "{}{} │ {}{} │ {}{} │ 📝 {} │ 💬 {}",
                 spinner_str,
                 self.status,
                 mode_str,
+                language,
                 toggles,
                 tts_viz,
                 self.context_words,
                 self.last_response_words
             ),
             StatusDisplayStyle::Text => format!(
-                " \x1b[1m{}\x1b[0m | {} | {} | Ctx: {} | Resp: {}",
-                self.status, mode_str, toggles, self.context_words, self.last_response_words
+                " \x1b[1m{}\x1b[0m | {}{} | {} | Ctx: {} | Resp: {}",
+                self.status,
+                mode_str,
+                language,
+                toggles,
+                self.context_words,
+                self.last_response_words
             ),
         };
 
@@ -0,0 +1,253 @@
+//! Transcript file writers for the listen pipeline
+//!
+//! Supports plain text (`[start-end] text`), SRT, WebVTT and JSON output.
+//! SRT/VTT cues are wrapped to subtitle-friendly line lengths and timed from
+//! the per-word timestamps on each `Transcript`.
+
+use crate::pipeline::Transcript;
+use crate::transcriber::WordHyp;
+use std::io::Write;
+
+/// Maximum characters per subtitle line
+const MAX_LINE_CHARS: usize = 42;
+/// Maximum lines per subtitle cue
+const MAX_CUE_LINES: usize = 2;
+
+/// Output format for transcripts
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum SubtitleFormat {
+    /// `[start-end] text` lines (default)
+    #[default]
+    Txt,
+    Srt,
+    Vtt,
+    /// Array of transcripts, written when the session ends
+    Json,
+}
+
+impl SubtitleFormat {
+    pub fn from_str(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "srt" => SubtitleFormat::Srt,
+            "vtt" => SubtitleFormat::Vtt,
+            "json" => SubtitleFormat::Json,
+            _ => SubtitleFormat::Txt,
+        }
+    }
+}
+
+/// Plain-text transcript line, also used for console output
+pub fn format_line(t: &Transcript) -> String {
+    match &t.source {
+        Some(src) => format!("[{:.2}-{:.2}] [{}] {}", t.start, t.end, src, t.text),
+        None => format!("[{:.2}-{:.2}] {}", t.start, t.end, t.text),
+    }
+}
+
+/// Format seconds as `HH:MM:SS<sep>mmm` (`,` for SRT, `.` for VTT)
+pub fn format_timestamp(secs: f32, sep: char) -> String {
+    let total_ms = (secs.max(0.0) * 1000.0).round() as u64;
+    let ms = total_ms % 1000;
+    let s = (total_ms / 1000) % 60;
+    let m = (total_ms / 60_000) % 60;
+    let h = total_ms / 3_600_000;
+    format!("{:02}:{:02}:{:02}{}{:03}", h, m, s, sep, ms)
+}
+
+/// A single subtitle cue
+#[derive(Debug, Clone, PartialEq)]
+struct Cue {
+    start: f32,
+    end: f32,
+    lines: Vec<String>,
+}
+
+/// Greedily wrap words into lines of at most `MAX_LINE_CHARS`, then group the
+/// lines into cues of at most `MAX_CUE_LINES`, timed from the words they hold
+fn build_cues(words: &[WordHyp]) -> Vec<Cue> {
+    let mut lines: Vec<&[WordHyp]> = Vec::new();
+    let mut line_start = 0;
+    let mut line_len = 0;
+    for (i, word) in words.iter().enumerate() {
+        let word_len = word.text.chars().count();
+        if i > line_start && line_len + 1 + word_len > MAX_LINE_CHARS {
+            lines.push(&words[line_start..i]);
+            line_start = i;
+            line_len = 0;
+        }
+        line_len += if line_len == 0 {
+            word_len
+        } else {
+            word_len + 1
+        };
+    }
+    if line_start < words.len() {
+        lines.push(&words[line_start..]);
+    }
+
+    lines
+        .chunks(MAX_CUE_LINES)
+        .map(|chunk| {
+            let first = chunk[0][0].start;
+            let last = chunk[chunk.len() - 1].last().map_or(first, |w| w.end);
+            Cue {
+                start: first,
+                end: last,
+                lines: chunk
+                    .iter()
+                    .map(|line| {
+                        line.iter()
+                            .map(|w| w.text.as_str())
+                            .collect::<Vec<_>>()
+                            .join(" ")
+                    })
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+/// Streams transcripts to `out` in the chosen format
+pub struct SubtitleWriter<W: Write> {
+    out: W,
+    format: SubtitleFormat,
+    cue_index: usize,
+    collected: Vec<Transcript>,
+}
+
+impl<W: Write> SubtitleWriter<W> {
+    pub fn new(mut out: W, format: SubtitleFormat) -> std::io::Result<Self> {
+        if format == SubtitleFormat::Vtt {
+            writeln!(out, "WEBVTT")?;
+            writeln!(out)?;
+        }
+        Ok(Self {
+            out,
+            format,
+            cue_index: 0,
+            collected: Vec::new(),
+        })
+    }
+
+    /// Write a transcript (JSON output is buffered until `finish`)
+    pub fn write(
+        &mut self,
+        t: &Transcript,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        match self.format {
+            SubtitleFormat::Txt => writeln!(self.out, "{}", format_line(t))?,
+            SubtitleFormat::Srt | SubtitleFormat::Vtt => {
+                for cue in build_cues(&t.words) {
+                    self.write_cue(&cue, t.source.as_deref())?;
+                }
+            }
+            SubtitleFormat::Json => self.collected.push(t.clone()),
+        }
+        self.out.flush()?;
+        Ok(())
+    }
+
+    fn write_cue(&mut self, cue: &Cue, source: Option<&str>) -> std::io::Result<()> {
+        self.cue_index += 1;
+        let sep = if self.format == SubtitleFormat::Srt {
+            writeln!(self.out, "{}", self.cue_index)?;
+            ','
+        } else {
+            '.'
+        };
+        writeln!(
+            self.out,
+            "{} --> {}",
+            format_timestamp(cue.start, sep),
+            format_timestamp(cue.end, sep)
+        )?;
+        for (i, line) in cue.lines.iter().enumerate() {
+            match source {
+                Some(src) if i == 0 && self.format == SubtitleFormat::Vtt => {
+                    writeln!(self.out, "<v {}>{}", src, line)?
+                }
+                Some(src) if i == 0 => writeln!(self.out, "[{}] {}", src, line)?,
+                _ => writeln!(self.out, "{}", line)?,
+            }
+        }
+        writeln!(self.out)
+    }
+
+    /// Flush any buffered output
+    pub fn finish(mut self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        if self.format == SubtitleFormat::Json {
+            serde_json::to_writer_pretty(&mut self.out, &self.collected)?;
+            writeln!(self.out)?;
+        }
+        self.out.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transcriber::interpolate_words;
+
+    fn transcript(text: &str, start: f32, end: f32) -> Transcript {
+        Transcript {
+            start,
+            end,
+            text: text.to_string(),
+            source: None,
+            words: interpolate_words(text, start, end),
+            partial: false,
+        }
+    }
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp(0.0, ','), "00:00:00,000");
+        assert_eq!(format_timestamp(3723.456, ','), "01:02:03,456");
+        assert_eq!(format_timestamp(61.5, '.'), "00:01:01.500");
+    }
+
+    #[test]
+    fn test_cues_wrap_to_line_limit() {
+        let text = "the quick brown fox jumps over the lazy dog and keeps running \
+                    across the field until the sun goes down behind the hills";
+        let cues = build_cues(&interpolate_words(text, 0.0, 10.0));
+        assert!(cues.len() > 1);
+        for cue in &cues {
+            assert!(cue.lines.len() <= MAX_CUE_LINES);
+            assert!(
+                cue.lines
+                    .iter()
+                    .all(|l| l.chars().count() <= MAX_LINE_CHARS)
+            );
+        }
+        assert!((cues[0].start - 0.0).abs() < 1e-4);
+        assert!((cues.last().unwrap().end - 10.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_srt_output() {
+        let mut buf = Vec::new();
+        let mut writer = SubtitleWriter::new(&mut buf, SubtitleFormat::Srt).unwrap();
+        writer.write(&transcript("hello world", 1.0, 2.0)).unwrap();
+        writer.finish().unwrap();
+        let out = String::from_utf8(buf).unwrap();
+        assert_eq!(out, "1\n00:00:01,000 --> 00:00:02,000\nhello world\n\n");
+    }
+
+    #[test]
+    fn test_vtt_header() {
+        let mut buf = Vec::new();
+        let writer = SubtitleWriter::new(&mut buf, SubtitleFormat::Vtt).unwrap();
+        writer.finish().unwrap();
+        assert!(String::from_utf8(buf).unwrap().starts_with("WEBVTT\n"));
+    }
+
+    #[test]
+    fn test_format_from_str() {
+        assert_eq!(SubtitleFormat::from_str("SRT"), SubtitleFormat::Srt);
+        assert_eq!(SubtitleFormat::from_str("vtt"), SubtitleFormat::Vtt);
+        assert_eq!(SubtitleFormat::from_str("json"), SubtitleFormat::Json);
+        assert_eq!(SubtitleFormat::from_str("txt"), SubtitleFormat::Txt);
+    }
+}
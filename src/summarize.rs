@@ -1,4 +1,4 @@
-use crate::config::{Config, LlmConfig};
+use crate::config::{self, Config, LlmConfig};
 use crate::llm::{LlmBackend, Message, Role};
 use std::fs;
 use std::io::{Write, stdout};
@@ -74,7 +74,7 @@ pub fn run_summarize(input: PathBuf) -> Result<(), Box<dyn std::error::Error + S
         return Ok(());
     }
 
-    let config = Config::load();
+    let config = Config::load().map_err(|p| config::problem_report(&p))?;
     let ctx_size = get_ctx_size(&config.llm) as usize;
     let total_tokens = rough_token_count(&content);
 
@@ -92,6 +92,7 @@ pub fn run_summarize(input: PathBuf) -> Result<(), Box<dyn std::error::Error + S
         let messages = vec![Message {
             role: Role::User,
             content: format!("Summarize this transcription:\n\n{}", content),
+            time: None,
         }];
 
         backend.generate(&messages, &mut |token| {
@@ -112,6 +113,7 @@ pub fn run_summarize(input: PathBuf) -> Result<(), Box<dyn std::error::Error + S
             let messages = vec![Message {
                 role: Role::User,
                 content: format!("{}{}", CHUNK_PROMPT, chunk),
+                time: None,
             }];
 
             let mut summary = String::new();
@@ -130,6 +132,7 @@ pub fn run_summarize(input: PathBuf) -> Result<(), Box<dyn std::error::Error + S
         let messages = vec![Message {
             role: Role::User,
             content: format!("{}{}", COMBINE_PROMPT, combined),
+            time: None,
         }];
 
         backend.generate(&messages, &mut |token| {
@@ -188,10 +191,11 @@ fn create_backend(
             Err("llama-cpp not enabled. Build with --features llama-cpp".into())
         }
         #[cfg(feature = "ollama")]
-        LlmConfig::Ollama { model } => Ok(Box::new(crate::llm::ollama::OllamaBackend::new(
+        LlmConfig::Ollama { model, host } => Ok(Box::new(crate::llm::ollama::OllamaBackend::new(
             model,
+            host,
             system_prompt,
-        ))),
+        )?)),
         #[cfg(not(feature = "ollama"))]
         LlmConfig::Ollama { .. } => Err("Ollama not enabled. Build with --features ollama".into()),
         #[cfg(feature = "openai-compat")]
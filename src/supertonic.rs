@@ -271,6 +271,13 @@ impl TextToSpeech {
     }
 }
 
+/// Whether `use_gpu` can take effect: CoreML is only used on Apple Silicon,
+/// everywhere else the models run on CPU
+pub const GPU_AVAILABLE: bool = cfg!(all(target_arch = "aarch64", target_os = "macos"));
+
+/// Models loaded by `load_text_to_speech`
+const MODEL_COUNT: usize = 4;
+
 pub fn load_text_to_speech<P: AsRef<Path>>(
     onnx_dir: P,
     use_gpu: bool,
@@ -282,22 +289,28 @@ pub fn load_text_to_speech<P: AsRef<Path>>(
 
     let text_processor = UnicodeProcessor::new(onnx_dir.join("unicode_indexer.json"))?;
 
+    // Models that fell back from CoreML to CPU, summarized once all are loaded
+    #[cfg(all(target_arch = "aarch64", target_os = "macos"))]
+    let on_cpu = std::cell::Cell::new(0);
+
     #[cfg(all(target_arch = "aarch64", target_os = "macos"))]
     let create_session = |model_path: &std::path::PathBuf,
                           model_name: &str|
      -> ort::Result<Session> {
         if use_gpu {
-            println!("Loading {} with CoreML...", model_name);
+            crate::logger::info!("Loading {} with CoreML...", model_name);
             match Session::builder()?.with_execution_providers([CoreMLExecutionProvider::default()
                 .with_subgraphs(true)
                 .build()])
             {
                 Ok(mut builder) => builder.commit_from_file(model_path),
                 Err(e) => {
-                    eprintln!(
+                    crate::logger::warn!(
                         "CoreML EP failed for {}, falling back to CPU: {}",
-                        model_name, e
+                        model_name,
+                        e
                     );
+                    on_cpu.set(on_cpu.get() + 1);
                     Session::builder()?.commit_from_file(model_path)
                 }
             }
@@ -321,6 +334,20 @@ pub fn load_text_to_speech<P: AsRef<Path>>(
         create_session(&onnx_dir.join("vector_estimator.onnx"), "vector_estimator")?;
     let vocoder_ort = create_session(&onnx_dir.join("vocoder.onnx"), "vocoder")?;
 
+    #[cfg(all(target_arch = "aarch64", target_os = "macos"))]
+    if use_gpu {
+        match on_cpu.get() {
+            0 => crate::logger::info!("Supertonic: all {} models on CoreML", MODEL_COUNT),
+            n => crate::logger::warn!(
+                "Supertonic: {}/{} models on CPU (CoreML failed)",
+                n,
+                MODEL_COUNT
+            ),
+        }
+    }
+    #[cfg(not(all(target_arch = "aarch64", target_os = "macos")))]
+    let _ = (use_gpu, MODEL_COUNT);
+
     let sample_rate = cfgs.ae.sample_rate;
     Ok(TextToSpeech {
         cfgs,
@@ -1,13 +1,94 @@
+use crate::logger;
+use crate::normalize::TextNormalizer;
 use crate::stats::{SharedStats, StatKind, Timer};
-use std::path::Path;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 pub use transcribe_rs::TranscriptionSegment;
 use transcribe_rs::{
     SpeechModel, TranscribeOptions, onnx::Quantization, onnx::parakeet::ParakeetModel,
 };
 
+const SAMPLE_RATE: f32 = 16000.0;
+/// Minimum audio before the incremental path bothers running the model
+const INCREMENTAL_MIN_SAMPLES: usize = 8000;
+/// Upper bound on the audio window re-encoded per incremental call
+const INCREMENTAL_WINDOW_SECS: f32 = 4.0;
+/// Length of the dummy buffer run by `warmup`
+const WARMUP_SECS: f32 = 0.5;
+/// Words ending this close to the end of the window may still change
+const INCREMENTAL_STABLE_MARGIN_SECS: f32 = 0.3;
+
+/// Loudest-frame level (dBFS) at or below which a hypothesis is treated as noise
+const CONFIDENCE_NOISE_DB: f32 = -55.0;
+/// Loudest-frame level (dBFS) at or above which audio is clearly speech
+const CONFIDENCE_SPEECH_DB: f32 = -35.0;
+/// Fastest plausible speaking rate; faster hypotheses are likely hallucinated
+const CONFIDENCE_MAX_WORDS_PER_SEC: f32 = 5.0;
+/// Frame size for the loudness check (30ms, matching the VAD)
+const CONFIDENCE_FRAME: usize = 480;
+
+/// Default loudest-frame RMS below which a buffer is treated as silence (~-50 dBFS)
+pub const DEFAULT_SILENCE_FLOOR: f32 = 0.003;
+/// Most a quiet segment is boosted by AGC (+20 dB), so noise isn't brought up
+/// to speech level
+const AGC_MAX_GAIN: f32 = 10.0;
+/// AGC never pushes the loudest sample past this, so boosting can't clip
+const AGC_PEAK_LIMIT: f32 = 0.95;
+
+/// Buffers shorter than this are checked against `HALLUCINATIONS`
+const HALLUCINATION_MAX_SECS: f32 = 1.5;
+/// Only buffers quieter than this (loudest-frame dBFS) are checked, so a
+/// clearly spoken "okay" or "thanks" gets through
+const HALLUCINATION_MAX_DB: f32 = CONFIDENCE_SPEECH_DB;
+/// Phrases the model tends to produce from noise or breaths on short buffers
+const HALLUCINATIONS: &[&str] = &[
+    "you",
+    "thank you",
+    "thanks",
+    "thank you very much",
+    "thanks for watching",
+    "thank you for watching",
+    "bye",
+    "okay",
+    "oh",
+    "uh",
+    "um",
+    "hmm",
+    "mm",
+];
+
+/// A word hypothesis with rough timing, relative to the start of the utterance
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct WordHyp {
+    pub text: String,
+    #[allow(dead_code)]
+    pub start: f32,
+    pub end: f32,
+}
+
+/// Rolling state for `Transcriber::transcribe_incremental`
+#[derive(Default)]
+struct IncrementalState {
+    /// Audio still inside the decode window
+    window: Vec<f32>,
+    /// Samples trimmed off the front of the window since the last reset
+    offset: usize,
+    /// End time (secs) of the last word handed out
+    committed_end: f32,
+}
+
 pub struct Transcriber {
     engine: ParakeetModel,
     stats: Option<SharedStats>,
+    incremental: IncrementalState,
+    /// Loudest-frame RMS below which `transcribe` returns nothing
+    silence_floor: f32,
+    /// Speech RMS that segments are normalized to before the model (`audio.agc`)
+    agc_target: Option<f32>,
+    /// Clean-up applied to `transcribe` results (`[normalize]`)
+    normalizer: Option<TextNormalizer>,
 }
 
 impl Transcriber {
@@ -21,45 +102,107 @@ impl Transcriber {
         model_path: impl AsRef<Path>,
         stats: Option<SharedStats>,
     ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
-        println!("Loading model... (CoreML accelerated on Apple Silicon)");
+        logger::info!("Loading model... (CoreML accelerated on Apple Silicon)");
 
         #[cfg(all(feature = "supertonic", target_arch = "aarch64", target_os = "macos"))]
         {
-            println!("Enabling CoreML execution provider for transcription...");
+            logger::info!("Enabling CoreML execution provider for transcription...");
         }
 
         let engine = ParakeetModel::load(model_path.as_ref(), &Quantization::Int8)
             .map_err(|e| e.to_string())?;
-        println!("Model loaded.");
-        Ok(Self { engine, stats })
+        logger::info!("Model loaded.");
+        Ok(Self {
+            engine,
+            stats,
+            incremental: IncrementalState::default(),
+            silence_floor: DEFAULT_SILENCE_FLOOR,
+            agc_target: None,
+            normalizer: None,
+        })
     }
 
-    #[hotpath::measure]
+    /// Set the loudest-frame RMS below which buffers are skipped as silence
+    pub fn with_silence_floor(mut self, silence_floor: f32) -> Self {
+        self.silence_floor = silence_floor.max(0.0);
+        self
+    }
+
+    /// Normalize each buffer's speech level to `target_rms` before
+    /// transcribing (`None` leaves levels alone). Only the model's copy is
+    /// changed; callers' buffers and recordings keep their original levels
+    pub fn with_agc(mut self, target_rms: Option<f32>) -> Self {
+        self.agc_target = target_rms;
+        self
+    }
+
+    /// Clean up final transcripts (capitalization, fillers, stutters).
+    /// `transcribe_incremental` previews are left as the model wrote them
+    pub fn with_normalizer(mut self, normalizer: TextNormalizer) -> Self {
+        self.normalizer = Some(normalizer);
+        self
+    }
+
+    /// Run the model once on a short dummy tone so ORT graph optimization
+    /// and allocation happen now instead of on the first utterance.
+    /// Returns how long it took.
+    pub fn warmup(&mut self) -> Duration {
+        let start = Instant::now();
+        let samples: Vec<f32> = (0..(SAMPLE_RATE * WARMUP_SECS) as usize)
+            .map(|i| 0.1 * (i as f32 * 0.05).sin())
+            .collect();
+        let _ = self
+            .engine
+            .transcribe(&samples, &TranscribeOptions::default());
+        start.elapsed()
+    }
+
+    /// Transcribe any owned or shared buffer (`Vec<f32>`, `Arc<[f32]>`, ...) in place
     pub fn transcribe(
         &mut self,
-        samples: &[f32],
+        samples: impl AsRef<[f32]>,
     ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
-        // Check for empty samples to prevent transcription errors
-        if samples.is_empty() {
-            return Ok(String::new());
-        }
+        self.transcribe_slice(samples.as_ref())
+    }
 
-        let timer = self
-            .stats
-            .as_ref()
-            .map(|s| Timer::new(s, StatKind::Transcription, samples.len()));
-        let result = self
-            .engine
-            .transcribe(samples, &TranscribeOptions::default())
-            .map_err(|e| e.to_string())?;
-        let text = result.text.trim().to_string();
-        if let Some(t) = timer {
-            t.finish(text.len());
-        }
-        Ok(text)
+    /// Transcribe borrowed samples without copying them.
+    ///
+    /// Parakeet normalizes its mel features internally, but very quiet input
+    /// still transcribes poorly, so with `with_agc` the speech is brought up
+    /// to a target level first. Near-silent buffers and short-buffer
+    /// hallucinations come back empty, and with `with_normalizer` the text
+    /// is cleaned up.
+    #[hotpath::measure]
+    pub fn transcribe_slice(
+        &mut self,
+        samples: &[f32],
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let engine = &mut self.engine;
+        let stats = self.stats.as_ref();
+        let (silence_floor, agc_target) = (self.silence_floor, self.agc_target);
+        let normalizer = self.normalizer.as_ref();
+        guard_no_speech(samples, silence_floor, |samples| {
+            let samples = match agc_target {
+                Some(target_rms) => apply_agc(samples, target_rms, silence_floor),
+                None => Cow::Borrowed(samples),
+            };
+            let samples = samples.as_ref();
+            let timer = stats.map(|s| Timer::new(s, StatKind::Transcription, samples.len()));
+            let result = engine
+                .transcribe(samples, &TranscribeOptions::default())
+                .map_err(|e| e.to_string())?;
+            let text = result.text.trim().to_string();
+            if let Some(t) = timer {
+                t.finish(text.len());
+            }
+            Ok(text)
+        })
+        .map(|text| match normalizer {
+            Some(normalizer) => normalizer.normalize(&text),
+            None => text,
+        })
     }
 
-    #[allow(dead_code)]
     pub fn transcribe_with_segments(
         &mut self,
         samples: &[f32],
@@ -71,4 +214,457 @@ impl Transcriber {
             .map_err(|e| e.to_string())?;
         Ok((result.text.trim().to_string(), result.segments))
     }
+
+    /// Feed new audio for the current utterance and return only newly-stable words.
+    ///
+    /// Parakeet (via transcribe-rs) doesn't expose its encoder state, so this keeps
+    /// a bounded trailing window of audio instead and re-encodes only that, rather
+    /// than the whole utterance. Words are held back until they end at least
+    /// `INCREMENTAL_STABLE_MARGIN_SECS` before the window edge, and audio is only
+    /// dropped once every word in it has been emitted.
+    ///
+    /// Tradeoff: latency is bounded by the window size instead of the utterance
+    /// length, but words are decoded with at most a few seconds of left context and
+    /// are never revised once emitted, so accuracy is below a full `transcribe()`.
+    /// Use this for previews and keep `transcribe()` for the final text. Call
+    /// `reset()` at every utterance boundary.
+    ///
+    /// Also returns the estimated confidence of the new words. It covers only the
+    /// audio they span, so words hypothesized over a mid-sentence pause score low
+    /// even when earlier audio was speech.
+    pub fn transcribe_incremental(
+        &mut self,
+        new_samples: &[f32],
+    ) -> Result<(Vec<WordHyp>, f32), Box<dyn std::error::Error + Send + Sync>> {
+        self.incremental.window.extend_from_slice(new_samples);
+        if self.incremental.window.len() < INCREMENTAL_MIN_SAMPLES {
+            return Ok((Vec::new(), 0.0));
+        }
+
+        let window = std::mem::take(&mut self.incremental.window);
+        let result = self.transcribe_with_segments(&window);
+        self.incremental.window = window;
+        let (text, segments) = result?;
+
+        let offset_secs = self.incremental.offset as f32 / SAMPLE_RATE;
+        let window_end = offset_secs + self.incremental.window.len() as f32 / SAMPLE_RATE;
+        let segments = segments.unwrap_or_else(|| {
+            vec![TranscriptionSegment {
+                start: 0.0,
+                end: window_end - offset_secs,
+                text,
+            }]
+        });
+
+        let stable_until = window_end - INCREMENTAL_STABLE_MARGIN_SECS;
+        let words: Vec<WordHyp> = split_words(&segments, offset_secs)
+            .into_iter()
+            .filter(|w| w.end > self.incremental.committed_end && w.end <= stable_until)
+            .collect();
+        let confidence = match (words.first(), words.last()) {
+            (Some(first), Some(last)) => {
+                let to_sample = |t: f32| {
+                    (((t - offset_secs).max(0.0) * SAMPLE_RATE) as usize)
+                        .min(self.incremental.window.len())
+                };
+                let span = &self.incremental.window[to_sample(first.start)..to_sample(last.end)];
+                let text: Vec<&str> = words.iter().map(|w| w.text.as_str()).collect();
+                estimate_confidence(&text.join(" "), span)
+            }
+            _ => 0.0,
+        };
+        if let Some(last) = words.last() {
+            self.incremental.committed_end = last.end;
+        }
+
+        // Trim audio that is fully committed, keeping the window bounded
+        let max_window = (INCREMENTAL_WINDOW_SECS * SAMPLE_RATE) as usize;
+        let committed_in_window =
+            ((self.incremental.committed_end - offset_secs).max(0.0) * SAMPLE_RATE) as usize;
+        let excess = self.incremental.window.len().saturating_sub(max_window);
+        let trim = excess.min(committed_in_window);
+        if trim > 0 {
+            self.incremental.window.drain(..trim);
+            self.incremental.offset += trim;
+        }
+
+        Ok((words, confidence))
+    }
+
+    /// Clear incremental state - call at utterance boundaries
+    pub fn reset(&mut self) {
+        self.incremental = IncrementalState::default();
+    }
+}
+
+/// RMS of the loudest 30ms frame
+fn peak_frame_rms(samples: &[f32]) -> f32 {
+    samples
+        .chunks(CONFIDENCE_FRAME)
+        .map(|frame| (frame.iter().map(|s| s * s).sum::<f32>() / frame.len() as f32).sqrt())
+        .fold(0.0f32, f32::max)
+}
+
+/// Scale `samples` so their speech sits at `target_rms`
+///
+/// The level is measured over 30ms frames louder than `silence_floor`, so
+/// pauses around the speech don't drag it down. The gain is capped at
+/// `AGC_MAX_GAIN` and where the loudest sample would reach `AGC_PEAK_LIMIT`.
+fn apply_agc(samples: &[f32], target_rms: f32, silence_floor: f32) -> Cow<'_, [f32]> {
+    let (energy, count) = samples
+        .chunks(CONFIDENCE_FRAME)
+        .map(|frame| (frame.iter().map(|s| s * s).sum::<f32>(), frame.len()))
+        .filter(|&(energy, len)| (energy / len as f32).sqrt() >= silence_floor.max(1e-6))
+        .fold((0.0, 0), |(total, n), (energy, len)| {
+            (total + energy, n + len)
+        });
+    if count == 0 {
+        return Cow::Borrowed(samples);
+    }
+
+    let speech_rms = (energy / count as f32).sqrt();
+    let peak = samples.iter().fold(0.0f32, |max, s| max.max(s.abs()));
+    let gain = (target_rms / speech_rms)
+        .min(AGC_MAX_GAIN)
+        .min(AGC_PEAK_LIMIT / peak);
+    if (gain - 1.0).abs() < 0.01 {
+        return Cow::Borrowed(samples);
+    }
+    Cow::Owned(samples.iter().map(|s| s * gain).collect())
+}
+
+/// Run `model` on `samples` unless they're silent, then drop likely hallucinations
+///
+/// Empty or near-silent buffers (loudest frame below `silence_floor`) skip the
+/// model entirely. Short, quiet buffers whose text is a known noise phrase
+/// ("thank you", "you") are discarded whatever the model emits.
+fn guard_no_speech<E>(
+    samples: &[f32],
+    silence_floor: f32,
+    model: impl FnOnce(&[f32]) -> Result<String, E>,
+) -> Result<String, E> {
+    let peak_rms = peak_frame_rms(samples);
+    if samples.is_empty() || peak_rms < silence_floor {
+        return Ok(String::new());
+    }
+
+    let text = model(samples)?;
+    let secs = samples.len() as f32 / SAMPLE_RATE;
+    let db = 20.0 * peak_rms.log10();
+    if secs < HALLUCINATION_MAX_SECS && db < HALLUCINATION_MAX_DB && is_hallucination(&text) {
+        return Ok(String::new());
+    }
+    Ok(text)
+}
+
+/// Whether `text` is one of the phrases the model invents from noise
+fn is_hallucination(text: &str) -> bool {
+    let normalized: String = text
+        .to_lowercase()
+        .chars()
+        .filter(|c| c.is_alphanumeric() || c.is_whitespace())
+        .collect();
+    let normalized = normalized.split_whitespace().collect::<Vec<_>>().join(" ");
+    HALLUCINATIONS.contains(&normalized.as_str())
+}
+
+/// Estimate how likely `text` is real speech heard in `samples` (0.0 - 1.0)
+///
+/// A stand-in for token logprobs, which transcribe-rs doesn't expose for
+/// Parakeet: hypotheses over near-silent audio, or with more words than could
+/// have been spoken in the time, are what show up as flickering nonsense
+/// previews.
+pub fn estimate_confidence(text: &str, samples: &[f32]) -> f32 {
+    let words = text
+        .split_whitespace()
+        .filter(|w| w.chars().any(char::is_alphanumeric))
+        .count();
+    if words == 0 || samples.is_empty() {
+        return 0.0;
+    }
+
+    // Loudest frame, so a short word among silence still counts as speech
+    let peak_rms = peak_frame_rms(samples);
+    let db = 20.0 * peak_rms.max(1e-10).log10();
+    let level_score =
+        ((db - CONFIDENCE_NOISE_DB) / (CONFIDENCE_SPEECH_DB - CONFIDENCE_NOISE_DB)).clamp(0.0, 1.0);
+
+    // Allow a minimum duration so a single short word isn't penalized
+    let secs = (samples.len() as f32 / SAMPLE_RATE).max(0.2);
+    let words_per_sec = words as f32 / secs;
+    let rate_score = if words_per_sec <= CONFIDENCE_MAX_WORDS_PER_SEC {
+        1.0
+    } else {
+        (CONFIDENCE_MAX_WORDS_PER_SEC / words_per_sec).powi(2)
+    };
+
+    level_score * rate_score
+}
+
+/// Split segments into words, spreading each segment's duration by word length
+fn split_words(segments: &[TranscriptionSegment], offset_secs: f32) -> Vec<WordHyp> {
+    segments
+        .iter()
+        .flat_map(|seg| {
+            interpolate_words(&seg.text, offset_secs + seg.start, offset_secs + seg.end)
+        })
+        .collect()
+}
+
+/// Assign rough per-word timestamps by distributing `start..end` across the
+/// words of `text` in proportion to their character counts
+pub fn interpolate_words(text: &str, start: f32, end: f32) -> Vec<WordHyp> {
+    let parts: Vec<&str> = text.split_whitespace().collect();
+    let total_chars: usize = parts.iter().map(|p| p.chars().count()).sum();
+    if total_chars == 0 {
+        return Vec::new();
+    }
+    let duration = (end - start).max(0.0);
+    let mut t = start;
+    parts
+        .into_iter()
+        .map(|part| {
+            let len = duration * part.chars().count() as f32 / total_chars as f32;
+            let word = WordHyp {
+                text: part.to_string(),
+                start: t,
+                end: t + len,
+            };
+            t += len;
+            word
+        })
+        .collect()
+}
+
+/// Models per language (`transcriber.models`), each loaded the first time
+/// it's needed and kept for later switches. `None`, or a language without a
+/// model, uses the default model.
+pub struct LanguageModels<T> {
+    default_path: PathBuf,
+    paths: BTreeMap<String, PathBuf>,
+    loaded: HashMap<Option<String>, T>,
+}
+
+impl<T> LanguageModels<T> {
+    pub fn new(default_path: PathBuf, paths: BTreeMap<String, PathBuf>) -> Self {
+        Self {
+            default_path,
+            paths,
+            loaded: HashMap::new(),
+        }
+    }
+
+    /// The model for `language`, loaded from its path with `load` unless cached
+    pub fn get<E>(
+        &mut self,
+        language: Option<&str>,
+        load: impl FnOnce(&Path) -> Result<T, E>,
+    ) -> Result<&mut T, E> {
+        let key = language
+            .filter(|l| self.paths.contains_key(*l))
+            .map(String::from);
+        match self.loaded.entry(key) {
+            std::collections::hash_map::Entry::Occupied(entry) => Ok(entry.into_mut()),
+            std::collections::hash_map::Entry::Vacant(entry) => {
+                let path = match entry.key() {
+                    Some(language) => &self.paths[language],
+                    None => &self.default_path,
+                };
+                let model = load(path)?;
+                Ok(entry.insert(model))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seg(start: f32, end: f32, text: &str) -> TranscriptionSegment {
+        TranscriptionSegment {
+            start,
+            end,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_split_words_spreads_timing() {
+        let words = split_words(&[seg(0.0, 1.0, "ab abc"), seg(1.0, 1.5, " ")], 2.0);
+        assert_eq!(words.len(), 2);
+        assert_eq!(words[0].text, "ab");
+        assert!((words[0].start - 2.0).abs() < 1e-4);
+        assert!((words[0].end - 2.4).abs() < 1e-4);
+        assert!((words[1].end - 3.0).abs() < 1e-4);
+    }
+
+    fn tone(amplitude: f32, secs: f32) -> Vec<f32> {
+        (0..(secs * SAMPLE_RATE) as usize)
+            .map(|i| amplitude * (i as f32 * 0.1).sin())
+            .collect()
+    }
+
+    #[test]
+    fn test_confidence_from_level() {
+        // ~-23 dBFS: clearly speech
+        let loud = tone(0.1, 1.0);
+        assert_eq!(estimate_confidence("hello there", &loud), 1.0);
+
+        // ~-63 dBFS: noise floor
+        let quiet = tone(0.001, 1.0);
+        assert_eq!(estimate_confidence("hello there", &quiet), 0.0);
+
+        // A short loud word among silence still counts
+        let mut mixed = vec![0.0; 16000];
+        mixed.extend(tone(0.1, 0.2));
+        assert_eq!(estimate_confidence("yes", &mixed), 1.0);
+    }
+
+    #[test]
+    fn test_confidence_penalizes_implausible_text() {
+        let loud = tone(0.1, 0.5);
+        assert_eq!(estimate_confidence("", &loud), 0.0);
+        assert_eq!(estimate_confidence(". ?", &loud), 0.0);
+        assert_eq!(estimate_confidence("a b", &[]), 0.0);
+
+        // 10 words in half a second is four times the plausible rate
+        let rushed = estimate_confidence("one two three four five six seven eight nine ten", &loud);
+        assert!((rushed - 0.0625).abs() < 1e-4, "rushed {}", rushed);
+    }
+
+    #[test]
+    fn test_silent_buffer_is_empty() {
+        let model = |_: &[f32]| Ok::<_, ()>("Thank you.".to_string());
+
+        // Silence never reaches the model
+        let silent = vec![0.0; 16000];
+        let result = guard_no_speech(&silent, DEFAULT_SILENCE_FLOOR, |_| -> Result<String, ()> {
+            panic!("model called on silence")
+        });
+        assert_eq!(result, Ok(String::new()));
+
+        // Low-level noise below the floor
+        let hiss = tone(0.001, 2.0);
+        assert_eq!(
+            guard_no_speech(&hiss, DEFAULT_SILENCE_FLOOR, model),
+            Ok(String::new())
+        );
+        assert_eq!(
+            guard_no_speech(&[], DEFAULT_SILENCE_FLOOR, model),
+            Ok(String::new())
+        );
+
+        // A floor of zero only skips empty buffers
+        assert_eq!(
+            guard_no_speech(&hiss, 0.0, model),
+            Ok("Thank you.".to_string())
+        );
+    }
+
+    fn rms(samples: &[f32]) -> f32 {
+        (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt()
+    }
+
+    #[test]
+    fn test_agc_boosts_quiet_speech() {
+        // Quiet speech with a pause: the pause doesn't count toward the level
+        let mut quiet = tone(0.02, 1.0);
+        quiet.extend(vec![0.0; 8000]);
+        let boosted = apply_agc(&quiet, 0.1, DEFAULT_SILENCE_FLOOR);
+        assert!((rms(&boosted[..16000]) - 0.1).abs() < 0.005);
+        assert!(boosted.iter().all(|s| s.abs() <= AGC_PEAK_LIMIT));
+        // The input is untouched
+        assert_eq!(quiet[100], 0.02 * (100.0f32 * 0.1).sin());
+
+        // A loud click limits the boost so nothing clips
+        let mut clicky = tone(0.02, 1.0);
+        clicky[500] = 0.5;
+        let boosted = apply_agc(&clicky, 0.1, DEFAULT_SILENCE_FLOOR);
+        let peak = boosted.iter().fold(0.0f32, |m, s| m.max(s.abs()));
+        assert!((peak - AGC_PEAK_LIMIT).abs() < 1e-4);
+
+        // Very quiet audio gets at most AGC_MAX_GAIN
+        let faint = tone(0.005, 1.0);
+        let boosted = apply_agc(&faint, 0.1, DEFAULT_SILENCE_FLOOR);
+        assert!((rms(&boosted) / rms(&faint) - AGC_MAX_GAIN).abs() < 1e-3);
+
+        // Silence and speech already at the target are left alone
+        assert!(matches!(
+            apply_agc(&tone(0.001, 1.0), 0.1, DEFAULT_SILENCE_FLOOR),
+            Cow::Borrowed(_)
+        ));
+        let level = tone(0.1 * std::f32::consts::SQRT_2, 1.0);
+        assert!(matches!(
+            apply_agc(&level, 0.1, DEFAULT_SILENCE_FLOOR),
+            Cow::Borrowed(_)
+        ));
+    }
+
+    #[test]
+    fn test_short_hallucinations_dropped() {
+        let short = tone(0.01, 0.5);
+        let long = tone(0.01, 3.0);
+        let says = |text: &'static str| move |_: &[f32]| Ok::<_, ()>(text.to_string());
+
+        assert_eq!(
+            guard_no_speech(&short, DEFAULT_SILENCE_FLOOR, says("Thank you.")),
+            Ok(String::new())
+        );
+        assert_eq!(
+            guard_no_speech(&short, DEFAULT_SILENCE_FLOOR, says("you")),
+            Ok(String::new())
+        );
+        assert_eq!(
+            guard_no_speech(&short, DEFAULT_SILENCE_FLOOR, says("Yes.")),
+            Ok("Yes.".to_string())
+        );
+        // Longer buffers may genuinely be someone saying thanks
+        assert_eq!(
+            guard_no_speech(&long, DEFAULT_SILENCE_FLOOR, says("Thank you.")),
+            Ok("Thank you.".to_string())
+        );
+        // So may short ones spoken clearly
+        let loud = tone(0.3, 0.5);
+        assert_eq!(
+            guard_no_speech(&loud, DEFAULT_SILENCE_FLOOR, says("Okay.")),
+            Ok("Okay.".to_string())
+        );
+    }
+
+    #[test]
+    fn test_interpolate_words_covers_range() {
+        let words = interpolate_words("hello big world", 10.0, 13.0);
+        assert_eq!(words.len(), 3);
+        assert!((words[0].start - 10.0).abs() < 1e-4);
+        assert!((words[1].start - words[0].end).abs() < 1e-4);
+        assert!((words[2].end - 13.0).abs() < 1e-4);
+        assert!(interpolate_words("   ", 0.0, 1.0).is_empty());
+    }
+    #[test]
+    fn test_language_models_load_once() {
+        let paths = BTreeMap::from([("es".to_string(), PathBuf::from("/models/es"))]);
+        let mut models = LanguageModels::new(PathBuf::from("/models/en"), paths);
+        let mut loads = 0;
+        let mut load = |language: Option<&str>| -> String {
+            models
+                .get(language, |path| {
+                    loads += 1;
+                    Ok::<_, ()>(path.display().to_string())
+                })
+                .unwrap()
+                .clone()
+        };
+        assert_eq!(load(Some("es")), "/models/es");
+        assert_eq!(load(None), "/models/en");
+        // Unknown languages fall back to the default model
+        assert_eq!(load(Some("fr")), "/models/en");
+        assert_eq!(load(Some("es")), "/models/es");
+        assert_eq!(loads, 2);
+
+        let err = LanguageModels::<String>::new(PathBuf::new(), BTreeMap::new())
+            .get(None, |_| Err("missing"))
+            .err();
+        assert_eq!(err, Some("missing"));
+    }
 }
@@ -1,6 +1,9 @@
+use crate::logger::{info, warn};
 use crate::state::SharedState;
 use crate::stats::{SharedStats, StatKind, Timer};
+use crate::tts_markup::{self, Segment};
 use cpal::Sample;
+use rodio::cpal::traits::{DeviceTrait, HostTrait};
 use rodio::{OutputStreamBuilder, Sink, Source};
 use std::sync::atomic::Ordering;
 use std::time::{Duration, Instant};
@@ -10,13 +13,117 @@ use crate::aec::{AecRenderTx, RenderFrame};
 
 pub trait TtsEngine: Send + Sync {
     fn synthesize(&self, text: &str) -> Result<(Vec<f32>, u32), Box<dyn std::error::Error>>;
+
+    /// Change speech speed for subsequent `synthesize` calls
+    fn set_speed(&mut self, speed: f32);
+
+    /// Current speech speed
+    fn speed(&self) -> f32;
+
+    /// Change the denoising step count, for engines that have one
+    fn set_steps(&mut self, _steps: usize) {}
+
+    /// Voices `set_voice` accepts, empty for single-voice engines
+    fn voices(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Switch voice for subsequent `synthesize` calls
+    fn set_voice(&mut self, _name: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        Err("The current TTS engine has a single voice".into())
+    }
+
+    /// Synthesize text in pieces, handing each one to `on_chunk` as soon as
+    /// it's ready so playback can start early. Defaults to a single piece.
+    fn synthesize_chunked(
+        &self,
+        text: &str,
+        on_chunk: &mut dyn FnMut(Vec<f32>, u32),
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let (audio, sample_rate) = self.synthesize(text)?;
+        on_chunk(audio, sample_rate);
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Output device
+// ============================================================================
+
+fn output_device_name(device: &rodio::cpal::Device) -> String {
+    device.name().unwrap_or_else(|_| "<unknown>".to_string())
+}
+
+/// Output device names and the default's name, for `silly devices`
+pub fn output_devices()
+-> Result<(Vec<String>, Option<String>), Box<dyn std::error::Error + Send + Sync>> {
+    let host = rodio::cpal::default_host();
+    let names = host
+        .output_devices()?
+        .map(|d| output_device_name(&d))
+        .collect();
+    let default = host.default_output_device().map(|d| output_device_name(&d));
+    Ok((names, default))
+}
+
+/// Open the output device matching `name` (`audio.output_device`, matched
+/// like the input device), or the default when unset or nothing matches
+fn open_output_stream(
+    name: Option<&str>,
+) -> Result<rodio::OutputStream, Box<dyn std::error::Error>> {
+    if let Some(query) = name.filter(|n| !n.trim().is_empty()) {
+        let devices: Vec<rodio::cpal::Device> =
+            rodio::cpal::default_host().output_devices()?.collect();
+        let names: Vec<String> = devices.iter().map(output_device_name).collect();
+        match crate::audio::find_device(&names, query) {
+            Some(idx) => {
+                info!("Audio output: {}", names[idx]);
+                let device = devices
+                    .into_iter()
+                    .nth(idx)
+                    .ok_or("Output device vanished")?;
+                return Ok(OutputStreamBuilder::from_device(device)?.open_stream_or_fallback()?);
+            }
+            None => warn!(
+                "No output device matches '{}', using the default (see `silly devices`)",
+                query
+            ),
+        }
+    }
+    Ok(OutputStreamBuilder::open_default_stream()?)
+}
+
+// ============================================================================
+// Wake chime
+// ============================================================================
+
+/// Sample rate of `wake_chime`
+pub const CHIME_SAMPLE_RATE: u32 = 24000;
+
+/// A short rising two-note chime, faded at the edges so it doesn't click
+pub fn wake_chime() -> Vec<f32> {
+    let rate = CHIME_SAMPLE_RATE as f32;
+    let note = move |freq: f32, secs: f32| {
+        (0..(secs * rate) as usize).map(move |i| {
+            let t = i as f32 / rate;
+            let fade = (t / 0.01).min((secs - t) / 0.03).clamp(0.0, 1.0);
+            0.2 * fade * (t * 2.0 * std::f32::consts::PI * freq).sin()
+        })
+    };
+    // E5 then A5
+    note(659.3, 0.08).chain(note(880.0, 0.12)).collect()
 }
 
 // ============================================================================
 // Audio Level Monitor - wraps a Source and monitors RMS levels in real-time
 // ============================================================================
 
-/// Wrapper around a Source that monitors audio levels as samples flow through
+/// Wrapper around a Source that applies the playback volume and monitors
+/// audio levels as samples flow through
+///
+/// The volume (`RuntimeState::playback_volume`, the user's volume with
+/// ducking) is re-read at each level update, so changes take effect within
+/// one update interval, even mid-sentence.
 pub struct MonitoredSource<I>
 where
     I: Source,
@@ -29,6 +136,7 @@ where
     #[cfg(feature = "aec")]
     sample_rate: u32,
     buffer: Vec<f32>,
+    volume: f32,
     last_update: Instant,
     update_interval: Duration,
 }
@@ -41,6 +149,7 @@ where
     pub fn new(input: I, state: SharedState) -> Self {
         #[cfg(feature = "aec")]
         let sample_rate = input.sample_rate();
+        let volume = state.playback_volume();
         Self {
             input,
             state,
@@ -49,6 +158,7 @@ where
             #[cfg(feature = "aec")]
             sample_rate,
             buffer: Vec::new(),
+            volume,
             last_update: Instant::now(),
             update_interval: Duration::from_millis(50),
         }
@@ -91,15 +201,16 @@ where
     type Item = I::Item;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let sample = self.input.next()?;
+        let sample = self.input.next()?.mul_amp(self.volume.to_sample());
 
         // Convert sample to f32 and add to buffer
         let float_sample = sample.to_float_sample();
         self.buffer.push(float_sample);
 
-        // Update level if enough time has passed
+        // Update level (and pick up volume changes) if enough time has passed
         if self.last_update.elapsed() >= self.update_interval {
             self.update_level();
+            self.volume = self.state.playback_volume();
             self.last_update = Instant::now();
         }
 
@@ -137,14 +248,14 @@ where
 ///
 /// This wraps a rodio Sink and provides:
 /// - Immediate stop capability
-/// - Volume ducking (reduce volume when user speaks)
+/// - Volume ducking (reduce volume when user speaks), applied to the samples
+///   by `MonitoredSource`
 /// - Integration with RuntimeState for coordinated control
 pub struct TtsController {
     sink: Sink,
     pub state: SharedState,
     #[cfg(feature = "aec")]
     pub aec_tx: Option<AecRenderTx>,
-    base_volume: f32,
 }
 
 impl TtsController {
@@ -155,7 +266,6 @@ impl TtsController {
             state,
             #[cfg(feature = "aec")]
             aec_tx: None,
-            base_volume: 1.0,
         }
     }
 
@@ -180,25 +290,46 @@ impl TtsController {
     /// Duck the volume (reduce to the level specified in state)
     #[allow(dead_code)]
     pub fn duck(&self) {
-        let duck_volume = self.state.get_tts_volume();
-        self.sink.set_volume(self.base_volume * duck_volume);
+        self.state.duck_tts();
     }
 
-    /// Restore volume to full
+    /// Restore volume after ducking
     #[allow(dead_code)]
     pub fn restore_volume(&self) {
         self.state.restore_tts_volume();
-        self.sink.set_volume(self.base_volume);
     }
 
-    /// Set the base volume level (0.0 - 1.0)
-    #[allow(dead_code)]
-    pub fn set_base_volume(&mut self, volume: f32) {
-        self.base_volume = volume.clamp(0.0, 1.0);
-        self.sink.set_volume(self.base_volume);
+    /// Queue mono audio, applying the playback volume and reporting levels
+    /// (and AEC reference frames) like speech
+    pub fn play(&self, audio: Vec<f32>, sample_rate: u32) {
+        let source = rodio::buffer::SamplesBuffer::new(1, sample_rate, audio);
+
+        // Wrap it in a monitored source that tracks audio levels in real-time
+        #[cfg(feature = "aec")]
+        let monitored_source = {
+            let ms = MonitoredSource::new(source, self.state.clone());
+            if let Some(ref tx) = self.aec_tx {
+                ms.with_aec_tx(tx.clone())
+            } else {
+                ms
+            }
+        };
+        #[cfg(not(feature = "aec"))]
+        let monitored_source = MonitoredSource::new(source, self.state.clone());
+
+        self.sink.append(monitored_source);
+    }
+
+    /// Queue a short, quiet tick (e.g. while waiting on a slow response)
+    pub fn tick(&self) {
+        let tick = rodio::source::SineWave::new(880.0)
+            .take_duration(Duration::from_millis(40))
+            .amplify(0.1 * self.state.playback_volume());
+        self.sink.append(tick);
     }
 
     /// Get the underlying sink for queueing audio
+    #[allow(dead_code)]
     pub fn sink(&self) -> &Sink {
         &self.sink
     }
@@ -208,12 +339,6 @@ impl TtsController {
         self.sink.sleep_until_end();
     }
 
-    /// Update volume based on current state (call periodically during playback)
-    pub fn update_volume(&self) {
-        let state_volume = self.state.get_tts_volume();
-        self.sink.set_volume(self.base_volume * state_volume);
-    }
-
     /// Check if cancellation was requested
     pub fn is_cancel_requested(&self) -> bool {
         self.state.is_cancel_requested()
@@ -300,17 +425,45 @@ impl TtsEngine for KokoroEngine {
         )?;
         Ok((audio, 24000))
     }
+
+    fn set_speed(&mut self, speed: f32) {
+        self.speed = speed;
+    }
+
+    fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    fn voices(&self) -> Vec<String> {
+        let mut voices = self.engine.get_available_voices();
+        voices.sort();
+        voices
+    }
+
+    fn set_voice(&mut self, name: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        if !self.engine.get_available_voices().iter().any(|v| v == name) {
+            return Err(format!("Unknown voice: {} (see /voices)", name).into());
+        }
+        self.style = name.to_string();
+        Ok(())
+    }
 }
 
 // ============================================================================
 // Supertonic TTS Engine
 // ============================================================================
 
+#[cfg(feature = "supertonic")]
+use crate::sentence::SentenceSplitter;
 #[cfg(feature = "supertonic")]
 use crate::supertonic;
 #[cfg(feature = "supertonic")]
 use std::sync::Mutex;
 
+/// Silence between chunks of chunked Supertonic synthesis (seconds)
+#[cfg(feature = "supertonic")]
+const SUPERTONIC_CHUNK_SILENCE: f32 = 0.3;
+
 #[cfg(feature = "supertonic")]
 pub struct SupertonicEngine {
     tts: Mutex<supertonic::TextToSpeech>,
@@ -325,6 +478,7 @@ impl SupertonicEngine {
         onnx_dir: &str,
         voice_style_path: &str,
         speed: f32,
+        steps: usize,
         use_gpu: bool,
     ) -> Result<Self, Box<dyn std::error::Error>> {
         let tts = supertonic::load_text_to_speech(onnx_dir, use_gpu)?;
@@ -332,7 +486,7 @@ impl SupertonicEngine {
         Ok(Self {
             tts: Mutex::new(tts),
             style,
-            total_step: 5,
+            total_step: steps,
             speed,
         })
     }
@@ -343,18 +497,147 @@ impl TtsEngine for SupertonicEngine {
     fn synthesize(&self, text: &str) -> Result<(Vec<f32>, u32), Box<dyn std::error::Error>> {
         let mut tts = self.tts.lock().unwrap();
         let sample_rate = tts.sample_rate;
-        let (wav, _) = tts.call(text, &self.style, self.total_step, self.speed, 0.3)?;
+        let (wav, _) = tts.call(
+            text,
+            &self.style,
+            self.total_step,
+            self.speed,
+            SUPERTONIC_CHUNK_SILENCE,
+        )?;
         Ok((wav, sample_rate as u32))
     }
+
+    /// One denoising pass per sentence, so a long paragraph starts playing
+    /// after its first sentence instead of after the whole thing
+    fn synthesize_chunked(
+        &self,
+        text: &str,
+        on_chunk: &mut dyn FnMut(Vec<f32>, u32),
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut splitter = SentenceSplitter::new();
+        let mut sentences = splitter.push(text);
+        sentences.extend(splitter.finish());
+
+        let mut tts = self.tts.lock().unwrap();
+        let sample_rate = tts.sample_rate as u32;
+        let gap = (SUPERTONIC_CHUNK_SILENCE * sample_rate as f32) as usize;
+        for (i, sentence) in sentences.iter().enumerate() {
+            let (wav, _) = tts.call(
+                sentence,
+                &self.style,
+                self.total_step,
+                self.speed,
+                SUPERTONIC_CHUNK_SILENCE,
+            )?;
+            let audio = if i == 0 {
+                wav
+            } else {
+                std::iter::repeat_n(0.0, gap).chain(wav).collect()
+            };
+            on_chunk(audio, sample_rate);
+        }
+        Ok(())
+    }
+
+    fn set_speed(&mut self, speed: f32) {
+        self.speed = speed;
+    }
+
+    fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    fn set_steps(&mut self, steps: usize) {
+        self.total_step = steps;
+    }
 }
 
 // ============================================================================
 // Unified TTS wrapper
 // ============================================================================
 
+/// Sample rate for text that is nothing but pauses
+const PAUSE_SAMPLE_RATE: u32 = 24000;
+
+fn silence(ms: u32, sample_rate: u32) -> impl Iterator<Item = f32> {
+    std::iter::repeat_n(0.0, (ms as u64 * sample_rate as u64 / 1000) as usize)
+}
+
+/// Synthesize parsed text, honoring `[pause]` and `[slow]` markup (see
+/// `tts_markup`), handing audio to `on_audio` chunk by chunk as the engine
+/// produces it. Pauses are prepended to the chunk that follows them.
+fn synthesize_each(
+    engine: &mut dyn TtsEngine,
+    segments: Vec<Segment>,
+    on_audio: &mut dyn FnMut(Vec<f32>, u32),
+) -> Result<(), Box<dyn std::error::Error>> {
+    let base_speed = engine.speed();
+    let mut sample_rate = None;
+    let mut pause_ms = 0;
+    for segment in segments {
+        match segment {
+            Segment::Pause(ms) => pause_ms += ms,
+            Segment::Speech { text, speed } => {
+                engine.set_speed((base_speed * speed).clamp(0.5, 2.0));
+                let result = engine.synthesize_chunked(&text, &mut |samples, rate| {
+                    let audio = silence(std::mem::take(&mut pause_ms), rate)
+                        .chain(samples)
+                        .collect();
+                    sample_rate = Some(rate);
+                    on_audio(audio, rate);
+                });
+                engine.set_speed(base_speed);
+                result?;
+            }
+        }
+    }
+    if pause_ms > 0 {
+        // Any rate will do for silence alone
+        let rate = sample_rate.unwrap_or(PAUSE_SAMPLE_RATE);
+        on_audio(silence(pause_ms, rate).collect(), rate);
+    }
+    Ok(())
+}
+
+/// Audio of the most recent response, kept for saving
+#[derive(Default)]
+struct Clip {
+    /// Samples and their sample rate
+    audio: Option<(Vec<f32>, u32)>,
+    /// The next recorded audio starts a new clip
+    restart: bool,
+    /// Queued audio isn't recorded (acknowledgements and other short replies)
+    paused: bool,
+}
+
+impl Clip {
+    fn start(&mut self) {
+        self.restart = true;
+        self.paused = false;
+    }
+
+    fn skip(&mut self) {
+        self.paused = true;
+    }
+
+    fn record(&mut self, samples: &[f32], sample_rate: u32) {
+        if self.paused {
+            return;
+        }
+        match &mut self.audio {
+            Some((clip, rate)) if !self.restart && *rate == sample_rate => {
+                clip.extend_from_slice(samples)
+            }
+            _ => self.audio = Some((samples.to_vec(), sample_rate)),
+        }
+        self.restart = false;
+    }
+}
+
 pub struct Tts {
     engine: Box<dyn TtsEngine>,
     stats: Option<SharedStats>,
+    clip: Clip,
 }
 
 impl Tts {
@@ -363,6 +646,7 @@ impl Tts {
         Self {
             engine,
             stats: None,
+            clip: Clip::default(),
         }
     }
 
@@ -370,25 +654,97 @@ impl Tts {
         Self {
             engine,
             stats: Some(stats),
+            clip: Clip::default(),
         }
     }
 
+    /// Change speech speed for subsequently queued text
+    pub fn set_speed(&mut self, speed: f32) {
+        self.engine.set_speed(speed);
+    }
+
+    /// Change denoising steps for subsequently queued text
+    pub fn set_steps(&mut self, steps: usize) {
+        self.engine.set_steps(steps);
+    }
+
+    /// Voices available to `set_voice`
+    pub fn voices(&self) -> Vec<String> {
+        self.engine.voices()
+    }
+
+    /// Switch voice for subsequently queued text
+    pub fn set_voice(
+        &mut self,
+        name: &str,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.engine.set_voice(name)
+    }
+
+    /// Synthesize text in one buffer (see `synthesize_each`)
+    pub fn synthesize(
+        &mut self,
+        text: &str,
+    ) -> Result<(Vec<f32>, u32), Box<dyn std::error::Error>> {
+        let mut audio = Vec::new();
+        let mut sample_rate = PAUSE_SAMPLE_RATE;
+        let segments = tts_markup::parse(text);
+        synthesize_each(self.engine.as_mut(), segments, &mut |samples, rate| {
+            audio.extend(samples);
+            sample_rate = rate;
+        })?;
+        Ok((audio, sample_rate))
+    }
+
+    /// Synthesize a short phrase and discard it, so the first response
+    /// doesn't pay for graph optimization and allocation. Returns how long
+    /// it took.
+    pub fn warmup(&mut self) -> Result<Duration, Box<dyn std::error::Error>> {
+        let start = Instant::now();
+        self.engine.synthesize("Hi.")?;
+        Ok(start.elapsed())
+    }
+
+    /// Start a new clip with the next queued audio, keeping the last one
+    /// until then (a response with TTS off doesn't discard it)
+    pub fn start_clip(&mut self) {
+        self.clip.start();
+    }
+
+    /// Leave the next queued audio out of the clips, keeping the last one
+    /// until `start_clip`
+    pub fn skip_clip(&mut self) {
+        self.clip.skip();
+    }
+
+    /// Audio queued since the last `start_clip`, with its sample rate
+    pub fn last_clip(&self) -> Option<(&[f32], u32)> {
+        self.clip
+            .audio
+            .as_ref()
+            .map(|(samples, rate)| (samples.as_slice(), *rate))
+    }
+
+    /// Speak `text` on `output_device` and wait until it's played
     #[allow(dead_code)]
-    pub fn speak(&self, text: &str) -> Result<(), Box<dyn std::error::Error>> {
-        let (audio, sample_rate) = self.engine.synthesize(text)?;
-        let stream = OutputStreamBuilder::open_default_stream()?;
-        let sink = Sink::connect_new(stream.mixer());
+    pub fn speak(
+        &mut self,
+        text: &str,
+        output_device: Option<&str>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let (audio, sample_rate) = self.synthesize(text)?;
+        let (_stream, sink) = Self::create_sink(output_device)?;
         sink.append(rodio::buffer::SamplesBuffer::new(1, sample_rate, audio));
         sink.sleep_until_end();
         Ok(())
     }
 
-    pub fn queue(&self, text: &str, sink: &Sink) -> Result<(), Box<dyn std::error::Error>> {
+    pub fn queue(&mut self, text: &str, sink: &Sink) -> Result<(), Box<dyn std::error::Error>> {
         let timer = self
             .stats
             .as_ref()
             .map(|s| Timer::new(s, StatKind::Tts, text.len()));
-        let (audio, sample_rate) = self.engine.synthesize(text)?;
+        let (audio, sample_rate) = self.synthesize(text)?;
         if let Some(t) = timer {
             t.finish(audio.len());
         }
@@ -396,54 +752,50 @@ impl Tts {
         Ok(())
     }
 
-    pub fn create_sink() -> Result<(rodio::OutputStream, Sink), Box<dyn std::error::Error>> {
-        let stream = OutputStreamBuilder::open_default_stream()?;
+    /// A sink playing to `output_device` (see `open_output_stream`)
+    pub fn create_sink(
+        output_device: Option<&str>,
+    ) -> Result<(rodio::OutputStream, Sink), Box<dyn std::error::Error>> {
+        let stream = open_output_stream(output_device)?;
         let sink = Sink::connect_new(stream.mixer());
         Ok((stream, sink))
     }
 
-    /// Create a TTS controller with the given state
+    /// Create a TTS controller with the given state, playing to
+    /// `output_device` (see `open_output_stream`)
     pub fn create_controller(
         state: SharedState,
+        output_device: Option<&str>,
     ) -> Result<(rodio::OutputStream, TtsController), Box<dyn std::error::Error>> {
-        let stream = OutputStreamBuilder::open_default_stream()?;
-        let sink = Sink::connect_new(stream.mixer());
+        let (stream, sink) = Self::create_sink(output_device)?;
         let controller = TtsController::new(sink, state);
         Ok((stream, controller))
     }
 
-    /// Queue text to a TTS controller
+    /// Queue text to a TTS controller, parsing it with `markup` so tags left
+    /// open by earlier sentences of the same response carry on
     pub fn queue_to_controller(
-        &self,
+        &mut self,
         text: &str,
+        markup: &mut tts_markup::Parser,
         controller: &TtsController,
     ) -> Result<(), Box<dyn std::error::Error>> {
         let timer = self
             .stats
             .as_ref()
             .map(|s| Timer::new(s, StatKind::Tts, text.len()));
-        let (audio, sample_rate) = self.engine.synthesize(text)?;
+        // Queue each chunk as soon as it's synthesized
+        let clip = &mut self.clip;
+        let mut total_samples = 0;
+        let segments = markup.parse(text);
+        synthesize_each(self.engine.as_mut(), segments, &mut |audio, sample_rate| {
+            total_samples += audio.len();
+            clip.record(&audio, sample_rate);
+            controller.play(audio, sample_rate);
+        })?;
         if let Some(t) = timer {
-            t.finish(audio.len());
+            t.finish(total_samples);
         }
-
-        // Create the audio buffer source
-        let source = rodio::buffer::SamplesBuffer::new(1, sample_rate, audio);
-
-        // Wrap it in a monitored source that tracks audio levels in real-time
-        #[cfg(feature = "aec")]
-        let monitored_source = {
-            let ms = MonitoredSource::new(source, controller.state.clone());
-            if let Some(ref tx) = controller.aec_tx {
-                ms.with_aec_tx(tx.clone())
-            } else {
-                ms
-            }
-        };
-        #[cfg(not(feature = "aec"))]
-        let monitored_source = MonitoredSource::new(source, controller.state.clone());
-
-        controller.sink().append(monitored_source);
         Ok(())
     }
 
@@ -459,3 +811,60 @@ impl Tts {
         std::mem::forget(stream); // Suppress "Dropping OutputStream" warning
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::state::RuntimeState;
+
+    #[test]
+    fn test_playback_volume_scales_samples() {
+        let config = Config::default();
+        let state = RuntimeState::new(&config);
+        let samples = vec![0.5, -0.5, 0.25, -1.0];
+        let play = |state: &SharedState| -> Vec<f32> {
+            let source = rodio::buffer::SamplesBuffer::new(1, 16000, samples.clone());
+            MonitoredSource::new(source, state.clone()).collect()
+        };
+        assert_eq!(play(&state), samples);
+
+        state.set_tts_volume(0.5);
+        assert_eq!(play(&state), vec![0.25, -0.25, 0.125, -0.5]);
+
+        // Ducking stacks on the user's volume
+        state.duck_tts();
+        let gain = 0.5 * config.interaction.duck_volume;
+        for (played, original) in play(&state).iter().zip(&samples) {
+            assert!((played - original * gain).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_acknowledgements_keep_last_clip() {
+        let mut clip = Clip::default();
+        clip.start();
+        clip.record(&[0.1; 4], 24000);
+        clip.record(&[0.2; 2], 24000);
+
+        // "Noted." isn't recorded over the response
+        clip.skip();
+        clip.record(&[0.9; 3], 24000);
+        assert_eq!(clip.audio.as_ref().unwrap().0.len(), 6);
+
+        clip.start();
+        clip.record(&[0.3; 5], 24000);
+        assert_eq!(clip.audio, Some((vec![0.3; 5], 24000)));
+    }
+
+    #[test]
+    fn test_wake_chime() {
+        let chime = wake_chime();
+        assert_eq!(chime.len(), CHIME_SAMPLE_RATE as usize / 5);
+        let peak = chime.iter().fold(0.0f32, |m, s| m.max(s.abs()));
+        assert!(peak > 0.1 && peak <= 0.2);
+        // Faded in and out
+        assert!(chime[0].abs() < 1e-3);
+        assert!(chime[chime.len() - 1].abs() < 0.01);
+    }
+}
@@ -0,0 +1,315 @@
+//! Minimal markup for spoken text
+//!
+//! `[pause 500ms]` (or `[pause 1.5s]`) inserts silence and `[slow]...[/slow]`
+//! speaks the enclosed text at reduced speed. Any other tag starting with a
+//! letter is dropped so it isn't read aloud; brackets like `[1]` are kept.
+//!
+//! Responses are spoken sentence by sentence, so a `Parser` carries `[slow]`
+//! from one sentence into the next, and `Stripper` takes the tags out of the
+//! text that's shown or kept in the history.
+
+/// Speed multiplier inside `[slow]...[/slow]`
+pub const SLOW_FACTOR: f32 = 0.75;
+
+/// Longest pause a single tag can insert
+const MAX_PAUSE_MS: u32 = 5000;
+
+/// Longest tag `Stripper` waits for the `]` of before showing it as text
+const MAX_TAG_LEN: usize = 32;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Segment {
+    /// Text to speak, `speed` multiplies the current TTS speed
+    Speech { text: String, speed: f32 },
+    /// Silence in milliseconds
+    Pause(u32),
+}
+
+/// Split text into speech and pause segments
+pub fn parse(text: &str) -> Vec<Segment> {
+    Parser::new().parse(text)
+}
+
+/// Parses consecutive pieces of one text, such as the sentences of a response
+pub struct Parser {
+    /// Speed multiplier left open by the last piece
+    speed: f32,
+}
+
+impl Parser {
+    pub fn new() -> Self {
+        Self { speed: 1.0 }
+    }
+
+    /// Split the next piece into speech and pause segments, continuing any
+    /// `[slow]` the previous pieces left open
+    pub fn parse(&mut self, text: &str) -> Vec<Segment> {
+        let (segments, speed) = parse_from(text, self.speed);
+        self.speed = speed;
+        segments
+    }
+}
+
+impl Default for Parser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Parse `text` starting at `speed`, returning the speed it ends at
+fn parse_from(text: &str, mut speed: f32) -> (Vec<Segment>, f32) {
+    let mut segments = Vec::new();
+    let mut current = String::new();
+    let mut rest = text;
+
+    while let Some(open) = rest.find('[') {
+        current.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+        let Some(close) = after.find(']') else {
+            // Unclosed bracket, keep it as text
+            current.push_str(&rest[open..]);
+            rest = "";
+            break;
+        };
+        let tag = after[..close].trim().to_lowercase();
+        rest = &after[close + 1..];
+
+        if !is_tag(&tag) {
+            current.push('[');
+            current.push_str(&after[..close]);
+            current.push(']');
+            continue;
+        }
+
+        if tag == "slow" || tag == "/slow" {
+            flush(&mut segments, &mut current, speed);
+            speed = if tag == "slow" { SLOW_FACTOR } else { 1.0 };
+        } else if let Some(ms) = tag.strip_prefix("pause").and_then(parse_duration) {
+            flush(&mut segments, &mut current, speed);
+            segments.push(Segment::Pause(ms.min(MAX_PAUSE_MS)));
+        } else {
+            // Unknown tag: drop it, keeping a word break
+            current.push(' ');
+        }
+    }
+    current.push_str(rest);
+    flush(&mut segments, &mut current, speed);
+    (segments, speed)
+}
+
+/// Whether the text between brackets is markup rather than something like `[1]`
+fn is_tag(inner: &str) -> bool {
+    inner
+        .trim()
+        .starts_with(|c: char| c.is_alphabetic() || c == '/')
+}
+
+/// Text without its markup, for display
+pub fn strip(text: &str) -> String {
+    let mut stripper = Stripper::default();
+    let mut shown = stripper.push(text);
+    shown.push_str(&stripper.finish());
+    shown
+}
+
+/// Removes markup from streamed text, holding back a possible tag until its
+/// `]` arrives
+#[derive(Default)]
+pub struct Stripper {
+    /// Text after an unmatched `[`
+    tag: Option<String>,
+    /// Something was shown that didn't end in whitespace
+    mid_word: bool,
+    /// A tag was dropped after whitespace, so the whitespace after it is
+    /// dropped too
+    skip_space: bool,
+}
+
+impl Stripper {
+    /// Add streamed text, returning what can be shown so far
+    pub fn push(&mut self, text: &str) -> String {
+        let mut shown = String::new();
+        for c in text.chars() {
+            let Some(tag) = &mut self.tag else {
+                if c == '[' {
+                    self.tag = Some(String::new());
+                } else {
+                    self.show(&mut shown, c);
+                }
+                continue;
+            };
+            if c == ']' {
+                let tag = self.tag.take().unwrap_or_default();
+                if is_tag(&tag) {
+                    self.skip_space = !self.mid_word;
+                } else {
+                    self.show_bracketed(&mut shown, &tag, "]");
+                }
+            } else if c == '[' || tag.len() >= MAX_TAG_LEN {
+                // Not a tag after all
+                let tag = self.tag.take().unwrap_or_default();
+                self.show_bracketed(&mut shown, &tag, "");
+                if c == '[' {
+                    self.tag = Some(String::new());
+                } else {
+                    self.show(&mut shown, c);
+                }
+            } else {
+                tag.push(c);
+            }
+        }
+        shown
+    }
+
+    /// The rest of the text once the stream has ended
+    pub fn finish(&mut self) -> String {
+        let mut shown = String::new();
+        if let Some(tag) = self.tag.take() {
+            self.show_bracketed(&mut shown, &tag, "");
+        }
+        shown
+    }
+
+    fn show(&mut self, shown: &mut String, c: char) {
+        if c.is_whitespace() {
+            if std::mem::take(&mut self.skip_space) {
+                return;
+            }
+            self.mid_word = false;
+        } else {
+            self.skip_space = false;
+            self.mid_word = true;
+        }
+        shown.push(c);
+    }
+
+    fn show_bracketed(&mut self, shown: &mut String, inner: &str, close: &str) {
+        for c in std::iter::once('[')
+            .chain(inner.chars())
+            .chain(close.chars())
+        {
+            self.show(shown, c);
+        }
+    }
+}
+
+/// Push pending text as a speech segment, normalizing whitespace
+fn flush(segments: &mut Vec<Segment>, current: &mut String, speed: f32) {
+    let text = current.split_whitespace().collect::<Vec<_>>().join(" ");
+    current.clear();
+    if !text.is_empty() {
+        segments.push(Segment::Speech { text, speed });
+    }
+}
+
+/// Parse "500ms", "1.5s" or a bare number of milliseconds
+fn parse_duration(s: &str) -> Option<u32> {
+    let s = s.trim();
+    if let Some(ms) = s.strip_suffix("ms") {
+        ms.trim().parse::<f32>().ok().map(|ms| ms as u32)
+    } else if let Some(secs) = s.strip_suffix('s') {
+        secs.trim()
+            .parse::<f32>()
+            .ok()
+            .map(|secs| (secs * 1000.0) as u32)
+    } else {
+        s.parse::<f32>().ok().map(|ms| ms as u32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sentence::SentenceSplitter;
+
+    fn speech(text: &str, speed: f32) -> Segment {
+        Segment::Speech {
+            text: text.to_string(),
+            speed,
+        }
+    }
+
+    #[test]
+    fn test_plain_text() {
+        assert_eq!(parse("Hello there."), vec![speech("Hello there.", 1.0)]);
+        assert_eq!(parse("  "), vec![]);
+    }
+
+    #[test]
+    fn test_pause_and_slow() {
+        assert_eq!(
+            parse("Ready? [pause 500ms] Here [slow]it comes[/slow] now."),
+            vec![
+                speech("Ready?", 1.0),
+                Segment::Pause(500),
+                speech("Here", 1.0),
+                speech("it comes", SLOW_FACTOR),
+                speech("now.", 1.0),
+            ]
+        );
+        assert_eq!(parse("[pause 1.5s]"), vec![Segment::Pause(1500)]);
+        assert_eq!(parse("[PAUSE 200]"), vec![Segment::Pause(200)]);
+        assert_eq!(parse("[pause 1m]"), vec![]);
+        assert_eq!(parse("[pause 60s]"), vec![Segment::Pause(MAX_PAUSE_MS)]);
+        // Unclosed slow lasts to the end
+        assert_eq!(
+            parse("[slow]Take it easy"),
+            vec![speech("Take it easy", SLOW_FACTOR)]
+        );
+    }
+
+    #[test]
+    fn test_unknown_tags() {
+        assert_eq!(parse("Hi [laughs] there"), vec![speech("Hi there", 1.0)]);
+        assert_eq!(parse("See note [1]."), vec![speech("See note [1].", 1.0)]);
+        assert_eq!(parse("Odd [bracket"), vec![speech("Odd [bracket", 1.0)]);
+        assert_eq!(parse("[/emphasis]Done"), vec![speech("Done", 1.0)]);
+    }
+
+    #[test]
+    fn test_slow_across_sentences() {
+        let response = "[slow]Take a deep breath. Hold it for a moment.[/slow] Now let it go.";
+        let mut splitter = SentenceSplitter::new();
+        let mut sentences = splitter.push(response);
+        sentences.extend(splitter.finish());
+        assert_eq!(sentences.len(), 2);
+
+        let mut parser = Parser::new();
+        let segments: Vec<Segment> = sentences.iter().flat_map(|s| parser.parse(s)).collect();
+        assert_eq!(
+            segments,
+            vec![
+                speech("Take a deep breath.", SLOW_FACTOR),
+                speech("Hold it for a moment.", SLOW_FACTOR),
+                speech("Now let it go.", 1.0),
+            ]
+        );
+        // A new response starts at normal speed
+        assert_eq!(
+            Parser::new().parse("Hold it."),
+            vec![speech("Hold it.", 1.0)]
+        );
+    }
+
+    #[test]
+    fn test_strip() {
+        assert_eq!(
+            strip("Ready? [pause 500ms] Here [slow]it comes[/slow] now."),
+            "Ready? Here it comes now."
+        );
+        assert_eq!(strip("[slow]Take it easy"), "Take it easy");
+        assert_eq!(strip("Hi [laughs] there"), "Hi there");
+        assert_eq!(strip("See note [1]."), "See note [1].");
+        assert_eq!(strip("Odd [bracket"), "Odd [bracket");
+        assert_eq!(strip("a [[slow]b"), "a [b");
+
+        // Tags split across streamed tokens are held back until they close
+        let mut stripper = Stripper::default();
+        let shown: Vec<String> = ["Say [sl", "ow]it", " slowly[/", "slow]. See [", "2]"]
+            .iter()
+            .map(|token| stripper.push(token))
+            .collect();
+        assert_eq!(shown, ["Say ", "it", " slowly", ". See ", "[2]"]);
+        assert_eq!(stripper.finish(), "");
+    }
+}
@@ -1,30 +1,15 @@
 //! Terminal UI with proper cursor management and synchronized updates
 
+use crate::logger;
 use crate::render::{OrbStyle, UiEvent, UiMode, UiRenderer};
 use crate::state::AppMode;
 use crate::status_bar::{SpinnerType, StatusBarState, StatusDisplayStyle, StatusRenderer};
 use crossterm::event::{self, Event, KeyCode, KeyModifiers};
 use crossterm::terminal::{self, ClearType};
 use crossterm::{cursor, execute, queue};
-use std::fs::OpenOptions;
 use std::io::{self, Write, stdout};
 use unicode_width::UnicodeWidthStr;
 
-fn debug_log(msg: &str) {
-    if let Ok(mut file) = OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open("debug.log")
-    {
-        let _ = writeln!(
-            file,
-            "{}: {}",
-            chrono::Utc::now().format("%H:%M:%S%.3f"),
-            msg
-        );
-    }
-}
-
 pub struct Tui {
     preview: String,
     input: String,
@@ -39,10 +24,10 @@ pub struct Tui {
 
 impl Tui {
     pub fn new() -> io::Result<Self> {
-        debug_log("TUI: Creating new TUI instance");
+        logger::debug!("TUI: Creating new TUI instance");
         terminal::enable_raw_mode()?;
         execute!(stdout(), cursor::Hide)?;
-        debug_log("TUI: Raw mode enabled, cursor hidden");
+        logger::debug!("TUI: Raw mode enabled, cursor hidden");
         let mut status_bar = StatusBarState::new();
         // Text UI always uses emoji style
         status_bar.display_style = StatusDisplayStyle::Emoji;
@@ -79,6 +64,10 @@ impl Tui {
         self.status_bar.mode = mode;
     }
 
+    pub fn set_language(&mut self, language: Option<&str>) {
+        self.status_bar.language = language.map(String::from);
+    }
+
     pub fn restore(&self) -> io::Result<()> {
         execute!(stdout(), cursor::Show, cursor::MoveToColumn(0))?;
         // Don't disable raw mode here - let the new UI handle terminal mode
@@ -189,6 +178,10 @@ impl Tui {
                 self.status_bar.spinner_type = SpinnerType::None;
                 self.preview.clear();
             }
+            UiEvent::WakeDetected => {
+                self.status_bar.status = "🎤 Listening".to_string();
+                self.status_bar.spinner_type = SpinnerType::None;
+            }
             UiEvent::Tick => {}
             UiEvent::ContextWords(count) => {
                 self.status_bar.context_words = count;
@@ -282,13 +275,13 @@ impl Tui {
     }
 
     pub fn poll_input(&mut self) -> io::Result<Option<String>> {
-        debug_log("TUI: poll_input called");
+        logger::debug!("TUI: poll_input called");
         let mut pending_submit = None;
 
         while event::poll(std::time::Duration::from_millis(0))? {
-            debug_log("TUI: Event available");
+            logger::debug!("TUI: Event available");
             if let Event::Key(key) = event::read()? {
-                debug_log(&format!("TUI: Key event: {:?}", key));
+                logger::debug!("TUI: Key event: {:?}", key);
                 self.keypress_activity = true;
 
                 if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
@@ -444,6 +437,10 @@ impl Tui {
         self.status_bar.audio_level = level;
     }
 
+    pub fn set_clipping(&mut self, clipping: bool) {
+        self.status_bar.clipping = clipping;
+    }
+
     pub fn set_tts_level(&mut self, level: f32) {
         self.status_bar.tts_level = level;
     }
@@ -545,6 +542,10 @@ impl UiRenderer for Tui {
         Tui::set_mode(self, mode)
     }
 
+    fn set_language(&mut self, language: Option<&str>) {
+        Tui::set_language(self, language)
+    }
+
     fn set_ready(&mut self) {
         Tui::set_ready(self)
     }
@@ -557,6 +558,10 @@ impl UiRenderer for Tui {
         Tui::set_audio_level(self, level)
     }
 
+    fn set_clipping(&mut self, clipping: bool) {
+        Tui::set_clipping(self, clipping)
+    }
+
     fn set_tts_level(&mut self, level: f32) {
         Tui::set_tts_level(self, level)
     }
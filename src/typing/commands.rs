@@ -3,6 +3,7 @@
 //! Handles the distinction between text to type and commands to execute.
 //! Uses smart detection based on pause duration, phrase length, and patterns.
 
+use super::numbers::words_to_digits;
 use std::collections::HashMap;
 
 /// Typing commands that can be recognized from speech
@@ -25,6 +26,7 @@ pub enum TypingCommand {
     // Undo/Redo
     Undo,
     Redo,
+    Scratch, // Undo everything the last phrase typed
 
     // Selection
     SelectAll,
@@ -41,6 +43,13 @@ pub enum TypingCommand {
     MoveUp(u32),
     MoveDown(u32),
 
+    // User-defined replacement text ("smiley" -> ":)")
+    InsertText(String),
+
+    // Modes
+    NumberMode(bool), // Convert spoken numbers to digits
+    CodeMode(bool),   // Spoken symbols for code and math, no auto-capitalization
+
     // Control
     Stop,   // Exit typing mode
     Pause,  // Pause (mute mic)
@@ -93,16 +102,108 @@ impl ParseResult {
     }
 }
 
+/// Default prefixes that make the following word(s) typed literally
+pub const DEFAULT_LITERAL_PREFIXES: &[&str] = &["literal", "spell", "type the word"];
+
+/// Stands in for an escaped phrase while commands and punctuation are parsed
+/// (a private-use character, so it never matches a spoken pattern)
+const LITERAL_MARKER: char = '\u{E000}';
+
+/// How a code mode symbol joins the words around it
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Glue {
+    /// Spaced like a word: "x = y"
+    Spaced,
+    /// Attached to the word before: "x,"
+    Left,
+    /// Attached to the word after: "!x"
+    Right,
+    /// Attached on both sides: "foo.bar"
+    Both,
+}
+
+impl Glue {
+    fn left(self) -> bool {
+        matches!(self, Glue::Left | Glue::Both)
+    }
+
+    fn right(self) -> bool {
+        matches!(self, Glue::Right | Glue::Both)
+    }
+}
+
+/// Spoken symbols in code mode, checked before the punctuation map.
+/// "x equals y plus two" -> "x = y + 2"
+const CODE_SYMBOLS: &[(&str, &str, Glue)] = &[
+    ("equals", "=", Glue::Spaced),
+    ("equal", "=", Glue::Spaced),
+    ("equals sign", "=", Glue::Spaced),
+    ("gets", "=", Glue::Spaced),
+    ("double equals", "==", Glue::Spaced),
+    ("equals equals", "==", Glue::Spaced),
+    ("is equal to", "==", Glue::Spaced),
+    ("not equals", "!=", Glue::Spaced),
+    ("not equal to", "!=", Glue::Spaced),
+    ("plus", "+", Glue::Spaced),
+    ("minus", "-", Glue::Spaced),
+    ("times", "*", Glue::Spaced),
+    ("multiplied by", "*", Glue::Spaced),
+    ("divided by", "/", Glue::Spaced),
+    ("over", "/", Glue::Spaced),
+    ("modulo", "%", Glue::Spaced),
+    ("mod", "%", Glue::Spaced),
+    ("plus equals", "+=", Glue::Spaced),
+    ("minus equals", "-=", Glue::Spaced),
+    ("times equals", "*=", Glue::Spaced),
+    ("less than", "<", Glue::Spaced),
+    ("greater than", ">", Glue::Spaced),
+    ("less than or equal to", "<=", Glue::Spaced),
+    ("greater than or equal to", ">=", Glue::Spaced),
+    ("and and", "&&", Glue::Spaced),
+    ("logical and", "&&", Glue::Spaced),
+    ("or or", "||", Glue::Spaced),
+    ("logical or", "||", Glue::Spaced),
+    ("arrow", "->", Glue::Spaced),
+    ("fat arrow", "=>", Glue::Spaced),
+    ("to the power of", "^", Glue::Spaced),
+    ("negative", "-", Glue::Right),
+    ("bang", "!", Glue::Right),
+    ("dot", ".", Glue::Both),
+    ("point", ".", Glue::Both),
+    ("double colon", "::", Glue::Both),
+    ("underscore", "_", Glue::Both),
+    ("open paren", "(", Glue::Both),
+    ("open parenthesis", "(", Glue::Both),
+    ("close paren", ")", Glue::Left),
+    ("close parenthesis", ")", Glue::Left),
+    ("open bracket", "[", Glue::Both),
+    ("close bracket", "]", Glue::Left),
+    ("open brace", "{", Glue::Spaced),
+    ("close brace", "}", Glue::Spaced),
+    ("open quote", "\"", Glue::Right),
+    ("close quote", "\"", Glue::Left),
+    ("backtick", "`", Glue::Both),
+];
+
+/// Longest phrase in `CODE_SYMBOLS`, in words
+const MAX_CODE_SYMBOL_WORDS: usize = 5;
+
 /// Parser for detecting commands in transcribed speech
 pub struct CommandParser {
     /// Phrase -> Command mappings (lowercase)
     patterns: HashMap<String, TypingCommand>,
     /// Punctuation phrase -> char mappings (lowercase)
     punctuation: HashMap<String, char>,
+    /// User-defined phrase -> multi-character replacement (lowercase)
+    custom_punctuation: HashMap<String, String>,
+    /// Prefixes that escape the next phrase ("literal comma" -> "comma"), longest first
+    literal_prefixes: Vec<String>,
     /// Minimum pause duration (ms) to consider short phrase as pure command
     min_pause_for_command: u32,
     /// Maximum words for a "short phrase" that could be a pure command
     max_words_for_command: usize,
+    /// Code mode: spoken symbols are typed as code ("x = y + 2")
+    code_mode: bool,
 }
 
 impl Default for CommandParser {
@@ -129,6 +230,8 @@ impl CommandParser {
         // Editing commands
         patterns.insert("undo".to_string(), TypingCommand::Undo);
         patterns.insert("redo".to_string(), TypingCommand::Redo);
+        patterns.insert("scratch that".to_string(), TypingCommand::Scratch);
+        patterns.insert("delete that".to_string(), TypingCommand::Scratch);
         patterns.insert("delete".to_string(), TypingCommand::Backspace);
         patterns.insert("backspace".to_string(), TypingCommand::Backspace);
         patterns.insert("back space".to_string(), TypingCommand::Backspace);
@@ -163,6 +266,22 @@ impl CommandParser {
             patterns.insert(phrase.to_string(), TypingCommand::GoToStartOfLine);
         }
 
+        // Numbers mode
+        for phrase in &["numbers on", "number mode on", "numbers mode on"] {
+            patterns.insert(phrase.to_string(), TypingCommand::NumberMode(true));
+        }
+        for phrase in &["numbers off", "number mode off", "numbers mode off"] {
+            patterns.insert(phrase.to_string(), TypingCommand::NumberMode(false));
+        }
+
+        // Code mode
+        for phrase in &["code mode on", "math mode on"] {
+            patterns.insert(phrase.to_string(), TypingCommand::CodeMode(true));
+        }
+        for phrase in &["code mode off", "math mode off"] {
+            patterns.insert(phrase.to_string(), TypingCommand::CodeMode(false));
+        }
+
         // Control commands - require "silly" prefix OR postfix to avoid accidental triggers
         // Exit commands: "silly terminate", "silly end", etc.
         for word in &["terminate", "end", "quit", "exit", "close"] {
@@ -243,9 +362,85 @@ impl CommandParser {
         Self {
             patterns,
             punctuation,
+            custom_punctuation: HashMap::new(),
+            literal_prefixes: Vec::new(),
             min_pause_for_command,
             max_words_for_command: 4, // Commands are typically short
+            code_mode: false,
+        }
+        .with_literal_prefixes(
+            DEFAULT_LITERAL_PREFIXES
+                .iter()
+                .map(|p| p.to_string())
+                .collect(),
+        )
+    }
+
+    /// Set the prefixes that make the following phrase typed as plain text
+    pub fn with_literal_prefixes(mut self, prefixes: Vec<String>) -> Self {
+        self.literal_prefixes = prefixes
+            .iter()
+            .map(|p| {
+                p.split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join(" ")
+                    .to_lowercase()
+            })
+            .filter(|p| !p.is_empty())
+            .collect();
+        // Longest first so "type the word" isn't shadowed by a shorter prefix
+        self.literal_prefixes
+            .sort_by_key(|p| std::cmp::Reverse(p.split_whitespace().count()));
+        self
+    }
+
+    /// Add user-defined punctuation phrases, e.g. "smiley" -> ":)" or "arrow" -> "->"
+    ///
+    /// Single-character replacements behave exactly like built-in punctuation.
+    /// A custom phrase overrides a built-in one with the same wording.
+    pub fn with_custom_punctuation(mut self, map: HashMap<String, String>) -> Self {
+        for (phrase, replacement) in map {
+            let phrase = phrase.trim().to_lowercase();
+            if phrase.is_empty() || replacement.is_empty() {
+                continue;
+            }
+            let mut chars = replacement.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => {
+                    self.custom_punctuation.remove(&phrase);
+                    self.punctuation.insert(phrase, c);
+                }
+                _ => {
+                    self.punctuation.remove(&phrase);
+                    self.custom_punctuation.insert(phrase, replacement);
+                }
+            }
         }
+        self
+    }
+
+    /// Switch code mode, where spoken symbols become operators and brackets
+    /// typed as code instead of punctuation commands
+    pub fn set_code_mode(&mut self, enabled: bool) {
+        self.code_mode = enabled;
+    }
+
+    pub fn code_mode(&self) -> bool {
+        self.code_mode
+    }
+
+    /// All punctuation phrases with their replacement, longest phrase first
+    fn sorted_punctuation(&self) -> Vec<(&str, TypingCommand)> {
+        let mut sorted: Vec<_> =
+            self.punctuation
+                .iter()
+                .map(|(phrase, &c)| (phrase.as_str(), TypingCommand::Punctuation(c)))
+                .chain(self.custom_punctuation.iter().map(|(phrase, text)| {
+                    (phrase.as_str(), TypingCommand::InsertText(text.clone()))
+                }))
+                .collect();
+        sorted.sort_by(|a, b| b.0.len().cmp(&a.0.len()).then(a.0.cmp(b.0)));
+        sorted
     }
 
     /// Print all available voice commands
@@ -287,6 +482,7 @@ impl CommandParser {
 ├─────────────────────────────────────────────────────────────┤
 │ EDITING                                                     │
 │   undo / redo                      Undo/redo last action    │
+│   scratch that / delete that       Undo the last phrase     │
 │   delete word / delete line        Delete word/line         │
 │   select all / select word         Selection                │
 ├─────────────────────────────────────────────────────────────┤
@@ -294,6 +490,15 @@ impl CommandParser {
 │   go to end of line                Move to end of line      │
 │   go to start of line              Move to start of line    │
 ├─────────────────────────────────────────────────────────────┤
+│ LITERALS                                                    │
+│   literal / spell <phrase>         Type phrase as words     │
+│   type the word <phrase>           (\"literal comma\")        │
+├─────────────────────────────────────────────────────────────┤
+│ MODES                                                       │
+│   numbers on / numbers off         Type numbers as digits   │
+│   code mode on / code mode off     Spoken symbols as code   │
+│     (\"x equals y plus two\" types \"x = y + 2\")               │
+├─────────────────────────────────────────────────────────────┤
 │ HOTKEYS                                                     │
 │   Double-tap Cmd                   Toggle typing on/off     │
 │   Ctrl+Space                       Push-to-talk             │
@@ -318,6 +523,10 @@ impl CommandParser {
         let lower = text.to_lowercase();
         let normalized = lower.trim_end_matches(|c: char| c.is_ascii_punctuation());
 
+        // Step 0: Hide escaped phrases ("literal comma") from command matching
+        let (escaped, literals) = self.escape_literals(normalized);
+        let normalized = escaped.as_str();
+
         // Step 1: Check if it's a pure command (short phrase after pause)
         if let Some(cmd) = self.is_pure_command(normalized, pause_duration_ms) {
             return ParseResult::command_only(cmd);
@@ -326,8 +535,13 @@ impl CommandParser {
         // Step 2: Extract trailing commands and process remaining text
         let (processed_text, commands) = self.extract_trailing_commands(normalized);
 
-        // Step 3: Replace inline punctuation in the remaining text
-        let final_text = self.replace_inline_punctuation(&processed_text);
+        // Step 3: Replace inline punctuation (or code symbols) in the remaining text
+        let final_text = if self.code_mode {
+            self.replace_code_symbols(&processed_text)
+        } else {
+            self.replace_inline_punctuation(&processed_text)
+        };
+        let final_text = Self::restore_literals(&final_text, &literals);
 
         if final_text.is_empty() && commands.is_empty() {
             ParseResult::empty()
@@ -342,6 +556,59 @@ impl CommandParser {
         }
     }
 
+    /// Replace "<prefix> <phrase>" with a marker so the phrase is typed verbatim
+    ///
+    /// The escaped phrase is the longest command or punctuation phrase following
+    /// the prefix ("literal new line"), or a single word otherwise.
+    /// Returns (escaped_text, literal_phrases)
+    fn escape_literals(&self, text: &str) -> (String, Vec<String>) {
+        let words: Vec<&str> = text.split_whitespace().collect();
+        let mut out: Vec<String> = Vec::with_capacity(words.len());
+        let mut literals = Vec::new();
+        let mut i = 0;
+
+        'words: while i < words.len() {
+            for prefix in &self.literal_prefixes {
+                let prefix_words = prefix.split_whitespace().count();
+                let rest = &words[(i + prefix_words).min(words.len())..];
+                if rest.is_empty() || words[i..i + prefix_words].join(" ") != *prefix {
+                    continue;
+                }
+
+                let len = (1..=rest.len().min(self.max_words_for_command))
+                    .rev()
+                    .find(|&n| self.is_phrase(&rest[..n].join(" ")))
+                    .unwrap_or(1);
+                out.push(format!("{}{}", LITERAL_MARKER, literals.len()));
+                literals.push(rest[..len].join(" "));
+                i += prefix_words + len;
+                continue 'words;
+            }
+            out.push(words[i].to_string());
+            i += 1;
+        }
+
+        (out.join(" "), literals)
+    }
+
+    /// Put escaped phrases back in place of their markers
+    fn restore_literals(text: &str, literals: &[String]) -> String {
+        let mut result = text.to_string();
+        // Highest index first so marker 1 doesn't match inside marker 10
+        for (i, literal) in literals.iter().enumerate().rev() {
+            result = result.replace(&format!("{}{}", LITERAL_MARKER, i), literal);
+        }
+        result
+    }
+
+    /// Whether `text` is a command or punctuation phrase
+    fn is_phrase(&self, text: &str) -> bool {
+        self.patterns.contains_key(text)
+            || self.punctuation.contains_key(text)
+            || self.custom_punctuation.contains_key(text)
+            || (self.code_mode && CODE_SYMBOLS.iter().any(|(phrase, ..)| *phrase == text))
+    }
+
     /// Check if text is a pure command (short phrase matching pattern)
     fn is_pure_command(&self, text: &str, pause_ms: u32) -> Option<TypingCommand> {
         let word_count = text.split_whitespace().count();
@@ -360,10 +627,18 @@ impl CommandParser {
                 return Some(cmd.clone());
             }
 
+            // Code mode types symbols as text, spaced to fit the code
+            if self.code_mode {
+                return None;
+            }
+
             // Check punctuation
             if let Some(&c) = self.punctuation.get(text) {
                 return Some(TypingCommand::Punctuation(c));
             }
+            if let Some(replacement) = self.custom_punctuation.get(text) {
+                return Some(TypingCommand::InsertText(replacement.clone()));
+            }
         }
 
         None
@@ -387,13 +662,7 @@ impl CommandParser {
                 if remaining.ends_with(pattern.as_str()) {
                     // Make sure it's a word boundary
                     let prefix_len = remaining.len() - pattern.len();
-                    if prefix_len == 0
-                        || remaining
-                            .chars()
-                            .nth(prefix_len - 1)
-                            .map(|c| c.is_whitespace())
-                            .unwrap_or(false)
-                    {
+                    if prefix_len == 0 || remaining[..prefix_len].ends_with(char::is_whitespace) {
                         commands.insert(0, self.patterns[pattern].clone());
                         remaining = remaining[..prefix_len].trim_end().to_string();
                         found = true;
@@ -402,23 +671,14 @@ impl CommandParser {
                 }
             }
 
-            // Check for trailing punctuation
-            if !found {
-                let mut sorted_punct: Vec<_> = self.punctuation.keys().collect();
-                sorted_punct.sort_by(|a, b| b.len().cmp(&a.len()));
-
-                for pattern in sorted_punct {
-                    if remaining.ends_with(pattern.as_str()) {
+            // Check for trailing punctuation (code mode types it inline)
+            if !found && !self.code_mode {
+                for (pattern, cmd) in self.sorted_punctuation() {
+                    if remaining.ends_with(pattern) {
                         let prefix_len = remaining.len() - pattern.len();
-                        if prefix_len == 0
-                            || remaining
-                                .chars()
-                                .nth(prefix_len - 1)
-                                .map(|c| c.is_whitespace())
-                                .unwrap_or(false)
+                        if prefix_len == 0 || remaining[..prefix_len].ends_with(char::is_whitespace)
                         {
-                            commands
-                                .insert(0, TypingCommand::Punctuation(self.punctuation[pattern]));
+                            commands.insert(0, cmd);
                             remaining = remaining[..prefix_len].trim_end().to_string();
                             found = true;
                             break;
@@ -440,14 +700,16 @@ impl CommandParser {
     fn replace_inline_punctuation(&self, text: &str) -> String {
         let mut result = text.to_string();
 
-        // Sort by length (longest first) to avoid partial replacements
-        let mut sorted_punct: Vec<_> = self.punctuation.iter().collect();
-        sorted_punct.sort_by(|a, b| b.0.len().cmp(&a.0.len()));
-
-        for (phrase, &c) in sorted_punct {
-            // Replace " phrase " with "c " (space after punctuation)
+        // Sorted by length (longest first) to avoid partial replacements
+        for (phrase, cmd) in self.sorted_punctuation() {
+            // Punctuation attaches to the previous word: " phrase " -> "c ".
+            // Multi-character replacements are spaced like words: " phrase " -> " text "
+            let replacement = match cmd {
+                TypingCommand::Punctuation(c) => format!("{} ", c),
+                TypingCommand::InsertText(text) => format!(" {} ", text),
+                _ => continue,
+            };
             let pattern_with_spaces = format!(" {} ", phrase);
-            let replacement = format!("{} ", c);
             result = result.replace(&pattern_with_spaces, &replacement);
 
             // Also handle " phrase" at end (but don't remove trailing since we want space handling)
@@ -460,6 +722,47 @@ impl CommandParser {
 
         result.trim().to_string()
     }
+
+    /// Replace spoken symbols and numbers for code mode
+    /// "x equals y plus two" -> "x = y + 2", "foo dot bar" -> "foo.bar"
+    fn replace_code_symbols(&self, text: &str) -> String {
+        let text = words_to_digits(text);
+        let words: Vec<&str> = text.split_whitespace().collect();
+        let mut result = String::new();
+        let mut glue_next = false;
+        let mut i = 0;
+
+        while i < words.len() {
+            // Longest symbol phrase first, so "less than or equal to" beats "less than"
+            let symbol = (1..=(words.len() - i).min(MAX_CODE_SYMBOL_WORDS))
+                .rev()
+                .find_map(|n| self.code_symbol(&words[i..i + n].join(" ")).map(|s| (s, n)));
+            let ((token, glue), len) = symbol.unwrap_or(((words[i].to_string(), Glue::Spaced), 1));
+
+            if !result.is_empty() && !glue_next && !glue.left() {
+                result.push(' ');
+            }
+            result.push_str(&token);
+            glue_next = glue.right();
+            i += len;
+        }
+
+        result
+    }
+
+    /// What a spoken phrase types in code mode: a code symbol, else punctuation
+    /// (attached to the word before) or a custom replacement
+    fn code_symbol(&self, phrase: &str) -> Option<(String, Glue)> {
+        if let Some((_, symbol, glue)) = CODE_SYMBOLS.iter().find(|(p, ..)| *p == phrase) {
+            return Some((symbol.to_string(), *glue));
+        }
+        if let Some(&c) = self.punctuation.get(phrase) {
+            return Some((c.to_string(), Glue::Left));
+        }
+        self.custom_punctuation
+            .get(phrase)
+            .map(|text| (text.clone(), Glue::Spaced))
+    }
 }
 
 #[cfg(test)]
@@ -599,6 +902,155 @@ mod tests {
         assert_eq!(result.commands, vec![TypingCommand::GoToStartOfLine]);
     }
 
+    #[test]
+    fn test_custom_punctuation() {
+        let custom = HashMap::from([
+            ("smiley".to_string(), ":)".to_string()),
+            ("right arrow".to_string(), "->".to_string()),
+            ("arrow".to_string(), "=>".to_string()),
+            ("Bullet".to_string(), "•".to_string()),
+        ]);
+        let parser = CommandParser::new(100).with_custom_punctuation(custom);
+
+        // Multi-character inline replacement
+        let result = parser.parse("that was fun smiley right", 0);
+        assert_eq!(result.text, Some("that was fun :) right".to_string()));
+
+        // Longer phrase wins over its suffix
+        let result = parser.parse("a right arrow b", 0);
+        assert_eq!(result.text, Some("a -> b".to_string()));
+
+        // Trailing and pure commands
+        let result = parser.parse("nice smiley", 0);
+        assert_eq!(result.text, Some("nice".to_string()));
+        assert_eq!(
+            result.commands,
+            vec![TypingCommand::InsertText(":)".to_string())]
+        );
+        let result = parser.parse("arrow", 200);
+        assert_eq!(
+            result.commands,
+            vec![TypingCommand::InsertText("=>".to_string())]
+        );
+
+        // Single characters act as plain punctuation
+        let result = parser.parse("bullet", 200);
+        assert_eq!(result.commands, vec![TypingCommand::Punctuation('•')]);
+    }
+
+    #[test]
+    fn test_literal_escape() {
+        let parser = CommandParser::new(100);
+
+        // Escaped command is typed, not executed
+        let result = parser.parse("literal enter", 200);
+        assert_eq!(result.text, Some("enter".to_string()));
+        assert!(result.commands.is_empty());
+        assert!(!result.had_command);
+
+        // Escaped punctuation inline and at the end
+        let result = parser.parse("type the word comma after this", 0);
+        assert_eq!(result.text, Some("comma after this".to_string()));
+        let result = parser.parse("add a spell comma", 0);
+        assert_eq!(result.text, Some("add a comma".to_string()));
+        assert!(result.commands.is_empty());
+
+        // Multi-word phrases are escaped whole, other commands still work
+        let result = parser.parse("say literal new line comma then period", 0);
+        assert_eq!(result.text, Some("say new line, then".to_string()));
+        assert_eq!(result.commands, vec![TypingCommand::Punctuation('.')]);
+
+        // A prefix with nothing after it is just a word
+        let result = parser.parse("literal", 200);
+        assert_eq!(result.text, Some("literal".to_string()));
+
+        // Custom prefixes
+        let parser = CommandParser::new(100).with_literal_prefixes(vec!["Verbatim".to_string()]);
+        let result = parser.parse("verbatim period", 200);
+        assert_eq!(result.text, Some("period".to_string()));
+        let result = parser.parse("literal period", 200);
+        assert_eq!(result.text, Some("literal".to_string()));
+        assert_eq!(result.commands, vec![TypingCommand::Punctuation('.')]);
+    }
+
+    #[test]
+    fn test_number_mode_commands() {
+        let parser = CommandParser::new(100);
+
+        let result = parser.parse("numbers on", 200);
+        assert_eq!(result.commands, vec![TypingCommand::NumberMode(true)]);
+        assert!(result.text.is_none());
+
+        let result = parser.parse("Numbers off.", 200);
+        assert_eq!(result.commands, vec![TypingCommand::NumberMode(false)]);
+    }
+
+    #[test]
+    fn test_code_mode() {
+        let mut parser = CommandParser::new(100);
+        let result = parser.parse("Code mode on.", 200);
+        assert_eq!(result.commands, vec![TypingCommand::CodeMode(true)]);
+
+        parser.set_code_mode(true);
+        let cases = [
+            ("x equals y plus two", "x = y + 2"),
+            ("if x is equal to ten", "if x == 10"),
+            (
+                "total plus equals price times quantity semicolon",
+                "total += price * quantity;",
+            ),
+            (
+                "print open paren foo dot bar comma one close paren",
+                "print(foo.bar, 1)",
+            ),
+            (
+                "a less than or equal to b and and bang done",
+                "a <= b && !done",
+            ),
+            ("my underscore var gets negative one", "my_var = -1"),
+        ];
+        for (spoken, typed) in cases {
+            let result = parser.parse(spoken, 0);
+            assert_eq!(result.text.as_deref(), Some(typed), "for {:?}", spoken);
+            assert!(result.commands.is_empty(), "for {:?}", spoken);
+        }
+
+        // Commands still work, symbols alone are typed rather than executed
+        let result = parser.parse("x plus one enter", 0);
+        assert_eq!(result.text, Some("x + 1".to_string()));
+        assert_eq!(result.commands, vec![TypingCommand::Enter]);
+        let result = parser.parse("semicolon", 500);
+        assert_eq!(result.text, Some(";".to_string()));
+        assert!(result.commands.is_empty());
+        let result = parser.parse("literal plus one", 0);
+        assert_eq!(result.text, Some("plus 1".to_string()));
+        assert_eq!(
+            parser.parse("code mode off", 500).commands,
+            vec![TypingCommand::CodeMode(false)]
+        );
+    }
+
+    #[test]
+    fn test_prose_ignores_code_symbols() {
+        let parser = CommandParser::new(100);
+        assert!(!parser.code_mode());
+
+        let result = parser.parse("two times over the limit", 0);
+        assert_eq!(result.text, Some("two times over the limit".to_string()));
+        let result = parser.parse("add one dot and move on", 0);
+        assert_eq!(result.text, Some("add one. and move on".to_string()));
+
+        // Turning code mode on and off again leaves prose as it was
+        let mut toggled = CommandParser::new(100);
+        toggled.set_code_mode(true);
+        toggled.set_code_mode(false);
+        for spoken in ["x equals y period", "a less than b", "my underscore var"] {
+            let (prose, after) = (parser.parse(spoken, 0), toggled.parse(spoken, 0));
+            assert_eq!(prose.text, after.text, "for {:?}", spoken);
+            assert_eq!(prose.commands, after.commands, "for {:?}", spoken);
+        }
+    }
+
     #[test]
     fn test_editing_commands() {
         let parser = CommandParser::default();
@@ -618,6 +1070,13 @@ mod tests {
         assert!(result.text.is_none());
         assert_eq!(result.commands, vec![TypingCommand::Redo]);
 
+        // "scratch that", also at the end of a phrase
+        let result = parser.parse("scratch that", 500);
+        assert_eq!(result.commands, vec![TypingCommand::Scratch]);
+        let result = parser.parse("no wait delete that", 0);
+        assert_eq!(result.text.as_deref(), Some("no wait"));
+        assert_eq!(result.commands, vec![TypingCommand::Scratch]);
+
         // "delete word"
         let result = parser.parse("delete word", 500);
         assert!(result.text.is_none());
@@ -23,25 +23,153 @@ pub enum HotkeyEvent {
 }
 
 /// Configuration for hotkey detection
+#[derive(Debug, Clone, Copy)]
 pub struct HotkeyConfig {
-    /// Maximum time between key presses for double-tap (ms)
-    pub double_tap_threshold_ms: u64,
+    /// Maximum gap between the two taps of a double-tap, and the longest a
+    /// single tap may be held (ms)
+    pub double_tap_ms: u64,
     /// Enable double-tap Command hotkey for toggle
     pub enable_double_tap_cmd: bool,
     /// Enable Ctrl+Space for push-to-talk
     pub enable_ctrl_space_ptt: bool,
 }
 
+/// Default double-tap window
+const DEFAULT_DOUBLE_TAP_MS: u64 = 350;
+
 impl Default for HotkeyConfig {
     fn default() -> Self {
         Self {
-            double_tap_threshold_ms: 400, // 400ms between taps
+            double_tap_ms: DEFAULT_DOUBLE_TAP_MS,
             enable_double_tap_cmd: true,
             enable_ctrl_space_ptt: true,
         }
     }
 }
 
+impl HotkeyConfig {
+    pub fn with_double_tap_ms(mut self, double_tap_ms: u64) -> Self {
+        self.double_tap_ms = double_tap_ms;
+        self
+    }
+}
+
+/// Hotkey state machine, fed key events with the time they arrived
+///
+/// Kept separate from the rdev listener so timing can be tested with
+/// synthetic timestamps.
+struct HotkeyDetector {
+    config: HotkeyConfig,
+    /// When Meta went down, None while released
+    meta_down_at: Option<Instant>,
+    /// Any other key pressed while Meta was held
+    other_key_with_meta: bool,
+    /// Release time of a clean tap waiting for its second tap
+    pending_tap: Option<Instant>,
+    ctrl_pressed: bool,
+    ptt_active: bool,
+}
+
+impl HotkeyDetector {
+    fn new(config: HotkeyConfig) -> Self {
+        Self {
+            config,
+            meta_down_at: None,
+            other_key_with_meta: false,
+            pending_tap: None,
+            ctrl_pressed: false,
+            ptt_active: false,
+        }
+    }
+
+    fn handle(&mut self, event: EventType, now: Instant) -> Option<HotkeyEvent> {
+        let window = Duration::from_millis(self.config.double_tap_ms);
+
+        match event {
+            EventType::KeyPress(Key::MetaLeft | Key::MetaRight) => {
+                // Auto-repeat sends more presses while held, only the first counts
+                if self.meta_down_at.is_none() {
+                    self.meta_down_at = Some(now);
+                    self.other_key_with_meta = false;
+                    if self
+                        .pending_tap
+                        .is_some_and(|tap| now.duration_since(tap) > window)
+                    {
+                        self.pending_tap = None;
+                    }
+                }
+                None
+            }
+            EventType::KeyRelease(Key::MetaLeft | Key::MetaRight) => {
+                let pressed_at = self.meta_down_at.take()?;
+                if !self.config.enable_double_tap_cmd {
+                    return None;
+                }
+
+                // Held too long or used as a modifier: not a tap, and breaks any pair
+                let clean = !self.other_key_with_meta && now.duration_since(pressed_at) <= window;
+                if !clean {
+                    self.pending_tap = None;
+                    return None;
+                }
+
+                if self.pending_tap.take().is_some() {
+                    Some(HotkeyEvent::Toggle)
+                } else {
+                    self.pending_tap = Some(now);
+                    None
+                }
+            }
+            EventType::KeyPress(Key::ControlLeft | Key::ControlRight) => {
+                self.ctrl_pressed = true;
+                self.note_other_key();
+                None
+            }
+            EventType::KeyRelease(Key::ControlLeft | Key::ControlRight) => {
+                self.ctrl_pressed = false;
+                self.end_ptt()
+            }
+            EventType::KeyPress(Key::Space) => {
+                self.note_other_key();
+                // Ctrl+Space push-to-talk START (ignores auto-repeat)
+                if self.config.enable_ctrl_space_ptt
+                    && self.ctrl_pressed
+                    && self.meta_down_at.is_none()
+                    && !self.ptt_active
+                {
+                    self.ptt_active = true;
+                    return Some(HotkeyEvent::PushToTalkStart);
+                }
+                None
+            }
+            EventType::KeyRelease(Key::Space) => self.end_ptt(),
+            EventType::KeyPress(_) => {
+                self.note_other_key();
+                None
+            }
+            _ => None,
+        }
+    }
+
+    /// A non-Meta key was pressed: Meta is a modifier, not a tap
+    fn note_other_key(&mut self) {
+        if self.meta_down_at.is_some() {
+            self.other_key_with_meta = true;
+        }
+        self.pending_tap = None;
+    }
+
+    /// Releasing Ctrl or Space ends push-to-talk
+    fn end_ptt(&mut self) -> Option<HotkeyEvent> {
+        if self.ptt_active && self.config.enable_ctrl_space_ptt {
+            self.ptt_active = false;
+            Some(HotkeyEvent::PushToTalkEnd)
+        } else {
+            None
+        }
+    }
+}
+
 /// Start the global hotkey listener
 ///
 /// Returns a receiver for hotkey events and a handle to stop the listener.
@@ -53,100 +181,15 @@ pub fn start_hotkey_listener(
     let running_clone = Arc::clone(&running);
 
     thread::spawn(move || {
-        let mut last_meta_release: Option<Instant> = None;
-        let mut meta_pressed = false;
-        let mut ctrl_pressed = false;
-        let mut _space_pressed = false;
-        let mut ptt_active = false;
-        let double_tap_threshold = Duration::from_millis(config.double_tap_threshold_ms);
-
-        // Track if any other key was pressed while Meta was held
-        let mut other_key_pressed_with_meta = false;
+        let mut detector = HotkeyDetector::new(config);
 
         let callback = move |event: Event| {
             if !running_clone.load(Ordering::SeqCst) {
                 return;
             }
 
-            match event.event_type {
-                EventType::KeyPress(key) => {
-                    match key {
-                        Key::MetaLeft | Key::MetaRight => {
-                            meta_pressed = true;
-                            other_key_pressed_with_meta = false; // Reset on new press
-                        }
-                        Key::ControlLeft | Key::ControlRight => {
-                            ctrl_pressed = true;
-                        }
-                        Key::Space => {
-                            _space_pressed = true;
-                            // Ctrl+Space push-to-talk START
-                            if config.enable_ctrl_space_ptt
-                                && ctrl_pressed
-                                && !meta_pressed
-                                && !ptt_active
-                            {
-                                ptt_active = true;
-                                let _ = tx.send(HotkeyEvent::PushToTalkStart);
-                            }
-                            // Mark other key pressed for double-tap detection
-                            if meta_pressed {
-                                other_key_pressed_with_meta = true;
-                            }
-                        }
-                        _ => {
-                            // Any other key pressed while meta is held
-                            if meta_pressed {
-                                other_key_pressed_with_meta = true;
-                            }
-                        }
-                    }
-                }
-                EventType::KeyRelease(key) => {
-                    match key {
-                        Key::MetaLeft | Key::MetaRight => {
-                            // Double-tap Cmd detection for TOGGLE
-                            if config.enable_double_tap_cmd
-                                && meta_pressed
-                                && !other_key_pressed_with_meta
-                            {
-                                // Clean meta release (no other keys pressed)
-                                let now = Instant::now();
-
-                                if let Some(last) = last_meta_release {
-                                    if now.duration_since(last) < double_tap_threshold {
-                                        // Double-tap detected!
-                                        let _ = tx.send(HotkeyEvent::Toggle);
-                                        last_meta_release = None; // Reset
-                                    } else {
-                                        last_meta_release = Some(now);
-                                    }
-                                } else {
-                                    last_meta_release = Some(now);
-                                }
-                            }
-                            meta_pressed = false;
-                        }
-                        Key::ControlLeft | Key::ControlRight => {
-                            ctrl_pressed = false;
-                            // If PTT was active and Ctrl is released, end PTT
-                            if ptt_active && config.enable_ctrl_space_ptt {
-                                ptt_active = false;
-                                let _ = tx.send(HotkeyEvent::PushToTalkEnd);
-                            }
-                        }
-                        Key::Space => {
-                            _space_pressed = false;
-                            // If PTT was active and Space is released, end PTT
-                            if ptt_active && config.enable_ctrl_space_ptt {
-                                ptt_active = false;
-                                let _ = tx.send(HotkeyEvent::PushToTalkEnd);
-                            }
-                        }
-                        _ => {}
-                    }
-                }
-                _ => {}
+            if let Some(hotkey) = detector.handle(event.event_type, Instant::now()) {
+                let _ = tx.send(hotkey);
             }
         };
 
@@ -166,8 +209,113 @@ mod tests {
     #[test]
     fn test_default_config() {
         let config = HotkeyConfig::default();
-        assert_eq!(config.double_tap_threshold_ms, 400);
+        assert_eq!(config.double_tap_ms, 350);
         assert!(config.enable_double_tap_cmd);
         assert!(config.enable_ctrl_space_ptt);
     }
+
+    /// Feed (ms offset, event) pairs and collect the hotkeys emitted
+    fn run(config: HotkeyConfig, events: &[(u64, EventType)]) -> Vec<HotkeyEvent> {
+        let start = Instant::now();
+        let mut detector = HotkeyDetector::new(config);
+        events
+            .iter()
+            .filter_map(|&(ms, event)| detector.handle(event, start + Duration::from_millis(ms)))
+            .collect()
+    }
+
+    fn tap(at: u64, hold: u64) -> [(u64, EventType); 2] {
+        [
+            (at, EventType::KeyPress(Key::MetaLeft)),
+            (at + hold, EventType::KeyRelease(Key::MetaLeft)),
+        ]
+    }
+
+    #[test]
+    fn test_double_tap_toggles() {
+        let events = [tap(0, 50), tap(200, 50)].concat();
+        assert_eq!(
+            run(HotkeyConfig::default(), &events),
+            vec![HotkeyEvent::Toggle]
+        );
+
+        // A third tap starts a new pair rather than toggling again
+        let events = [tap(0, 50), tap(200, 50), tap(400, 50)].concat();
+        assert_eq!(
+            run(HotkeyConfig::default(), &events),
+            vec![HotkeyEvent::Toggle]
+        );
+    }
+
+    #[test]
+    fn test_slow_taps_do_not_toggle() {
+        let events = [tap(0, 50), tap(1000, 50)].concat();
+        assert!(run(HotkeyConfig::default(), &events).is_empty());
+
+        // But do with a wider window
+        let config = HotkeyConfig::default().with_double_tap_ms(1200);
+        assert_eq!(run(config, &events), vec![HotkeyEvent::Toggle]);
+    }
+
+    #[test]
+    fn test_auto_repeat_is_not_a_tap() {
+        // Held Meta auto-repeats presses, then releases once
+        let mut events = vec![(0, EventType::KeyPress(Key::MetaLeft))];
+        events.extend((1..20).map(|i| (i * 30, EventType::KeyPress(Key::MetaLeft))));
+        events.push((600, EventType::KeyRelease(Key::MetaLeft)));
+        events.extend(tap(700, 50));
+        assert!(run(HotkeyConfig::default(), &events).is_empty());
+
+        // Quick repeats within a short hold don't count as extra taps
+        let events = [
+            (0, EventType::KeyPress(Key::MetaLeft)),
+            (30, EventType::KeyPress(Key::MetaLeft)),
+            (60, EventType::KeyPress(Key::MetaLeft)),
+            (90, EventType::KeyRelease(Key::MetaLeft)),
+        ];
+        assert!(run(HotkeyConfig::default(), &events).is_empty());
+    }
+
+    #[test]
+    fn test_meta_as_modifier_is_not_a_tap() {
+        let mut events = tap(0, 50).to_vec();
+        events.extend([
+            (150, EventType::KeyPress(Key::MetaLeft)),
+            (170, EventType::KeyPress(Key::KeyC)),
+            (190, EventType::KeyRelease(Key::KeyC)),
+            (200, EventType::KeyRelease(Key::MetaLeft)),
+        ]);
+        assert!(run(HotkeyConfig::default(), &events).is_empty());
+
+        // Typing between taps breaks the pair
+        let mut events = tap(0, 50).to_vec();
+        events.push((100, EventType::KeyPress(Key::KeyA)));
+        events.extend(tap(150, 50));
+        assert!(run(HotkeyConfig::default(), &events).is_empty());
+    }
+
+    #[test]
+    fn test_push_to_talk() {
+        let events = [
+            (0, EventType::KeyPress(Key::ControlLeft)),
+            (50, EventType::KeyPress(Key::Space)),
+            (80, EventType::KeyPress(Key::Space)), // auto-repeat
+            (500, EventType::KeyRelease(Key::Space)),
+            (550, EventType::KeyRelease(Key::ControlLeft)),
+        ];
+        assert_eq!(
+            run(HotkeyConfig::default(), &events),
+            vec![HotkeyEvent::PushToTalkStart, HotkeyEvent::PushToTalkEnd]
+        );
+    }
+
+    #[test]
+    fn test_double_tap_disabled() {
+        let config = HotkeyConfig {
+            enable_double_tap_cmd: false,
+            ..Default::default()
+        };
+        let events = [tap(0, 50), tap(200, 50)].concat();
+        assert!(run(config, &events).is_empty());
+    }
 }
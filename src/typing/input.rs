@@ -1,13 +1,97 @@
 //! Keyboard input methods using enigo
 //!
 //! Provides two methods for typing text into applications:
-//! - **Clipboard**: Copy text to clipboard, then send Cmd/Ctrl+V (more reliable)
+//! - **Clipboard**: Copy text to clipboard, then send Cmd/Ctrl+V (more reliable).
+//!   The previous clipboard text is put back afterwards unless disabled
 //! - **Direct**: Use enigo's native text input (faster, but may fail with some characters)
+//!
+//! A third, **DryRun**, sends nothing and records the intended actions instead.
+//!
+//! Some apps drop characters when keys arrive too fast. `KeyPacing` spaces key
+//! events out, typing direct text one character at a time, at the cost of
+//! latency: a 10ms delay adds a second to a 100-character sentence.
+//!
+//! Platform backends:
+//! - **macOS**: Cmd+V paste, keystrokes via CoreGraphics
+//! - **Linux**: Ctrl+V paste, clipboard via X11 or Wayland (`wlr-data-control`),
+//!   keystrokes via X11 (XWayland on Wayland sessions)
+//! - **Windows**: Ctrl+V paste, keystrokes via SendInput
 
+use crate::logger;
 use arboard::Clipboard;
 use enigo::{Direction, Enigo, Key, Keyboard, Settings};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+
+/// Wait after setting the clipboard before pasting (ms)
+const CLIPBOARD_READY_MS: u64 = 50;
+
+/// Wait after pasting before the clipboard changes again (ms), whether
+/// restored or set for the next paste
+///
+/// On Linux the clipboard is served on request by the owning process, so the
+/// target app may read it some time after Ctrl+V; give it longer.
+#[cfg(target_os = "linux")]
+const PASTE_SETTLE_MS: u64 = 250;
+#[cfg(not(target_os = "linux"))]
+const PASTE_SETTLE_MS: u64 = 100;
+
+/// Longest delay adaptive pacing backs off to (ms)
+const MAX_ADAPTIVE_DELAY_MS: u64 = 200;
+
+/// First step when adaptive pacing backs off from no delay (ms)
+const MIN_BACKOFF_MS: u64 = 5;
+
+/// Successful key events before adaptive pacing speeds back up
+const SPEEDUP_AFTER_EVENTS: u32 = 100;
+
+/// The steps of a clipboard paste, so the sequencing can be tested
+trait PasteTarget {
+    /// Current clipboard text, if it holds any
+    fn get_clipboard(&mut self) -> Option<String>;
+    fn set_clipboard(&mut self, text: &str) -> Result<(), TypingError>;
+    fn paste(&mut self) -> Result<(), TypingError>;
+    fn wait(&mut self, ms: u64);
+}
+
+/// Paste `text` through the clipboard, then put back what it held
+///
+/// The old text goes back once the paste has had time to complete, unless
+/// something else replaced our text in the meantime (the user copied again).
+/// Only text is saved; an image or file on the clipboard is lost.
+fn paste_via_clipboard(
+    target: &mut impl PasteTarget,
+    text: &str,
+    restore: bool,
+) -> Result<(), TypingError> {
+    let saved = if restore {
+        target.get_clipboard()
+    } else {
+        None
+    };
+
+    target.set_clipboard(text)?;
+    target.wait(CLIPBOARD_READY_MS);
+
+    if let Err(e) = target.paste() {
+        eprintln!("[TYPING] Paste failed: {}", e);
+        // Nothing was pasted, so there's nothing to wait for
+        if let Some(old) = saved {
+            let _ = target.set_clipboard(&old);
+        }
+        return Err(e);
+    }
+
+    // Let the target read the text before anything replaces it
+    target.wait(PASTE_SETTLE_MS);
+    if let Some(old) = saved {
+        if target.get_clipboard().as_deref() == Some(text) {
+            // Best effort: the paste already happened
+            let _ = target.set_clipboard(&old);
+        }
+    }
+    Ok(())
+}
 
 /// Input method for typing text
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
@@ -17,17 +101,30 @@ pub enum InputMethod {
     Direct,
     /// Copy to clipboard, then paste with Cmd/Ctrl+V (may have issues)
     Clipboard,
+    /// Log what would be typed instead of sending keystrokes
+    DryRun,
 }
 
 impl InputMethod {
     pub fn from_str(s: &str) -> Self {
         match s.to_lowercase().as_str() {
             "clipboard" => InputMethod::Clipboard,
+            "dry-run" | "dry_run" | "dryrun" => InputMethod::DryRun,
             _ => InputMethod::Direct,
         }
     }
 }
 
+/// Spacing between key events, for apps that drop fast keystrokes
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct KeyPacing {
+    /// Minimum gap between key events (ms); 0 types at full speed
+    pub delay_ms: u64,
+    /// Slow down when a key event fails and retry it, then speed back up to
+    /// `delay_ms`. Dropped characters can't be seen, only failed events.
+    pub adaptive: bool,
+}
+
 /// Error type for typing operations
 #[derive(Debug)]
 pub enum TypingError {
@@ -46,29 +143,162 @@ impl std::fmt::Display for TypingError {
 
 impl std::error::Error for TypingError {}
 
-/// Keyboard input handler using enigo
-pub struct TypingInput {
+/// Keyboard backends, absent in dry-run mode
+struct Devices {
     enigo: Enigo,
     clipboard: Clipboard,
+}
+
+/// Keyboard input handler using enigo
+pub struct TypingInput {
+    devices: Option<Devices>,
     method: InputMethod,
+    /// Actions recorded in dry-run mode, e.g. `type "Hello"` or `key Control+Backspace`
+    dry_run_log: Vec<String>,
+    /// When each dry-run action happened
+    dry_run_times: Vec<Instant>,
+    pacing: KeyPacing,
+    /// Current gap between key events (above `pacing.delay_ms` after backing off)
+    delay: Duration,
+    last_event: Option<Instant>,
+    /// Successful events since the last back-off
+    clean_events: u32,
+    /// Put the clipboard back after a clipboard paste
+    restore_clipboard: bool,
 }
 
 impl TypingInput {
     /// Create a new typing input handler
-    pub fn new(method: InputMethod) -> Result<Self, TypingError> {
-        let enigo = Enigo::new(&Settings::default())
-            .map_err(|e| TypingError::Enigo(format!("Failed to initialize Enigo: {}", e)))?;
-        let clipboard = Clipboard::new().map_err(|e| {
-            TypingError::Clipboard(format!("Failed to initialize clipboard: {}", e))
-        })?;
+    pub fn new(method: InputMethod, pacing: KeyPacing) -> Result<Self, TypingError> {
+        let devices = if method == InputMethod::DryRun {
+            None
+        } else {
+            let enigo = Enigo::new(&Settings::default())
+                .map_err(|e| TypingError::Enigo(format!("Failed to initialize Enigo: {}", e)))?;
+            let clipboard = Clipboard::new().map_err(|e| {
+                TypingError::Clipboard(format!("Failed to initialize clipboard: {}", e))
+            })?;
+            Some(Devices { enigo, clipboard })
+        };
 
         Ok(Self {
-            enigo,
-            clipboard,
+            devices,
             method,
+            dry_run_log: Vec::new(),
+            dry_run_times: Vec::new(),
+            pacing,
+            delay: Duration::from_millis(pacing.delay_ms),
+            last_event: None,
+            clean_events: 0,
+            restore_clipboard: true,
         })
     }
 
+    /// How text is sent
+    pub fn method(&self) -> InputMethod {
+        self.method
+    }
+
+    /// Whether clipboard pastes put back what the clipboard held (default on)
+    pub fn set_restore_clipboard(&mut self, restore: bool) {
+        self.restore_clipboard = restore;
+    }
+
+    /// Actions recorded so far in dry-run mode
+    pub fn dry_run_log(&self) -> &[String] {
+        &self.dry_run_log
+    }
+
+    /// Record an action instead of performing it
+    fn log_dry_run(&mut self, action: String) {
+        eprintln!("[DRY RUN] {}", action);
+        self.dry_run_log.push(action);
+        self.dry_run_times.push(Instant::now());
+    }
+
+    /// Whether text is typed a character at a time
+    fn is_paced(&self) -> bool {
+        !self.delay.is_zero() || self.pacing.adaptive
+    }
+
+    /// Wait until the keystroke delay has passed since the last key event
+    fn pace(&mut self) {
+        if let Some(ready) = self.last_event.map(|last| last + self.delay) {
+            let now = Instant::now();
+            if ready > now {
+                thread::sleep(ready - now);
+            }
+        }
+        self.last_event = Some(Instant::now());
+    }
+
+    /// Send a paced key event, backing off and retrying once if it fails
+    fn paced_event(
+        &mut self,
+        event: impl Fn(&mut Self) -> Result<(), TypingError>,
+    ) -> Result<(), TypingError> {
+        self.pace();
+        match event(self) {
+            Ok(()) => {
+                self.note_success();
+                Ok(())
+            }
+            Err(e) if self.pacing.adaptive => {
+                self.back_off();
+                logger::warn!(
+                    "Key event failed ({}), slowing to {}ms per key",
+                    e,
+                    self.delay.as_millis()
+                );
+                self.pace();
+                event(self)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Double the delay after a failed key event
+    fn back_off(&mut self) {
+        let doubled = (self.delay * 2).max(Duration::from_millis(MIN_BACKOFF_MS));
+        self.delay = doubled.min(Duration::from_millis(MAX_ADAPTIVE_DELAY_MS));
+        self.clean_events = 0;
+    }
+
+    /// Halve a backed-off delay after a run of successful events
+    fn note_success(&mut self) {
+        let floor = Duration::from_millis(self.pacing.delay_ms);
+        if !self.pacing.adaptive || self.delay <= floor {
+            return;
+        }
+        self.clean_events += 1;
+        if self.clean_events >= SPEEDUP_AFTER_EVENTS {
+            let halved = self.delay / 2;
+            // Below the first back-off step, go straight back to the floor
+            self.delay = if halved < Duration::from_millis(MIN_BACKOFF_MS) {
+                floor
+            } else {
+                halved.max(floor)
+            };
+            self.clean_events = 0;
+        }
+    }
+
+    /// The enigo handle (only called outside dry-run mode)
+    fn enigo(&mut self) -> &mut Enigo {
+        &mut self.devices().enigo
+    }
+
+    /// The clipboard handle (only called outside dry-run mode)
+    fn clipboard(&mut self) -> &mut Clipboard {
+        &mut self.devices().clipboard
+    }
+
+    fn devices(&mut self) -> &mut Devices {
+        self.devices
+            .as_mut()
+            .expect("keyboard devices are initialized unless dry-run")
+    }
+
     /// Type text using the configured method
     pub fn type_text(&mut self, text: &str) -> Result<(), TypingError> {
         if text.is_empty() {
@@ -76,6 +306,17 @@ impl TypingInput {
         }
 
         match self.method {
+            InputMethod::DryRun if self.is_paced() => {
+                for c in text.chars() {
+                    self.pace();
+                    self.log_dry_run(format!("type {:?}", c.to_string()));
+                }
+                Ok(())
+            }
+            InputMethod::DryRun => {
+                self.log_dry_run(format!("type {:?}", text));
+                Ok(())
+            }
             InputMethod::Direct => self.type_direct(text),
             InputMethod::Clipboard => {
                 // Try clipboard, fall back to direct if it fails
@@ -92,16 +333,36 @@ impl TypingInput {
 
     /// Send a single key press
     pub fn send_key(&mut self, key: Key) -> Result<(), TypingError> {
-        self.enigo
-            .key(key, Direction::Click)
-            .map_err(|e| TypingError::Enigo(format!("Failed to send key: {}", e)))
+        if self.method == InputMethod::DryRun {
+            self.pace();
+            self.log_dry_run(format!("key {:?}", key));
+            return Ok(());
+        }
+        self.paced_event(|input| {
+            input
+                .enigo()
+                .key(key, Direction::Click)
+                .map_err(|e| TypingError::Enigo(format!("Failed to send key: {}", e)))
+        })
     }
 
     /// Send key with modifiers (e.g., Cmd+Z for undo)
     pub fn send_key_combo(&mut self, modifiers: &[Key], key: Key) -> Result<(), TypingError> {
+        // Not retried: a failure may leave modifiers held
+        self.pace();
+        if self.method == InputMethod::DryRun {
+            let combo: Vec<String> = modifiers
+                .iter()
+                .chain(std::iter::once(&key))
+                .map(|k| format!("{:?}", k))
+                .collect();
+            self.log_dry_run(format!("key {}", combo.join("+")));
+            return Ok(());
+        }
+
         // Press all modifiers
         for modifier in modifiers {
-            self.enigo
+            self.enigo()
                 .key(*modifier, Direction::Press)
                 .map_err(|e| TypingError::Enigo(format!("Failed to press modifier: {}", e)))?;
         }
@@ -110,7 +371,7 @@ impl TypingInput {
         thread::sleep(Duration::from_millis(10));
 
         // Click the main key
-        self.enigo
+        self.enigo()
             .key(key, Direction::Click)
             .map_err(|e| TypingError::Enigo(format!("Failed to click key: {}", e)))?;
 
@@ -119,7 +380,7 @@ impl TypingInput {
 
         // Release all modifiers in reverse order
         for modifier in modifiers.iter().rev() {
-            self.enigo
+            self.enigo()
                 .key(*modifier, Direction::Release)
                 .map_err(|e| TypingError::Enigo(format!("Failed to release modifier: {}", e)))?;
         }
@@ -141,49 +402,56 @@ impl TypingInput {
 
     /// Type text via clipboard (copy to clipboard, then paste)
     fn type_via_clipboard(&mut self, text: &str) -> Result<(), TypingError> {
-        // Save current clipboard content (best effort)
-        let old_content = self.clipboard.get_text().ok();
-
-        // Set new content
-        self.clipboard
-            .set_text(text)
-            .map_err(|e| TypingError::Clipboard(format!("Failed to set clipboard: {}", e)))?;
-
-        // Small delay for clipboard to be ready
-        thread::sleep(Duration::from_millis(50));
-
-        // Send paste command
-        if let Err(e) = self.send_paste() {
-            eprintln!("[TYPING] Paste failed: {}", e);
-            // Try to restore clipboard before returning error
-            if let Some(old) = old_content {
-                let _ = self.clipboard.set_text(old);
-            }
-            return Err(e);
-        }
-
-        // Small delay for paste to complete
-        thread::sleep(Duration::from_millis(100));
-
-        // Restore old clipboard content (best effort)
-        if let Some(old) = old_content {
-            let _ = self.clipboard.set_text(old);
-        }
-
-        Ok(())
+        let restore = self.restore_clipboard;
+        paste_via_clipboard(self, text, restore)
     }
 
-    /// Send paste command (Cmd+V on macOS, Ctrl+V elsewhere)
+    /// Send paste command (Cmd+V on macOS, Ctrl+V on Linux and Windows)
     fn send_paste(&mut self) -> Result<(), TypingError> {
         // Use Unicode 'v' which enigo should map correctly
         self.send_key_combo(&[Self::modifier_key()], Key::Unicode('v'))
     }
 
     /// Type text directly using enigo's text method
+    ///
+    /// Paced typing sends one character at a time.
     fn type_direct(&mut self, text: &str) -> Result<(), TypingError> {
-        self.enigo
-            .text(text)
-            .map_err(|e| TypingError::Enigo(format!("Failed to type text: {}", e)))
+        if !self.is_paced() {
+            return self
+                .enigo()
+                .text(text)
+                .map_err(|e| TypingError::Enigo(format!("Failed to type text: {}", e)));
+        }
+        for c in text.chars() {
+            let key = c.to_string();
+            self.paced_event(|input| {
+                input
+                    .enigo()
+                    .text(&key)
+                    .map_err(|e| TypingError::Enigo(format!("Failed to type text: {}", e)))
+            })?;
+        }
+        Ok(())
+    }
+}
+
+impl PasteTarget for TypingInput {
+    fn get_clipboard(&mut self) -> Option<String> {
+        self.clipboard().get_text().ok()
+    }
+
+    fn set_clipboard(&mut self, text: &str) -> Result<(), TypingError> {
+        self.clipboard()
+            .set_text(text)
+            .map_err(|e| TypingError::Clipboard(format!("Failed to set clipboard: {}", e)))
+    }
+
+    fn paste(&mut self) -> Result<(), TypingError> {
+        self.send_paste()
+    }
+
+    fn wait(&mut self, ms: u64) {
+        thread::sleep(Duration::from_millis(ms));
     }
 }
 
@@ -191,6 +459,113 @@ impl TypingInput {
 mod tests {
     use super::*;
 
+    /// Records each clipboard step instead of touching the real clipboard
+    #[derive(Default)]
+    struct MockClipboard {
+        contents: Option<String>,
+        steps: Vec<String>,
+        fail_paste: bool,
+        /// Text "copied by the user" while we wait for the paste to settle
+        copied_during_paste: Option<String>,
+    }
+
+    impl PasteTarget for MockClipboard {
+        fn get_clipboard(&mut self) -> Option<String> {
+            self.steps.push("get".to_string());
+            self.contents.clone()
+        }
+
+        fn set_clipboard(&mut self, text: &str) -> Result<(), TypingError> {
+            self.steps.push(format!("set {}", text));
+            self.contents = Some(text.to_string());
+            Ok(())
+        }
+
+        fn paste(&mut self) -> Result<(), TypingError> {
+            self.steps.push("paste".to_string());
+            if self.fail_paste {
+                return Err(TypingError::Enigo("no keyboard".to_string()));
+            }
+            Ok(())
+        }
+
+        fn wait(&mut self, ms: u64) {
+            self.steps.push(format!("wait {}", ms));
+            let copied = self.copied_during_paste.take_if(|_| ms == PASTE_SETTLE_MS);
+            if copied.is_some() {
+                self.contents = copied;
+            }
+        }
+    }
+
+    fn mock(contents: Option<&str>) -> MockClipboard {
+        MockClipboard {
+            contents: contents.map(String::from),
+            ..MockClipboard::default()
+        }
+    }
+
+    #[test]
+    fn test_clipboard_restored_after_paste() {
+        let mut clipboard = mock(Some("copied"));
+        paste_via_clipboard(&mut clipboard, "hello", true).unwrap();
+        assert_eq!(
+            clipboard.steps,
+            [
+                "get".to_string(),
+                "set hello".to_string(),
+                format!("wait {}", CLIPBOARD_READY_MS),
+                "paste".to_string(),
+                format!("wait {}", PASTE_SETTLE_MS),
+                "get".to_string(),
+                "set copied".to_string(),
+            ]
+        );
+        assert_eq!(clipboard.contents.as_deref(), Some("copied"));
+
+        // Without restoring, the dictated text stays, but the paste still
+        // settles so the next one can't replace it before it's read
+        let mut clipboard = mock(Some("copied"));
+        paste_via_clipboard(&mut clipboard, "hello", false).unwrap();
+        assert_eq!(
+            clipboard.steps[1..],
+            [
+                format!("wait {}", CLIPBOARD_READY_MS),
+                "paste".to_string(),
+                format!("wait {}", PASTE_SETTLE_MS),
+            ]
+        );
+        assert_eq!(clipboard.contents.as_deref(), Some("hello"));
+
+        // Nothing to put back from an empty (or non-text) clipboard
+        let mut clipboard = mock(None);
+        paste_via_clipboard(&mut clipboard, "hello", true).unwrap();
+        assert_eq!(
+            clipboard.steps.last().unwrap(),
+            &format!("wait {}", PASTE_SETTLE_MS)
+        );
+    }
+
+    #[test]
+    fn test_clipboard_restore_edge_cases() {
+        // A failed paste restores straight away
+        let mut clipboard = MockClipboard {
+            fail_paste: true,
+            ..mock(Some("copied"))
+        };
+        assert!(paste_via_clipboard(&mut clipboard, "hello", true).is_err());
+        assert_eq!(clipboard.steps[3..], ["paste", "set copied"]);
+
+        // Something copied while the paste settled isn't overwritten
+        let mut clipboard = MockClipboard {
+            copied_during_paste: Some("newer".to_string()),
+            ..mock(Some("copied"))
+        };
+        paste_via_clipboard(&mut clipboard, "hello", true).unwrap();
+        assert_eq!(clipboard.steps.last().unwrap(), "get");
+        assert_eq!(clipboard.contents.as_deref(), Some("newer"));
+    }
+
     #[test]
     fn test_input_method_from_str() {
         assert_eq!(InputMethod::from_str("direct"), InputMethod::Direct);
@@ -198,5 +573,83 @@ mod tests {
         assert_eq!(InputMethod::from_str("clipboard"), InputMethod::Clipboard);
         assert_eq!(InputMethod::from_str("Clipboard"), InputMethod::Clipboard);
         assert_eq!(InputMethod::from_str("unknown"), InputMethod::Direct);
+        assert_eq!(InputMethod::from_str("dry-run"), InputMethod::DryRun);
+        assert_eq!(InputMethod::from_str("dry_run"), InputMethod::DryRun);
+    }
+
+    #[test]
+    fn test_modifier_key() {
+        #[cfg(target_os = "macos")]
+        assert_eq!(TypingInput::modifier_key(), Key::Meta);
+        #[cfg(not(target_os = "macos"))]
+        assert_eq!(TypingInput::modifier_key(), Key::Control);
+    }
+
+    #[test]
+    fn test_dry_run_records_actions() {
+        let mut input = TypingInput::new(InputMethod::DryRun, KeyPacing::default()).unwrap();
+        input.type_text("Hello").unwrap();
+        input.type_text("").unwrap();
+        input.send_key(Key::Return).unwrap();
+        input
+            .send_key_combo(&[Key::Control, Key::Shift], Key::LeftArrow)
+            .unwrap();
+        assert_eq!(
+            input.dry_run_log(),
+            [
+                "type \"Hello\"",
+                "key Return",
+                "key Control+Shift+LeftArrow"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_keystroke_delay_spaces_events() {
+        let pacing = KeyPacing {
+            delay_ms: 20,
+            adaptive: false,
+        };
+        let mut input = TypingInput::new(InputMethod::DryRun, pacing).unwrap();
+        input.type_text("ab").unwrap();
+        input.send_key(Key::Return).unwrap();
+        input.send_key_combo(&[Key::Shift], Key::Tab).unwrap();
+        assert_eq!(
+            input.dry_run_log(),
+            ["type \"a\"", "type \"b\"", "key Return", "key Shift+Tab"]
+        );
+        for pair in input.dry_run_times.windows(2) {
+            assert!(pair[1] - pair[0] >= Duration::from_millis(20));
+        }
+    }
+
+    #[test]
+    fn test_adaptive_backoff() {
+        let pacing = KeyPacing {
+            delay_ms: 0,
+            adaptive: true,
+        };
+        let mut input = TypingInput::new(InputMethod::DryRun, pacing).unwrap();
+        input.back_off();
+        assert_eq!(input.delay, Duration::from_millis(MIN_BACKOFF_MS));
+        input.back_off();
+        assert_eq!(input.delay, Duration::from_millis(MIN_BACKOFF_MS * 2));
+        for _ in 0..10 {
+            input.back_off();
+        }
+        assert_eq!(input.delay, Duration::from_millis(MAX_ADAPTIVE_DELAY_MS));
+
+        // A clean run speeds back up, but never below the configured delay
+        for _ in 0..SPEEDUP_AFTER_EVENTS {
+            input.note_success();
+        }
+        assert_eq!(
+            input.delay,
+            Duration::from_millis(MAX_ADAPTIVE_DELAY_MS / 2)
+        );
+        for _ in 0..SPEEDUP_AFTER_EVENTS * 20 {
+            input.note_success();
+        }
+        assert_eq!(input.delay, Duration::ZERO);
     }
 }
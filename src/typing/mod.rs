@@ -10,15 +10,25 @@
 //! - **Inline punctuation**: "hello comma world" becomes "hello, world"
 //! - **Navigation commands**: "go to end of line", "select all", etc.
 //! - **Undo/Redo support**: Tracks typed operations for reversal
-//! - **Configurable input method**: Clipboard+paste (default) or direct typing
+//! - **Numbers mode**: "numbers on" types "twenty five" as "25"
+//! - **Code mode**: "code mode on" types "x equals y plus two" as "x = y + 2"
+//! - **Auto-pairing**: With `typing.auto_pair`, spoken closers step over the
+//!   brackets and quotes a code editor inserted instead of doubling them
+//! - **Configurable input method**: Clipboard+paste (default) or direct typing,
+//!   plus a dry-run method that records actions instead of typing
 //! - **Global hotkeys**: Double-tap Cmd or Ctrl+Space to toggle
+//! - **Status line**: Optional one-line display of the typing state, last
+//!   command and undo depth (`typing.status_line`)
 
 mod commands;
 mod hotkey;
 mod input;
+mod numbers;
 mod processor;
+mod status;
 
 pub use commands::CommandParser;
 pub use hotkey::{HotkeyConfig, HotkeyEvent, start_hotkey_listener};
-pub use input::InputMethod;
+pub use input::{InputMethod, KeyPacing};
 pub use processor::{ProcessResult, TypingProcessor};
+pub use status::{StatusLine, TypingMode};
@@ -0,0 +1,326 @@
+//! Spoken number to digit conversion for numbers mode
+//!
+//! Converts runs of number words to digits: "one hundred and twenty three"
+//! becomes "123", "twenty-first" becomes "21st". Covers 0 to 999,999.
+//! Two adjacent two-digit groups are read year-style, so "nineteen eighty
+//! four" becomes "1984".
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Kind {
+    Unit,
+    Teen,
+    Tens,
+    Hundred,
+    Thousand,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct NumberWord {
+    kind: Kind,
+    value: u32,
+    ordinal: bool,
+}
+
+fn lookup(word: &str) -> Option<NumberWord> {
+    let (kind, value, ordinal) = match word {
+        "zero" => (Kind::Unit, 0, false),
+        "one" => (Kind::Unit, 1, false),
+        "two" => (Kind::Unit, 2, false),
+        "three" => (Kind::Unit, 3, false),
+        "four" => (Kind::Unit, 4, false),
+        "five" => (Kind::Unit, 5, false),
+        "six" => (Kind::Unit, 6, false),
+        "seven" => (Kind::Unit, 7, false),
+        "eight" => (Kind::Unit, 8, false),
+        "nine" => (Kind::Unit, 9, false),
+        "ten" => (Kind::Teen, 10, false),
+        "eleven" => (Kind::Teen, 11, false),
+        "twelve" => (Kind::Teen, 12, false),
+        "thirteen" => (Kind::Teen, 13, false),
+        "fourteen" => (Kind::Teen, 14, false),
+        "fifteen" => (Kind::Teen, 15, false),
+        "sixteen" => (Kind::Teen, 16, false),
+        "seventeen" => (Kind::Teen, 17, false),
+        "eighteen" => (Kind::Teen, 18, false),
+        "nineteen" => (Kind::Teen, 19, false),
+        "twenty" => (Kind::Tens, 20, false),
+        "thirty" => (Kind::Tens, 30, false),
+        "forty" => (Kind::Tens, 40, false),
+        "fifty" => (Kind::Tens, 50, false),
+        "sixty" => (Kind::Tens, 60, false),
+        "seventy" => (Kind::Tens, 70, false),
+        "eighty" => (Kind::Tens, 80, false),
+        "ninety" => (Kind::Tens, 90, false),
+        "hundred" => (Kind::Hundred, 100, false),
+        "thousand" => (Kind::Thousand, 1000, false),
+
+        "first" => (Kind::Unit, 1, true),
+        "second" => (Kind::Unit, 2, true),
+        "third" => (Kind::Unit, 3, true),
+        "fourth" => (Kind::Unit, 4, true),
+        "fifth" => (Kind::Unit, 5, true),
+        "sixth" => (Kind::Unit, 6, true),
+        "seventh" => (Kind::Unit, 7, true),
+        "eighth" => (Kind::Unit, 8, true),
+        "ninth" => (Kind::Unit, 9, true),
+        "tenth" => (Kind::Teen, 10, true),
+        "eleventh" => (Kind::Teen, 11, true),
+        "twelfth" => (Kind::Teen, 12, true),
+        "thirteenth" => (Kind::Teen, 13, true),
+        "fourteenth" => (Kind::Teen, 14, true),
+        "fifteenth" => (Kind::Teen, 15, true),
+        "sixteenth" => (Kind::Teen, 16, true),
+        "seventeenth" => (Kind::Teen, 17, true),
+        "eighteenth" => (Kind::Teen, 18, true),
+        "nineteenth" => (Kind::Teen, 19, true),
+        "twentieth" => (Kind::Tens, 20, true),
+        "thirtieth" => (Kind::Tens, 30, true),
+        "fortieth" => (Kind::Tens, 40, true),
+        "fiftieth" => (Kind::Tens, 50, true),
+        "sixtieth" => (Kind::Tens, 60, true),
+        "seventieth" => (Kind::Tens, 70, true),
+        "eightieth" => (Kind::Tens, 80, true),
+        "ninetieth" => (Kind::Tens, 90, true),
+        "hundredth" => (Kind::Hundred, 100, true),
+        "thousandth" => (Kind::Thousand, 1000, true),
+        _ => return None,
+    };
+    Some(NumberWord {
+        kind,
+        value,
+        ordinal,
+    })
+}
+
+/// Number words in a token, split on hyphens ("twenty-five")
+fn lookup_token(token: &str) -> Option<Vec<NumberWord>> {
+    token.split('-').map(lookup).collect()
+}
+
+/// Accumulates number words left to right, rejecting words that can't continue
+#[derive(Debug, Clone, Default)]
+struct NumberParser {
+    total: u32,
+    current: u32,
+    last: Option<Kind>,
+    words: usize,
+    scaled: bool,
+    ordinal: bool,
+    zero: bool,
+}
+
+impl NumberParser {
+    fn accepts(&self, word: &NumberWord) -> bool {
+        if self.ordinal || self.zero {
+            return false;
+        }
+        match word.kind {
+            // "zero" only stands alone
+            Kind::Unit if word.value == 0 => self.last.is_none(),
+            Kind::Unit => matches!(
+                self.last,
+                None | Some(Kind::Tens | Kind::Hundred | Kind::Thousand)
+            ),
+            Kind::Teen | Kind::Tens => {
+                matches!(self.last, None | Some(Kind::Hundred | Kind::Thousand))
+            }
+            Kind::Hundred => {
+                matches!(self.last, Some(Kind::Unit | Kind::Teen | Kind::Tens))
+                    && self.current > 0
+                    && self.current < 100
+            }
+            Kind::Thousand => self.last.is_some() && self.total == 0 && self.current > 0,
+        }
+    }
+
+    fn push(&mut self, word: &NumberWord) -> bool {
+        if !self.accepts(word) {
+            return false;
+        }
+        match word.kind {
+            Kind::Unit | Kind::Teen | Kind::Tens => self.current += word.value,
+            Kind::Hundred => {
+                self.current *= 100;
+                self.scaled = true;
+            }
+            Kind::Thousand => {
+                self.total = self.current * 1000;
+                self.current = 0;
+                self.scaled = true;
+            }
+        }
+        self.zero = word.kind == Kind::Unit && word.value == 0;
+        self.last = Some(word.kind);
+        self.ordinal = word.ordinal;
+        self.words += 1;
+        true
+    }
+
+    fn value(&self) -> u32 {
+        self.total + self.current
+    }
+
+    /// A bare two-digit group like "nineteen" or "eighty four"
+    fn is_pair(&self) -> bool {
+        !self.scaled && !self.ordinal && (10..=99).contains(&self.value())
+    }
+}
+
+struct ParsedNumber {
+    parser: NumberParser,
+    /// Index of the first token after the number
+    end: usize,
+    /// Punctuation that trailed the last number word
+    trailing: String,
+}
+
+/// Parse the longest number starting at `tokens[start]`
+fn parse_number(tokens: &[&str], start: usize) -> Option<ParsedNumber> {
+    let mut parser = NumberParser::default();
+    let mut trailing = String::new();
+    let mut i = start;
+
+    while i < tokens.len() {
+        let token = tokens[i];
+        let core = token.trim_end_matches(|c: char| c.is_ascii_punctuation());
+        let punct = &token[core.len()..];
+        let lower = core.to_lowercase();
+
+        // "and" joins "one hundred and five", but only mid-number
+        if lower == "and" && punct.is_empty() {
+            let continues = matches!(parser.last, Some(Kind::Hundred | Kind::Thousand))
+                && tokens.get(i + 1).is_some_and(|next| {
+                    let next = next
+                        .trim_end_matches(|c: char| c.is_ascii_punctuation())
+                        .to_lowercase();
+                    lookup_token(&next)
+                        .and_then(|words| words.first().copied())
+                        .is_some_and(|w| parser.accepts(&w))
+                });
+            if continues {
+                i += 1;
+                continue;
+            }
+            break;
+        }
+
+        // Too ambiguous on its own ("wait a second")
+        if parser.words == 0 && lower == "second" {
+            break;
+        }
+
+        let Some(words) = lookup_token(&lower) else {
+            break;
+        };
+        let mut trial = parser.clone();
+        if !words.iter().all(|w| trial.push(w)) {
+            break;
+        }
+        parser = trial;
+        i += 1;
+
+        if !punct.is_empty() {
+            trailing = punct.to_string();
+            break;
+        }
+    }
+
+    (parser.words > 0).then_some(ParsedNumber {
+        parser,
+        end: i,
+        trailing,
+    })
+}
+
+fn ordinal_suffix(n: u32) -> &'static str {
+    match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    }
+}
+
+/// Replace spoken numbers in `text` with digits
+pub fn words_to_digits(text: &str) -> String {
+    let tokens: Vec<&str> = text.split_whitespace().collect();
+    let mut out: Vec<String> = Vec::with_capacity(tokens.len());
+    let mut i = 0;
+
+    while i < tokens.len() {
+        let Some(mut number) = parse_number(&tokens, i) else {
+            out.push(tokens[i].to_string());
+            i += 1;
+            continue;
+        };
+
+        let mut digits = number.parser.value().to_string();
+        if number.parser.is_pair() && number.trailing.is_empty() {
+            // Year-style: "nineteen eighty four" -> 1984
+            if let Some(next) = parse_number(&tokens, number.end) {
+                if next.parser.is_pair() {
+                    digits.push_str(&next.parser.value().to_string());
+                    number = next;
+                }
+            }
+        }
+        if number.parser.ordinal {
+            digits.push_str(ordinal_suffix(number.parser.value()));
+        }
+        digits.push_str(&number.trailing);
+        out.push(digits);
+        i = number.end;
+    }
+
+    out.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cardinals() {
+        assert_eq!(words_to_digits("zero"), "0");
+        assert_eq!(words_to_digits("twenty five"), "25");
+        assert_eq!(words_to_digits("twenty-five"), "25");
+        assert_eq!(words_to_digits("one hundred and twenty three"), "123");
+        assert_eq!(words_to_digits("one thousand and five"), "1005");
+        assert_eq!(
+            words_to_digits("nine hundred ninety nine thousand nine hundred ninety nine"),
+            "999999"
+        );
+        assert_eq!(words_to_digits("twenty five hundred"), "2500");
+    }
+
+    #[test]
+    fn test_ordinals() {
+        assert_eq!(words_to_digits("first"), "1st");
+        assert_eq!(words_to_digits("twenty second"), "22nd");
+        assert_eq!(words_to_digits("the forty-third time"), "the 43rd time");
+        assert_eq!(words_to_digits("one hundred and twelfth"), "112th");
+        assert_eq!(words_to_digits("one hundredth"), "100th");
+        assert_eq!(words_to_digits("wait a second"), "wait a second");
+    }
+
+    #[test]
+    fn test_year_style() {
+        assert_eq!(words_to_digits("nineteen eighty four"), "1984");
+        assert_eq!(words_to_digits("in twenty twenty"), "in 2020");
+        assert_eq!(words_to_digits("nineteen hundred"), "1900");
+    }
+
+    #[test]
+    fn test_mixed_text() {
+        assert_eq!(
+            words_to_digits("I have twenty-five apples, and three pears."),
+            "I have 25 apples, and 3 pears."
+        );
+        assert_eq!(words_to_digits("one two three"), "1 2 3");
+        assert_eq!(words_to_digits("five and six"), "5 and 6");
+        assert_eq!(words_to_digits("rock and roll"), "rock and roll");
+        assert_eq!(words_to_digits("a thousand thanks"), "a thousand thanks");
+        assert_eq!(words_to_digits("zero one"), "0 1");
+    }
+}
@@ -4,9 +4,11 @@
 //! Maintains an undo buffer for reverting operations.
 
 use super::commands::{CommandParser, TypingCommand};
-use super::input::{InputMethod, TypingError, TypingInput};
+use super::input::{InputMethod, KeyPacing, TypingError, TypingInput};
+use super::numbers::words_to_digits;
+use crate::logger;
 use enigo::Key;
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
 use std::io::{self, Write};
 
 /// Represents a typed operation for undo/redo
@@ -18,6 +20,106 @@ enum TypedOperation {
     Punctuation(char),
     /// Pressed Enter
     Enter,
+    /// Deleted known text (undo retypes it)
+    Deleted(String),
+    /// Deleted text we couldn't see; nothing before this can be undone reliably
+    Irreversible,
+    /// Stepped over a closer the editor auto-inserted (undo steps back)
+    MovedPast(char),
+}
+
+/// The closer an editor auto-inserts after `c`, if it auto-pairs it
+///
+/// Single quotes are left out: editors don't pair them after a letter, and
+/// apostrophes in prose ("don't") would otherwise open a pair.
+fn auto_closer(c: char) -> Option<char> {
+    match c {
+        '(' => Some(')'),
+        '[' => Some(']'),
+        '{' => Some('}'),
+        '"' => Some('"'),
+        _ => None,
+    }
+}
+
+/// Most characters remembered behind the cursor
+const MAX_TAIL_CHARS: usize = 1024;
+
+/// Text we know sits immediately before the cursor
+///
+/// Built from what we typed since the cursor last moved, so deletions can
+/// record what they removed. Anything older than the tail is unknown.
+#[derive(Debug, Default)]
+struct TypedTail {
+    text: String,
+}
+
+impl TypedTail {
+    fn push(&mut self, text: &str) {
+        self.text.push_str(text);
+        let excess = self.text.chars().count().saturating_sub(MAX_TAIL_CHARS);
+        if excess > 0 {
+            let cut = self.text.char_indices().nth(excess).map_or(0, |(i, _)| i);
+            self.text.drain(..cut);
+        }
+    }
+
+    /// Forget the tail, e.g. after the cursor moves
+    fn clear(&mut self) {
+        self.text.clear();
+    }
+
+    fn last_char(&self) -> Option<char> {
+        self.text.chars().last()
+    }
+
+    /// Remove `n` characters from the end (known or not)
+    fn remove(&mut self, n: usize) {
+        for _ in 0..n {
+            if self.text.pop().is_none() {
+                break;
+            }
+        }
+    }
+
+    /// Remove the text a Backspace deletes, if known
+    fn delete_char(&mut self) -> Option<String> {
+        self.text.pop().map(String::from)
+    }
+
+    /// Remove the text a delete-word deletes, if known
+    ///
+    /// Matches editor behaviour: trailing whitespace, then either a run of word
+    /// characters or a run of punctuation. Unknown if the run reaches the start
+    /// of the tail, since the word may continue before it.
+    fn delete_word(&mut self) -> Option<String> {
+        let trimmed = self.text.trim_end();
+        let last = trimmed.chars().last()?;
+        let is_word = |c: char| c.is_alphanumeric() || c == '_';
+        let in_run = |c: char| {
+            !c.is_whitespace()
+                && if is_word(last) {
+                    is_word(c)
+                } else {
+                    !is_word(c)
+                }
+        };
+        let start = trimmed
+            .char_indices()
+            .rev()
+            .find(|&(_, c)| !in_run(c))
+            .map(|(i, c)| i + c.len_utf8())?;
+        Some(self.text.split_off(start))
+    }
+
+    /// Remove the text a delete-line deletes, if known
+    ///
+    /// Assumes nothing follows the cursor on the line. Unknown unless we typed
+    /// the line break that starts the line.
+    fn delete_line(&mut self) -> Option<String> {
+        let start = self.text.rfind('\n')? + 1;
+        Some(self.text.split_off(start))
+    }
 }
 
 /// Result of processing a segment
@@ -37,7 +139,8 @@ pub enum ProcessResult {
 pub struct TypingProcessor {
     input: TypingInput,
     parser: CommandParser,
-    undo_stack: VecDeque<TypedOperation>,
+    /// Operations tagged with the segment that performed them
+    undo_stack: VecDeque<(u64, TypedOperation)>,
     redo_stack: Vec<TypedOperation>,
     undo_buffer_size: usize,
     feedback_enabled: bool,
@@ -46,6 +149,18 @@ pub struct TypingProcessor {
     last_char: Option<char>,
     /// Track if we need to capitalize the next word
     capitalize_next: bool,
+    /// Numbers mode: spoken numbers are typed as digits
+    number_mode: bool,
+    /// Known text before the cursor, so deletions can be undone
+    tail: TypedTail,
+    /// Assume the target editor auto-inserts closing brackets and quotes
+    auto_pair: bool,
+    /// Closers the editor inserted after the cursor, innermost last
+    open_pairs: Vec<char>,
+    /// Id of the segment being processed, so "scratch that" can undo it whole
+    segment: u64,
+    /// Most recent command recognized, for the status line
+    last_command: Option<TypingCommand>,
 }
 
 impl TypingProcessor {
@@ -55,9 +170,10 @@ impl TypingProcessor {
         undo_buffer_size: usize,
         feedback_enabled: bool,
         command_pause_ms: u32,
+        pacing: KeyPacing,
     ) -> Result<Self, TypingError> {
         Ok(Self {
-            input: TypingInput::new(method)?,
+            input: TypingInput::new(method, pacing)?,
             parser: CommandParser::new(command_pause_ms),
             undo_stack: VecDeque::with_capacity(undo_buffer_size),
             redo_stack: Vec::new(),
@@ -66,9 +182,47 @@ impl TypingProcessor {
             verbose: false,
             last_char: None,
             capitalize_next: true, // Start with capital
+            number_mode: false,
+            tail: TypedTail::default(),
+            auto_pair: false,
+            open_pairs: Vec::new(),
+            segment: 0,
+            last_command: None,
         })
     }
 
+    /// Add user-defined punctuation phrases (phrase -> replacement text)
+    pub fn with_custom_punctuation(mut self, punctuation: HashMap<String, String>) -> Self {
+        self.parser = std::mem::take(&mut self.parser).with_custom_punctuation(punctuation);
+        self
+    }
+
+    /// Set the prefixes that type the next phrase literally ("literal comma")
+    pub fn with_literal_prefixes(mut self, prefixes: Vec<String>) -> Self {
+        self.parser = std::mem::take(&mut self.parser).with_literal_prefixes(prefixes);
+        self
+    }
+
+    /// Assume the target auto-pairs brackets and quotes, as code editors do
+    ///
+    /// A spoken closer then steps over the one the editor inserted instead of
+    /// typing a second. Ignored with clipboard input, since pasted text isn't
+    /// paired.
+    pub fn with_auto_pair(mut self, auto_pair: bool) -> Self {
+        let pasting = self.input.method() == InputMethod::Clipboard;
+        if auto_pair && pasting {
+            logger::warn!("typing.auto_pair has no effect with clipboard input");
+        }
+        self.auto_pair = auto_pair && !pasting;
+        self
+    }
+
+    /// Whether clipboard pastes put back what the clipboard held (default on)
+    pub fn with_restore_clipboard(mut self, restore: bool) -> Self {
+        self.input.set_restore_clipboard(restore);
+        self
+    }
+
     /// Enable verbose logging
     pub fn with_verbose(mut self, verbose: bool) -> Self {
         self.verbose = verbose;
@@ -87,6 +241,7 @@ impl TypingProcessor {
             eprintln!("[TYPING] Input: \"{}\" (pause: {}ms)", text, pause_ms);
         }
 
+        self.segment += 1;
         let result = self.parser.parse(text, pause_ms);
 
         if self.verbose {
@@ -97,12 +252,19 @@ impl TypingProcessor {
         }
 
         // Type any text first
-        if let Some(ref text) = result.text {
+        if let Some(text) = result.text {
+            // Numbers mode: "twenty five" -> "25"
+            let text = if self.number_mode {
+                words_to_digits(&text)
+            } else {
+                text
+            };
+
             // Smart spacing: add space before text if needed
-            let text_to_type = self.prepare_text(text);
+            let text_to_type = self.prepare_text(&text);
 
             if self.verbose {
-                if text_to_type != *text {
+                if text_to_type != text {
                     eprintln!("[TYPING] Smart text: \"{}\" -> \"{}\"", text, text_to_type);
                 } else {
                     eprintln!("[TYPING] Typing text: \"{}\"", text_to_type);
@@ -116,6 +278,7 @@ impl TypingProcessor {
             if self.verbose {
                 eprintln!("[TYPING] Executing command: {:?}", cmd);
             }
+            self.last_command = Some(cmd.clone());
             match cmd {
                 TypingCommand::Stop => {
                     self.play_feedback("stop");
@@ -144,25 +307,45 @@ impl TypingProcessor {
     }
 
     /// Prepare text for typing with smart spacing and capitalization
+    ///
+    /// Code mode never capitalizes, and spaces operators from the code before
+    /// them while keeping separators and brackets attached ("x" then "= 2").
     fn prepare_text(&mut self, text: &str) -> String {
         let mut result = String::new();
+        let code_mode = self.parser.code_mode();
+
+        // Nothing goes between an opener and the closer the editor paired it with
+        let in_new_pair = self
+            .last_char
+            .and_then(auto_closer)
+            .is_some_and(|closer| self.open_pairs.last() == Some(&closer));
 
         // Check if we need a leading space
-        let needs_space = self.last_char.map_or(false, |c| {
-            // Add space if last char was alphanumeric or closing punctuation
-            c.is_alphanumeric() || c == ')' || c == ']' || c == '}' || c == '"' || c == '\''
-        });
+        let needs_space = !in_new_pair
+            && self.last_char.map_or(false, |c| {
+                if code_mode {
+                    !c.is_whitespace() && !matches!(c, '(' | '[' | '.' | '!' | '_')
+                } else {
+                    // Add space if last char was alphanumeric or closing punctuation
+                    c.is_alphanumeric() || c == ')' || c == ']' || c == '}' || c == '"' || c == '\''
+                }
+            });
 
         if needs_space && !text.is_empty() {
             let first = text.chars().next().unwrap();
-            // Don't add space before punctuation
-            if !first.is_ascii_punctuation() {
+            let attaches = if code_mode {
+                matches!(first, '.' | ',' | ';' | ':' | '(' | ')' | '[' | ']' | '_')
+            } else {
+                // Don't add space before punctuation
+                first.is_ascii_punctuation()
+            };
+            if !attaches {
                 result.push(' ');
             }
         }
 
         // Apply capitalization if needed
-        let text_to_add = if self.capitalize_next && !text.is_empty() {
+        let text_to_add = if self.capitalize_next && !code_mode && !text.is_empty() {
             let mut chars = text.chars();
             match chars.next() {
                 Some(c) => {
@@ -182,11 +365,14 @@ impl TypingProcessor {
 
     /// Type text and add to undo buffer
     fn type_text(&mut self, text: &str) -> Result<(), TypingError> {
+        let text = self.skip_closers(text)?;
         if text.is_empty() {
             return Ok(());
         }
 
         self.input.type_text(text)?;
+        self.tail.push(text);
+        self.track_pairs(text);
         self.push_undo(TypedOperation::Text(text.to_string()));
         self.redo_stack.clear(); // Clear redo on new action
 
@@ -200,16 +386,86 @@ impl TypingProcessor {
         Ok(())
     }
 
+    /// Step over auto-inserted closers that `text` starts with
+    ///
+    /// Returns the rest of the text. Spacing before the closer is dropped too.
+    fn skip_closers<'a>(&mut self, text: &'a str) -> Result<&'a str, TypingError> {
+        let mut rest = text;
+        loop {
+            let trimmed = rest.trim_start();
+            match trimmed.chars().next() {
+                Some(c) if self.open_pairs.last() == Some(&c) => {
+                    self.move_past(c)?;
+                    rest = &trimmed[c.len_utf8()..];
+                }
+                _ => return Ok(rest),
+            }
+        }
+    }
+
+    /// Press Right over the innermost auto-inserted closer
+    fn move_past(&mut self, closer: char) -> Result<(), TypingError> {
+        self.input.send_key(Key::RightArrow)?;
+        self.open_pairs.pop();
+        self.tail.push(&closer.to_string());
+        self.push_undo(TypedOperation::MovedPast(closer));
+        self.redo_stack.clear();
+        self.last_char = Some(closer);
+        self.capitalize_next = false;
+        Ok(())
+    }
+
+    /// Follow the pairs an auto-pairing editor opens and types over in `text`
+    fn track_pairs(&mut self, text: &str) {
+        if !self.auto_pair {
+            return;
+        }
+        for c in text.chars() {
+            if self.open_pairs.last() == Some(&c) {
+                // Typing the closer steps over the inserted one
+                self.open_pairs.pop();
+            } else if let Some(closer) = auto_closer(c) {
+                self.open_pairs.push(closer);
+            }
+        }
+    }
+
     /// Execute a typing command
     fn execute_command(&mut self, cmd: TypingCommand) -> Result<(), TypingError> {
+        // Selection and navigation leave the cursor next to text we haven't seen
+        if matches!(
+            cmd,
+            TypingCommand::SelectAll
+                | TypingCommand::SelectWord
+                | TypingCommand::SelectLine
+                | TypingCommand::GoToEndOfLine
+                | TypingCommand::GoToStartOfLine
+                | TypingCommand::GoToEnd
+                | TypingCommand::GoToStart
+                | TypingCommand::MoveLeft(_)
+                | TypingCommand::MoveRight(_)
+                | TypingCommand::MoveUp(_)
+                | TypingCommand::MoveDown(_)
+        ) {
+            self.tail.clear();
+            self.open_pairs.clear();
+        }
+
         match cmd {
             TypingCommand::Undo => self.undo()?,
             TypingCommand::Redo => self.redo()?,
+            TypingCommand::Scratch => self.scratch()?,
+
+            TypingCommand::Punctuation(c) if self.open_pairs.last() == Some(&c) => {
+                self.move_past(c)?;
+            }
 
             TypingCommand::Punctuation(c) => {
                 // Smart punctuation: add space after if it's sentence-ending
                 let text = c.to_string();
                 self.input.type_text(&text)?;
+                self.tail.push(&text);
+                self.track_pairs(&text);
                 self.push_undo(TypedOperation::Punctuation(c));
                 self.redo_stack.clear();
 
@@ -218,8 +474,18 @@ impl TypingProcessor {
                 self.capitalize_next = c == '.' || c == '!' || c == '?';
             }
 
+            TypingCommand::InsertText(text) => {
+                // Spaced like a word, but typed verbatim (no capitalization)
+                let text = match self.last_char {
+                    Some(c) if !c.is_whitespace() => format!(" {}", text),
+                    _ => text,
+                };
+                self.type_text(&text)?;
+            }
+
             TypingCommand::Enter => {
                 self.input.send_key(Key::Return)?;
+                self.tail.push("\n");
                 self.push_undo(TypedOperation::Enter);
                 self.redo_stack.clear();
 
@@ -230,23 +496,28 @@ impl TypingProcessor {
 
             TypingCommand::Tab => {
                 self.input.send_key(Key::Tab)?;
+                self.tail.push("\t");
                 self.last_char = Some('\t');
             }
 
             TypingCommand::Space => {
                 self.input.send_key(Key::Space)?;
+                self.tail.push(" ");
                 self.last_char = Some(' ');
             }
 
             TypingCommand::Backspace => {
                 self.input.send_key(Key::Backspace)?;
-                // After backspace, we don't know what char is now last
-                // Reset to unknown state - next text will add space if needed
-                self.last_char = None;
+                let deleted = self.tail.delete_char();
+                self.forget_pair(deleted.as_deref());
+                self.record_deletion(deleted);
             }
 
             TypingCommand::Delete => {
+                // Forward delete removes text we never saw (or the next closer)
                 self.input.send_key(Key::Delete)?;
+                self.open_pairs.pop();
+                self.record_deletion(None);
             }
 
             TypingCommand::DeleteWord => {
@@ -255,7 +526,8 @@ impl TypingProcessor {
                 self.input.send_key_combo(&[Key::Alt], Key::Backspace)?;
                 #[cfg(not(target_os = "macos"))]
                 self.input.send_key_combo(&[Key::Control], Key::Backspace)?;
-                self.last_char = None;
+                let deleted = self.tail.delete_word();
+                self.record_deletion(deleted);
             }
 
             TypingCommand::DeleteLine => {
@@ -273,7 +545,8 @@ impl TypingProcessor {
                     self.input.send_key_combo(&[Key::Shift], Key::End)?;
                     self.input.send_key(Key::Backspace)?;
                 }
-                self.last_char = None;
+                let deleted = self.tail.delete_line();
+                self.record_deletion(deleted);
                 self.capitalize_next = true;
             }
 
@@ -364,6 +637,23 @@ impl TypingProcessor {
                 }
             }
 
+            TypingCommand::NumberMode(enabled) => {
+                self.number_mode = enabled;
+                if self.verbose {
+                    eprintln!(
+                        "[TYPING] Numbers mode {}",
+                        if enabled { "on" } else { "off" }
+                    );
+                }
+            }
+
+            TypingCommand::CodeMode(enabled) => {
+                self.parser.set_code_mode(enabled);
+                if self.verbose {
+                    eprintln!("[TYPING] Code mode {}", if enabled { "on" } else { "off" });
+                }
+            }
+
             // Control commands handled in process_segment
             TypingCommand::Stop | TypingCommand::Pause | TypingCommand::Resume => {}
         }
@@ -371,9 +661,36 @@ impl TypingProcessor {
         Ok(())
     }
 
+    /// Backspace over an opener also deletes the closer the editor paired with it
+    fn forget_pair(&mut self, deleted: Option<&str>) {
+        let closer = deleted
+            .filter(|d| d.chars().count() == 1)
+            .and_then(|d| d.chars().next())
+            .and_then(auto_closer);
+        if closer.is_some() && self.open_pairs.last() == closer.as_ref() {
+            self.open_pairs.pop();
+        }
+    }
+
+    /// Record a deletion on the undo stack
+    ///
+    /// Known text can be retyped on undo; unknown text leaves a boundary.
+    fn record_deletion(&mut self, deleted: Option<String>) {
+        match deleted {
+            Some(text) => self.push_undo(TypedOperation::Deleted(text)),
+            None => {
+                self.tail.clear();
+                self.push_undo(TypedOperation::Irreversible);
+            }
+        }
+        self.redo_stack.clear();
+        // Smart spacing follows what's now before the cursor (none if unknown)
+        self.last_char = self.tail.last_char();
+    }
+
     /// Undo the last operation
     fn undo(&mut self) -> Result<(), TypingError> {
-        if let Some(op) = self.undo_stack.pop_back() {
+        if let Some((_, op)) = self.undo_stack.pop_back() {
             match &op {
                 TypedOperation::Text(text) => {
                     // Select and delete the text we typed
@@ -382,44 +699,105 @@ impl TypingProcessor {
                         self.input.send_key_combo(&[Key::Shift], Key::LeftArrow)?;
                     }
                     self.input.send_key(Key::Backspace)?;
+                    self.tail.remove(text.chars().count());
+                    for c in text.chars().rev() {
+                        self.forget_pair(Some(&c.to_string()));
+                    }
                 }
-                TypedOperation::Punctuation(_) => {
+                TypedOperation::Punctuation(c) => {
                     self.input.send_key(Key::Backspace)?;
+                    self.tail.remove(1);
+                    self.forget_pair(Some(&c.to_string()));
                 }
                 TypedOperation::Enter => {
                     self.input.send_key(Key::Backspace)?;
+                    self.tail.remove(1);
+                }
+                TypedOperation::MovedPast(c) => {
+                    self.input.send_key(Key::LeftArrow)?;
+                    self.tail.remove(1);
+                    self.open_pairs.push(*c);
+                }
+                TypedOperation::Deleted(text) => {
+                    self.input.type_text(text)?;
+                    self.tail.push(text);
+                }
+                TypedOperation::Irreversible => {
+                    // Earlier operations no longer line up with the document
+                    eprintln!("\n[Can't undo past a deletion of unknown text]");
+                    self.undo_stack.clear();
+                    self.redo_stack.clear();
+                    return Ok(());
                 }
             }
+            self.last_char = self.tail.last_char();
             self.redo_stack.push(op);
         }
         Ok(())
     }
 
+    /// Undo every operation of the most recent segment that typed anything
+    ///
+    /// A phrase like "hello world comma" is several operations; this reverts
+    /// them all. Redo brings them back one at a time.
+    fn scratch(&mut self) -> Result<(), TypingError> {
+        let Some(&(segment, _)) = self.undo_stack.back() else {
+            return Ok(());
+        };
+        while self
+            .undo_stack
+            .back()
+            .is_some_and(|(tag, _)| *tag == segment)
+        {
+            self.undo()?;
+        }
+        Ok(())
+    }
+
     /// Redo the last undone operation
     fn redo(&mut self) -> Result<(), TypingError> {
         if let Some(op) = self.redo_stack.pop() {
             match &op {
                 TypedOperation::Text(text) => {
                     self.input.type_text(text)?;
+                    self.tail.push(text);
+                    self.track_pairs(text);
                 }
                 TypedOperation::Punctuation(c) => {
                     self.input.type_text(&c.to_string())?;
+                    self.tail.push(&c.to_string());
+                    self.track_pairs(&c.to_string());
                 }
                 TypedOperation::Enter => {
                     self.input.send_key(Key::Return)?;
+                    self.tail.push("\n");
                 }
+                TypedOperation::MovedPast(c) => {
+                    self.input.send_key(Key::RightArrow)?;
+                    self.tail.push(&c.to_string());
+                    self.open_pairs.pop();
+                }
+                TypedOperation::Deleted(text) => {
+                    for _ in 0..text.chars().count() {
+                        self.input.send_key(Key::Backspace)?;
+                    }
+                    self.tail.remove(text.chars().count());
+                }
+                // Never pushed to the redo stack
+                TypedOperation::Irreversible => {}
             }
+            self.last_char = self.tail.last_char();
             self.push_undo(op);
         }
         Ok(())
     }
 
-    /// Push an operation to the undo stack
+    /// Push an operation to the undo stack, tagged with the current segment
     fn push_undo(&mut self, op: TypedOperation) {
         if self.undo_stack.len() >= self.undo_buffer_size {
             self.undo_stack.pop_front();
         }
-        self.undo_stack.push_back(op);
+        self.undo_stack.push_back((self.segment, op));
     }
 
     /// Play feedback (visual/audio) when command is recognized
@@ -471,6 +849,39 @@ impl TypingProcessor {
                     .spawn();
             });
         }
+
+        // On Linux, many terminals silence the bell, so try the desktop sound
+        // (PulseAudio/PipeWire first, then libcanberra)
+        #[cfg(target_os = "linux")]
+        {
+            std::thread::spawn(|| {
+                let players: [(&str, &[&str]); 2] = [
+                    ("paplay", &["/usr/share/sounds/freedesktop/stereo/bell.oga"]),
+                    ("canberra-gtk-play", &["--id", "bell"]),
+                ];
+                for (program, args) in players {
+                    let played = std::process::Command::new(program)
+                        .args(args)
+                        .stdout(std::process::Stdio::null())
+                        .stderr(std::process::Stdio::null())
+                        .status()
+                        .is_ok_and(|status| status.success());
+                    if played {
+                        break;
+                    }
+                }
+            });
+        }
+    }
+
+    /// Actions recorded when created with `InputMethod::DryRun`
+    pub fn dry_run_transcript(&self) -> &[String] {
+        self.input.dry_run_log()
+    }
+
+    /// The most recent command recognized, if any
+    pub fn last_command(&self) -> Option<&TypingCommand> {
+        self.last_command.as_ref()
     }
 
     /// Get the number of operations in the undo buffer
@@ -483,3 +894,285 @@ impl TypingProcessor {
         self.redo_stack.len()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dry_run() -> TypingProcessor {
+        TypingProcessor::new(InputMethod::DryRun, 50, false, 100, KeyPacing::default()).unwrap()
+    }
+
+    fn tail(text: &str) -> TypedTail {
+        let mut tail = TypedTail::default();
+        tail.push(text);
+        tail
+    }
+
+    #[test]
+    fn test_backspace_deletion() {
+        let mut t = tail("hi.");
+        assert_eq!(t.delete_char().as_deref(), Some("."));
+        assert_eq!(t.delete_char().as_deref(), Some("i"));
+        assert_eq!(t.delete_char().as_deref(), Some("h"));
+        // Beyond what we typed
+        assert_eq!(t.delete_char(), None);
+    }
+
+    #[test]
+    fn test_word_deletion() {
+        let mut t = tail("Hello there  ");
+        assert_eq!(t.delete_word().as_deref(), Some("there  "));
+        assert_eq!(t.text, "Hello ");
+
+        let mut t = tail(" wait...");
+        assert_eq!(t.delete_word().as_deref(), Some("..."));
+        assert_eq!(t.delete_word().as_deref(), Some("wait"));
+
+        // The word may continue before the tail
+        let mut t = tail("hello");
+        assert_eq!(t.delete_word(), None);
+        assert_eq!(t.text, "hello");
+    }
+
+    #[test]
+    fn test_line_deletion() {
+        let mut t = tail("first\nsecond line");
+        assert_eq!(t.delete_line().as_deref(), Some("second line"));
+        assert_eq!(t.text, "first\n");
+        assert_eq!(t.delete_line().as_deref(), Some(""));
+
+        let mut t = tail("no break");
+        assert_eq!(t.delete_line(), None);
+    }
+
+    #[test]
+    fn test_undo_restores_deleted_text() {
+        // Retyping what a deletion removed puts the tail back as it was
+        let mut t = tail("Hello there");
+        let deleted = t.delete_word().unwrap();
+        t.push(&deleted);
+        assert_eq!(t.text, "Hello there");
+        // Redo removes it again
+        t.remove(deleted.chars().count());
+        assert_eq!(t.text, "Hello ");
+    }
+
+    #[test]
+    fn test_tail_is_bounded() {
+        let mut t = tail(&"ab".repeat(MAX_TAIL_CHARS));
+        assert_eq!(t.text.chars().count(), MAX_TAIL_CHARS);
+        t.push("é");
+        assert_eq!(t.text.chars().count(), MAX_TAIL_CHARS);
+        assert_eq!(t.last_char(), Some('é'));
+    }
+
+    #[test]
+    fn test_dry_run_pipeline() {
+        let mut proc = dry_run();
+        proc.process_segment("hello comma world", 0).unwrap();
+        proc.process_segment("new line", 500).unwrap();
+        proc.process_segment("literal enter", 500).unwrap();
+        assert_eq!(
+            proc.dry_run_transcript(),
+            ["type \"Hello, world\"", "key Return", "type \"Enter\""]
+        );
+
+        let mut proc = dry_run();
+        assert_eq!(
+            proc.process_segment("pause silly", 500).unwrap(),
+            ProcessResult::Pause
+        );
+        assert!(proc.dry_run_transcript().is_empty());
+    }
+
+    #[test]
+    fn test_last_command() {
+        let mut proc = dry_run();
+        assert_eq!(proc.last_command(), None);
+        proc.process_segment("hello", 0).unwrap();
+        assert_eq!(proc.last_command(), None);
+        proc.process_segment("new line", 500).unwrap();
+        assert_eq!(proc.last_command(), Some(&TypingCommand::Enter));
+        // A literal "enter" is typed, not run, so the last command stays
+        proc.process_segment("literal enter", 500).unwrap();
+        assert_eq!(proc.last_command(), Some(&TypingCommand::Enter));
+        proc.process_segment("comma", 500).unwrap();
+        assert_eq!(proc.last_command(), Some(&TypingCommand::Punctuation(',')));
+    }
+
+    #[test]
+    fn test_code_mode_pipeline() {
+        let mut proc = dry_run();
+        proc.process_segment("code mode on", 500).unwrap();
+        proc.process_segment("x equals y plus two", 0).unwrap();
+        proc.process_segment("semicolon", 500).unwrap();
+        proc.process_segment("new line", 500).unwrap();
+        proc.process_segment("print open paren x", 0).unwrap();
+        proc.process_segment("close paren", 500).unwrap();
+        proc.process_segment("code mode off", 500).unwrap();
+        proc.process_segment("done period", 0).unwrap();
+        assert_eq!(
+            proc.dry_run_transcript(),
+            [
+                "type \"x = y + 2\"",
+                "type \";\"",
+                "key Return",
+                "type \"print(x\"",
+                "type \")\"",
+                "type \" done\"",
+                "type \".\""
+            ]
+        );
+    }
+
+    #[test]
+    fn test_auto_pair_steps_over_closers() {
+        let segments = [
+            ("open paren", 500),
+            ("x", 0),
+            ("close paren", 500),
+            ("quote", 500),
+            ("hi", 0),
+            ("quote", 500),
+        ];
+
+        // Plain text fields get every character typed
+        let mut proc = dry_run();
+        for (text, pause) in segments {
+            proc.process_segment(text, pause).unwrap();
+        }
+        assert_eq!(
+            proc.dry_run_transcript(),
+            [
+                "type \"(\"",
+                "type \"x\"",
+                "type \")\"",
+                "type \"\\\"\"",
+                "type \" hi\"",
+                "type \"\\\"\""
+            ]
+        );
+
+        // Editors already inserted the closers, so step over them
+        let mut proc = dry_run().with_auto_pair(true);
+        for (text, pause) in segments {
+            proc.process_segment(text, pause).unwrap();
+        }
+        assert_eq!(
+            proc.dry_run_transcript(),
+            [
+                "type \"(\"",
+                "type \"x\"",
+                "key RightArrow",
+                "type \"\\\"\"",
+                "type \"hi\"",
+                "key RightArrow"
+            ]
+        );
+        assert_eq!(proc.tail.text, "(x)\"hi\"");
+
+        // Undo steps back inside the quotes, redo steps out again
+        proc.process_segment("undo", 500).unwrap();
+        proc.process_segment("redo", 500).unwrap();
+        assert_eq!(
+            proc.dry_run_transcript()[6..],
+            ["key LeftArrow", "key RightArrow"]
+        );
+    }
+
+    #[test]
+    fn test_auto_pair_code_mode() {
+        let mut proc = dry_run().with_auto_pair(true);
+        proc.process_segment("code mode on", 500).unwrap();
+        proc.process_segment("print open paren foo open bracket one", 0)
+            .unwrap();
+        proc.process_segment("close bracket close paren", 500)
+            .unwrap();
+        // Text typed in one go types over its own closers
+        proc.process_segment("f open paren x close paren", 0)
+            .unwrap();
+        proc.process_segment("close paren", 500).unwrap();
+        assert_eq!(
+            proc.dry_run_transcript(),
+            [
+                "type \"print(foo[1\"",
+                "key RightArrow",
+                "key RightArrow",
+                "type \" f(x)\"",
+                "type \")\""
+            ]
+        );
+
+        // Undoing text forgets the pairs it opened
+        let mut proc = dry_run().with_auto_pair(true);
+        proc.process_segment("code mode on", 500).unwrap();
+        proc.process_segment("f open paren x", 0).unwrap();
+        proc.process_segment("undo", 500).unwrap();
+        proc.process_segment("close paren", 500).unwrap();
+        assert_eq!(proc.dry_run_transcript().last().unwrap(), "type \")\"");
+
+        // Moving the cursor forgets the pairs we opened
+        let mut proc = dry_run().with_auto_pair(true);
+        proc.process_segment("open paren", 500).unwrap();
+        proc.process_segment("go to end of line", 500).unwrap();
+        proc.process_segment("close paren", 500).unwrap();
+        assert_eq!(proc.dry_run_transcript().last().unwrap(), "type \")\"");
+    }
+
+    #[test]
+    fn test_undo_backspace_retypes() {
+        let mut proc = dry_run();
+        proc.process_segment("hi", 0).unwrap();
+        proc.process_segment("backspace", 500).unwrap();
+        proc.process_segment("undo", 500).unwrap();
+        proc.process_segment("redo", 500).unwrap();
+        assert_eq!(
+            proc.dry_run_transcript(),
+            [
+                "type \"Hi\"",
+                "key Backspace",
+                "type \"i\"",
+                "key Backspace"
+            ]
+        );
+        assert_eq!(proc.undo_count(), 2);
+    }
+
+    #[test]
+    fn test_scratch_that_undoes_segment() {
+        let mut proc = dry_run();
+        proc.process_segment("hello world comma", 0).unwrap();
+        assert_eq!(proc.undo_count(), 2);
+        proc.process_segment("scratch that", 500).unwrap();
+        assert_eq!(proc.tail.text, "");
+        assert_eq!(proc.undo_count(), 0);
+        assert_eq!(
+            proc.dry_run_transcript()[2..4],
+            ["key Backspace", "key Shift+LeftArrow"]
+        );
+
+        // Only the last segment goes, even when said in the same breath
+        let mut proc = dry_run();
+        proc.process_segment("first period", 0).unwrap();
+        proc.process_segment("second thought delete that", 0)
+            .unwrap();
+        assert_eq!(proc.tail.text, "First.");
+        assert_eq!(proc.undo_count(), 2);
+    }
+
+    #[test]
+    fn test_undo_stops_at_unknown_deletion() {
+        let mut proc = dry_run();
+        proc.process_segment("hi", 0).unwrap();
+        proc.process_segment("go to start of line", 500).unwrap();
+        proc.process_segment("backspace", 500).unwrap();
+        assert_eq!(proc.undo_count(), 2);
+
+        // Undo can't restore the unknown character or safely remove "Hi"
+        proc.process_segment("undo", 500).unwrap();
+        assert_eq!(proc.undo_count(), 0);
+        assert_eq!(proc.redo_count(), 0);
+        assert_eq!(proc.dry_run_transcript().last().unwrap(), "key Backspace");
+    }
+}
@@ -0,0 +1,170 @@
+//! One-line typing status on stderr (`typing.status_line`)
+//!
+//! Shows whether dictation is being typed, the last command recognized and
+//! the undo depth, so it's clear why text isn't appearing. The line is
+//! rewritten in place; call `clear()` before printing anything else and
+//! `draw()` afterwards so log output doesn't run into it.
+
+use super::commands::TypingCommand;
+use std::io::{self, IsTerminal, Write};
+
+/// Longest command label shown before truncating
+const MAX_COMMAND_CHARS: usize = 32;
+
+/// What happens to speech right now
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TypingMode {
+    Typing,
+    /// Push-to-talk key held
+    PushToTalk,
+    Paused,
+}
+
+impl TypingMode {
+    /// The mode for the typing toggle and push-to-talk key states
+    pub fn from_state(typing_enabled: bool, ptt_active: bool) -> Self {
+        if !typing_enabled {
+            TypingMode::Paused
+        } else if ptt_active {
+            TypingMode::PushToTalk
+        } else {
+            TypingMode::Typing
+        }
+    }
+}
+
+pub struct StatusLine {
+    enabled: bool,
+    /// Whether the line is on screen and needs erasing before other output
+    visible: bool,
+    mode: TypingMode,
+    last_command: Option<String>,
+    undo_depth: usize,
+}
+
+impl StatusLine {
+    /// A status line, only drawn when enabled and stderr is a terminal
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled: enabled && io::stderr().is_terminal(),
+            visible: false,
+            mode: TypingMode::Typing,
+            last_command: None,
+            undo_depth: 0,
+        }
+    }
+
+    pub fn set_mode(&mut self, mode: TypingMode) {
+        self.mode = mode;
+    }
+
+    /// Record the outcome of a processed segment
+    pub fn update(&mut self, last_command: Option<&TypingCommand>, undo_depth: usize) {
+        if let Some(cmd) = last_command {
+            self.last_command = Some(command_label(cmd));
+        }
+        self.undo_depth = undo_depth;
+    }
+
+    /// The status text, e.g. `● typing │ last: enter │ undo: 3`
+    fn render(&self) -> String {
+        let mode = match self.mode {
+            TypingMode::Typing => "● typing",
+            TypingMode::PushToTalk => "● push-to-talk",
+            TypingMode::Paused => "○ paused",
+        };
+        let last = match &self.last_command {
+            Some(cmd) if cmd.chars().count() > MAX_COMMAND_CHARS => {
+                let cut: String = cmd.chars().take(MAX_COMMAND_CHARS - 1).collect();
+                format!("{}…", cut)
+            }
+            Some(cmd) => cmd.clone(),
+            None => "-".to_string(),
+        };
+        format!("{} │ last: {} │ undo: {}", mode, last, self.undo_depth)
+    }
+
+    /// Rewrite the line with the current status
+    pub fn draw(&mut self) {
+        if !self.enabled {
+            return;
+        }
+        eprint!("\r\x1b[2K{}", self.render());
+        let _ = io::stderr().flush();
+        self.visible = true;
+    }
+
+    /// Erase the line so other output starts on a clean line
+    pub fn clear(&mut self) {
+        if self.visible {
+            eprint!("\r\x1b[2K");
+            let _ = io::stderr().flush();
+            self.visible = false;
+        }
+    }
+}
+
+/// What a command is called on the status line, close to how it's spoken
+fn command_label(cmd: &TypingCommand) -> String {
+    let on_off = |on: bool| if on { "on" } else { "off" };
+    match cmd {
+        TypingCommand::Punctuation(c) => format!("\"{}\"", c),
+        TypingCommand::Enter => "enter".to_string(),
+        TypingCommand::Tab => "tab".to_string(),
+        TypingCommand::Space => "space".to_string(),
+        TypingCommand::Backspace => "backspace".to_string(),
+        TypingCommand::Delete => "delete".to_string(),
+        TypingCommand::DeleteWord => "delete word".to_string(),
+        TypingCommand::DeleteLine => "delete line".to_string(),
+        TypingCommand::Undo => "undo".to_string(),
+        TypingCommand::Redo => "redo".to_string(),
+        TypingCommand::Scratch => "scratch that".to_string(),
+        TypingCommand::SelectAll => "select all".to_string(),
+        TypingCommand::SelectWord => "select word".to_string(),
+        TypingCommand::SelectLine => "select line".to_string(),
+        TypingCommand::GoToEndOfLine => "end of line".to_string(),
+        TypingCommand::GoToStartOfLine => "start of line".to_string(),
+        TypingCommand::GoToEnd => "go to end".to_string(),
+        TypingCommand::GoToStart => "go to start".to_string(),
+        TypingCommand::MoveLeft(n) => format!("left {}", n),
+        TypingCommand::MoveRight(n) => format!("right {}", n),
+        TypingCommand::MoveUp(n) => format!("up {}", n),
+        TypingCommand::MoveDown(n) => format!("down {}", n),
+        TypingCommand::InsertText(text) => format!("\"{}\"", text),
+        TypingCommand::NumberMode(on) => format!("numbers {}", on_off(*on)),
+        TypingCommand::CodeMode(on) => format!("code mode {}", on_off(*on)),
+        TypingCommand::Stop => "stop".to_string(),
+        TypingCommand::Pause => "pause".to_string(),
+        TypingCommand::Resume => "resume".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_status() {
+        let mut status = StatusLine::new(false);
+        assert_eq!(status.render(), "● typing │ last: - │ undo: 0");
+
+        status.update(Some(&TypingCommand::Enter), 2);
+        // Segments without a command keep the last one shown
+        status.update(None, 3);
+        status.set_mode(TypingMode::from_state(false, true));
+        assert_eq!(status.render(), "○ paused │ last: enter │ undo: 3");
+
+        status.update(Some(&TypingCommand::CodeMode(true)), 3);
+        assert!(status.render().contains("last: code mode on │"));
+        status.update(Some(&TypingCommand::MoveLeft(3)), 3);
+        assert!(status.render().contains("last: left 3 │"));
+
+        status.update(Some(&TypingCommand::InsertText("x".repeat(40))), 3);
+        assert!(status.render().contains("last: \"xxx"));
+        assert!(status.render().contains("… │"));
+
+        // Disabled lines never draw, so there's nothing to clear
+        status.draw();
+        assert!(!status.visible);
+    }
+}
@@ -1,29 +1,146 @@
 use vad_rs::Vad;
 
-const VAD_THRESHOLD: f32 = 0.3;
-const VAD_THRESHOLD_END: f32 = 0.25;
+/// Default Silero probability needed to start speech
+pub const VAD_THRESHOLD: f32 = 0.3;
+/// Default Silero probability below which speech ends
+pub const VAD_THRESHOLD_END: f32 = 0.25;
 const ENERGY_THRESHOLD: f32 = 0.01;
 const ENERGY_THRESHOLD_END: f32 = 0.006;
 
+/// Default multiplier over the noise floor for the adaptive energy VAD
+pub const ADAPTIVE_ENERGY_FACTOR: f32 = 3.0;
+/// Noise floor history: ~2s of 30ms frames
+const NOISE_FLOOR_FRAMES: usize = 66;
+/// Lower bound on the noise floor so digital silence doesn't make everything speech
+const MIN_NOISE_FLOOR: f32 = 0.001;
+/// Speech frames (~10s) after which the floor learns from them anyway, so a
+/// step up in background noise can't keep speech going forever
+const MAX_FROZEN_FRAMES: usize = 333;
+
+/// Rolling RMS noise floor tracked over recent non-speech frames
+pub struct NoiseFloor {
+    history: Vec<f32>,
+    pos: usize,
+    factor: f32,
+    /// Consecutive frames classified while speaking
+    speech_run: usize,
+}
+
+impl NoiseFloor {
+    fn new(factor: f32) -> Self {
+        Self {
+            history: Vec::with_capacity(NOISE_FLOOR_FRAMES),
+            pos: 0,
+            factor,
+            speech_run: 0,
+        }
+    }
+
+    fn push(&mut self, rms: f32) {
+        if self.history.len() < NOISE_FLOOR_FRAMES {
+            self.history.push(rms);
+        } else {
+            self.history[self.pos] = rms;
+            self.pos = (self.pos + 1) % NOISE_FLOOR_FRAMES;
+        }
+    }
+
+    fn floor(&self) -> f32 {
+        let mean = self.history.iter().sum::<f32>() / self.history.len().max(1) as f32;
+        mean.max(MIN_NOISE_FLOOR)
+    }
+
+    /// Classify a frame, learning from it when it isn't speech or speech has
+    /// gone on for longer than `MAX_FROZEN_FRAMES`.
+    ///
+    /// The very first frame is assumed to be background noise to seed the floor.
+    fn is_speech(&mut self, rms: f32, currently_speaking: bool) -> bool {
+        if self.history.is_empty() {
+            self.push(rms);
+            return false;
+        }
+        let mut threshold = self.floor() * self.factor;
+        if currently_speaking {
+            // Same onset/offset hysteresis ratio as the fixed energy VAD
+            threshold *= ENERGY_THRESHOLD_END / ENERGY_THRESHOLD;
+        }
+        let speech = rms > threshold;
+        self.speech_run = if currently_speaking {
+            self.speech_run + 1
+        } else {
+            0
+        };
+        if (!speech && !currently_speaking) || self.speech_run > MAX_FROZEN_FRAMES {
+            self.push(rms);
+        }
+        speech
+    }
+}
+
+/// Details behind a single frame's speech decision
+#[derive(Debug, Clone, Copy)]
+pub struct VadDecision {
+    /// Frame RMS energy
+    pub rms: f32,
+    /// Silero speech probability (None for energy VADs)
+    pub prob: Option<f32>,
+    pub is_speech: bool,
+}
+
+/// Separate onset/offset probability thresholds so speech has to clear a
+/// higher bar to start than to keep going, avoiding chatter at boundaries
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hysteresis {
+    pub onset: f32,
+    pub offset: f32,
+}
+
+impl Hysteresis {
+    /// `offset` is capped at `onset`; a higher offset would end speech
+    /// before it could start
+    pub fn new(onset: f32, offset: f32) -> Self {
+        Self {
+            onset,
+            offset: offset.min(onset),
+        }
+    }
+
+    fn is_speech(&self, prob: f32, currently_speaking: bool) -> bool {
+        let threshold = if currently_speaking {
+            self.offset
+        } else {
+            self.onset
+        };
+        prob > threshold
+    }
+}
+
+impl Default for Hysteresis {
+    fn default() -> Self {
+        Self::new(VAD_THRESHOLD, VAD_THRESHOLD_END)
+    }
+}
+
 pub enum VadEngine {
-    Silero(Vad),
+    Silero(Vad, Hysteresis),
     Energy,
+    EnergyAdaptive(NoiseFloor),
 }
 
 impl VadEngine {
     #[cfg(all(feature = "supertonic", target_arch = "aarch64", target_os = "macos"))]
     pub fn silero_with_gpu(model_path: &str, sample_rate: usize) -> Result<Self, String> {
-        println!("VAD: Silero with CoreML...");
+        crate::logger::info!("VAD: Silero with CoreML...");
         match Vad::new(model_path, sample_rate) {
             Ok(vad) => {
-                println!("VAD: Silero enabled with CoreML");
-                Ok(VadEngine::Silero(vad))
+                crate::logger::info!("VAD: Silero enabled with CoreML");
+                Ok(VadEngine::Silero(vad, Hysteresis::default()))
             }
             Err(e) => {
-                eprintln!("VAD: CoreML failed, using CPU: {}", e);
+                crate::logger::warn!("VAD: CoreML failed, using CPU: {}", e);
                 // Try to load with CPU fallback
                 Vad::new(model_path, sample_rate)
-                    .map(VadEngine::Silero)
+                    .map(|vad| VadEngine::Silero(vad, Hysteresis::default()))
                     .map_err(|e| e.to_string())
             }
         }
@@ -31,41 +148,67 @@ impl VadEngine {
 
     pub fn silero(model_path: &str, sample_rate: usize) -> Result<Self, String> {
         let vad = Vad::new(model_path, sample_rate).map_err(|e| e.to_string())?;
-        Ok(VadEngine::Silero(vad))
+        Ok(VadEngine::Silero(vad, Hysteresis::default()))
+    }
+
+    /// Set Silero's onset/offset thresholds (no effect on energy VADs)
+    pub fn with_thresholds(mut self, thresholds: Hysteresis) -> Self {
+        if let VadEngine::Silero(_, current) = &mut self {
+            *current = thresholds;
+        }
+        self
     }
 
+    #[allow(dead_code)]
     pub fn energy() -> Self {
         VadEngine::Energy
     }
 
+    /// Energy VAD whose threshold is `factor` times a rolling noise floor
+    pub fn energy_adaptive(factor: f32) -> Self {
+        VadEngine::EnergyAdaptive(NoiseFloor::new(factor))
+    }
+
     pub fn is_speech(&mut self, frame: &[f32], currently_speaking: bool) -> bool {
-        let threshold = if currently_speaking {
-            match self {
-                VadEngine::Silero(_) => VAD_THRESHOLD_END,
-                VadEngine::Energy => ENERGY_THRESHOLD_END,
-            }
-        } else {
-            match self {
-                VadEngine::Silero(_) => VAD_THRESHOLD,
-                VadEngine::Energy => ENERGY_THRESHOLD,
-            }
-        };
+        self.classify(frame, currently_speaking).is_speech
+    }
+
+    /// Like `is_speech`, but also returns the measurements behind the decision
+    pub fn classify(&mut self, frame: &[f32], currently_speaking: bool) -> VadDecision {
+        let rms = frame_rms(frame);
 
         match self {
-            VadEngine::Silero(vad) => vad
-                .compute(frame)
-                .map(|r| r.prob > threshold)
-                .unwrap_or(false),
+            VadEngine::Silero(vad, thresholds) => {
+                let prob = vad.compute(frame).map(|r| r.prob).ok();
+                VadDecision {
+                    rms,
+                    prob,
+                    is_speech: prob.is_some_and(|p| thresholds.is_speech(p, currently_speaking)),
+                }
+            }
             VadEngine::Energy => {
-                let rms = (frame.iter().map(|&s| s * s).sum::<f32>() / frame.len() as f32).sqrt();
-                rms > threshold
+                let threshold = if currently_speaking {
+                    ENERGY_THRESHOLD_END
+                } else {
+                    ENERGY_THRESHOLD
+                };
+                VadDecision {
+                    rms,
+                    prob: None,
+                    is_speech: rms > threshold,
+                }
             }
+            VadEngine::EnergyAdaptive(floor) => VadDecision {
+                rms,
+                prob: None,
+                is_speech: floor.is_speech(rms, currently_speaking),
+            },
         }
     }
 
     #[allow(dead_code)]
     pub fn reset(&mut self) {
-        if let VadEngine::Silero(vad) = self {
+        if let VadEngine::Silero(vad, _) = self {
             vad.reset();
         }
     }
@@ -73,8 +216,111 @@ impl VadEngine {
     #[allow(dead_code)]
     pub fn name(&self) -> &'static str {
         match self {
-            VadEngine::Silero(_) => "Silero",
+            VadEngine::Silero(..) => "Silero",
             VadEngine::Energy => "Energy",
+            VadEngine::EnergyAdaptive(_) => "Energy (adaptive)",
+        }
+    }
+}
+
+fn frame_rms(frame: &[f32]) -> f32 {
+    (frame.iter().map(|&s| s * s).sum::<f32>() / frame.len() as f32).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic pseudo-random noise in [-amplitude, amplitude]
+    fn noise_frame(seed: &mut u32, amplitude: f32) -> Vec<f32> {
+        (0..480)
+            .map(|_| {
+                *seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                ((*seed >> 8) as f32 / (1u32 << 24) as f32 * 2.0 - 1.0) * amplitude
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_adaptive_energy_ignores_noise_detects_burst() {
+        let mut vad = VadEngine::energy_adaptive(ADAPTIVE_ENERGY_FACTOR);
+        let mut seed = 42;
+
+        // Room noise well above the fixed energy threshold
+        for _ in 0..100 {
+            let frame = noise_frame(&mut seed, 0.05);
+            assert!(!vad.is_speech(&frame, false));
+        }
+
+        // A much louder burst on top of the noise
+        for _ in 0..5 {
+            let burst: Vec<f32> = noise_frame(&mut seed, 0.05)
+                .iter()
+                .enumerate()
+                .map(|(i, n)| n + 0.3 * (i as f32 * 0.2).sin())
+                .collect();
+            assert!(vad.is_speech(&burst, false));
         }
     }
+
+    #[test]
+    fn test_adaptive_energy_follows_noise_step() {
+        let mut vad = VadEngine::energy_adaptive(ADAPTIVE_ENERGY_FACTOR);
+        let mut seed = 7;
+        for _ in 0..100 {
+            assert!(!vad.is_speech(&noise_frame(&mut seed, 0.01), false));
+        }
+
+        // The room gets louder for good: it starts as speech, but the floor
+        // catches up once the run outlasts the freeze
+        let mut speaking = false;
+        let mut speech_frames = 0;
+        for _ in 0..MAX_FROZEN_FRAMES * 2 {
+            speaking = vad.is_speech(&noise_frame(&mut seed, 0.1), speaking);
+            if !speaking {
+                break;
+            }
+            speech_frames += 1;
+        }
+        assert!(!speaking, "still speaking after {} frames", speech_frames);
+        assert!(speech_frames > MAX_FROZEN_FRAMES);
+
+        // The new level is background from now on
+        for _ in 0..100 {
+            assert!(!vad.is_speech(&noise_frame(&mut seed, 0.1), false));
+        }
+    }
+
+    #[test]
+    fn test_hysteresis_reduces_chatter() {
+        // Probabilities wobbling around a single 0.3 threshold
+        let probs = [0.1, 0.32, 0.28, 0.31, 0.27, 0.33, 0.29, 0.2, 0.28, 0.1];
+        let run = |h: Hysteresis| {
+            let mut speaking = false;
+            probs
+                .iter()
+                .map(|&p| {
+                    speaking = h.is_speech(p, speaking);
+                    speaking
+                })
+                .collect::<Vec<_>>()
+        };
+        let toggles = |states: &[bool]| states.windows(2).filter(|w| w[0] != w[1]).count();
+
+        let single = run(Hysteresis::new(0.3, 0.3));
+        assert_eq!(toggles(&single), 6);
+
+        // One onset, one offset once the probability really drops
+        let states = run(Hysteresis::new(0.3, 0.25));
+        assert_eq!(
+            states,
+            [
+                false, true, true, true, true, true, true, false, false, false
+            ]
+        );
+        assert_eq!(toggles(&states), 2);
+
+        // Offset above onset is capped rather than ending speech immediately
+        assert_eq!(Hysteresis::new(0.3, 0.5).offset, 0.3);
+    }
 }
@@ -0,0 +1,246 @@
+//! VAD accuracy check (`silly vad-check`)
+//!
+//! Runs a VAD engine over a file with hand-labeled speech regions and reports
+//! frame-level precision/recall plus how long each region took to be detected,
+//! so threshold tweaks can be measured instead of judged by ear. Labels are a
+//! CSV of `start_secs,end_secs` lines; `#` comments and a header are skipped.
+
+use crate::audiofile;
+use crate::config::{self, Config};
+use crate::model_manager;
+use crate::vad::{self, VadEngine};
+use std::path::Path;
+
+const SAMPLE_RATE: usize = 16000;
+/// 30ms frames, same as the live pipeline
+const FRAME_SAMPLES: usize = 480;
+const FRAME_SECS: f32 = FRAME_SAMPLES as f32 / SAMPLE_RATE as f32;
+
+/// A labeled speech region in seconds
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Region {
+    start: f32,
+    end: f32,
+}
+
+/// Frame counts of VAD decisions against the labels
+#[derive(Debug, Default, PartialEq)]
+struct Confusion {
+    true_pos: usize,
+    false_pos: usize,
+    false_neg: usize,
+    true_neg: usize,
+}
+
+impl Confusion {
+    fn precision(&self) -> f32 {
+        ratio(self.true_pos, self.true_pos + self.false_pos)
+    }
+
+    fn recall(&self) -> f32 {
+        ratio(self.true_pos, self.true_pos + self.false_neg)
+    }
+}
+
+fn ratio(n: usize, d: usize) -> f32 {
+    if d == 0 { 0.0 } else { n as f32 / d as f32 }
+}
+
+/// Parse `start_secs,end_secs` lines
+fn parse_labels(text: &str) -> Result<Vec<Region>, String> {
+    let mut regions = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut fields = line.split(',').map(|f| f.trim().parse::<f32>());
+        match (fields.next(), fields.next(), fields.next()) {
+            (Some(Ok(start)), Some(Ok(end)), None) if end > start => {
+                regions.push(Region { start, end })
+            }
+            // Header row
+            _ if i == 0 && !line.starts_with(|c: char| c.is_ascii_digit()) => {}
+            _ => return Err(format!("line {}: expected start_secs,end_secs", i + 1)),
+        }
+    }
+    regions.sort_by(|a, b| a.start.total_cmp(&b.start));
+    Ok(regions)
+}
+
+/// Ground truth per frame: speech if the frame's midpoint is inside a region
+fn label_frames(regions: &[Region], frames: usize) -> Vec<bool> {
+    (0..frames)
+        .map(|i| {
+            let mid = (i as f32 + 0.5) * FRAME_SECS;
+            regions.iter().any(|r| mid >= r.start && mid < r.end)
+        })
+        .collect()
+}
+
+fn confusion(truth: &[bool], detected: &[bool]) -> Confusion {
+    let mut c = Confusion::default();
+    for (&t, &d) in truth.iter().zip(detected) {
+        match (t, d) {
+            (true, true) => c.true_pos += 1,
+            (false, true) => c.false_pos += 1,
+            (true, false) => c.false_neg += 1,
+            (false, false) => c.true_neg += 1,
+        }
+    }
+    c
+}
+
+/// Seconds from each region's start to the first frame detected inside it
+/// (None when the region was missed entirely)
+fn onset_latencies(regions: &[Region], detected: &[bool]) -> Vec<Option<f32>> {
+    regions
+        .iter()
+        .map(|r| {
+            let first = (r.start / FRAME_SECS) as usize;
+            let last = ((r.end / FRAME_SECS).ceil() as usize).min(detected.len());
+            (first..last)
+                .find(|&i| detected[i])
+                .map(|i| (i as f32 * FRAME_SECS - r.start).max(0.0))
+        })
+        .collect()
+}
+
+fn load_engine(name: &str) -> Result<VadEngine, Box<dyn std::error::Error + Send + Sync>> {
+    match name {
+        "silero" => {
+            let config = Config::load().map_err(|p| config::problem_report(&p))?;
+            let path = model_manager::vad_model_path(&config);
+            let thresholds = config.interaction.vad_thresholds();
+            println!(
+                "Model: {} (onset {}, offset {})",
+                path.display(),
+                thresholds.onset,
+                thresholds.offset
+            );
+            Ok(
+                VadEngine::silero(&path.to_string_lossy(), SAMPLE_RATE)?
+                    .with_thresholds(thresholds),
+            )
+        }
+        "energy" => Ok(VadEngine::energy()),
+        "adaptive" => Ok(VadEngine::energy_adaptive(vad::ADAPTIVE_ENERGY_FACTOR)),
+        other => Err(format!("Unknown VAD engine: {}", other).into()),
+    }
+}
+
+pub fn run_vad_check(
+    wav: &Path,
+    labels: &Path,
+    engine: &str,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let regions = parse_labels(&std::fs::read_to_string(labels)?)
+        .map_err(|e| format!("{}: {}", labels.display(), e))?;
+    let samples = audiofile::read_audio_16k(wav)?;
+    let mut vad = load_engine(engine)?;
+
+    // Feed decisions back as `currently_speaking` so hysteresis matches live use
+    let mut speaking = false;
+    let detected: Vec<bool> = samples
+        .chunks(FRAME_SAMPLES)
+        .map(|chunk| {
+            let mut frame = chunk.to_vec();
+            frame.resize(FRAME_SAMPLES, 0.0);
+            speaking = vad.is_speech(&frame, speaking);
+            speaking
+        })
+        .collect();
+    let truth = label_frames(&regions, detected.len());
+    let c = confusion(&truth, &detected);
+
+    println!(
+        "VAD: {} | {:.1}s audio, {} frames, {} labeled regions",
+        vad.name(),
+        samples.len() as f32 / SAMPLE_RATE as f32,
+        detected.len(),
+        regions.len()
+    );
+    println!();
+    println!("                  detected speech   detected silence");
+    println!("  labeled speech  {:>15}   {:>16}", c.true_pos, c.false_neg);
+    println!("  labeled silence {:>15}   {:>16}", c.false_pos, c.true_neg);
+    println!();
+    println!(
+        "Precision: {:.1}% | Recall: {:.1}%",
+        c.precision() * 100.0,
+        c.recall() * 100.0
+    );
+
+    let latencies = onset_latencies(&regions, &detected);
+    let hits: Vec<f32> = latencies.iter().flatten().copied().collect();
+    if hits.is_empty() {
+        println!("Onset latency: no regions detected");
+    } else {
+        let mean = hits.iter().sum::<f32>() / hits.len() as f32;
+        let max = hits.iter().copied().fold(0.0, f32::max);
+        println!(
+            "Onset latency: mean {}ms | max {}ms | missed {}/{} regions",
+            (mean * 1000.0).round(),
+            (max * 1000.0).round(),
+            latencies.len() - hits.len(),
+            latencies.len()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_labels() {
+        let labels = parse_labels("start,end\n2.0, 3.5\n# note\n\n0.5,1.0\n").unwrap();
+        assert_eq!(
+            labels,
+            vec![
+                Region {
+                    start: 0.5,
+                    end: 1.0
+                },
+                Region {
+                    start: 2.0,
+                    end: 3.5
+                },
+            ]
+        );
+        assert!(parse_labels("1.0,0.5").is_err());
+        assert!(parse_labels("0.5,1.0\nfoo,bar").is_err());
+    }
+
+    #[test]
+    fn test_confusion_and_onset() {
+        // 0.3s..0.6s is frames 10..20
+        let regions = [Region {
+            start: 0.3,
+            end: 0.6,
+        }];
+        let truth = label_frames(&regions, 30);
+        assert_eq!(truth.iter().filter(|&&t| t).count(), 10);
+        assert!(truth[10] && !truth[9] && !truth[20]);
+
+        // Detected two frames late and runs three frames over
+        let detected: Vec<bool> = (0..30).map(|i| (12..23).contains(&i)).collect();
+        let c = confusion(&truth, &detected);
+        assert_eq!(
+            c,
+            Confusion {
+                true_pos: 8,
+                false_pos: 3,
+                false_neg: 2,
+                true_neg: 17,
+            }
+        );
+        assert!((c.precision() - 8.0 / 11.0).abs() < 1e-6);
+        assert!((c.recall() - 0.8).abs() < 1e-6);
+
+        let latency = onset_latencies(&regions, &detected)[0].unwrap();
+        assert!((latency - 0.06).abs() < 1e-4);
+        assert_eq!(onset_latencies(&regions, &[false; 30]), vec![None]);
+    }
+}
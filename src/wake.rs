@@ -1,27 +1,134 @@
-use crate::fuzzy::fuzzy_match;
+use crate::fuzzy::{fuzzy_match, levenshtein};
+use crate::kws::KeywordSpotter;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
 
-/// Wake word detection - checks if transcribed text starts with wake phrase
-pub struct WakeWord {
-    #[allow(dead_code)]
+/// Fraction of the phrase's letters that may be wrong at sensitivity 1.0
+const MAX_ERROR_RATIO: f32 = 0.5;
+
+/// A single configured wake phrase
+struct WakePhrase {
     phrase: String,
     words: Vec<String>,
+    /// Edit-distance budget for the whole phrase; `None` uses per-word fuzzy matching
+    max_distance: Option<usize>,
+}
+
+/// A successful wake word detection
+#[derive(Debug, Clone, PartialEq)]
+pub struct WakeMatch {
+    /// The configured phrase that matched
+    pub phrase: String,
+    /// Text following the wake phrase
+    pub rest: String,
+}
+
+/// Wake word detection - checks if transcribed text starts with any wake phrase
+pub struct WakeWord {
+    phrases: Vec<WakePhrase>,
+    /// Screens audio before it is transcribed (`wake.kws_templates`)
+    spotter: Option<KeywordSpotter>,
 }
 
 impl WakeWord {
-    pub fn new(phrase: &str) -> Self {
+    pub fn new<S: AsRef<str>>(phrases: &[S]) -> Self {
         Self {
-            phrase: phrase.to_string(),
-            words: phrase
-                .to_lowercase()
-                .split_whitespace()
-                .map(String::from)
+            phrases: phrases
+                .iter()
+                .map(|p| WakePhrase {
+                    phrase: p.as_ref().to_string(),
+                    words: p
+                        .as_ref()
+                        .to_lowercase()
+                        .split_whitespace()
+                        .map(String::from)
+                        .collect(),
+                    max_distance: None,
+                })
+                .filter(|p| !p.words.is_empty())
                 .collect(),
+            spotter: None,
         }
     }
 
-    /// Check if text starts with wake word (fuzzy), return remaining text if matched
-    pub fn detect(&self, text: &str) -> Option<String> {
+    /// Screen audio with a keyword spotter so only likely wake phrases
+    /// need transcribing
+    pub fn with_spotter(mut self, spotter: KeywordSpotter) -> Self {
+        self.spotter = Some(spotter);
+        self
+    }
+
+    pub fn has_spotter(&self) -> bool {
+        self.spotter.is_some()
+    }
+
+    /// Match each phrase as a whole, accepting up to `max_distance` letter edits.
+    ///
+    /// Spaces and punctuation are ignored, so mis-split words ("heysilly") and
+    /// small mis-transcriptions ("hey silary") still match.
+    #[allow(dead_code)]
+    pub fn with_sensitivity<S: AsRef<str>>(phrases: &[S], max_distance: usize) -> Self {
+        let mut wake = Self::new(phrases);
+        for p in &mut wake.phrases {
+            p.max_distance = Some(max_distance);
+        }
+        wake
+    }
+
+    /// Like `with_sensitivity`, but each phrase gets a budget scaled by its own length
+    pub fn from_sensitivity<S: AsRef<str>>(phrases: &[S], sensitivity: f32) -> Self {
+        let mut wake = Self::new(phrases);
+        for p in &mut wake.phrases {
+            p.max_distance = Some(sensitivity_to_distance(&p.phrase, sensitivity));
+        }
+        wake
+    }
+
+    /// Check if text starts with a wake phrase (fuzzy), returning which phrase
+    /// matched and the remaining text
+    pub fn detect(&self, text: &str) -> Option<WakeMatch> {
         let text_words: Vec<&str> = text.split_whitespace().collect();
+
+        // Prefer the closest match when several phrases are similar
+        let (phrase, matched_words, _) = self
+            .phrases
+            .iter()
+            .filter_map(|p| {
+                let (taken, dist) = match p.max_distance {
+                    Some(max_distance) => p.match_joined(&text_words, max_distance)?,
+                    None => (p.match_per_word(&text_words)?, 0),
+                };
+                Some((p, taken, dist))
+            })
+            .min_by_key(|(_, _, dist)| *dist)?;
+
+        // Return the rest of the text after wake words
+        let rest: String = text_words[matched_words..].join(" ");
+        let rest = rest.trim_start_matches([',', '!', '.', ' ']).to_string();
+        Some(WakeMatch {
+            phrase: phrase.phrase.clone(),
+            rest,
+        })
+    }
+
+    /// Whether an utterance may start with a wake phrase, judged from the
+    /// audio alone. Always true without a spotter, leaving it to `detect`
+    /// on the transcript.
+    pub fn detect_from_audio(&self, audio: &[f32]) -> bool {
+        self.spotter
+            .as_ref()
+            .is_none_or(|spotter| spotter.detect(audio))
+    }
+
+    #[allow(dead_code)]
+    pub fn phrases(&self) -> Vec<&str> {
+        self.phrases.iter().map(|p| p.phrase.as_str()).collect()
+    }
+}
+
+impl WakePhrase {
+    /// Fuzzy-match each wake word against the corresponding spoken word
+    fn match_per_word(&self, text_words: &[&str]) -> Option<usize> {
         if text_words.len() < self.words.len() {
             return None;
         }
@@ -34,15 +141,226 @@ impl WakeWord {
                 return None;
             }
         }
+        Some(self.words.len())
+    }
 
-        // Return the rest of the text after wake words
-        let rest: String = text_words[self.words.len()..].join(" ");
-        let rest = rest.trim_start_matches([',', '!', '.', ' ']).to_string();
-        Some(rest)
+    /// Compare the joined letters of the phrase against the first n-1..=n+1
+    /// spoken words, returning (words consumed, distance) for the best match
+    fn match_joined(&self, text_words: &[&str], max_distance: usize) -> Option<(usize, usize)> {
+        let expected: String = self
+            .words
+            .iter()
+            .flat_map(|w| w.chars().filter(|c| c.is_alphabetic()))
+            .collect();
+        let n = self.words.len();
+        let mut best: Option<(usize, usize)> = None;
+
+        for take in n.saturating_sub(1).max(1)..=(n + 1).min(text_words.len()) {
+            let spoken: String = text_words[..take]
+                .iter()
+                .flat_map(|w| w.chars().filter(|c| c.is_alphabetic()))
+                .flat_map(char::to_lowercase)
+                .collect();
+            if spoken.is_empty() {
+                continue;
+            }
+            let dist = levenshtein(&expected, &spoken);
+            if dist <= max_distance && best.is_none_or(|(_, d)| dist < d) {
+                best = Some((take, dist));
+            }
+        }
+        best
     }
+}
 
-    #[allow(dead_code)]
-    pub fn phrase(&self) -> &str {
-        &self.phrase
+/// Recent speech the wake gate turned away, so a request started just before
+/// the wake phrase isn't lost (`wake.preroll_secs`)
+///
+/// Transcripts are kept with the time they arrived and how long they were
+/// spoken for. When the wake phrase shows up, `take` hands back those that
+/// ended close enough to it, to be joined to the request.
+pub struct Preroll {
+    utterances: VecDeque<(Instant, String, Duration)>,
+    window: Duration,
+}
+
+impl Preroll {
+    pub fn new(secs: f32) -> Self {
+        Self {
+            utterances: VecDeque::new(),
+            window: Duration::from_secs_f32(secs.max(0.0)),
+        }
+    }
+
+    /// Remember the transcript of an utterance `len` long the gate turned away
+    pub fn push(&mut self, text: String, len: Duration, now: Instant) {
+        if self.window.is_zero() {
+            return;
+        }
+        self.utterances.push_back((now, text, len));
+        // Drop utterances the newer ones have already filled the window without
+        let mut total: Duration = self.utterances.iter().map(|(_, _, len)| *len).sum();
+        while let Some((_, _, oldest)) = self.utterances.front() {
+            if total - *oldest < self.window {
+                break;
+            }
+            total -= *oldest;
+            self.utterances.pop_front();
+        }
+    }
+
+    /// Transcripts of the utterances that arrived at most `preroll_secs`
+    /// before a wake utterance lasting `wake_len` (handed on at `now`), oldest
+    /// first. Empties the buffer.
+    pub fn take(&mut self, now: Instant, wake_len: Duration) -> String {
+        let max_age = self.window + wake_len;
+        self.utterances
+            .drain(..)
+            .filter(|(at, _, _)| now.duration_since(*at) <= max_age)
+            .map(|(_, text, _)| text)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// The request with the pre-wake transcript in front, as a wake utterance:
+/// `<phrase> <pre> <rest>`
+pub fn join_preroll(m: &WakeMatch, pre: &str) -> String {
+    [m.phrase.as_str(), pre.trim(), m.rest.as_str()]
+        .iter()
+        .filter(|s| !s.is_empty())
+        .copied()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Map a 0.0-1.0 sensitivity to an edit-distance budget scaled by phrase length
+pub fn sensitivity_to_distance(phrase: &str, sensitivity: f32) -> usize {
+    let letters = phrase.chars().filter(|c| c.is_alphabetic()).count();
+    (sensitivity.clamp(0.0, 1.0) * letters as f32 * MAX_ERROR_RATIO).round() as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wake(sensitivity: f32) -> WakeWord {
+        WakeWord::from_sensitivity(&["Hey Silly"], sensitivity)
+    }
+
+    fn rest(w: &WakeWord, text: &str) -> Option<String> {
+        w.detect(text).map(|m| m.rest)
+    }
+
+    #[test]
+    fn test_sensitivity_to_distance() {
+        assert_eq!(sensitivity_to_distance("Hey Silly", 0.0), 0);
+        assert_eq!(sensitivity_to_distance("Hey Silly", 0.5), 2);
+        assert_eq!(sensitivity_to_distance("Hey Silly", 1.0), 4);
+        assert_eq!(sensitivity_to_distance("Hey Silly", 7.0), 4);
+    }
+
+    #[test]
+    fn test_common_mistranscriptions() {
+        let w = wake(0.5);
+        assert_eq!(
+            rest(&w, "hey silary what time is it"),
+            Some("what time is it".into())
+        );
+        assert_eq!(rest(&w, "Hey, Sily."), Some(String::new()));
+        assert_eq!(
+            rest(&w, "hay silly tell me a joke"),
+            Some("tell me a joke".into())
+        );
+        assert_eq!(rest(&w, "heysilly stop"), Some("stop".into()));
+        assert_eq!(rest(&w, "hey sil ly hello"), Some("hello".into()));
+    }
+
+    #[test]
+    fn test_unrelated_phrases() {
+        let w = wake(0.5);
+        assert_eq!(rest(&w, "they said hello"), None);
+        assert_eq!(rest(&w, "what time is it"), None);
+        assert_eq!(rest(&w, "hey siri"), None);
+        assert_eq!(rest(&w, ""), None);
+    }
+
+    #[test]
+    fn test_zero_sensitivity_is_exact() {
+        let w = wake(0.0);
+        assert_eq!(rest(&w, "hey silly hi"), Some("hi".into()));
+        assert_eq!(rest(&w, "hey sily hi"), None);
+    }
+
+    #[test]
+    fn test_multiple_phrases() {
+        let w = WakeWord::from_sensitivity(&["hey silly", "okay silly"], 0.5);
+        let m = w.detect("okay silly what's up").unwrap();
+        assert_eq!(m.phrase, "okay silly");
+        assert_eq!(m.rest, "what's up");
+        let m = w.detect("hey silly hello").unwrap();
+        assert_eq!(m.phrase, "hey silly");
+        assert!(w.detect("hello silly").is_none());
+    }
+
+    #[test]
+    fn test_per_word_matching() {
+        let w = WakeWord::new(&["Hey Silly"]);
+        assert_eq!(rest(&w, "hey sily, hi"), Some("hi".into()));
+        assert_eq!(w.detect("hey"), None);
+    }
+
+    #[test]
+    fn test_detect_from_audio() {
+        let audio = vec![0.1; 16000];
+        // Without a spotter everything goes on to the transcript check
+        let w = WakeWord::new(&["Hey Silly"]);
+        assert!(!w.has_spotter());
+        assert!(w.detect_from_audio(&audio));
+
+        let w = w.with_spotter(KeywordSpotter::new(&[], 0.25));
+        assert!(w.has_spotter());
+        assert!(!w.detect_from_audio(&audio));
+    }
+
+    #[test]
+    fn test_preroll_keeps_pre_wake_audio() {
+        let start = Instant::now();
+        let at = |ms| start + Duration::from_millis(ms);
+        let ms = Duration::from_millis;
+
+        // One second: utterances until the newer ones fill it, oldest first
+        let mut preroll = Preroll::new(1.0);
+        preroll.push("too early".into(), ms(400), at(0));
+        preroll.push("what's".into(), ms(400), at(100));
+        preroll.push("the weather".into(), ms(800), at(500));
+        assert_eq!(preroll.take(at(900), Duration::ZERO), "what's the weather");
+        assert!(preroll.take(at(900), Duration::ZERO).is_empty());
+
+        // Speech well before the wake utterance started isn't joined
+        preroll.push("earlier".into(), ms(400), at(0));
+        assert!(preroll.take(at(5000), Duration::from_secs(2)).is_empty());
+        preroll.push("earlier".into(), ms(400), at(0));
+        assert_eq!(preroll.take(at(2500), Duration::from_secs(2)), "earlier");
+
+        let mut off = Preroll::new(0.0);
+        off.push("hello".into(), ms(400), at(0));
+        assert!(off.take(at(0), Duration::ZERO).is_empty());
+    }
+
+    #[test]
+    fn test_join_preroll() {
+        let w = wake(0.5);
+        let m = w.detect("hey silly, in Paris").unwrap();
+        let joined = join_preroll(&m, "What's the weather ");
+        assert_eq!(joined, "Hey Silly What's the weather in Paris");
+        // Reads downstream like the request was spoken after the wake phrase
+        assert_eq!(
+            rest(&w, &joined),
+            Some("What's the weather in Paris".into())
+        );
+
+        let m = w.detect("hey silly").unwrap();
+        assert_eq!(join_preroll(&m, "remind me"), "Hey Silly remind me");
     }
 }
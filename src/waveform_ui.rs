@@ -0,0 +1,393 @@
+//! Scrolling waveform UI - an alternative to the orb
+//!
+//! Mic (or TTS) level history is drawn as vertical bars across the terminal,
+//! newest on the right. Each bar keeps the color of the assistant state it was
+//! sampled in, so listening, thinking and speaking read as colored stretches.
+
+use crate::graphical_ui::WAKE_FLASH;
+use crate::line_editor::{LineEdit, LineEditor};
+use crate::render::{UiEvent, UiMode, UiRenderer};
+use crate::state::AppMode;
+use crate::status_bar::{StatusBarState, StatusDisplayStyle, StatusRenderer};
+use crossterm::event::{self, Event, KeyCode, KeyModifiers};
+use crossterm::terminal::{self, ClearType};
+use crossterm::{cursor, execute};
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::io::{self, Write, stdout};
+use std::time::{Duration, Instant};
+
+/// Time between history samples, matching the audio thread's level updates
+const SAMPLE_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Scale from raw RMS to bar height (speech RMS is roughly 0.01-0.1)
+const MIC_GAIN: f32 = 20.0;
+/// Scale from TTS output level to bar height
+const TTS_GAIN: f32 = 10.0;
+
+/// Bar glyphs by eighths of a cell
+const BAR_CHARS: [char; 9] = [' ', '▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Assistant state, used to color the bars
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum WaveState {
+    Idle,
+    Listening,
+    Thinking,
+    Speaking,
+    Error,
+}
+
+impl WaveState {
+    fn color(self) -> (u8, u8, u8) {
+        match self {
+            WaveState::Idle => (90, 90, 110),
+            WaveState::Listening => (80, 220, 120),
+            WaveState::Thinking => (230, 190, 60),
+            WaveState::Speaking => (90, 160, 255),
+            WaveState::Error => (240, 80, 80),
+        }
+    }
+}
+
+/// Glyph for one cell of a bar, `row` counted up from the bottom
+///
+/// The bottom row always shows at least a sliver so the timeline stays visible.
+fn bar_char(level: f32, row: usize, height: usize) -> char {
+    let eighths = level.clamp(0.0, 1.0) * height as f32 * 8.0;
+    let filled = (eighths - row as f32 * 8.0).clamp(0.0, 8.0).round() as usize;
+    if row == 0 {
+        BAR_CHARS[filled.max(1)]
+    } else {
+        BAR_CHARS[filled]
+    }
+}
+
+pub struct WaveformUi {
+    state: WaveState,
+    /// Bar heights (0.0-1.0) and the state they were sampled in, oldest first
+    history: VecDeque<(f32, WaveState)>,
+    last_sample: Instant,
+    preview: String,
+    input: LineEditor,
+    input_activity: bool,
+    keypress_activity: bool,
+    status_bar: StatusBarState,
+    /// Terminal output, reused across frames
+    out: String,
+    /// When the Listening flash for a detected wake word ends
+    wake_flash_until: Option<Instant>,
+}
+
+impl WaveformUi {
+    pub fn new() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(
+            stdout(),
+            terminal::EnterAlternateScreen,
+            cursor::Hide,
+            terminal::Clear(ClearType::All)
+        )?;
+
+        let mut status_bar = StatusBarState::new();
+        status_bar.display_style = StatusDisplayStyle::Text;
+
+        Ok(Self {
+            state: WaveState::Idle,
+            history: VecDeque::new(),
+            last_sample: Instant::now(),
+            preview: String::new(),
+            input: LineEditor::default(),
+            input_activity: false,
+            keypress_activity: false,
+            status_bar,
+            out: String::new(),
+            wake_flash_until: None,
+        })
+    }
+
+    /// Current bar height for the active state
+    fn current_level(&self) -> f32 {
+        let level = match self.state {
+            WaveState::Listening => self.status_bar.audio_level * MIC_GAIN,
+            WaveState::Speaking => self.status_bar.tts_level * TTS_GAIN,
+            _ => 0.0,
+        };
+        level.clamp(0.0, 1.0)
+    }
+
+    fn set_state(&mut self, state: WaveState, status: &str) {
+        self.state = state;
+        self.status_bar.status = status.to_string();
+    }
+}
+
+impl UiRenderer for WaveformUi {
+    fn handle_ui_event(&mut self, event: UiEvent) -> io::Result<()> {
+        match event {
+            UiEvent::Preview(text) => {
+                self.preview = text;
+                self.set_state(WaveState::Listening, "Listening");
+            }
+            UiEvent::Final(_text) => {
+                self.preview.clear();
+                self.status_bar.status = "Processing".to_string();
+            }
+            UiEvent::Thinking => self.set_state(WaveState::Thinking, "Thinking"),
+            UiEvent::Speaking => self.set_state(WaveState::Speaking, "Speaking"),
+            UiEvent::SpeakingDone => self.set_state(WaveState::Idle, "Ready"),
+            UiEvent::ResponseChunk(_) | UiEvent::ResponseEnd | UiEvent::Tick => {}
+            UiEvent::Idle => {
+                self.set_state(WaveState::Idle, "Idle");
+                self.preview.clear();
+            }
+            UiEvent::WakeDetected => {
+                self.set_state(WaveState::Listening, "Listening");
+                self.wake_flash_until = Some(Instant::now() + WAKE_FLASH);
+            }
+            UiEvent::ContextWords(count) => {
+                self.status_bar.context_words = count;
+            }
+            UiEvent::SwitchUiMode(_) => {
+                // Handled in the main loop
+            }
+            UiEvent::Error(msg) => {
+                self.set_state(WaveState::Error, &format!("Error: {}", msg));
+            }
+        }
+        Ok(())
+    }
+
+    fn draw(&mut self) -> io::Result<()> {
+        let (tw, th) = terminal::size()?;
+        let w = tw as usize;
+        let h = (th as usize).saturating_sub(3); // Reserve space for status bars
+
+        let now = Instant::now();
+
+        // End the wake flash unless the user has started speaking
+        if self.wake_flash_until.is_some_and(|until| now >= until) {
+            self.wake_flash_until = None;
+            if self.state == WaveState::Listening && self.preview.is_empty() {
+                self.set_state(WaveState::Idle, "Idle");
+            }
+        }
+
+        // Sample at a fixed rate so scroll speed doesn't depend on redraw rate
+        if now.duration_since(self.last_sample) >= SAMPLE_INTERVAL {
+            self.history.push_back((self.current_level(), self.state));
+            self.last_sample = now;
+        }
+        while self.history.len() > w {
+            self.history.pop_front();
+        }
+
+        let out = &mut self.out;
+        out.clear();
+        out.push_str("\x1b[H"); // Home cursor
+
+        // History is right-aligned, newest bar in the last column
+        let pad = w - self.history.len();
+        let mut last_color = None;
+        for screen_row in 0..h {
+            let row = h - 1 - screen_row;
+            out.extend(std::iter::repeat_n(' ', pad));
+            for &(level, state) in &self.history {
+                let ch = bar_char(level, row, h);
+                if ch != ' ' && last_color != Some(state) {
+                    let (r, g, b) = state.color();
+                    let _ = write!(out, "\x1b[38;2;{};{};{}m", r, g, b);
+                    last_color = Some(state);
+                }
+                out.push(ch);
+            }
+            out.push_str("\r\n");
+        }
+
+        // Reset color and draw status bar
+        out.push_str("\x1b[0m");
+        let _ = write!(
+            out,
+            "{} | Tab: Switch to Text UI\x1b[K\r\n",
+            self.status_bar
+                .render_status(self.status_bar.display_style, None)
+        );
+
+        let timer_bar = self.status_bar.auto_submit_bar();
+        if !timer_bar.is_empty() {
+            out.push_str(&timer_bar);
+        }
+        if !self.preview.is_empty() {
+            let _ = write!(out, "\x1b[90m{}\x1b[0m ", self.preview);
+        }
+        let _ = write!(out, "\x1b[32m>\x1b[0m {}\x1b[K", self.input.text());
+
+        let mut stdout = stdout();
+        stdout.write_all(out.as_bytes())?;
+        stdout.flush()
+    }
+
+    fn poll_input(&mut self) -> io::Result<Option<String>> {
+        let mut pending_submit = None;
+
+        while event::poll(Duration::from_millis(0))? {
+            if let Event::Key(key) = event::read()? {
+                self.keypress_activity = true;
+
+                if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+                    return Ok(Some("\x03".to_string()));
+                }
+                if key.code == KeyCode::Char('m') && key.modifiers.contains(KeyModifiers::CONTROL) {
+                    return Ok(Some("/mute".to_string()));
+                }
+
+                // Tab to switch to text UI mode
+                if key.code == KeyCode::Tab {
+                    return Ok(Some("/ui text".to_string()));
+                }
+
+                match self
+                    .input
+                    .handle_key(&key, || event::poll(Duration::from_millis(0)))?
+                {
+                    LineEdit::Submit(text) => pending_submit = text,
+                    LineEdit::Newline => {
+                        self.input_activity = true;
+                        pending_submit = None;
+                    }
+                    LineEdit::Changed => self.input_activity = true,
+                    LineEdit::Unchanged => {}
+                }
+            }
+        }
+
+        Ok(pending_submit)
+    }
+
+    fn restore(&self) -> io::Result<()> {
+        execute!(stdout(), cursor::Show, terminal::LeaveAlternateScreen)
+    }
+
+    fn cleanup(&self) -> io::Result<()> {
+        execute!(stdout(), cursor::Show, terminal::LeaveAlternateScreen)?;
+        terminal::disable_raw_mode()
+    }
+
+    fn show_message(&mut self, text: &str) {
+        if let Some(line) = text.lines().last() {
+            self.status_bar.status = line.to_string();
+        }
+    }
+
+    fn set_auto_submit_progress(&mut self, progress: Option<f32>) {
+        self.status_bar.auto_submit_progress = progress;
+    }
+
+    fn set_mic_muted(&mut self, muted: bool) {
+        self.status_bar.mic_muted = muted;
+    }
+
+    fn set_tts_enabled(&mut self, enabled: bool) {
+        self.status_bar.tts_enabled = enabled;
+    }
+
+    fn set_wake_enabled(&mut self, enabled: bool) {
+        self.status_bar.wake_enabled = enabled;
+    }
+
+    fn set_mode(&mut self, mode: AppMode) {
+        self.status_bar.mode = mode;
+    }
+
+    fn set_language(&mut self, language: Option<&str>) {
+        self.status_bar.language = language.map(String::from);
+    }
+
+    fn set_ready(&mut self) {
+        self.status_bar.status = "Ready".to_string();
+    }
+
+    fn set_last_response_words(&mut self, words: usize) {
+        self.status_bar.last_response_words = words;
+    }
+
+    fn set_audio_level(&mut self, level: f32) {
+        self.status_bar.audio_level = level;
+    }
+
+    fn set_tts_level(&mut self, level: f32) {
+        self.status_bar.tts_level = level;
+    }
+
+    fn has_input_activity(&mut self) -> bool {
+        std::mem::take(&mut self.input_activity)
+    }
+
+    fn has_keypress_activity(&mut self) -> bool {
+        std::mem::take(&mut self.keypress_activity)
+    }
+
+    fn has_pending_input(&self) -> bool {
+        !self.input.text().trim().is_empty()
+    }
+
+    fn take_input(&mut self) -> Option<String> {
+        self.input.take()
+    }
+
+    fn append_input(&mut self, text: &str) {
+        self.input.append(text);
+    }
+
+    fn ui_mode(&self) -> UiMode {
+        UiMode::Waveform
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl Drop for WaveformUi {
+    fn drop(&mut self) {
+        let _ = self.restore();
+    }
+}
+
+impl StatusRenderer for WaveformUi {
+    fn update_status(&mut self, state: &StatusBarState) {
+        self.status_bar = state.clone();
+    }
+
+    fn status_state(&self) -> &StatusBarState {
+        &self.status_bar
+    }
+
+    fn status_state_mut(&mut self) -> &mut StatusBarState {
+        &mut self.status_bar
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bar_chars() {
+        // Half height over 4 rows fills two rows exactly
+        let column: Vec<char> = (0..4).map(|row| bar_char(0.5, row, 4)).collect();
+        assert_eq!(column, vec!['█', '█', ' ', ' ']);
+
+        // Partial cells use eighth blocks
+        assert_eq!(bar_char(0.5, 0, 1), '▄');
+        assert_eq!(bar_char(2.0, 3, 4), '█');
+
+        // Silence keeps a baseline on the bottom row only
+        assert_eq!(bar_char(0.0, 0, 4), '▁');
+        assert_eq!(bar_char(0.0, 1, 4), ' ');
+    }
+}